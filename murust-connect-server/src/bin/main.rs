@@ -50,8 +50,13 @@ fn main() {
     id: 1,
     socket: "0.0.0.0:0".parse().unwrap(),
     maximum_players: 3,
+    maps: vec![0, 1, 2, 3],
+    tick_rate: 20,
+    terrain_path: None,
+    shop_path: None,
+    banned_words: Vec::new(),
   };
-  let gs = mugs::GameServer::spawn(config, manager);
+  let gs = mugs::GameServer::spawn(config, manager).unwrap();
   let gs_rpc = mugs::rpc::spawn_service("0.0.0.0:0".parse().unwrap(), gs.context()).unwrap();
   server.add_game_server(gs_rpc.uri()).unwrap();
 
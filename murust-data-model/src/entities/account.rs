@@ -1,8 +1,7 @@
-use entities::Character;
+use entities::{Character, Vault};
 
 // TODO: Security code should be a string? Can be prefixed with zeros.
 // TODO: Include characters here as well
-// TODO: Add vault ID
 #[derive(Debug)]
 pub struct Account {
   pub id: i32,
@@ -10,4 +9,6 @@ pub struct Account {
   pub security_code: u32,
   pub email: String,
   pub characters: Vec<Character>,
+  /// The warehouse shared by the account's characters.
+  pub vault: Vault,
 }
@@ -0,0 +1,276 @@
+use entities::{Character, Item};
+use types::{ExcellentOptions, ItemAttribute, ItemSlot};
+
+/// The equipment slots that contribute defense, excluding any weapons.
+const ARMOR_SLOTS: [ItemSlot; 6] = [
+  ItemSlot::WeaponLeft,
+  ItemSlot::Helm,
+  ItemSlot::Armor,
+  ItemSlot::Pants,
+  ItemSlot::Gloves,
+  ItemSlot::Boots,
+];
+
+/// The attack speed added by each excellent attack speed option.
+const EXCELLENT_ATTACK_SPEED: u32 = 7;
+
+/// The share (in percent) of maximum health or mana added by each excellent
+/// option increasing it.
+const EXCELLENT_MAXIMUM_INCREASE: u32 = 4;
+
+/// The attributes of a character derived from its class, level, stats and
+/// equipment.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CharacterAttributes {
+  pub max_health: u32,
+  pub max_mana: u32,
+  pub max_ability: u32,
+  pub max_shield: u32,
+  pub attack_speed: u32,
+  pub magic_speed: u32,
+  pub defense: u32,
+}
+
+impl CharacterAttributes {
+  /// Computes the attributes of a character.
+  pub fn new(character: &Character) -> Self {
+    let info = character.class.info();
+    let levels = u32::from(character.level.saturating_sub(1));
+
+    // Ancient set bonuses raise the stats used by all derived attributes
+    let equipment = &character.equipment;
+    let stats = [
+      u32::from(character.strength) + equipment.set_bonus(ItemAttribute::Strength),
+      u32::from(character.agility) + equipment.set_bonus(ItemAttribute::Agility),
+      u32::from(character.vitality) + equipment.set_bonus(ItemAttribute::Vitality),
+      u32::from(character.energy) + equipment.set_bonus(ItemAttribute::Energy),
+      u32::from(character.command) + equipment.set_bonus(ItemAttribute::Command),
+    ];
+    let agility = stats[1];
+
+    let vitality = stats[2].saturating_sub(u32::from(info.vitality));
+    let max_health = u32::from(info.life)
+      + levels * u32::from(info.level_life) / 10
+      + vitality * u32::from(info.vitality_life) / 10;
+    let max_health = increase(
+      max_health,
+      equipment.excellent_count(Item::defensive_options, ExcellentOptions::MaxHealth),
+    );
+
+    let energy = stats[3].saturating_sub(u32::from(info.energy));
+    let max_mana = u32::from(info.mana)
+      + levels * u32::from(info.level_mana) / 10
+      + energy * u32::from(info.energy_mana) / 10;
+    let max_mana = increase(
+      max_mana,
+      equipment.excellent_count(Item::defensive_options, ExcellentOptions::MaxMana),
+    );
+
+    let max_ability = stats
+      .iter()
+      .zip(info.ability.iter())
+      .map(|(&stat, &rate)| stat * u32::from(rate))
+      .sum::<u32>() / 100;
+
+    let level = u32::from(character.level);
+    let max_shield = stats.iter().sum::<u32>() * 12 / 10 + level * level / 30;
+
+    let attack_speed_boost = equipment
+      .into_iter()
+      .filter_map(|(_, item)| item.as_ref())
+      .filter(|item| !item.is_broken())
+      .map(|item| u32::from(item.boost(ItemAttribute::AttackSpeed)))
+      .sum::<u32>()
+      + equipment.set_bonus(ItemAttribute::AttackSpeed)
+      + equipment.excellent_count(Item::offensive_options, ExcellentOptions::AttackSpeed)
+        * EXCELLENT_ATTACK_SPEED;
+
+    let defense_boost = ARMOR_SLOTS
+      .iter()
+      .filter_map(|&slot| character.equipment[slot].as_ref())
+      .filter(|item| !item.is_broken() && !item.is_weapon())
+      .filter(|item| item.boost(ItemAttribute::Defense) > 0)
+      .map(|item| {
+        u32::from(item.boost(ItemAttribute::Defense)) + item.level_bonus() + item.option_bonus()
+      })
+      .sum::<u32>()
+      + equipment.set_bonus(ItemAttribute::Defense);
+
+    CharacterAttributes {
+      max_health,
+      max_mana,
+      max_ability,
+      max_shield,
+      attack_speed: agility / u32::from(info.agility_attack_speed) + attack_speed_boost,
+      magic_speed: agility / u32::from(info.agility_magic_speed) + attack_speed_boost,
+      defense: agility / u32::from(info.agility_defense) + defense_boost,
+    }
+  }
+}
+
+/// Increases a maximum by a share for each excellent option increasing it.
+fn increase(maximum: u32, options: u32) -> u32 {
+  maximum * (100 + options * EXCELLENT_MAXIMUM_INCREASE) / 100
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use entities::{Equipment, Inventory, Item, ItemDefinition, ItemSet, ItemSetBonus};
+  use std::sync::Arc;
+  use types::{Class, ItemCode, ItemGroup, Position};
+
+  fn character(class: Class) -> Character {
+    let info = class.info();
+    Character {
+      id: 1,
+      slot: 0,
+      name: "test".into(),
+      level: 1,
+      class,
+      experience: 0,
+      points: 0,
+      strength: info.strength,
+      agility: info.agility,
+      vitality: info.vitality,
+      energy: info.energy,
+      command: info.command,
+      map: 0,
+      position: Position::new(0, 0),
+      player_kills: 0,
+      equipment: Equipment::default(),
+      inventory: Inventory::new(8, 8),
+      skills: Vec::new(),
+    }
+  }
+
+  fn item(slot: ItemSlot, attribute: ItemAttribute, boost: u16, level: u8) -> Item {
+    let mut definition = ItemDefinition::new(ItemCode::new(ItemGroup::Helm, 0), "Test");
+    definition.equippable_slot = Some(slot);
+    definition.attribute_boosts.insert(attribute, boost);
+    let mut item = Item::with_definition(definition);
+    item.level = level;
+    item
+  }
+
+  #[test]
+  fn base_attributes_of_new_characters() {
+    let attributes = CharacterAttributes::new(&character(Class::DarkKnight));
+    assert_eq!(attributes.max_health, 110);
+    assert_eq!(attributes.max_mana, 20);
+    assert_eq!(attributes.max_ability, 25);
+    assert_eq!(attributes.max_shield, 99);
+    assert_eq!(attributes.attack_speed, 1);
+    assert_eq!(attributes.defense, 6);
+
+    let attributes = CharacterAttributes::new(&character(Class::DarkWizard));
+    assert_eq!(attributes.max_health, 60);
+    assert_eq!(attributes.max_mana, 60);
+    assert_eq!(attributes.magic_speed, 1);
+  }
+
+  #[test]
+  fn levels_and_stats_increase_attributes() {
+    let mut character = character(Class::DarkKnight);
+    character.level = 11;
+    character.vitality += 10;
+    character.energy += 10;
+
+    let attributes = CharacterAttributes::new(&character);
+    assert_eq!(attributes.max_health, 110 + 20 + 30);
+    assert_eq!(attributes.max_mana, 20 + 5 + 10);
+  }
+
+  #[test]
+  fn equipment_boosts_defense_and_attack_speed() {
+    let mut character = character(Class::DarkKnight);
+    character.equipment[ItemSlot::Helm] = Some(item(ItemSlot::Helm, ItemAttribute::Defense, 24, 2));
+    character.equipment[ItemSlot::Gloves] =
+      Some(item(ItemSlot::Gloves, ItemAttribute::AttackSpeed, 3, 0));
+
+    let attributes = CharacterAttributes::new(&character);
+    assert_eq!(attributes.defense, 6 + 24 + 6);
+    assert_eq!(attributes.attack_speed, 1 + 3);
+  }
+
+  #[test]
+  fn item_options_boost_attributes() {
+    let mut character = character(Class::DarkKnight);
+    let mut helm = item(ItemSlot::Helm, ItemAttribute::Defense, 24, 0);
+    helm.option = 2;
+    helm.excellent = ExcellentOptions::MaxHealth;
+    character.equipment[ItemSlot::Helm] = Some(helm);
+
+    let mut pendant = item(ItemSlot::Amulet, ItemAttribute::Defense, 0, 0);
+    pendant.excellent = ExcellentOptions::AttackSpeed;
+    character.equipment[ItemSlot::Amulet] = Some(pendant);
+
+    let attributes = CharacterAttributes::new(&character);
+    assert_eq!(attributes.defense, 6 + 24 + 8);
+    assert_eq!(attributes.max_health, 110 * 104 / 100);
+    assert_eq!(attributes.max_mana, 20);
+    assert_eq!(attributes.attack_speed, 1 + 7);
+  }
+
+  #[test]
+  fn ancient_set_bonuses_require_two_pieces() {
+    let set = Arc::new(ItemSet {
+      id: 1,
+      name: "Test".into(),
+      tier: 1,
+      members: Vec::new(),
+      bonuses: vec![
+        ItemSetBonus {
+          pieces: 2,
+          attribute: ItemAttribute::Defense,
+          bonus: 10,
+        },
+        ItemSetBonus {
+          pieces: 3,
+          attribute: ItemAttribute::Vitality,
+          bonus: 10,
+        },
+      ],
+    });
+
+    let mut character = character(Class::DarkKnight);
+    let mut helm = item(ItemSlot::Helm, ItemAttribute::Defense, 5, 0);
+    helm.set = Some(set.clone());
+    character.equipment[ItemSlot::Helm] = Some(helm);
+    assert_eq!(CharacterAttributes::new(&character).defense, 6 + 5);
+
+    let mut definition = ItemDefinition::new(ItemCode::new(ItemGroup::Gloves, 0), "Gloves");
+    definition.equippable_slot = Some(ItemSlot::Gloves);
+    let mut gloves = Item::with_definition(definition);
+    gloves.set = Some(set);
+    character.equipment[ItemSlot::Gloves] = Some(gloves);
+
+    let attributes = CharacterAttributes::new(&character);
+    assert_eq!(attributes.defense, 6 + 5 + 10);
+    assert_eq!(attributes.max_health, 110);
+  }
+
+  #[test]
+  fn broken_armor_stops_contributing_defense() {
+    let mut definition = ItemDefinition::new(ItemCode::new(ItemGroup::Helm, 0), "Helm");
+    definition.equippable_slot = Some(ItemSlot::Helm);
+    definition.max_durability = 20;
+    definition.attribute_boosts.insert(ItemAttribute::Defense, 24);
+
+    let mut helm = Item::with_definition(definition);
+    helm.durability = 1;
+    helm.wear = 97;
+
+    let mut character = character(Class::DarkKnight);
+    character.equipment[ItemSlot::Helm] = Some(helm);
+    assert!(character.equipment.wear_armor().is_empty());
+    assert_eq!(CharacterAttributes::new(&character).defense, 6 + 24);
+
+    assert_eq!(character.equipment.wear_armor(), vec![ItemSlot::Helm]);
+    assert!(character.equipment[ItemSlot::Helm].as_ref().unwrap().is_broken());
+    assert_eq!(CharacterAttributes::new(&character).defense, 6);
+
+    character.equipment[ItemSlot::Helm].as_mut().unwrap().repair();
+    assert_eq!(CharacterAttributes::new(&character).defense, 6 + 24);
+  }
+}
@@ -1,5 +1,5 @@
-use entities::{Equipment, Inventory};
-use types::{Class, Position};
+use entities::{Equipment, Inventory, Item};
+use types::{CharacterStat, Class, HeroStatus, ItemLocation, Position, SkillId};
 
 #[derive(Debug)]
 pub struct Character {
@@ -9,6 +9,7 @@ pub struct Character {
   pub level: u16,
   pub class: Class,
   pub experience: u32,
+  pub points: u16,
   pub strength: u16,
   pub agility: u16,
   pub vitality: u16,
@@ -19,4 +20,241 @@ pub struct Character {
   pub player_kills: i32,
   pub equipment: Equipment,
   pub inventory: Inventory,
+  pub skills: Vec<SkillId>,
+}
+
+impl Character {
+  /// Returns the value of one of the character's stats.
+  pub fn stat(&self, stat: CharacterStat) -> u16 {
+    match stat {
+      CharacterStat::Strength => self.strength,
+      CharacterStat::Agility => self.agility,
+      CharacterStat::Vitality => self.vitality,
+      CharacterStat::Energy => self.energy,
+      CharacterStat::Command => self.command,
+    }
+  }
+
+  /// Returns the character's hero status, derived from its player kills.
+  ///
+  /// Killing other players makes a character an outlaw, while slaying
+  /// outlaws (counted as negative kills) makes it a hero.
+  pub fn hero_status(&self) -> HeroStatus {
+    match self.player_kills {
+      kills if kills <= -10 => HeroStatus::Revered,
+      kills if kills < 0 => HeroStatus::Hero,
+      0 => HeroStatus::Commoner,
+      1 => HeroStatus::Outcast,
+      2 => HeroStatus::Outlaw,
+      _ => HeroStatus::Murderer,
+    }
+  }
+
+  /// Spends a level-up point on a stat, returning whether it succeeded.
+  ///
+  /// This fails if no points are available, the stat is maxed out or, in the
+  /// case of command, the character is not a Dark Lord.
+  pub fn add_stat_point(&mut self, stat: CharacterStat) -> bool {
+    if self.points == 0 || (stat == CharacterStat::Command && self.class != Class::DarkLord) {
+      return false;
+    }
+
+    let value = match self.stat(stat).checked_add(1) {
+      Some(value) => value,
+      None => return false,
+    };
+
+    match stat {
+      CharacterStat::Strength => self.strength = value,
+      CharacterStat::Agility => self.agility = value,
+      CharacterStat::Vitality => self.vitality = value,
+      CharacterStat::Energy => self.energy = value,
+      CharacterStat::Command => self.command = value,
+    }
+
+    self.points -= 1;
+    true
+  }
+
+  /// Returns the item at a location, if any.
+  pub fn item(&self, location: ItemLocation) -> Option<&Item> {
+    match location {
+      ItemLocation::Equipment(slot) => self.equipment[slot].as_ref(),
+      ItemLocation::Inventory(slot) => self.inventory.get_item_at_slot(slot),
+    }
+  }
+
+  /// Returns the item at a location mutably, if any.
+  pub fn item_mut(&mut self, location: ItemLocation) -> Option<&mut Item> {
+    match location {
+      ItemLocation::Equipment(slot) => self.equipment[slot].as_mut(),
+      ItemLocation::Inventory(slot) => self.inventory.get_item_at_slot_mut(slot),
+    }
+  }
+
+  /// Moves a carried item between the inventory and equipment, returning
+  /// whether it succeeded.
+  ///
+  /// Items are only equipped in slots they fit and by characters eligible to
+  /// wear them.
+  pub fn move_item(&mut self, source: ItemLocation, target: ItemLocation) -> bool {
+    match (source, target) {
+      (ItemLocation::Inventory(source), ItemLocation::Inventory(target)) => self
+        .inventory
+        .move_item_between_slots(source, target)
+        .is_some(),
+      (ItemLocation::Inventory(source), ItemLocation::Equipment(slot)) => {
+        let equippable = self.equipment[slot].is_none() && self
+          .inventory
+          .get_item_at_slot(source)
+          .map_or(false, |item| {
+            self.inventory.get_item_slot(item) == Some(source)
+              && !self.inventory.is_item_locked(item.id)
+              && item.is_equippable_by(self, slot)
+          });
+
+        if equippable {
+          self.equipment[slot] = self.inventory.remove_item_at_slot(source);
+        }
+        equippable
+      },
+      (ItemLocation::Equipment(slot), ItemLocation::Inventory(target)) => {
+        let item = match self.equipment[slot].take() {
+          Some(item) => item,
+          None => return false,
+        };
+
+        match self.inventory.add_item_at_slot(target, item) {
+          Ok(()) => true,
+          Err(item) => {
+            self.equipment[slot] = Some(item);
+            false
+          },
+        }
+      },
+      (ItemLocation::Equipment(source), ItemLocation::Equipment(target)) => {
+        let equippable = self.equipment[target].is_none() && self.equipment[source]
+          .as_ref()
+          .map_or(false, |item| item.is_equippable_by(self, target));
+
+        if equippable {
+          let item = self.equipment[source].take();
+          self.equipment[target] = item;
+        }
+        equippable
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use entities::{Item, ItemDefinition};
+  use std::sync::Arc;
+  use types::{ItemAttribute, ItemCode, ItemGroup, ItemSlot};
+
+  fn character(class: Class) -> Character {
+    let info = class.info();
+    Character {
+      id: 1,
+      slot: 0,
+      name: "test".into(),
+      level: 1,
+      class,
+      experience: 0,
+      points: 0,
+      strength: info.strength,
+      agility: info.agility,
+      vitality: info.vitality,
+      energy: info.energy,
+      command: info.command,
+      map: 0,
+      position: Position::new(0, 0),
+      player_kills: 0,
+      equipment: Equipment::default(),
+      inventory: Inventory::new(8, 8),
+      skills: Vec::new(),
+    }
+  }
+
+  fn sword() -> Item {
+    let mut definition = ItemDefinition::new(ItemCode::new(ItemGroup::Sword, 0), "Kris");
+    definition.equippable_slot = Some(ItemSlot::WeaponRight);
+    definition.eligible_classes = vec![Class::DarkKnight];
+    definition.height = 2;
+    Item::with_definition(definition)
+  }
+
+  #[test]
+  fn move_items_between_inventory_and_equipment() {
+    let mut character = character(Class::DarkKnight);
+    character.inventory.add_item_at_slot(8, sword()).unwrap();
+
+    let (weapon, left) = (
+      ItemLocation::Equipment(ItemSlot::WeaponRight),
+      ItemLocation::Equipment(ItemSlot::WeaponLeft),
+    );
+    assert!(!character.move_item(ItemLocation::Inventory(16), weapon));
+    let helm = ItemLocation::Equipment(ItemSlot::Helm);
+    assert!(!character.move_item(ItemLocation::Inventory(8), helm));
+    assert!(character.move_item(ItemLocation::Inventory(8), weapon));
+    assert_eq!(character.inventory.items(), 0);
+
+    assert!(character.move_item(weapon, left));
+    assert!(character.equipment[ItemSlot::WeaponRight].is_none());
+
+    character.inventory.add_item_at_slot(0, sword()).unwrap();
+    assert!(!character.move_item(left, ItemLocation::Inventory(8)));
+    assert!(character.move_item(left, ItemLocation::Inventory(2)));
+    assert!(character.equipment[ItemSlot::WeaponLeft].is_none());
+    assert_eq!(character.inventory.items(), 2);
+  }
+
+  #[test]
+  fn locked_items_cannot_be_equipped() {
+    let mut character = character(Class::DarkKnight);
+    let sword = sword();
+    let sword_id = sword.id;
+    character.inventory.add_item_at_slot(0, sword).unwrap();
+    character.inventory.lock_item(sword_id);
+
+    let weapon = ItemLocation::Equipment(ItemSlot::WeaponRight);
+    assert!(!character.move_item(ItemLocation::Inventory(0), weapon));
+    assert!(character.equipment[ItemSlot::WeaponRight].is_none());
+  }
+
+  #[test]
+  fn items_are_only_equipped_by_eligible_classes() {
+    let mut character = character(Class::DarkWizard);
+    character.inventory.add_item_at_slot(0, sword()).unwrap();
+
+    let weapon = ItemLocation::Equipment(ItemSlot::WeaponRight);
+    assert!(!character.move_item(ItemLocation::Inventory(0), weapon));
+    assert_eq!(character.inventory.items(), 1);
+  }
+
+  #[test]
+  fn items_are_only_equipped_when_requirements_are_met() {
+    let mut item = sword();
+    {
+      let definition = Arc::get_mut(&mut item.definition).unwrap();
+      definition.drop_level = 6;
+      definition.attribute_requirements.insert(ItemAttribute::Strength, 30);
+      definition.attribute_requirements.insert(ItemAttribute::Level, 5);
+    }
+    item.level = 2;
+    assert_eq!(item.requirement(ItemAttribute::Strength), 40);
+    assert_eq!(item.requirement(ItemAttribute::Level), 5);
+
+    let mut character = character(Class::DarkKnight);
+    character.inventory.add_item_at_slot(0, item).unwrap();
+
+    let weapon = ItemLocation::Equipment(ItemSlot::WeaponRight);
+    character.strength = 40;
+    assert!(!character.move_item(ItemLocation::Inventory(0), weapon));
+
+    character.level = 5;
+    assert!(character.move_item(ItemLocation::Inventory(0), weapon));
+  }
 }
@@ -1,11 +1,111 @@
-use entities::Item;
+use entities::{Item, ItemSet};
 use num_traits::FromPrimitive;
 use std::ops::{Index, IndexMut};
-use types::ItemSlot;
+use types::{ExcellentOptions, ItemAttribute, ItemCode, ItemSlot};
+
+/// The equipment slots worn down when attacking.
+const WEAPON_SLOTS: [ItemSlot; 2] = [ItemSlot::WeaponRight, ItemSlot::WeaponLeft];
+
+/// The equipment slots worn down when being hit.
+const ARMOR_SLOTS: [ItemSlot; 6] = [
+  ItemSlot::WeaponLeft,
+  ItemSlot::Helm,
+  ItemSlot::Armor,
+  ItemSlot::Pants,
+  ItemSlot::Gloves,
+  ItemSlot::Boots,
+];
+
+/// The wear of each weapon per attack.
+const WEAPON_WEAR: u16 = 4;
+
+/// The wear of each armor piece per hit taken.
+const ARMOR_WEAR: u16 = 2;
 
 #[derive(Default, Debug)]
 pub struct Equipment([Option<Item>; ItemSlot::SIZE]);
 
+impl Equipment {
+  /// Returns the number of unbroken items with an excellent option, among the
+  /// options selected from each item.
+  pub fn excellent_count<F>(&self, options: F, option: ExcellentOptions) -> u32
+  where
+    F: Fn(&Item) -> ExcellentOptions,
+  {
+    self
+      .0
+      .iter()
+      .filter_map(|item| item.as_ref())
+      .filter(|item| !item.is_broken() && options(item).contains(option))
+      .count() as u32
+  }
+
+  /// Returns the bonus of an attribute granted by the equipped ancient sets.
+  ///
+  /// Each distinct piece of a set counts once, so a pair of identical rings
+  /// is only a single piece.
+  pub fn set_bonus(&self, attribute: ItemAttribute) -> u32 {
+    let mut sets: Vec<(&ItemSet, Vec<ItemCode>)> = Vec::new();
+    let items = self
+      .0
+      .iter()
+      .filter_map(|item| item.as_ref())
+      .filter(|item| !item.is_broken());
+    for item in items {
+      let set = match item.set {
+        Some(ref set) => set.as_ref(),
+        None => continue,
+      };
+
+      match sets.iter().position(|&(other, _)| other.id == set.id) {
+        Some(index) => {
+          if !sets[index].1.contains(&item.code) {
+            sets[index].1.push(item.code);
+          }
+        },
+        None => sets.push((set, vec![item.code])),
+      }
+    }
+
+    sets
+      .iter()
+      .flat_map(|&(set, ref pieces)| set.bonuses(pieces.len() as u8))
+      .filter(|bonus| bonus.attribute == attribute)
+      .map(|bonus| u32::from(bonus.bonus))
+      .sum()
+  }
+
+  /// Wears down the equipped weapons after an attack, returning the slots of
+  /// any that lost durability.
+  pub fn wear_weapons(&mut self) -> Vec<ItemSlot> {
+    self.wear(&WEAPON_SLOTS, WEAPON_WEAR, |item| item.is_weapon())
+  }
+
+  /// Wears down the equipped armor after being hit, returning the slots of
+  /// any pieces that lost durability.
+  pub fn wear_armor(&mut self) -> Vec<ItemSlot> {
+    self.wear(&ARMOR_SLOTS, ARMOR_WEAR, |item| !item.is_weapon())
+  }
+
+  /// Wears down the items in any of the slots that match a predicate.
+  fn wear<F: Fn(&Item) -> bool>(
+    &mut self,
+    slots: &[ItemSlot],
+    amount: u16,
+    predicate: F,
+  ) -> Vec<ItemSlot> {
+    let items = &mut self.0;
+    slots
+      .iter()
+      .cloned()
+      .filter(|&slot| match items[slot as usize] {
+        Some(ref mut item) if predicate(item) => item.wear(amount),
+        _ => false,
+      })
+      .collect()
+  }
+}
+
 impl Index<ItemSlot> for Equipment {
   type Output = Option<Item>;
 
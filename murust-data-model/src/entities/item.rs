@@ -1,27 +1,224 @@
-use entities::ItemDefinition;
+use entities::{Character, ItemDefinition, ItemSet};
 use std::ops::Deref;
 use std::sync::Arc;
+use types::{Class, ExcellentOptions, ItemAttribute, ItemGroup, ItemSlot};
 use uuid::Uuid;
 
 /// The type of ID used by item entities.
 pub type Id = Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Item {
   pub id: Id,
   pub level: u8,
   pub durability: u8,
+  /// The wear accumulated since the item last lost durability.
+  pub wear: u16,
+  /// Whether the item grants its skill.
+  pub skill: bool,
+  pub luck: bool,
+  /// The additional option level, each adding four damage or defense.
+  pub option: u8,
+  pub excellent: ExcellentOptions,
+  /// The ancient set the item belongs to, if any.
+  pub set: Option<Arc<ItemSet>>,
   pub definition: Arc<ItemDefinition>,
 }
 
+/// The stat requirement shared by all items, which is not scaled by level.
+const MIN_REQUIREMENT: u16 = 20;
+
+/// The wear an item endures before losing a point of durability.
+const WEAR_PER_DURABILITY: u16 = 100;
+
+/// The damage or defense added by each level of an additional option.
+const OPTION_BONUS: u32 = 4;
+
+/// The largest number of apples or potions held by a single stack.
+const MAX_POTION_STACK: u8 = 3;
+
+/// The damage and defense gained by an item's level, indexed by level.
+static LEVEL_BONUS: [u32; 16] = [0, 3, 6, 9, 12, 15, 18, 21, 24, 27, 31, 36, 42, 49, 57, 66];
+
 impl Item {
-  pub fn with_definition(definition: ItemDefinition) -> Self {
+  pub fn with_definition<D: Into<Arc<ItemDefinition>>>(definition: D) -> Self {
+    let definition = definition.into();
     Item {
       id: Id::new_v4(),
       level: 0,
       durability: definition.max_durability,
-      definition: Arc::new(definition),
+      wear: 0,
+      skill: false,
+      luck: false,
+      option: 0,
+      excellent: ExcellentOptions::default(),
+      set: None,
+      definition,
+    }
+  }
+
+  /// Returns a copy of the item with a new ID.
+  pub fn duplicate(&self) -> Self {
+    Item {
+      id: Id::new_v4(),
+      wear: 0,
+      set: self.set.clone(),
+      definition: self.definition.clone(),
+      ..*self
+    }
+  }
+
+  /// Returns whether a character may equip the item in a slot.
+  ///
+  /// Rings fit either ring slot, and classes wielding two weapons may hold a
+  /// right hand weapon in their left hand as well. The character must also
+  /// meet all of the item's requirements.
+  pub fn is_equippable_by(&self, character: &Character, slot: ItemSlot) -> bool {
+    let fits = match (self.equippable_slot, slot) {
+      (Some(ItemSlot::RingRight), ItemSlot::RingLeft) => true,
+      (Some(ItemSlot::RingLeft), ItemSlot::RingRight) => true,
+      (Some(ItemSlot::WeaponRight), ItemSlot::WeaponLeft) => match character.class {
+        Class::DarkKnight | Class::BladeKnight | Class::MagicGladiator => true,
+        _ => false,
+      },
+      (Some(expected), slot) => expected == slot,
+      (None, _) => false,
+    };
+
+    fits && self.eligible_classes.contains(&character.class) && self.is_usable_by(character)
+  }
+
+  /// Returns whether a character meets all of the item's requirements.
+  pub fn is_usable_by(&self, character: &Character) -> bool {
+    self.attribute_requirements.keys().all(|&attribute| {
+      let value = match attribute {
+        ItemAttribute::Level => character.level,
+        ItemAttribute::Strength => character.strength,
+        ItemAttribute::Agility => character.agility,
+        ItemAttribute::Vitality => character.vitality,
+        ItemAttribute::Energy => character.energy,
+        ItemAttribute::Command => character.command,
+        _ => return true,
+      };
+      value >= self.requirement(attribute)
+    })
+  }
+
+  /// Returns the item's requirement of an attribute, scaled by its level.
+  ///
+  /// Stat requirements grow with each level relative to the item's drop
+  /// level, while level requirements remain the same.
+  pub fn requirement(&self, attribute: ItemAttribute) -> u16 {
+    let base = self.definition.requirement(attribute);
+    match attribute {
+      ItemAttribute::Level => base,
+      _ if base <= MIN_REQUIREMENT || self.drop_level == 0 => base,
+      _ => {
+        let level = u32::from(self.level);
+        let extra = u32::from(base - MIN_REQUIREMENT) * 3 * level / u32::from(self.drop_level);
+        (u32::from(base) + extra).min(u32::from(u16::max_value())) as u16
+      },
+    }
+  }
+
+  /// The highest additional option level of an item.
+  pub const MAX_OPTION: u8 = 7;
+
+  /// Returns whether the item has lost all of its durability.
+  ///
+  /// Broken items remain equipped, but no longer contribute any stats.
+  pub fn is_broken(&self) -> bool { self.max_durability > 0 && self.durability == 0 }
+
+  /// Wears the item down, returning whether it lost durability.
+  pub fn wear(&mut self, amount: u16) -> bool {
+    if self.durability == 0 {
+      return false;
+    }
+
+    self.wear += amount;
+    if self.wear < WEAR_PER_DURABILITY {
+      return false;
     }
+
+    let lost = (self.wear / WEAR_PER_DURABILITY).min(u16::from(self.durability));
+    self.wear %= WEAR_PER_DURABILITY;
+    self.durability -= lost as u8;
+    true
+  }
+
+  /// Returns whether the item has lost any durability.
+  pub fn is_damaged(&self) -> bool { self.durability < self.max_durability }
+
+  /// Restores the item's durability to its maximum.
+  pub fn repair(&mut self) {
+    self.durability = self.max_durability;
+    self.wear = 0;
+  }
+
+  /// Returns the largest number of items held by a single stack.
+  ///
+  /// Stackable items keep their count in their durability, the way the
+  /// client expects it.
+  pub fn max_stack(&self) -> u8 {
+    match self.code.tuple() {
+      (ItemGroup::Potion, index) if index <= 6 => MAX_POTION_STACK,
+      _ => 1,
+    }
+  }
+
+  /// Returns whether the item may be stacked.
+  pub fn is_stackable(&self) -> bool { self.max_stack() > 1 }
+
+  /// Returns the number of items held by the stack.
+  pub fn stack_size(&self) -> u8 {
+    if self.is_stackable() {
+      self.durability
+    } else {
+      1
+    }
+  }
+
+  /// Returns whether the item may be stacked along with another.
+  pub fn stacks_with(&self, other: &Item) -> bool {
+    self.is_stackable() && self.code == other.code && self.level == other.level
+  }
+
+  /// Returns whether the item has any excellent options.
+  pub fn is_excellent(&self) -> bool { !self.excellent.is_empty() }
+
+  /// Returns the excellent options of a weapon or pendant, which are empty
+  /// for any other item.
+  pub fn offensive_options(&self) -> ExcellentOptions {
+    if self.has_offensive_options() {
+      self.excellent
+    } else {
+      ExcellentOptions::None
+    }
+  }
+
+  /// Returns the excellent options of an armor, shield or ring, which are
+  /// empty for any other item.
+  pub fn defensive_options(&self) -> ExcellentOptions {
+    if self.has_offensive_options() {
+      ExcellentOptions::None
+    } else {
+      self.excellent
+    }
+  }
+
+  /// Returns the damage or defense gained by the item's additional option.
+  pub fn option_bonus(&self) -> u32 { u32::from(self.option) * OPTION_BONUS }
+
+  /// Returns the item's ancient tier, or zero if it belongs to no set.
+  pub fn tier(&self) -> u8 { self.set.as_ref().map_or(0, |set| set.tier) }
+
+  /// Returns the damage or defense gained by the item's level.
+  pub fn level_bonus(&self) -> u32 { LEVEL_BONUS[(self.level as usize).min(LEVEL_BONUS.len() - 1)] }
+
+  /// Returns whether the item's excellent options are those of weapons and
+  /// pendants, since their bits are shared with those of armors and rings.
+  fn has_offensive_options(&self) -> bool {
+    self.is_weapon() || self.equippable_slot == Some(ItemSlot::Amulet)
   }
 }
 
@@ -1,4 +1,5 @@
-use types::{Class, ItemCode, ItemSlot};
+use std::collections::HashMap;
+use types::{Class, ItemAttribute, ItemCode, ItemSlot};
 
 #[derive(Debug)]
 // TODO: Should Item definition arc itself?
@@ -12,6 +13,8 @@ pub struct ItemDefinition {
   pub drop_from_monster: bool,
   pub drop_level: u16,
   pub eligible_classes: Vec<Class>,
+  pub attribute_boosts: HashMap<ItemAttribute, u16>,
+  pub attribute_requirements: HashMap<ItemAttribute, u16>,
 }
 
 impl ItemDefinition {
@@ -26,6 +29,26 @@ impl ItemDefinition {
       drop_from_monster: false,
       drop_level: 0,
       eligible_classes: Vec::new(),
+      attribute_boosts: HashMap::new(),
+      attribute_requirements: HashMap::new(),
     }
   }
+
+  /// Returns the item's boost of an attribute, or zero if it has none.
+  pub fn boost(&self, attribute: ItemAttribute) -> u16 {
+    self.attribute_boosts.get(&attribute).cloned().unwrap_or(0)
+  }
+
+  /// Returns the item's base requirement of an attribute, or zero if it has
+  /// none.
+  pub fn requirement(&self, attribute: ItemAttribute) -> u16 {
+    self
+      .attribute_requirements
+      .get(&attribute)
+      .cloned()
+      .unwrap_or(0)
+  }
+
+  /// Returns whether the item deals damage or not.
+  pub fn is_weapon(&self) -> bool { self.boost(ItemAttribute::MaxDamage) > 0 }
 }
@@ -0,0 +1,37 @@
+use types::{ItemAttribute, ItemCode};
+
+/// The type of ID used by item set entities.
+pub type Id = u16;
+
+/// An ancient set, granting bonuses when several of its pieces are equipped.
+#[derive(Debug)]
+pub struct ItemSet {
+  pub id: Id,
+  pub name: String,
+  /// The ancient tier of the set's pieces (each item may belong to one set
+  /// per tier).
+  pub tier: u8,
+  pub members: Vec<ItemCode>,
+  pub bonuses: Vec<ItemSetBonus>,
+}
+
+/// An attribute bonus granted once a number of set pieces are equipped.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ItemSetBonus {
+  pub pieces: u8,
+  pub attribute: ItemAttribute,
+  pub bonus: u16,
+}
+
+impl ItemSet {
+  /// The least number of pieces required for any bonus.
+  pub const MIN_PIECES: u8 = 2;
+
+  /// Returns the bonuses granted with a number of equipped pieces.
+  pub fn bonuses(&self, pieces: u8) -> impl Iterator<Item = &ItemSetBonus> {
+    self
+      .bonuses
+      .iter()
+      .filter(move |bonus| pieces >= Self::MIN_PIECES && bonus.pieces <= pieces)
+  }
+}
@@ -0,0 +1,8 @@
+use entities::Item;
+
+/// Loot lying on the ground, e.g dropped by a slain monster.
+#[derive(Debug)]
+pub enum Loot {
+  Item(Item),
+  Money(u32),
+}
@@ -1,13 +1,31 @@
 pub use self::account::Account;
+pub use self::attributes::CharacterAttributes;
 pub use self::character::Character;
 pub use self::equipment::Equipment;
 pub use self::inventory::Inventory;
 pub use self::item::Item;
 pub use self::item_definition::ItemDefinition;
+pub use self::item_set::{ItemSet, ItemSetBonus};
+pub use self::loot::Loot;
+pub use self::monster::{MonsterDefinition, MonsterSpawn};
+pub use self::npc::NpcDefinition;
+pub use self::shop::Shop;
+pub use self::skill::SkillDefinition;
+pub use self::trade::TradeOffer;
+pub use self::vault::Vault;
 
 pub mod account;
+pub mod attributes;
 pub mod character;
 pub mod equipment;
 pub mod inventory;
 pub mod item;
 pub mod item_definition;
+pub mod item_set;
+pub mod loot;
+pub mod monster;
+pub mod npc;
+pub mod shop;
+pub mod skill;
+pub mod trade;
+pub mod vault;
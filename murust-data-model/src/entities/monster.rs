@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use std::time::Duration;
+use types::{Direction, Position};
+
+/// The attributes shared by all monsters of a class.
+#[derive(Debug)]
+pub struct MonsterDefinition {
+  pub class: u16,
+  pub name: String,
+  pub level: u16,
+  pub max_health: u32,
+  pub min_damage: u16,
+  pub max_damage: u16,
+  pub defense: u16,
+  pub attack_rate: u16,
+  pub defense_rate: u16,
+  pub move_range: u8,
+  pub attack_range: u8,
+  pub view_range: u8,
+  pub move_delay: Duration,
+  pub attack_delay: Duration,
+  pub respawn_time: Duration,
+}
+
+/// An area where monsters of a class are spawned.
+#[derive(Debug)]
+pub struct MonsterSpawn {
+  pub id: i32,
+  pub definition: Arc<MonsterDefinition>,
+  pub map: u8,
+  pub area_start: Position,
+  pub area_end: Position,
+  /// The direction monsters face, random if unspecified.
+  pub direction: Option<Direction>,
+  pub count: u8,
+}
@@ -0,0 +1,14 @@
+use types::{Direction, NpcKind, Position};
+
+/// A non-player character standing at a fixed location.
+#[derive(Debug)]
+pub struct NpcDefinition {
+  pub id: i32,
+  /// The monster class used by the client to render the NPC.
+  pub class: u16,
+  pub name: String,
+  pub kind: NpcKind,
+  pub map: u8,
+  pub position: Position,
+  pub direction: Direction,
+}
@@ -0,0 +1,36 @@
+use std::ops::{Deref, DerefMut};
+use types::ItemStorage;
+
+/// The wares sold by an NPC.
+#[derive(Debug)]
+pub struct Shop {
+  /// The ID of the NPC selling the wares.
+  pub npc: i32,
+  pub storage: ItemStorage,
+}
+
+impl Shop {
+  /// The width of a shop window.
+  pub const WIDTH: u8 = 8;
+
+  /// The height of a shop window.
+  pub const HEIGHT: u8 = 15;
+
+  /// Constructs a new, empty shop.
+  pub fn new(npc: i32) -> Self {
+    Shop {
+      npc,
+      storage: ItemStorage::new(Self::WIDTH, Self::HEIGHT),
+    }
+  }
+}
+
+impl Deref for Shop {
+  type Target = ItemStorage;
+
+  fn deref(&self) -> &Self::Target { &self.storage }
+}
+
+impl DerefMut for Shop {
+  fn deref_mut(&mut self) -> &mut Self::Target { &mut self.storage }
+}
@@ -0,0 +1,30 @@
+use entities::Character;
+use std::time::Duration;
+use types::{Class, SkillArea, SkillId};
+
+/// The costs, requirements and effects of a skill.
+#[derive(Debug)]
+pub struct SkillDefinition {
+  pub id: SkillId,
+  pub name: String,
+  pub damage: u16,
+  /// Whether the damage is based on the caster's magic or weapons.
+  pub magic: bool,
+  pub mana_cost: u16,
+  pub ability_cost: u16,
+  pub cooldown: Duration,
+  pub range: u8,
+  pub area: SkillArea,
+  pub min_level: u16,
+  pub min_energy: u16,
+  pub eligible_classes: Vec<Class>,
+}
+
+impl SkillDefinition {
+  /// Returns whether a character meets the skill's requirements.
+  pub fn is_usable_by(&self, character: &Character) -> bool {
+    self.eligible_classes.contains(&character.class)
+      && character.level >= self.min_level
+      && character.energy >= self.min_energy
+  }
+}
@@ -0,0 +1,49 @@
+use entities::item;
+use std::ops::{Deref, DerefMut};
+use types::ItemStorage;
+
+/// The items and zen offered by one side of a trade.
+///
+/// The items are copies of those within the owner's inventory, which remain
+/// there, locked, until the trade is completed.
+#[derive(Debug)]
+pub struct TradeOffer {
+  pub storage: ItemStorage,
+  pub money: u32,
+  /// Whether the owner has accepted the current offers.
+  pub confirmed: bool,
+}
+
+impl TradeOffer {
+  /// The width of a trade window.
+  pub const WIDTH: u8 = 8;
+
+  /// The height of a trade window.
+  pub const HEIGHT: u8 = 4;
+
+  /// Returns the IDs of the offered items.
+  pub fn item_ids(&self) -> Vec<item::Id> {
+    self.storage.into_iter().map(|(_, item)| item.id).collect()
+  }
+}
+
+impl Default for TradeOffer {
+  /// Constructs a new, empty offer.
+  fn default() -> Self {
+    TradeOffer {
+      storage: ItemStorage::new(Self::WIDTH, Self::HEIGHT),
+      money: 0,
+      confirmed: false,
+    }
+  }
+}
+
+impl Deref for TradeOffer {
+  type Target = ItemStorage;
+
+  fn deref(&self) -> &Self::Target { &self.storage }
+}
+
+impl DerefMut for TradeOffer {
+  fn deref_mut(&mut self) -> &mut Self::Target { &mut self.storage }
+}
@@ -0,0 +1,98 @@
+use entities::Inventory;
+use std::ops::{Deref, DerefMut};
+
+/// An account's warehouse, shared by all of its characters.
+#[derive(Debug)]
+pub struct Vault {
+  pub inventory: Inventory,
+  /// The optional PIN protecting the vault.
+  pub password: Option<u16>,
+  locked: bool,
+}
+
+impl Vault {
+  /// The width of a vault.
+  pub const WIDTH: u8 = 8;
+
+  /// The height of a vault.
+  pub const HEIGHT: u8 = 15;
+
+  /// The highest allowed PIN.
+  pub const MAX_PASSWORD: u16 = 9999;
+
+  /// Constructs a vault from an existing inventory.
+  ///
+  /// Vaults protected by a password start out locked.
+  pub fn with_inventory(inventory: Inventory, password: Option<u16>) -> Self {
+    Vault {
+      inventory,
+      password,
+      locked: password.is_some(),
+    }
+  }
+
+  /// Returns whether the vault must be unlocked before being used.
+  pub fn is_locked(&self) -> bool { self.locked }
+
+  /// Unlocks the vault, returning whether the password was correct.
+  pub fn unlock(&mut self, password: u16) -> bool {
+    self.locked = self.locked && self.password != Some(password);
+    !self.locked
+  }
+
+  /// Protects the vault with a new password, if it's unlocked.
+  ///
+  /// The vault stays unlocked until it's reconstructed.
+  pub fn set_password(&mut self, password: u16) -> bool {
+    let allowed = !self.locked && password <= Self::MAX_PASSWORD;
+    if allowed {
+      self.password = Some(password);
+    }
+    allowed
+  }
+
+  /// Removes the vault's password, if it's unlocked.
+  pub fn remove_password(&mut self) -> bool {
+    if !self.locked {
+      self.password = None;
+    }
+    !self.locked
+  }
+}
+
+impl Default for Vault {
+  /// Constructs a new, empty and unprotected vault.
+  fn default() -> Self { Self::with_inventory(Inventory::new(Self::WIDTH, Self::HEIGHT), None) }
+}
+
+impl Deref for Vault {
+  type Target = Inventory;
+
+  fn deref(&self) -> &Self::Target { &self.inventory }
+}
+
+impl DerefMut for Vault {
+  fn deref_mut(&mut self) -> &mut Self::Target { &mut self.inventory }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn vaults_with_passwords_must_be_unlocked() {
+    let mut vault = Vault::with_inventory(Inventory::new(Vault::WIDTH, Vault::HEIGHT), Some(1234));
+    assert!(vault.is_locked());
+    assert!(!vault.set_password(4321));
+    assert!(!vault.remove_password());
+
+    assert!(!vault.unlock(4321));
+    assert!(vault.unlock(1234));
+    assert!(!vault.set_password(10000));
+    assert!(vault.set_password(4321));
+    assert_eq!(vault.password, Some(4321));
+
+    assert!(vault.remove_password());
+    assert!(Vault::default().unlock(0));
+  }
+}
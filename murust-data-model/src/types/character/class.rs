@@ -12,7 +12,142 @@ pub enum Class {
   MuseElf = 10,
 }
 
+/// The starting stats and growth rates of a class.
+///
+/// Fractional rates are expressed in tenths, except for AG which uses
+/// hundredths per stat point.
+#[derive(Debug)]
+pub struct ClassInfo {
+  pub strength: u16,
+  pub agility: u16,
+  pub vitality: u16,
+  pub energy: u16,
+  pub command: u16,
+  pub life: u16,
+  pub level_life: u16,
+  pub vitality_life: u16,
+  pub mana: u16,
+  pub level_mana: u16,
+  pub energy_mana: u16,
+  /// The AG gained per strength, agility, vitality, energy and command.
+  pub ability: [u16; 5],
+  /// The agility required per point of attack speed.
+  pub agility_attack_speed: u16,
+  /// The agility required per point of magic speed.
+  pub agility_magic_speed: u16,
+  /// The agility required per point of defense.
+  pub agility_defense: u16,
+  /// The stat points awarded per level.
+  pub points_per_level: u16,
+}
+
+static DARK_WIZARD: ClassInfo = ClassInfo {
+  strength: 18,
+  agility: 18,
+  vitality: 15,
+  energy: 30,
+  command: 0,
+  life: 60,
+  level_life: 10,
+  vitality_life: 20,
+  mana: 60,
+  level_mana: 20,
+  energy_mana: 20,
+  ability: [20, 40, 30, 20, 0],
+  agility_attack_speed: 20,
+  agility_magic_speed: 10,
+  agility_defense: 4,
+  points_per_level: 5,
+};
+
+static DARK_KNIGHT: ClassInfo = ClassInfo {
+  strength: 28,
+  agility: 20,
+  vitality: 25,
+  energy: 10,
+  command: 0,
+  life: 110,
+  level_life: 20,
+  vitality_life: 30,
+  mana: 20,
+  level_mana: 5,
+  energy_mana: 10,
+  ability: [15, 20, 30, 100, 0],
+  agility_attack_speed: 15,
+  agility_magic_speed: 20,
+  agility_defense: 3,
+  points_per_level: 5,
+};
+
+static FAIRY_ELF: ClassInfo = ClassInfo {
+  strength: 22,
+  agility: 25,
+  vitality: 20,
+  energy: 15,
+  command: 0,
+  life: 80,
+  level_life: 10,
+  vitality_life: 20,
+  mana: 30,
+  level_mana: 15,
+  energy_mana: 15,
+  ability: [30, 20, 30, 20, 0],
+  agility_attack_speed: 50,
+  agility_magic_speed: 50,
+  agility_defense: 10,
+  points_per_level: 5,
+};
+
+static MAGIC_GLADIATOR: ClassInfo = ClassInfo {
+  strength: 26,
+  agility: 26,
+  vitality: 26,
+  energy: 26,
+  command: 0,
+  life: 110,
+  level_life: 10,
+  vitality_life: 20,
+  mana: 60,
+  level_mana: 10,
+  energy_mana: 20,
+  ability: [20, 25, 30, 15, 0],
+  agility_attack_speed: 15,
+  agility_magic_speed: 20,
+  agility_defense: 4,
+  points_per_level: 7,
+};
+
+static DARK_LORD: ClassInfo = ClassInfo {
+  strength: 26,
+  agility: 20,
+  vitality: 20,
+  energy: 15,
+  command: 25,
+  life: 90,
+  level_life: 15,
+  vitality_life: 20,
+  mana: 40,
+  level_mana: 10,
+  energy_mana: 15,
+  ability: [30, 20, 10, 15, 30],
+  agility_attack_speed: 10,
+  agility_magic_speed: 10,
+  agility_defense: 7,
+  points_per_level: 7,
+};
+
 impl Class {
+  /// Returns the starting stats and growth rates of the class.
+  pub fn info(self) -> &'static ClassInfo {
+    match self {
+      Class::DarkWizard | Class::SoulMaster => &DARK_WIZARD,
+      Class::DarkKnight | Class::BladeKnight => &DARK_KNIGHT,
+      Class::FairyElf | Class::MuseElf => &FAIRY_ELF,
+      Class::MagicGladiator => &MAGIC_GLADIATOR,
+      Class::DarkLord => &DARK_LORD,
+    }
+  }
+
   pub fn from_str(input: &str) -> Option<Self> {
     match input {
       "DW" => Some(Class::DarkWizard),
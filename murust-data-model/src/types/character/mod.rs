@@ -1,11 +1,13 @@
-pub use self::class::Class;
+pub use self::class::{Class, ClassInfo};
 pub use self::guild::GuildRole;
 pub use self::hero::HeroStatus;
+pub use self::stat::CharacterStat;
 use std::ops::Range;
 
 mod class;
 mod guild;
 mod hero;
+mod stat;
 
 /// The range of slots availabe for a character.
 pub const CHARACTER_SLOTS: Range<usize> = 0..5;
@@ -0,0 +1,12 @@
+/// A collection of stats distributable with level-up points.
+#[repr(u8)]
+#[derive(Primitive, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CharacterStat {
+  Strength = 0,
+  Agility = 1,
+  Vitality = 2,
+  Energy = 3,
+  Command = 4,
+}
+
+primitive_serialize!(CharacterStat, u8);
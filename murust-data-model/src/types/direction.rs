@@ -14,6 +14,23 @@ pub enum Direction {
 
 primitive_serialize!(Direction, u8);
 
+impl Direction {
+  /// Returns all possible direction values.
+  pub fn values() -> ::std::slice::Iter<'static, Direction> {
+    static DIRECTIONS: [Direction; 8] = [
+      Direction::SouthWest,
+      Direction::South,
+      Direction::SouthEast,
+      Direction::East,
+      Direction::NorthEast,
+      Direction::North,
+      Direction::NorthWest,
+      Direction::West,
+    ];
+    DIRECTIONS.iter()
+  }
+}
+
 impl Default for Direction {
   fn default() -> Self { Direction::SouthEast }
 }
@@ -0,0 +1,55 @@
+/// A collection of item attributes, used for both boosts and requirements.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ItemAttribute {
+  MinDamage,
+  MaxDamage,
+  AttackSpeed,
+  MagicPower,
+  Defense,
+  DefenseRate,
+  Level,
+  Strength,
+  Agility,
+  Vitality,
+  Energy,
+  Command,
+}
+
+impl ItemAttribute {
+  pub fn from_str(input: &str) -> Option<Self> {
+    match input {
+      "MinDamage" => Some(ItemAttribute::MinDamage),
+      "MaxDamage" => Some(ItemAttribute::MaxDamage),
+      "AttackSpeed" => Some(ItemAttribute::AttackSpeed),
+      "MagicPower" => Some(ItemAttribute::MagicPower),
+      "Defense" => Some(ItemAttribute::Defense),
+      "DefenseRate" => Some(ItemAttribute::DefenseRate),
+      "Level" => Some(ItemAttribute::Level),
+      "Strength" => Some(ItemAttribute::Strength),
+      "Agility" => Some(ItemAttribute::Agility),
+      "Vitality" => Some(ItemAttribute::Vitality),
+      "Energy" => Some(ItemAttribute::Energy),
+      "Command" => Some(ItemAttribute::Command),
+      _ => None,
+    }
+  }
+}
+
+impl From<ItemAttribute> for &'static str {
+  fn from(attribute: ItemAttribute) -> Self {
+    match attribute {
+      ItemAttribute::MinDamage => "MinDamage",
+      ItemAttribute::MaxDamage => "MaxDamage",
+      ItemAttribute::AttackSpeed => "AttackSpeed",
+      ItemAttribute::MagicPower => "MagicPower",
+      ItemAttribute::Defense => "Defense",
+      ItemAttribute::DefenseRate => "DefenseRate",
+      ItemAttribute::Level => "Level",
+      ItemAttribute::Strength => "Strength",
+      ItemAttribute::Agility => "Agility",
+      ItemAttribute::Vitality => "Vitality",
+      ItemAttribute::Energy => "Energy",
+      ItemAttribute::Command => "Command",
+    }
+  }
+}
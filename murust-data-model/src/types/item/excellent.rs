@@ -0,0 +1,25 @@
+bitflags! {
+  /// A collection of excellent options.
+  ///
+  /// Weapons and pendants share their bits with the options of armors and
+  /// rings, so the meaning of each bit depends on the item.
+  pub struct ExcellentOptions: u8 {
+    const None            = 0;
+    const ManaAfterKill   = (1 << 0);
+    const LifeAfterKill   = (1 << 1);
+    const AttackSpeed     = (1 << 2);
+    const DamagePercent   = (1 << 3);
+    const DamageLevel     = (1 << 4);
+    const ExcellentDamage = (1 << 5);
+    const MoneyIncrease   = (1 << 0);
+    const DefenseRate     = (1 << 1);
+    const ReflectDamage   = (1 << 2);
+    const DamageDecrease  = (1 << 3);
+    const MaxMana         = (1 << 4);
+    const MaxHealth       = (1 << 5);
+  }
+}
+
+impl Default for ExcellentOptions {
+  fn default() -> Self { ExcellentOptions::None }
+}
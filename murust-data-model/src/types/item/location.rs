@@ -0,0 +1,10 @@
+use types::ItemSlot;
+
+/// The location of an item carried by a character.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ItemLocation {
+  /// An equipment slot.
+  Equipment(ItemSlot),
+  /// The top-left slot of an item within the inventory.
+  Inventory(u8),
+}
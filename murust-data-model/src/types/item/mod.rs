@@ -1,9 +1,15 @@
+pub use self::attribute::ItemAttribute;
 pub use self::code::ItemCode;
+pub use self::excellent::ExcellentOptions;
 pub use self::group::ItemGroup;
+pub use self::location::ItemLocation;
 pub use self::slot::ItemSlot;
 pub use self::storage::ItemStorage;
 
+mod attribute;
 mod code;
+mod excellent;
 mod group;
+mod location;
 mod slot;
 mod storage;
@@ -1,13 +1,14 @@
 use entities::item::{self, Item};
-use std::collections::{hash_map, HashMap};
+use std::collections::{hash_map, HashMap, HashSet};
 use std::iter::{IntoIterator, Iterator};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ItemStorage {
   width: u8,
   height: u8,
   items: HashMap<item::Id, Item>,
   grid: Vec<Option<item::Id>>,
+  locked: HashSet<item::Id>,
 }
 
 impl ItemStorage {
@@ -18,6 +19,7 @@ impl ItemStorage {
       height,
       items: HashMap::new(),
       grid: vec![None; (width * height) as usize],
+      locked: HashSet::new(),
     }
   }
 
@@ -53,8 +55,12 @@ impl ItemStorage {
     }
   }
 
-  /// Removes an item based on its ID.
+  /// Removes an item based on its ID, unless it's locked.
   pub fn remove_item(&mut self, item_id: item::Id) -> Option<Item> {
+    if self.locked.contains(&item_id) {
+      return None;
+    }
+
     let item = self.items.remove(&item_id)?;
     let slot = self
       .get_item_slot(&item)
@@ -71,8 +77,13 @@ impl ItemStorage {
   }
 
   /// Tries to move an item at one slot to another.
+  ///
+  /// The source must be the top-left slot of the item.
   pub fn move_item_between_slots(&mut self, source: u8, target: u8) -> Option<()> {
-    // TODO: Source should be ensured that it's top left corner.
+    if self.get_item_at_slot(source).and_then(|item| self.get_item_slot(item)) != Some(source) {
+      return None;
+    }
+
     let item = self.remove_item_at_slot(source)?;
     self
       .add_item_at_slot(target, item)
@@ -84,8 +95,117 @@ impl ItemStorage {
       .ok()
   }
 
+  /// Tries to move an item at one slot to a slot within another storage.
+  ///
+  /// The source must be the top-left slot of the item.
+  pub fn transfer_item(&mut self, source: u8, storage: &mut ItemStorage, target: u8) -> Option<()> {
+    if self.get_item_at_slot(source).and_then(|item| self.get_item_slot(item)) != Some(source) {
+      return None;
+    }
+
+    let item = self.remove_item_at_slot(source)?;
+    storage
+      .add_item_at_slot(target, item)
+      .map_err(|item| {
+        self
+          .add_item_at_slot(source, item)
+          .expect("returning item slot")
+      })
+      .ok()
+  }
+
+  /// Merges the stack at one slot onto a stack of the same kind at another,
+  /// returning the number of items moved.
+  ///
+  /// As many items as the target has room for are moved, removing the source
+  /// once it has been emptied. Locked items are left as they are.
+  pub fn merge_items(&mut self, source: u8, target: u8) -> Option<u8> {
+    let source_id = self.get_item_id_from_slot(source)?;
+    let target_id = self.get_item_id_from_slot(target)?;
+    if source_id == target_id
+      || self.locked.contains(&source_id)
+      || self.locked.contains(&target_id)
+    {
+      return None;
+    }
+
+    let count = {
+      let (source, target) = (&self.items[&source_id], &self.items[&target_id]);
+      if !source.stacks_with(target) {
+        return None;
+      }
+      source
+        .stack_size()
+        .min(target.max_stack().saturating_sub(target.stack_size()))
+    };
+
+    if count == 0 {
+      return None;
+    }
+
+    self
+      .items
+      .get_mut(&target_id)
+      .expect("retrieving merge target")
+      .durability += count;
+
+    let emptied = {
+      let source = self
+        .items
+        .get_mut(&source_id)
+        .expect("retrieving merge source");
+      source.durability -= count;
+      source.durability == 0
+    };
+
+    if emptied {
+      self.remove_item(source_id);
+    }
+    Some(count)
+  }
+
+  /// Splits a number of items off the stack at a slot, returning them as a
+  /// new stack.
+  ///
+  /// The new stack is not placed within the storage, and the stack split must
+  /// keep at least one item.
+  pub fn split_item(&mut self, slot: u8, count: u8) -> Option<Item> {
+    let id = self.get_item_id_from_slot(slot)?;
+    if self.locked.contains(&id) {
+      return None;
+    }
+
+    let item = self
+      .items
+      .get_mut(&id)
+      .expect("retrieving grid item from map");
+    if !item.is_stackable() || count == 0 || count >= item.durability {
+      return None;
+    }
+
+    item.durability -= count;
+    let mut split = item.duplicate();
+    split.durability = count;
+    Some(split)
+  }
+
+  /// Locks an item, preventing it from being moved or removed until it's
+  /// unlocked.
+  pub fn lock_item(&mut self, item_id: item::Id) -> bool {
+    self.items.contains_key(&item_id) && self.locked.insert(item_id)
+  }
+
+  /// Unlocks a previously locked item.
+  pub fn unlock_item(&mut self, item_id: item::Id) -> bool { self.locked.remove(&item_id) }
+
+  /// Unlocks all items within the storage.
+  pub fn unlock_all(&mut self) { self.locked.clear(); }
+
+  /// Returns whether an item is locked or not.
+  pub fn is_item_locked(&self, item_id: item::Id) -> bool { self.locked.contains(&item_id) }
+
   /// Returns an item based on its ID.
-  pub fn get_item(&mut self, item_id: item::Id) -> Option<&Item> { self.items.get(&item_id) }
+  pub fn get_item(&self, item_id: item::Id) -> Option<&Item> { self.items.get(&item_id) }
 
   /// Returns any item that resides within the specified slot.
   pub fn get_item_at_slot(&self, slot: u8) -> Option<&Item> {
@@ -94,6 +214,17 @@ impl ItemStorage {
       .map(|id| self.items.get(&id).expect("retrieving grid item from map"))
   }
 
+  /// Returns any item that resides within the specified slot, mutably.
+  pub fn get_item_at_slot_mut(&mut self, slot: u8) -> Option<&mut Item> {
+    let id = self.get_item_id_from_slot(slot)?;
+    Some(
+      self
+        .items
+        .get_mut(&id)
+        .expect("retrieving grid item from map"),
+    )
+  }
+
   /// Returns the most top-left slot of an item.
   pub fn get_item_slot(&self, item: &Item) -> Option<u8> {
     self
@@ -115,6 +246,7 @@ impl ItemStorage {
   /// Clears the storage, removing any items within.
   pub fn clear(&mut self) {
     self.items.clear();
+    self.locked.clear();
     for entry in &mut self.grid {
       *entry = None;
     }
@@ -154,9 +286,10 @@ impl ItemStorage {
 
   /// Clears an item ID from the grid.
   fn clear_item_id(&mut self, top_left_slot: u8, item: &Item) {
-    for y in top_left_slot..(top_left_slot + item.definition.height) {
-      for x in top_left_slot..(top_left_slot + item.definition.width) {
-        self.grid[(y * self.width + x) as usize] = None;
+    let (base_x, base_y) = (top_left_slot % self.width, top_left_slot / self.width);
+    for y in base_y..(base_y + item.definition.height) {
+      for x in base_x..(base_x + item.definition.width) {
+        self.grid[y as usize * self.width as usize + x as usize] = None;
       }
     }
   }
@@ -270,6 +403,114 @@ mod tests {
     assert!(storage.add_item(item_with_size(8, 2)).is_err());
   }
 
+  #[test]
+  fn remove_item_clears_all_slots() {
+    let mut storage = ItemStorage::new(8, 8);
+    storage.add_item_at_slot(8 + 3, item_with_size(2, 3)).unwrap();
+    assert_eq!(storage.slots_free(), 58);
+
+    assert!(storage.remove_item_at_slot(2 * 8 + 4).is_some());
+    assert_eq!(storage.slots_free(), 64);
+    assert_eq!(storage.items(), 0);
+  }
+
+  #[test]
+  fn move_item_between_slots() {
+    let mut storage = ItemStorage::new(8, 8);
+    storage.add_item_at_slot(0, item_with_size(2, 2)).unwrap();
+    storage.add_item_at_slot(4, item_with_size(1, 1)).unwrap();
+
+    // Items may overlap their previous position
+    assert!(storage.move_item_between_slots(0, 1).is_some());
+    assert!(storage.get_item_at_slot(0).is_none());
+    assert!(storage.get_item_at_slot(8 + 2).is_some());
+
+    // Only the top-left slot of an item may be moved
+    assert!(storage.move_item_between_slots(8 + 2, 16).is_none());
+
+    // Occupied targets leave the item in place
+    assert!(storage.move_item_between_slots(1, 3).is_none());
+    assert!(storage.get_item_at_slot(1).is_some());
+    assert_eq!(storage.slots_free(), 59);
+  }
+
+  #[test]
+  fn transfer_item_between_storages() {
+    let (mut storage, mut other) = (ItemStorage::new(8, 8), ItemStorage::new(8, 15));
+    storage.add_item_at_slot(0, item_with_size(1, 2)).unwrap();
+    other.add_item_at_slot(8 * 14, item_with_size(1, 1)).unwrap();
+
+    // Occupied targets leave the item in place
+    assert!(storage.transfer_item(0, &mut other, 8 * 13).is_none());
+    assert!(storage.get_item_at_slot(8).is_some());
+
+    assert!(storage.transfer_item(0, &mut other, 8 * 13 + 1).is_some());
+    assert_eq!(storage.items(), 0);
+    assert!(other.get_item_at_slot(8 * 14 + 1).is_some());
+  }
+
+  #[test]
+  fn locked_items_stay_in_place() {
+    let mut storage = ItemStorage::new(8, 8);
+    let item = item_with_size(1, 1);
+    let item_id = item.id;
+    storage.add_item_at_slot(0, item).unwrap();
+
+    assert!(storage.lock_item(item_id));
+    assert!(storage.move_item_between_slots(0, 1).is_none());
+    assert!(storage.remove_item_at_slot(0).is_none());
+    assert!(storage.is_item_locked(item_id));
+
+    assert!(storage.unlock_item(item_id));
+    assert!(storage.remove_item(item_id).is_some());
+    assert!(!storage.lock_item(item_id));
+  }
+
+  #[test]
+  fn merge_stacks_up_to_their_limit() {
+    let mut storage = ItemStorage::new(8, 8);
+    storage.add_item_at_slot(0, potion(2)).unwrap();
+    storage.add_item_at_slot(1, potion(2)).unwrap();
+    storage.add_item_at_slot(2, item_with_size(1, 1)).unwrap();
+
+    // Only items of the same kind are merged
+    assert!(storage.merge_items(0, 2).is_none());
+
+    // The source keeps whatever does not fit
+    assert_eq!(storage.merge_items(0, 1), Some(1));
+    assert_eq!(storage.get_item_at_slot(0).unwrap().stack_size(), 1);
+    assert_eq!(storage.get_item_at_slot(1).unwrap().stack_size(), 3);
+    assert!(storage.merge_items(0, 1).is_none());
+
+    // An emptied source is removed
+    storage.add_item_at_slot(3, potion(1)).unwrap();
+    assert_eq!(storage.merge_items(3, 0), Some(1));
+    assert!(storage.get_item_at_slot(3).is_none());
+    assert_eq!(storage.get_item_at_slot(0).unwrap().stack_size(), 2);
+  }
+
+  #[test]
+  fn split_stacks_into_new_items() {
+    let mut storage = ItemStorage::new(8, 8);
+    storage.add_item_at_slot(0, potion(3)).unwrap();
+    storage.add_item_at_slot(1, item_with_size(1, 1)).unwrap();
+
+    assert!(storage.split_item(0, 3).is_none());
+    assert!(storage.split_item(1, 1).is_none());
+
+    let split = storage.split_item(0, 2).unwrap();
+    assert_eq!(split.stack_size(), 2);
+    assert_ne!(split.id, storage.get_item_at_slot(0).unwrap().id);
+    assert_eq!(storage.get_item_at_slot(0).unwrap().stack_size(), 1);
+  }
+
+  fn potion(count: u8) -> Item {
+    let definition = ItemDefinition::new(ItemCode::new(ItemGroup::Potion, 1), "Potion");
+    let mut item = Item::with_definition(definition);
+    item.durability = count;
+    item
+  }
+
   fn item_with_size(width: u8, height: u8) -> Item {
     let mut definition = ItemDefinition::new(ItemCode::new(ItemGroup::Helper, 0), "Test");
     definition.width = width;
@@ -2,13 +2,17 @@ pub use self::character::*;
 pub use self::ctl::*;
 pub use self::direction::*;
 pub use self::item::*;
+pub use self::npc::*;
 pub use self::position::*;
+pub use self::skill::*;
 
 mod character;
 mod ctl;
 mod direction;
 mod item;
+mod npc;
 mod position;
+mod skill;
 
 // The in-game ID for an object.
 pub type ObjectId = u16;
@@ -0,0 +1,25 @@
+/// The kind of an NPC, determining what happens when players talk to it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum NpcKind {
+  /// A merchant selling items.
+  Shop,
+  /// A vault keeper, storing items in the account's warehouse.
+  Warehouse,
+  /// A goblin, combining items in the chaos machine.
+  ChaosGoblin,
+  /// A guild master, creating guilds.
+  GuildMaster,
+}
+
+impl NpcKind {
+  /// Parses a kind from its name.
+  pub fn from_str(input: &str) -> Option<Self> {
+    match input {
+      "Shop" => Some(NpcKind::Shop),
+      "Warehouse" => Some(NpcKind::Warehouse),
+      "ChaosGoblin" => Some(NpcKind::ChaosGoblin),
+      "GuildMaster" => Some(NpcKind::GuildMaster),
+      _ => None,
+    }
+  }
+}
@@ -30,6 +30,23 @@ impl Position {
     ((self.x as isize - other.x as isize).abs() + (self.y as isize - other.y as isize).abs()) as u8
   }
 
+  /// Returns whether another point is within a square range or not.
+  pub fn is_within(&self, other: &Self, range: u8) -> bool {
+    (self.x as isize - other.x as isize).abs() <= range as isize
+      && (self.y as isize - other.y as isize).abs() <= range as isize
+  }
+
+  /// Returns the direction of an adjacent point, if any.
+  pub fn direction_to(&self, other: &Self) -> Option<Direction> {
+    if self == other {
+      return None;
+    }
+
+    Direction::values()
+      .find(|&&direction| self.advance(direction) == *other)
+      .cloned()
+  }
+
   /// Returns a coordinates neighbors within a specified distance.
   pub fn neighbors<'a>(&'a self, distance: u8) -> impl Iterator<Item = Self> + 'a {
     let (start_x, end_x) = (
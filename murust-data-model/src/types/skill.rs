@@ -0,0 +1,32 @@
+use types::Position;
+
+/// The ID of a skill, matching the client's skill numbers.
+pub type SkillId = u8;
+
+/// The shape of the area affected by a skill.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SkillArea {
+  /// Only the targeted object is affected.
+  Single,
+  /// All objects within a radius of the targeted position are affected.
+  Circle(u8),
+}
+
+impl SkillArea {
+  /// Parses an area from its name and size.
+  pub fn from_str(input: &str, size: u8) -> Option<Self> {
+    match input {
+      "Single" => Some(SkillArea::Single),
+      "Circle" => Some(SkillArea::Circle(size)),
+      _ => None,
+    }
+  }
+
+  /// Returns whether a position is within the area, centered at a position.
+  pub fn contains(&self, center: Position, position: Position) -> bool {
+    match *self {
+      SkillArea::Single => center == position,
+      SkillArea::Circle(radius) => center.is_within(&position, radius),
+    }
+  }
+}
@@ -0,0 +1,180 @@
+use error::Result;
+use murust_data_model::types::{Direction, ObjectId, Position};
+use murust_game_world::combat::AttackOutcome;
+use murust_game_world::{Entity, GameWorld};
+use super::{CharacterDurabilityAction, CharacterLootAction, CharacterProgressAction};
+use player::{Player, PlayerEvent, PlayerState};
+
+/// The maximum distance between an attacker and its target.
+const MAX_ATTACK_DISTANCE: u8 = 3;
+
+pub struct CharacterAttackAction {
+  progress_action: CharacterProgressAction,
+  loot_action: CharacterLootAction,
+  durability_action: CharacterDurabilityAction,
+}
+
+impl CharacterAttackAction {
+  pub fn new(
+    progress_action: CharacterProgressAction,
+    loot_action: CharacterLootAction,
+    durability_action: CharacterDurabilityAction,
+  ) -> Self {
+    CharacterAttackAction {
+      progress_action,
+      loot_action,
+      durability_action,
+    }
+  }
+
+  /// Lets a character attack a target with its equipped weapons.
+  ///
+  /// Targets that are dead, unknown or out of range are silently ignored,
+  /// since the client may act on a slightly outdated view of the world. The
+  /// same goes for attacks faster than the character's attack speed allows,
+  /// and for attacks between players when either stands in a safe zone.
+  pub fn attack(
+    &self,
+    player: &mut Player,
+    target: ObjectId,
+    animation: u8,
+    direction: Direction,
+  ) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let (id, map) = (player.id, player.character()?.map);
+    let worlds = player.context.worlds().clone();
+    let mut world = match worlds.get(map) {
+      Some(world) => world,
+      None => return Ok(()),
+    };
+
+    let (entity, target_entity) = match (
+      world.player(id),
+      world.monster(target).or_else(|| world.player(target)),
+    ) {
+      (Some(entity), Some(target_entity)) if entity != target_entity => (entity, target_entity),
+      _ => return Ok(()),
+    };
+
+    let in_range = match (world.location(entity), world.location(target_entity)) {
+      (Some(attacker), Some(defender)) => attacker
+        .position
+        .is_within(&defender.position, MAX_ATTACK_DISTANCE),
+      _ => false,
+    };
+
+    if !in_range {
+      return Ok(());
+    }
+
+    let outcome = match world.attack(entity, target_entity) {
+      Some(outcome) => outcome,
+      None => return Ok(()),
+    };
+
+    let hit = Hit::new(&world, target_entity, outcome);
+    let observers = world.observers(entity);
+    drop(world);
+
+    for (observer, view) in player.context.player_views(&observers) {
+      if let Err(error) = view.show_object_animation(id, direction, animation, target) {
+        warn!("Failed to show attack to player {}: {}", observer, error);
+      }
+    }
+
+    self.durability_action.wear_weapons(player)?;
+
+    match hit {
+      Some(hit) => {
+        hit.show(player, 0)?;
+        let hits = [hit];
+        self.progress_action.reward_kills(player, &hits)?;
+        self.loot_action.drop_loot(player, &hits)
+      },
+      None => Ok(()),
+    }
+  }
+}
+
+/// The outcome of an attack on a target.
+pub(super) struct Hit {
+  target: ObjectId,
+  position: Position,
+  outcome: AttackOutcome,
+  killed: bool,
+  monster_level: Option<u16>,
+  observers: Vec<ObjectId>,
+}
+
+impl Hit {
+  /// Captures the outcome of an attack on a target within a world.
+  pub fn new(world: &GameWorld, target: Entity, outcome: AttackOutcome) -> Option<Self> {
+    Some(Hit {
+      target: world.object_id(target)?,
+      position: world.location(target)?.position,
+      outcome,
+      killed: world.is_dead(target),
+      monster_level: world
+        .monster_definition(target)
+        .map(|definition| definition.level),
+      observers: world.observers(target),
+    })
+  }
+
+  /// Returns the target's object ID.
+  pub fn target(&self) -> ObjectId { self.target }
+
+  /// Returns the target's position when hit.
+  pub fn position(&self) -> Position { self.position }
+
+  /// Returns the damage dealt.
+  pub fn damage(&self) -> u32 {
+    match self.outcome {
+      AttackOutcome::Miss => 0,
+      AttackOutcome::Hit { damage, .. } => damage,
+    }
+  }
+
+  /// Returns the level of the target, if it was a monster that was killed.
+  pub fn killed_monster_level(&self) -> Option<u16> {
+    if self.killed {
+      self.monster_level
+    } else {
+      None
+    }
+  }
+
+  /// Shows the hit to the attacker and its target, and any killing blow to
+  /// everyone observing the target.
+  pub fn show(&self, player: &Player, skill: u8) -> Result<()> {
+    let (id, target) = (player.id, self.target);
+    player.player_view.show_object_hit(target, self.outcome)?;
+    if let Some(view) = player.context.player_view(target) {
+      if let Err(error) = view.show_object_hit(target, self.outcome) {
+        warn!("Failed to show damage to player {}: {}", target, error);
+      }
+
+      if self.damage() > 0 {
+        player.context.notify_player(target, PlayerEvent::Hit);
+      }
+    }
+
+    if self.killed {
+      player.player_view.show_object_killed(target, skill, id)?;
+      let observers = self
+        .observers
+        .iter()
+        .filter(|&&observer| observer != id)
+        .collect::<Vec<_>>();
+
+      for (observer, view) in player.context.player_views(observers) {
+        if let Err(error) = view.show_object_killed(target, skill, id) {
+          warn!("Failed to show death to player {}: {}", observer, error);
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,97 @@
+use chat::ChatChannel;
+use error::Result;
+use murust_data_model::types::ObjectId;
+use player::{Player, PlayerState};
+use std::time::Instant;
+
+pub struct CharacterChatAction;
+
+impl CharacterChatAction {
+  /// Sends a chat message to the channel described by its prefix.
+  ///
+  /// Normal messages are shown to all players observing the character, while
+  /// party and guild messages are shown to the members of its groups.
+  pub fn chat(&self, player: &mut Player, message: &str) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let message = match accept_message(player, message) {
+      Some(message) => message,
+      None => return Ok(()),
+    };
+
+    let channel = ChatChannel::of(&message);
+    let recipients = match channel {
+      ChatChannel::Normal => Self::nearby_players(player)?,
+      ChatChannel::Party => player.context.chat().groups().party_members(player.id),
+      ChatChannel::Guild => player.context.chat().groups().guild_members(player.id),
+    };
+
+    if recipients.is_empty() {
+      debug!("Player {} has no recipients within {:?} chat", player.id, channel);
+      return Ok(());
+    }
+
+    let name = &player.character()?.name;
+    for (recipient, view) in player.context.player_views(&recipients) {
+      if let Err(error) = view.show_chat_message(name, &message) {
+        warn!("Failed to show chat message to player {}: {}", recipient, error);
+      }
+    }
+    Ok(())
+  }
+
+  /// Whispers a message to a character, by its name.
+  ///
+  /// The character is informed if the receiving character is not online.
+  pub fn whisper(&self, player: &mut Player, name: &str, message: &str) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let message = match accept_message(player, message) {
+      Some(message) => message,
+      None => return Ok(()),
+    };
+
+    let (target, view) = match player.context.find_player_by_name(name) {
+      Some(target) => target,
+      None => return player.player_view.show_whisper_failure(),
+    };
+
+    let sender = &player.character()?.name;
+    if let Err(error) = view.show_whisper(sender, &message) {
+      warn!("Failed to show whisper to player {}: {}", target, error);
+    }
+    Ok(())
+  }
+
+  /// Returns the players observing a character, including itself.
+  fn nearby_players(player: &Player) -> Result<Vec<ObjectId>> {
+    let map = player.character()?.map;
+    let worlds = player.context.worlds().clone();
+    let world = match worlds.get(map) {
+      Some(world) => world,
+      None => return Ok(Vec::new()),
+    };
+
+    let mut observers = match world.player(player.id) {
+      Some(entity) => world.observers(entity),
+      None => return Ok(Vec::new()),
+    };
+    observers.push(player.id);
+    Ok(observers)
+  }
+}
+
+/// Returns a message as it should be delivered, unless it is empty, exceeds
+/// the player's rate limit or is dropped by the chat filter.
+fn accept_message(player: &Player, message: &str) -> Option<String> {
+  if message.trim().is_empty() {
+    return None;
+  }
+
+  let chat = player.context.chat();
+  if !chat.allow(player.id, Instant::now()) {
+    debug!("Player {} exceeded the chat rate limit", player.id);
+    return None;
+  }
+  chat.filter(message)
+}
@@ -0,0 +1,160 @@
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::types::ItemSlot;
+use murust_service::{taught_skill, Jewel, JewelService, Potion, PotionService, SkillService};
+use player::{Player, PlayerState};
+
+pub struct CharacterConsumeAction {
+  jewel_service: JewelService,
+  potion_service: PotionService,
+  skill_service: SkillService,
+}
+
+impl CharacterConsumeAction {
+  pub fn new(
+    jewel_service: JewelService,
+    potion_service: PotionService,
+    skill_service: SkillService,
+  ) -> Self {
+    CharacterConsumeAction {
+      jewel_service,
+      potion_service,
+      skill_service,
+    }
+  }
+
+  /// Uses an inventory item, e.g applying a jewel onto another item, drinking
+  /// a potion or learning a skill from an orb or scroll.
+  pub fn use_item(&self, player: &mut Player, source: u8, target: u8) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    // Equipped items cannot be used directly
+    let source = match source.checked_sub(ItemSlot::SIZE as u8) {
+      Some(source) => source,
+      None => return Ok(()),
+    };
+
+    let (jewel, potion, skill) = {
+      let item = player.character()?.inventory.get_item_at_slot(source);
+      (
+        item.and_then(Jewel::from_item),
+        item.and_then(Potion::from_item),
+        item.and_then(taught_skill),
+      )
+    };
+
+    match (jewel, potion, skill) {
+      (Some(_), _, _) => self.apply_jewel(player, source, target),
+      (_, Some(potion), _) => self.drink_potion(player, source, potion),
+      (_, _, Some(_)) => self.learn_skill(player, source),
+      _ => {
+        debug!("Player {} used an unusable item at {}", player.id, source);
+        Ok(())
+      },
+    }
+  }
+
+  /// Applies a jewel onto an inventory item, showing the upgraded item along
+  /// with the jewel's removal.
+  fn apply_jewel(&self, player: &mut Player, source: u8, target: u8) -> Result<()> {
+    let target = match target.checked_sub(ItemSlot::SIZE as u8) {
+      Some(target) => target,
+      None => return Ok(()),
+    };
+
+    let result = self
+      .jewel_service
+      .apply(player.character_mut()?, source, target)
+      .context("Jewel service failed to apply jewel")?;
+
+    if let Err(error) = result {
+      debug!("Player {} could not apply jewel: {:?}", player.id, error);
+      return Ok(());
+    }
+
+    let inventory = &player.character()?.inventory;
+    let item = inventory
+      .get_item_at_slot(target)
+      .expect("retrieving upgraded item");
+    let slot = inventory
+      .get_item_slot(item)
+      .expect("retrieving upgraded item");
+
+    let offset = ItemSlot::SIZE as u8;
+    player.player_view.show_item_removed(source + offset, true)?;
+    player.player_view.show_item_update(slot + offset, item)
+  }
+
+  /// Drinks a potion off a stack within the inventory, letting the character
+  /// recover its share of health or mana over time.
+  ///
+  /// The world is only locked to check whether the character is alive, and
+  /// once the potion has been consumed.
+  fn drink_potion(&self, player: &mut Player, source: u8, potion: Potion) -> Result<()> {
+    let map = player.character()?.map;
+    let worlds = player.context.worlds().clone();
+    let alive = worlds.get(map).map_or(false, |world| {
+      world
+        .player(player.id)
+        .map_or(false, |entity| !world.is_dead(entity))
+    });
+    if !alive {
+      return Ok(());
+    }
+
+    let result = self
+      .potion_service
+      .consume(player.character_mut()?, source)
+      .context("Potion service failed to consume potion")?;
+
+    if let Err(error) = result {
+      debug!("Player {} could not drink potion: {:?}", player.id, error);
+      return Ok(());
+    }
+
+    let mut world = match worlds.get(map) {
+      Some(world) => world,
+      None => return Ok(()),
+    };
+
+    let entity = match world.player(player.id) {
+      Some(entity) => entity,
+      None => return Ok(()),
+    };
+
+    let health = world
+      .health(entity)
+      .map_or(0, |health| health.maximum * potion.health() / 100);
+    let mana = world
+      .mana(entity)
+      .map_or(0, |mana| mana.maximum * potion.mana() / 100);
+    world.recover(entity, health, mana);
+    drop(world);
+
+    let slot = source + ItemSlot::SIZE as u8;
+    match player.character()?.inventory.get_item_at_slot(source) {
+      Some(stack) => player
+        .player_view
+        .show_stack_consumed(slot, stack.stack_size()),
+      None => player.player_view.show_item_removed(slot, true),
+    }
+  }
+
+  /// Learns the skill taught by an orb or scroll, showing the item's removal
+  /// along with the updated skill list.
+  fn learn_skill(&self, player: &mut Player, source: u8) -> Result<()> {
+    let result = self
+      .skill_service
+      .learn(player.character_mut()?, source)
+      .context("Skill service failed to learn skill")?;
+
+    if let Err(error) = result {
+      debug!("Player {} could not learn skill: {:?}", player.id, error);
+      return Ok(());
+    }
+
+    let slot = source + ItemSlot::SIZE as u8;
+    player.player_view.show_item_removed(slot, true)?;
+    player.player_view.update_skill_list(player)
+  }
+}
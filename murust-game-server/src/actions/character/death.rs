@@ -0,0 +1,61 @@
+use error::{cxerr, Result};
+use failure::ResultExt;
+use murust_data_model::types::{ObjectId, Position};
+use murust_service::{CharacterService, ExperienceService};
+use player::{Player, PlayerState};
+
+pub struct CharacterDeathAction {
+  experience_service: ExperienceService,
+  character_service: CharacterService,
+}
+
+impl CharacterDeathAction {
+  pub fn new(experience_service: ExperienceService, character_service: CharacterService) -> Self {
+    CharacterDeathAction {
+      experience_service,
+      character_service,
+    }
+  }
+
+  /// Applies the penalty of the character's death, ignoring any packets from
+  /// the player until it respawns.
+  pub fn die(&self, player: &mut Player, killer: ObjectId) -> Result<()> {
+    if !player.state.try_advance_to(PlayerState::Dead) {
+      return Err(cxerr(format!("Player died in state {}", player.state)));
+    }
+
+    let penalty = self
+      .experience_service
+      .apply_death_penalty(player.character_mut()?);
+    debug!(
+      "Player {} was killed by {}, losing {} experience and {} zen",
+      player.id, killer, penalty.experience, penalty.money
+    );
+
+    let character = player.character()?;
+    if penalty.experience > 0 {
+      self
+        .character_service
+        .save_progress(character)
+        .context("Character service failed to save progress")?;
+    }
+
+    if penalty.money > 0 {
+      self
+        .character_service
+        .save_money_change(character, -(penalty.money as i32))
+        .context("Character service failed to save money")?;
+    }
+    Ok(())
+  }
+
+  /// Revives the character at the position chosen by its world.
+  pub fn respawn(&self, player: &mut Player, position: Position) -> Result<()> {
+    if !player.state.try_advance_to(PlayerState::InGame) {
+      return Err(cxerr(format!("Player respawned in state {}", player.state)));
+    }
+
+    player.character_mut()?.position = position;
+    player.player_view.show_respawn(player.character()?)
+  }
+}
@@ -0,0 +1,54 @@
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::types::ItemSlot;
+use murust_service::ItemService;
+use player::Player;
+
+pub struct CharacterDurabilityAction {
+  item_service: ItemService,
+}
+
+impl CharacterDurabilityAction {
+  pub fn new(item_service: ItemService) -> Self { CharacterDurabilityAction { item_service } }
+
+  /// Wears down the character's weapons after an attack.
+  pub fn wear_weapons(&self, player: &mut Player) -> Result<()> {
+    let slots = player.character_mut()?.equipment.wear_weapons();
+    self.save_wear(player, &slots)
+  }
+
+  /// Wears down the character's armor after being hit.
+  pub fn wear_armor(&self, player: &mut Player) -> Result<()> {
+    let slots = player.character_mut()?.equipment.wear_armor();
+    self.save_wear(player, &slots)
+  }
+
+  /// Persists and shows the durability lost by equipped items, refreshing the
+  /// character's stats if any of them broke.
+  fn save_wear(&self, player: &Player, slots: &[ItemSlot]) -> Result<()> {
+    let character = player.character()?;
+    let mut broken = false;
+
+    for &slot in slots {
+      let item = character.equipment[slot]
+        .as_ref()
+        .expect("retrieving worn item");
+      self
+        .item_service
+        .save_durability(item)
+        .context("Item service failed to save durability")?;
+      player
+        .player_view
+        .show_item_durability(slot as u8, item.durability)?;
+      broken |= item.is_broken();
+    }
+
+    if broken {
+      player
+        .context
+        .worlds()
+        .refresh_player(player.id, character, false);
+    }
+    Ok(())
+  }
+}
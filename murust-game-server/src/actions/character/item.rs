@@ -0,0 +1,163 @@
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::types::{ItemLocation, ItemSlot};
+use murust_service::CharacterService;
+use player::{Player, PlayerState};
+use protocol::game::client::StorageType;
+use protocol::game::server::ViewportPlayer;
+use views::ItemMoveResult;
+
+pub struct CharacterItemAction {
+  character_service: CharacterService,
+}
+
+impl CharacterItemAction {
+  pub fn new(character_service: CharacterService) -> Self {
+    CharacterItemAction { character_service }
+  }
+
+  /// Moves an item between the character's inventory and equipment.
+  ///
+  /// Stacks moved onto another stack of the same kind are merged instead. Any
+  /// other storages are not supported yet, and refused like any invalid move.
+  pub fn move_item(
+    &self,
+    player: &mut Player,
+    source: (StorageType, u8),
+    target: (StorageType, u8),
+  ) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let (source, target) = match (Self::location(source), Self::location(target)) {
+      (Some(source), Some(target)) if source != target => (source, target),
+      _ => {
+        return player
+          .player_view
+          .show_item_move_result(ItemMoveResult::Failure)
+      },
+    };
+
+    if let (ItemLocation::Inventory(source), ItemLocation::Inventory(target)) = (source, target) {
+      if self.merge_stacks(player, source, target)? {
+        return Ok(());
+      }
+    }
+
+    if !player.character_mut()?.move_item(source, target) {
+      return player
+        .player_view
+        .show_item_move_result(ItemMoveResult::Failure);
+    }
+
+    let saved = self
+      .character_service
+      .save_item_move(player.character()?, source, target);
+    if saved.is_err() {
+      // Keep the character in sync with the underlying storage
+      player.character_mut()?.move_item(target, source);
+    }
+    saved.context("Character service failed to save item move")?;
+
+    let character = player.character()?;
+
+    let (slot, item) = match target {
+      ItemLocation::Equipment(slot) => (slot as u8, character.equipment[slot].as_ref()),
+      ItemLocation::Inventory(slot) => (
+        slot + ItemSlot::SIZE as u8,
+        character.inventory.get_item_at_slot(slot),
+      ),
+    };
+    let item = item.expect("retrieving moved item");
+    player
+      .player_view
+      .show_item_move_result(ItemMoveResult::Success(slot, item))?;
+
+    let equipment_changed = match (source, target) {
+      (ItemLocation::Inventory(_), ItemLocation::Inventory(_)) => false,
+      _ => true,
+    };
+
+    if equipment_changed {
+      let appearance = ViewportPlayer::new(player.id, character);
+      let worlds = player.context.worlds();
+      worlds.refresh_player(player.id, character, false);
+      player
+        .context
+        .update_player_appearance(player.id, appearance.clone());
+
+      let observers = worlds
+        .get(character.map)
+        .and_then(|world| world.player(player.id).map(|entity| world.observers(entity)))
+        .unwrap_or_default();
+      for (observer, view) in player.context.player_views(&observers) {
+        if let Err(error) = view.show_viewport_players(vec![appearance.clone()]) {
+          warn!("Failed to show equipment to player {}: {}", observer, error);
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Merges an inventory stack onto another of the same kind, returning
+  /// whether they were merged.
+  ///
+  /// The moved stack is returned to its slot, showing what remains of it.
+  fn merge_stacks(&self, player: &mut Player, source: u8, target: u8) -> Result<bool> {
+    let source_id = match player.character()?.inventory.get_item_at_slot(source) {
+      Some(item) => item.id,
+      None => return Ok(false),
+    };
+
+    {
+      let character = player.character_mut()?;
+      let storage = character.inventory.storage.clone();
+      if character.inventory.merge_items(source, target).is_none() {
+        return Ok(false);
+      }
+
+      // The stacks are restored unless the merge is persisted
+      let saved = self
+        .character_service
+        .save_item_merge(character, source_id, target);
+      if saved.is_err() {
+        character.inventory.storage = storage;
+      }
+      saved.context("Character service failed to save item merge")?;
+    }
+
+    let inventory = &player.character()?.inventory;
+
+    let offset = ItemSlot::SIZE as u8;
+    player
+      .player_view
+      .show_item_move_result(ItemMoveResult::Merged)?;
+    match inventory.get_item(source_id) {
+      Some(stack) => player
+        .player_view
+        .show_item_durability(source + offset, stack.durability)?,
+      None => player.player_view.show_item_removed(source + offset, false)?,
+    }
+
+    let stack = inventory
+      .get_item_at_slot(target)
+      .expect("retrieving merged stack");
+    let slot = inventory
+      .get_item_slot(stack)
+      .expect("retrieving merged stack");
+    player
+      .player_view
+      .show_item_durability(slot + offset, stack.durability)?;
+    Ok(true)
+  }
+
+  /// Returns the location of a storage slot, if supported.
+  fn location((storage, slot): (StorageType, u8)) -> Option<ItemLocation> {
+    match storage {
+      StorageType::Equipment => ItemSlot::values()
+        .find(|&&item_slot| item_slot as u8 == slot)
+        .map(|&item_slot| ItemLocation::Equipment(item_slot)),
+      StorageType::Inventory => Some(ItemLocation::Inventory(slot)),
+      _ => None,
+    }
+  }
+}
@@ -5,7 +5,11 @@ pub struct CharacterListAction;
 
 impl CharacterListAction {
   pub fn list(&self, player: &mut Player) -> Result<()> {
+    let leaving = player.state == PlayerState::InGame;
     if player.state.try_advance_to(PlayerState::CharacterSelection) {
+      if leaving {
+        player.leave_world()?;
+      }
       player.player_view.show_character_list(player)?;
     }
     Ok(())
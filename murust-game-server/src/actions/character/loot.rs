@@ -0,0 +1,177 @@
+use super::attack::Hit;
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::entities::Loot;
+use murust_data_model::types::{ItemSlot, ObjectId};
+use murust_game_world::PickupError;
+use murust_service::{CharacterService, DropService, ItemService, MAX_MONEY};
+use player::{Player, PlayerState};
+use views::ItemPickupResult;
+
+/// The loot stored by a character.
+enum PickedUp {
+  /// An item, stored at an inventory slot.
+  Item(u8),
+  /// The character's new total of zen.
+  Money(u32),
+}
+
+pub struct CharacterLootAction {
+  drop_service: DropService,
+  item_service: ItemService,
+  character_service: CharacterService,
+}
+
+impl CharacterLootAction {
+  pub fn new(
+    drop_service: DropService,
+    item_service: ItemService,
+    character_service: CharacterService,
+  ) -> Self {
+    CharacterLootAction {
+      drop_service,
+      item_service,
+      character_service,
+    }
+  }
+
+  /// Drops the loot of each monster killed by the player, owned by the player
+  /// for a short while.
+  ///
+  /// The loot is rolled before locking the world, since it may require the
+  /// drop service to access the underlying storage.
+  pub(super) fn drop_loot(&self, player: &Player, hits: &[Hit]) -> Result<()> {
+    let mut drops = Vec::new();
+    for hit in hits {
+      let monster_level = match hit.killed_monster_level() {
+        Some(level) => level,
+        None => continue,
+      };
+
+      let loot = self
+        .drop_service
+        .roll(monster_level)
+        .context("Drop service failed to roll loot")?;
+      if let Some(loot) = loot {
+        drops.push((loot, hit.position()));
+      }
+    }
+
+    if drops.is_empty() {
+      return Ok(());
+    }
+
+    let (id, map) = (player.id, player.character()?.map);
+    let worlds = player.context.worlds().clone();
+    let mut world = match worlds.get(map) {
+      Some(world) => world,
+      None => return Ok(()),
+    };
+
+    let owner = world.player(id);
+    for (loot, position) in drops {
+      world.drop_loot(loot, position, owner);
+    }
+    Ok(())
+  }
+
+  /// Lets a character pick up a ground item, storing it in its inventory.
+  ///
+  /// The loot is persisted before it leaves the ground, so any loot that
+  /// cannot be saved remains where it lies.
+  pub fn pick_up(&self, player: &mut Player, item: ObjectId) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let (id, map) = (player.id, player.character()?.map);
+    let worlds = player.context.worlds().clone();
+    let mut failure = None;
+    let result = {
+      let mut world = match worlds.get(map) {
+        Some(world) => world,
+        None => return player.player_view.show_item_pickup_result(ItemPickupResult::Failure),
+      };
+
+      let entity = match world.player(id) {
+        Some(entity) => entity,
+        None => return player.player_view.show_item_pickup_result(ItemPickupResult::Failure),
+      };
+
+      let character = player.character_mut()?;
+      world.pick_up(entity, item, |loot| match loot {
+        Loot::Item(item) => {
+          let item_id = item.id;
+          character.inventory.add_item(item).map_err(Loot::Item)?;
+          let saved = {
+            let inventory = &character.inventory;
+            let (slot, item) = inventory
+              .into_iter()
+              .find(|&(_, item)| item.id == item_id)
+              .expect("retrieving added inventory item");
+            self
+              .item_service
+              .add_to_inventory(inventory.id, slot, item)
+              .map(|_| slot)
+              .context("Item service failed to add item to inventory")
+          };
+
+          saved.map(PickedUp::Item).map_err(|error| {
+            failure = Some(error);
+            let item = character
+              .inventory
+              .remove_item(item_id)
+              .expect("removing unsaved inventory item");
+            Loot::Item(item)
+          })
+        },
+        Loot::Money(amount) => match character.inventory.money.checked_add(amount) {
+          Some(money) if money <= MAX_MONEY => {
+            let saved = self
+              .character_service
+              .save_money_change(character, amount as i32)
+              .context("Character service failed to save money");
+            if let Err(error) = saved {
+              failure = Some(error);
+              return Err(Loot::Money(amount));
+            }
+
+            character.inventory.money = money;
+            Ok(PickedUp::Money(money))
+          },
+          _ => Err(Loot::Money(amount)),
+        },
+      })
+    };
+
+    if let Some(error) = failure {
+      player
+        .player_view
+        .show_item_pickup_result(ItemPickupResult::Failure)?;
+      return Err(error.into());
+    }
+
+    match result {
+      Ok(PickedUp::Item(slot)) => {
+        let item = player
+          .character()?
+          .inventory
+          .get_item_at_slot(slot)
+          .expect("retrieving picked up item");
+        let slot = slot + ItemSlot::SIZE as u8;
+        player
+          .player_view
+          .show_item_pickup_result(ItemPickupResult::Item(slot, item))
+      },
+      Ok(PickedUp::Money(money)) => player
+        .player_view
+        .show_item_pickup_result(ItemPickupResult::Money(money)),
+      Err(error) => {
+        if error != PickupError::Refused {
+          debug!("Player {} could not pick up item {}: {:?}", id, item, error);
+        }
+        player
+          .player_view
+          .show_item_pickup_result(ItemPickupResult::Failure)
+      },
+    }
+  }
+}
@@ -1,9 +1,31 @@
+pub use self::attack::CharacterAttackAction;
+pub use self::chat::CharacterChatAction;
+pub use self::consume::CharacterConsumeAction;
 pub use self::create::CharacterCreateAction;
+pub use self::death::CharacterDeathAction;
 pub use self::delete::CharacterDeleteAction;
+pub use self::durability::CharacterDurabilityAction;
+pub use self::item::CharacterItemAction;
 pub use self::list::CharacterListAction;
+pub use self::loot::CharacterLootAction;
+pub use self::movement::CharacterMoveAction;
+pub use self::progress::CharacterProgressAction;
 pub use self::select::CharacterSelectAction;
+pub use self::skill::CharacterSkillAction;
+pub use self::trade::CharacterTradeAction;
 
+mod attack;
+mod chat;
+mod consume;
 mod create;
+mod death;
 mod delete;
+mod durability;
+mod item;
 mod list;
+mod loot;
+mod movement;
+mod progress;
 mod select;
+mod skill;
+mod trade;
@@ -0,0 +1,138 @@
+use error::Result;
+use murust_data_model::types::{Direction, Position};
+use murust_game_world::find_path;
+use murust_game_world::resources::Terrain;
+use player::{Player, PlayerState};
+
+/// The maximum number of steps a client may send in a single path.
+const MAX_PATH_LENGTH: usize = 15;
+
+/// The maximum distance a path may start from the server's position.
+const MAX_POSITION_DEVIATION: u8 = 3;
+
+pub struct CharacterMoveAction;
+
+impl CharacterMoveAction {
+  /// Moves a character along a client supplied path.
+  ///
+  /// The first position of the path is where the client believes the character
+  /// stands. The server's position remains authoritative, so the character
+  /// walks there from its current position before following the path.
+  ///
+  /// An invalid path, or one redirecting the character faster than it walks,
+  /// snaps the client back to the server's position.
+  pub fn walk(&self, player: &mut Player, direction: Direction, path: &[Position]) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let (id, map) = (player.id, player.character()?.map);
+    let worlds = player.context.worlds().clone();
+    let mut world = match worlds.get(map) {
+      Some(world) => world,
+      None => return Ok(()),
+    };
+
+    let entity = match world.player(id) {
+      Some(entity) if !world.is_dead(entity) => entity,
+      _ => return Ok(()),
+    };
+
+    let current = world
+      .location(entity)
+      .map(|location| location.position)
+      .unwrap_or(player.character()?.position);
+
+    let steps = {
+      let terrain = world.terrain();
+      if Self::is_valid_path(&terrain, current, path) {
+        let deviation = MAX_POSITION_DEVIATION as usize;
+        let is_walkable = |position| terrain.is_walkable(position);
+        find_path(is_walkable, current, path[0], deviation).map(|mut steps| {
+          steps.extend_from_slice(&path[1..]);
+          steps
+        })
+      } else {
+        None
+      }
+    };
+
+    let accepted = match steps {
+      Some(steps) => world.walk(entity, current, direction, steps),
+      None => false,
+    };
+
+    player.character_mut()?.position = current;
+    if !accepted {
+      world.stop(entity);
+      return player.player_view.show_object_position(id, current);
+    }
+
+    let destination = world.destination(entity).unwrap_or(current);
+    let observers = world.observers(entity);
+    drop(world);
+
+    for (observer, view) in player.context.player_views(&observers) {
+      if let Err(error) = view.show_object_move(id, destination, direction) {
+        warn!("Failed to show movement to player {}: {}", observer, error);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Returns whether a path is walkable, continuous and starts near a position.
+  fn is_valid_path(terrain: &Terrain, current: Position, path: &[Position]) -> bool {
+    if path.is_empty() || path.len() > MAX_PATH_LENGTH + 1 {
+      return false;
+    }
+
+    path[0].is_within(&current, MAX_POSITION_DEVIATION)
+      && path.windows(2).all(|step| step[0].direction_to(&step[1]).is_some())
+      && path.iter().all(|&position| terrain.is_walkable(position))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use murust_game_world::resources::TerrainAttribute;
+
+  fn path(positions: &[(u8, u8)]) -> Vec<Position> {
+    positions.iter().map(|&(x, y)| Position::new(x, y)).collect()
+  }
+
+  #[test]
+  fn paths_are_continuous_and_start_nearby() {
+    let terrain = Terrain::new(0);
+    let current = Position::new(100, 100);
+
+    let valid = path(&[(101, 100), (102, 101), (103, 102)]);
+    assert!(CharacterMoveAction::is_valid_path(&terrain, current, &valid));
+
+    let gap = path(&[(100, 100), (102, 100)]);
+    assert!(!CharacterMoveAction::is_valid_path(&terrain, current, &gap));
+
+    let distant = path(&[(104, 100), (105, 100)]);
+    assert!(!CharacterMoveAction::is_valid_path(&terrain, current, &distant));
+
+    assert!(!CharacterMoveAction::is_valid_path(&terrain, current, &[]));
+  }
+
+  #[test]
+  fn paths_are_bounded_and_walkable() {
+    let current = Position::new(100, 100);
+    let long = (0..MAX_PATH_LENGTH as u8 + 2)
+      .map(|step| Position::new(100 + step, 100))
+      .collect::<Vec<_>>();
+    assert!(!CharacterMoveAction::is_valid_path(&Terrain::new(0), current, &long));
+    assert!(CharacterMoveAction::is_valid_path(
+      &Terrain::new(0),
+      current,
+      &long[..MAX_PATH_LENGTH + 1]
+    ));
+
+    let mut terrain = Terrain::new(0);
+    terrain.set_attributes(Position::new(101, 100), TerrainAttribute::Blocked);
+    let blocked = path(&[(100, 100), (101, 100), (102, 100)]);
+    assert!(!CharacterMoveAction::is_valid_path(&terrain, current, &blocked));
+  }
+}
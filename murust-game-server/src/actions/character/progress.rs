@@ -0,0 +1,94 @@
+use super::attack::Hit;
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::entities::CharacterAttributes;
+use murust_data_model::types::CharacterStat;
+use murust_service::{CharacterService, ExperienceService};
+use player::{Player, PlayerState};
+use views::StatIncreaseResult;
+
+pub struct CharacterProgressAction {
+  experience_service: ExperienceService,
+  character_service: CharacterService,
+}
+
+impl CharacterProgressAction {
+  pub fn new(experience_service: ExperienceService, character_service: CharacterService) -> Self {
+    CharacterProgressAction {
+      experience_service,
+      character_service,
+    }
+  }
+
+  /// Spends one of the character's level-up points on a stat.
+  pub fn add_stat_point(&self, player: &mut Player, stat: CharacterStat) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    if !player.character_mut()?.add_stat_point(stat) {
+      return player
+        .player_view
+        .show_stat_increase_result(StatIncreaseResult::Failure);
+    }
+
+    self.save_progress(player)?;
+    let character = player.character()?;
+    player
+      .context
+      .worlds()
+      .refresh_player(player.id, character, false);
+    player
+      .player_view
+      .show_stat_increase_result(StatIncreaseResult::Success(character, stat))
+  }
+
+  /// Awards the character experience for each monster killed, leveling up
+  /// as the experience accumulates.
+  pub(super) fn reward_kills(&self, player: &mut Player, hits: &[Hit]) -> Result<()> {
+    let mut rewarded = false;
+
+    for hit in hits {
+      let monster_level = match hit.killed_monster_level() {
+        Some(level) => level,
+        None => continue,
+      };
+
+      let experience = self
+        .experience_service
+        .monster_experience(player.character()?.level, monster_level);
+      let levels = self
+        .experience_service
+        .gain_experience(player.character_mut()?, experience);
+
+      player
+        .player_view
+        .show_experience_gain(hit.target(), experience, hit.damage())?;
+      if levels > 0 {
+        let character = player.character()?;
+        player
+          .context
+          .worlds()
+          .refresh_player(player.id, character, true);
+        let attributes = CharacterAttributes::new(character);
+        player.player_view.show_level_up(character)?;
+        player
+          .player_view
+          .update_mana_ability(attributes.max_mana, attributes.max_ability)?;
+      }
+      rewarded = true;
+    }
+
+    if rewarded {
+      self.save_progress(player)?;
+    }
+    Ok(())
+  }
+
+  /// Persists the character's level, experience and stats.
+  fn save_progress(&self, player: &Player) -> Result<()> {
+    self
+      .character_service
+      .save_progress(player.character()?)
+      .context("Character service failed to save progress")?;
+    Ok(())
+  }
+}
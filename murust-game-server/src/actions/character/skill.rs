@@ -0,0 +1,132 @@
+use super::attack::Hit;
+use super::{CharacterDurabilityAction, CharacterLootAction, CharacterProgressAction};
+use error::Result;
+use murust_data_model::types::{ObjectId, Position, SkillId};
+use murust_game_world::SkillTarget;
+use player::{Player, PlayerState};
+
+/// The target of a skill, as requested by a client.
+#[derive(Copy, Clone)]
+enum Target {
+  Object(ObjectId),
+  Area { center: Position, rotation: u8 },
+}
+
+pub struct CharacterSkillAction {
+  progress_action: CharacterProgressAction,
+  loot_action: CharacterLootAction,
+  durability_action: CharacterDurabilityAction,
+}
+
+impl CharacterSkillAction {
+  pub fn new(
+    progress_action: CharacterProgressAction,
+    loot_action: CharacterLootAction,
+    durability_action: CharacterDurabilityAction,
+  ) -> Self {
+    CharacterSkillAction {
+      progress_action,
+      loot_action,
+      durability_action,
+    }
+  }
+
+  /// Lets a character use a skill on a target.
+  pub fn use_on_target(&self, player: &mut Player, skill: SkillId, target: ObjectId) -> Result<()> {
+    self.use_skill(player, skill, Target::Object(target))
+  }
+
+  /// Lets a character use a skill on an area.
+  pub fn use_on_area(
+    &self,
+    player: &mut Player,
+    skill: SkillId,
+    center: Position,
+    rotation: u8,
+  ) -> Result<()> {
+    self.use_skill(player, skill, Target::Area { center, rotation })
+  }
+
+  /// Uses a skill learned by the player's character.
+  ///
+  /// Skills that are unknown, not learned or currently unusable are silently
+  /// ignored, since the client may act on a slightly outdated state.
+  fn use_skill(&self, player: &mut Player, skill: SkillId, target: Target) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let skill = match player.context.skills().get(skill) {
+      Some(skill) => skill,
+      None => return Ok(()),
+    };
+
+    {
+      let character = player.character()?;
+      if !character.skills.contains(&skill.id) || !skill.is_usable_by(character) {
+        return Ok(());
+      }
+    }
+
+    let (id, map) = (player.id, player.character()?.map);
+    let worlds = player.context.worlds().clone();
+    let mut world = match worlds.get(map) {
+      Some(world) => world,
+      None => return Ok(()),
+    };
+
+    let entity = match world.player(id) {
+      Some(entity) => entity,
+      None => return Ok(()),
+    };
+
+    let skill_target = match target {
+      Target::Object(object) => match world.monster(object).or_else(|| world.player(object)) {
+        Some(target_entity) => SkillTarget::Object(target_entity),
+        None => return Ok(()),
+      },
+      Target::Area { center, .. } => SkillTarget::Position(center),
+    };
+
+    let hits = match world.use_skill(entity, &skill, skill_target) {
+      Ok(hits) => hits,
+      Err(error) => {
+        debug!("Player {} could not use skill {}: {:?}", id, skill.id, error);
+        return Ok(());
+      },
+    };
+
+    let hits = hits
+      .into_iter()
+      .filter_map(|(target_entity, outcome)| Hit::new(&world, target_entity, outcome))
+      .collect::<Vec<_>>();
+    let mut observers = world.observers(entity);
+    observers.push(id);
+    let mana = world.mana(entity).map_or(0, |mana| mana.current);
+    let ability = world.ability(entity).map_or(0, |ability| ability.current);
+    drop(world);
+
+    for (observer, view) in player.context.player_views(&observers) {
+      let result = match target {
+        Target::Object(object) => view.show_object_skill(skill.id, id, object),
+        Target::Area { center, rotation } => {
+          view.show_object_area_skill(skill.id, id, center, rotation)
+        },
+      };
+
+      if let Err(error) = result {
+        warn!("Failed to show skill to player {}: {}", observer, error);
+      }
+    }
+
+    for hit in &hits {
+      hit.show(player, skill.id)?;
+    }
+    player.player_view.update_mana_ability(mana, ability)?;
+
+    // Only skills relying on weapons wear them down
+    if !skill.magic {
+      self.durability_action.wear_weapons(player)?;
+    }
+    self.progress_action.reward_kills(player, &hits)?;
+    self.loot_action.drop_loot(player, &hits)
+  }
+}
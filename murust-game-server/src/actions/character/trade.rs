@@ -0,0 +1,408 @@
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::entities::{item, TradeOffer};
+use murust_data_model::types::{ItemSlot, ObjectId};
+use murust_game_world::VIEW_RANGE;
+use murust_service::{CharacterService, TradeError, TradeService};
+use player::{Player, PlayerEvent, PlayerState};
+use protocol::game::client::StorageType;
+use protocol::game::server::TradeOutcome;
+use trades::TradeRequest;
+use views::{ItemMoveResult, PlayerView};
+
+pub struct CharacterTradeAction {
+  trade_service: TradeService,
+  character_service: CharacterService,
+}
+
+impl CharacterTradeAction {
+  pub fn new(trade_service: TradeService, character_service: CharacterService) -> Self {
+    CharacterTradeAction {
+      trade_service,
+      character_service,
+    }
+  }
+
+  /// Asks another player whether to trade with the character.
+  pub fn request(&self, player: &mut Player, target: ObjectId) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let view = match player.context.player_view(target) {
+      Some(view) => view,
+      None => return Ok(()),
+    };
+
+    if !Self::is_nearby(player, target)? {
+      debug!("Player {} requested a trade with distant {}", player.id, target);
+      return Ok(());
+    }
+
+    let character = player.character()?;
+    let request = TradeRequest {
+      from: player.id,
+      name: character.name.clone(),
+      level: character.level,
+      inventory: character.inventory.id,
+    };
+
+    if player.context.trades().request(target, request) {
+      view.show_trade_request(&character.name)
+    } else {
+      debug!("Player {} could not request a trade with {}", player.id, target);
+      Ok(())
+    }
+  }
+
+  /// Accepts or declines the trade requested from the character.
+  pub fn respond(&self, player: &mut Player, accepted: bool) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let character = player.character()?;
+    let request = if accepted {
+      let inventory = character.inventory.id;
+      player.context.trades().accept(player.id, inventory)
+    } else {
+      player.context.trades().decline(player.id)
+    };
+
+    let request = match request {
+      Some(request) => request,
+      None => return Ok(()),
+    };
+
+    show_partner(player, request.from, |view| {
+      view.show_trade_response(accepted, &character.name, character.level)
+    });
+
+    if accepted {
+      player
+        .player_view
+        .show_trade_response(true, &request.name, request.level)?;
+    }
+    Ok(())
+  }
+
+  /// Moves an item between the character's inventory and its side of the
+  /// trade, or within the trade itself.
+  ///
+  /// Offered items remain in the inventory, locked, until the trade has ended.
+  pub fn move_item(
+    &self,
+    player: &mut Player,
+    source: (StorageType, u8),
+    target: (StorageType, u8),
+  ) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    match (source, target) {
+      ((StorageType::Inventory, source), (StorageType::Trade, target)) => {
+        Self::offer_item(player, source, target)
+      },
+      ((StorageType::Trade, source), (StorageType::Inventory, _)) => {
+        Self::withdraw_item(player, source)
+      },
+      ((StorageType::Trade, source), (StorageType::Trade, target)) => {
+        Self::move_offered_item(player, source, target)
+      },
+      _ => player
+        .player_view
+        .show_item_move_result(ItemMoveResult::Failure),
+    }
+  }
+
+  /// Offers an amount of zen to the trade partner.
+  pub fn offer_money(&self, player: &mut Player, money: u32) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let partner = if money <= player.character()?.inventory.money {
+      player
+        .context
+        .trades()
+        .update(player.id, |offer, other| {
+          offer.money = money;
+          unconfirm(offer, other);
+        })
+        .map(|(partner, _)| partner)
+    } else {
+      None
+    };
+
+    player
+      .player_view
+      .show_trade_money_result(partner.is_some())?;
+    match partner {
+      Some(partner) => show_offer_changed(player, partner, |view| {
+        view.show_trade_partner_money(money)
+      }),
+      None => Ok(()),
+    }
+  }
+
+  /// Confirms or retracts the character's acceptance of the current offers,
+  /// exchanging them once both sides have confirmed.
+  pub fn confirm(&self, player: &mut Player, confirmed: bool) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let partner = match player
+      .context
+      .trades()
+      .update(player.id, |offer, _| offer.confirmed = confirmed)
+    {
+      Some((partner, _)) => partner,
+      None => return Ok(()),
+    };
+    show_partner(player, partner, |view| view.show_trade_button(confirmed));
+
+    let (side, other) = match player.context.trades().complete(player.id) {
+      Some(sides) => sides,
+      None => return Ok(()),
+    };
+
+    let result = self
+      .trade_service
+      .exchange(
+        (side.inventory, &side.offer),
+        (other.inventory, &other.offer),
+      )
+      .context("Trade service failed to exchange offers")?;
+
+    let outcome = match result {
+      Ok(()) => TradeOutcome::Success,
+      Err(error) => {
+        debug!("Player {} could not complete trade: {:?}", player.id, error);
+        match error {
+          TradeError::InventoryFull => TradeOutcome::InventoryFull,
+          _ => TradeOutcome::Cancelled,
+        }
+      },
+    };
+
+    let event = PlayerEvent::TradeClosed {
+      outcome,
+      offered: other.offer.item_ids(),
+    };
+    player.context.notify_player(partner, event);
+    self.close(player, outcome, &side.offer.item_ids())
+  }
+
+  /// Cancels the character's trade, or any pending trade request.
+  pub fn cancel(&self, player: &mut Player) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    match player.context.trades().cancel(player.id) {
+      Some((side, other)) => {
+        let outcome = TradeOutcome::Cancelled;
+        let event = PlayerEvent::TradeClosed {
+          outcome,
+          offered: other.offer.item_ids(),
+        };
+        player.context.notify_player(side.partner, event);
+        self.close(player, outcome, &side.offer.item_ids())
+      },
+      None => Ok(()),
+    }
+  }
+
+  /// Closes the character's trade window once the trade has ended.
+  ///
+  /// A completed trade is only persisted, so the character's inventory is
+  /// reloaded to reflect it, keeping the items in the chaos box locked.
+  /// Otherwise the offered items are simply unlocked. Either way, the
+  /// character is settled and the client is shown where its items have ended
+  /// up.
+  pub fn close(
+    &self,
+    player: &mut Player,
+    outcome: TradeOutcome,
+    offered: &[item::Id],
+  ) -> Result<()> {
+    if outcome == TradeOutcome::Success {
+      self
+        .character_service
+        .refresh_inventory(player.character_mut()?)
+        .context("Character service failed to refresh inventory")?;
+
+      let boxed = player
+        .chaos_box
+        .into_iter()
+        .map(|(_, item)| item.id)
+        .collect::<Vec<_>>();
+      let inventory = &mut player.character_mut()?.inventory;
+      for item_id in boxed {
+        inventory.lock_item(item_id);
+      }
+    } else {
+      let inventory = &mut player.character_mut()?.inventory;
+      for &item_id in offered {
+        inventory.unlock_item(item_id);
+      }
+    }
+
+    player.context.trades().settle(player.id);
+    player.player_view.show_trade_result(outcome)?;
+    player.player_view.update_inventory_list(player)?;
+    player
+      .player_view
+      .show_money(player.character()?.inventory.money)
+  }
+
+  /// Returns whether another player is within view of the character, in the
+  /// same world.
+  fn is_nearby(player: &Player, target: ObjectId) -> Result<bool> {
+    let world = match player.context.worlds().get(player.character()?.map) {
+      Some(world) => world,
+      None => return Ok(false),
+    };
+
+    let locations = (
+      world.player(player.id).and_then(|entity| world.location(entity)),
+      world.player(target).and_then(|entity| world.location(entity)),
+    );
+    Ok(match locations {
+      (Some(location), Some(other)) => location.position.is_within(&other.position, VIEW_RANGE),
+      _ => false,
+    })
+  }
+
+  /// Offers an inventory item to the trade partner.
+  fn offer_item(player: &mut Player, source: u8, target: u8) -> Result<()> {
+    let item = {
+      let inventory = &player.character()?.inventory;
+      match inventory.get_item_at_slot(source) {
+        Some(item) if !inventory.is_item_locked(item.id) => Some(item.clone()),
+        _ => None,
+      }
+    };
+
+    let offered = item.and_then(|item| {
+      let item_id = item.id;
+      player
+        .context
+        .trades()
+        .update(player.id, |offer, other| {
+          let added = offer.add_item_at_slot(target, item).is_ok();
+          if added {
+            unconfirm(offer, other);
+          }
+          added
+        })
+        .and_then(|(partner, added)| if added { Some((partner, item_id)) } else { None })
+    });
+
+    let (partner, item_id) = match offered {
+      Some(offered) => offered,
+      None => {
+        return player
+          .player_view
+          .show_item_move_result(ItemMoveResult::Failure)
+      },
+    };
+
+    player.character_mut()?.inventory.lock_item(item_id);
+    let item = player
+      .character()?
+      .inventory
+      .get_item_at_slot(source)
+      .expect("retrieving offered item");
+    player
+      .player_view
+      .show_item_move_result(ItemMoveResult::Trade(target, item))?;
+    show_offer_changed(player, partner, |view| view.show_trade_item_added(target, item))
+  }
+
+  /// Withdraws an offered item, leaving it at its original inventory slot.
+  fn withdraw_item(player: &mut Player, source: u8) -> Result<()> {
+    let withdrawn = player.context.trades().update(player.id, |offer, other| {
+      let item = offer.remove_item_at_slot(source);
+      if item.is_some() {
+        unconfirm(offer, other);
+      }
+      item
+    });
+
+    let (partner, item) = match withdrawn {
+      Some((partner, Some(item))) => (partner, item),
+      _ => {
+        return player
+          .player_view
+          .show_item_move_result(ItemMoveResult::Failure)
+      },
+    };
+
+    player.character_mut()?.inventory.unlock_item(item.id);
+    let inventory = &player.character()?.inventory;
+    let slot = inventory
+      .get_item_slot(&item)
+      .expect("retrieving withdrawn item");
+    let item = inventory
+      .get_item_at_slot(slot)
+      .expect("retrieving withdrawn item");
+
+    let slot = slot + ItemSlot::SIZE as u8;
+    player
+      .player_view
+      .show_item_move_result(ItemMoveResult::Success(slot, item))?;
+    show_offer_changed(player, partner, |view| view.show_trade_item_removed(source))
+  }
+
+  /// Moves an offered item within the trade.
+  fn move_offered_item(player: &mut Player, source: u8, target: u8) -> Result<()> {
+    let moved = player.context.trades().update(player.id, |offer, other| {
+      let item = offer
+        .move_item_between_slots(source, target)
+        .and_then(|_| offer.get_item_at_slot(target).cloned());
+      if item.is_some() {
+        unconfirm(offer, other);
+      }
+      item
+    });
+
+    let (partner, item) = match moved {
+      Some((partner, Some(item))) => (partner, item),
+      _ => {
+        return player
+          .player_view
+          .show_item_move_result(ItemMoveResult::Failure)
+      },
+    };
+
+    player
+      .player_view
+      .show_item_move_result(ItemMoveResult::Trade(target, &item))?;
+    show_offer_changed(player, partner, |view| {
+      view.show_trade_item_removed(source)?;
+      view.show_trade_item_added(target, &item)
+    })
+  }
+}
+
+/// Retracts the confirmation of both sides, once either offer has changed.
+fn unconfirm(offer: &mut TradeOffer, other: &mut TradeOffer) {
+  offer.confirmed = false;
+  other.confirmed = false;
+}
+
+/// Shows a change of a player's offer to its trade partner, along with the
+/// retracted confirmation of both sides.
+fn show_offer_changed<F>(player: &Player, partner: ObjectId, show: F) -> Result<()>
+where
+  F: FnOnce(&PlayerView) -> Result<()>,
+{
+  show_partner(player, partner, |view| {
+    show(view)?;
+    view.show_trade_button(false)
+  });
+  player.player_view.show_trade_button(false)
+}
+
+/// Shows something to a player's trade partner, if still connected.
+fn show_partner<F>(player: &Player, partner: ObjectId, show: F)
+where
+  F: FnOnce(&PlayerView) -> Result<()>,
+{
+  if let Some(view) = player.context.player_view(partner) {
+    if let Err(error) = show(&view) {
+      warn!("Failed to show trade to player {}: {}", partner, error);
+    }
+  }
+}
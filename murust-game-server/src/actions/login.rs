@@ -37,6 +37,7 @@ impl LoginAction {
 
         player.account = Some(account);
         player.characters = characters;
+        player.state.try_advance_to(PlayerState::Authenticated);
         LoginResult::Success
       },
     };
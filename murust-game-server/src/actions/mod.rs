@@ -1,5 +1,7 @@
 pub use self::character::*;
 pub use self::login::*;
+pub use self::npc::*;
 
 mod character;
 mod login;
+mod npc;
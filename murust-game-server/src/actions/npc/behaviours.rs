@@ -0,0 +1,101 @@
+use super::vault::protection_state;
+use error::Result;
+use murust_data_model::entities::NpcDefinition;
+use murust_data_model::types::NpcKind;
+use player::Player;
+use protocol::game::server::NpcWindow;
+use std::collections::HashMap;
+
+/// The behaviour of an NPC kind when players talk to it.
+pub trait NpcBehaviour: Send + Sync {
+  /// Lets a player talk to an NPC, e.g opening its window.
+  fn talk(&self, player: &mut Player, npc: &NpcDefinition) -> Result<()>;
+}
+
+/// A registry of the behaviour of each NPC kind.
+#[derive(Default)]
+pub struct NpcBehaviours {
+  behaviours: HashMap<NpcKind, Box<NpcBehaviour>>,
+}
+
+impl NpcBehaviours {
+  /// Constructs a registry with the behaviours of all supported NPC kinds.
+  pub fn with_defaults() -> Self {
+    let mut behaviours = Self::default();
+    behaviours.register(NpcKind::Shop, ShopBehaviour);
+    behaviours.register(NpcKind::Warehouse, VaultBehaviour);
+    behaviours.register(NpcKind::ChaosGoblin, ChaosBehaviour);
+    behaviours.register(NpcKind::GuildMaster, GuildMasterBehaviour);
+    behaviours
+  }
+
+  /// Registers the behaviour of an NPC kind, replacing any previous one.
+  pub fn register<B: NpcBehaviour + 'static>(&mut self, kind: NpcKind, behaviour: B) {
+    self.behaviours.insert(kind, Box::new(behaviour));
+  }
+
+  /// Returns the behaviour of an NPC kind, if any.
+  pub fn get(&self, kind: NpcKind) -> Option<&NpcBehaviour> {
+    self.behaviours.get(&kind).map(AsRef::as_ref)
+  }
+}
+
+/// Opens a shop window, listing the NPC's wares.
+struct ShopBehaviour;
+
+impl NpcBehaviour for ShopBehaviour {
+  fn talk(&self, player: &mut Player, npc: &NpcDefinition) -> Result<()> {
+    player.player_view.show_npc_window(NpcWindow::Shop)?;
+    match player.context.shops().get(npc.id) {
+      Some(shop) => player.player_view.show_shop_items(&shop),
+      None => Ok(()),
+    }
+  }
+}
+
+/// Opens the vault of the player's account, listing its contents.
+struct VaultBehaviour;
+
+impl NpcBehaviour for VaultBehaviour {
+  fn talk(&self, player: &mut Player, _: &NpcDefinition) -> Result<()> {
+    player.player_view.show_npc_window(NpcWindow::Warehouse)?;
+
+    let vault = &player.account()?.vault;
+    let money = player.character()?.inventory.money;
+    player.player_view.show_vault_items(vault)?;
+    player
+      .player_view
+      .show_vault_money(true, vault.money, money)?;
+    player
+      .player_view
+      .show_vault_protection(protection_state(player)?)
+  }
+}
+
+/// Opens the chaos machine, listing the contents of the player's chaos box.
+struct ChaosBehaviour;
+
+impl NpcBehaviour for ChaosBehaviour {
+  fn talk(&self, player: &mut Player, _: &NpcDefinition) -> Result<()> {
+    player
+      .player_view
+      .show_npc_window(NpcWindow::ChaosMachine)?;
+    player.player_view.show_chaos_box_items(&player.chaos_box)
+  }
+}
+
+/// Asks the player whether to create a guild, unless it already belongs to
+/// one.
+struct GuildMasterBehaviour;
+
+impl NpcBehaviour for GuildMasterBehaviour {
+  fn talk(&self, player: &mut Player, _: &NpcDefinition) -> Result<()> {
+    // The members of a guild always include the player itself
+    let members = player.context.chat().groups().guild_members(player.id);
+    if !members.is_empty() {
+      return player.player_view.show_notice("You are already in a guild");
+    }
+
+    player.player_view.show_guild_master_question()
+  }
+}
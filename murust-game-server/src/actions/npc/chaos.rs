@@ -0,0 +1,228 @@
+use super::talk::opened_npc;
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::types::{ItemSlot, NpcKind};
+use murust_service::{ChaosError, ChaosService};
+use player::{Player, PlayerState};
+use protocol::game::client::StorageType;
+use protocol::game::server::ChaosMixOutcome;
+use views::ItemMoveResult;
+
+pub struct NpcChaosAction {
+  chaos_service: ChaosService,
+}
+
+impl NpcChaosAction {
+  pub fn new(chaos_service: ChaosService) -> Self { NpcChaosAction { chaos_service } }
+
+  /// Moves an item between a character's inventory and its chaos box, or
+  /// within the box itself.
+  ///
+  /// Items placed in the box remain in the inventory, locked, until they have
+  /// been mixed or the box has been closed.
+  pub fn move_item(
+    &self,
+    player: &mut Player,
+    source: (StorageType, u8),
+    target: (StorageType, u8),
+  ) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    if !is_chaos_machine_open(player)? {
+      return player
+        .player_view
+        .show_item_move_result(ItemMoveResult::Failure);
+    }
+
+    match (source, target) {
+      ((StorageType::Inventory, source), (StorageType::ChaosBox, target)) => {
+        Self::insert_item(player, source, target)
+      },
+      ((StorageType::ChaosBox, source), (StorageType::Inventory, _)) => {
+        Self::withdraw_item(player, source)
+      },
+      ((StorageType::ChaosBox, source), (StorageType::ChaosBox, target)) => {
+        Self::move_boxed_item(player, source, target)
+      },
+      _ => player
+        .player_view
+        .show_item_move_result(ItemMoveResult::Failure),
+    }
+  }
+
+  /// Mixes the items within a character's chaos box, according to the recipe
+  /// they match.
+  ///
+  /// A successful mix leaves its product within the box.
+  pub fn mix(&self, player: &mut Player) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    if !is_chaos_machine_open(player)? {
+      return Ok(());
+    }
+
+    let ingredients = player
+      .chaos_box
+      .into_iter()
+      .map(|(_, item)| item.id)
+      .collect::<Vec<_>>();
+
+    let result = self
+      .chaos_service
+      .mix(player.character_mut()?, &ingredients)
+      .context("Chaos service failed to mix items")?;
+
+    let outcome = match result {
+      Ok(Some(slot)) => {
+        let product = {
+          let inventory = &mut player.character_mut()?.inventory;
+          let product = inventory
+            .get_item_at_slot(slot)
+            .expect("retrieving mix product")
+            .clone();
+          inventory.lock_item(product.id);
+          product
+        };
+
+        player.chaos_box.clear();
+        player
+          .chaos_box
+          .add_item(product)
+          .expect("placing mix product");
+        ChaosMixOutcome::Success
+      },
+      Ok(None) => {
+        player.chaos_box.clear();
+        ChaosMixOutcome::Failure
+      },
+      Err(error) => {
+        debug!("Player {} could not mix items: {:?}", player.id, error);
+        match error {
+          ChaosError::InsufficientMoney => ChaosMixOutcome::InsufficientMoney,
+          _ => ChaosMixOutcome::InvalidItems,
+        }
+      },
+    };
+
+    let product = if outcome == ChaosMixOutcome::Success {
+      player.chaos_box.into_iter().map(|(_, item)| item).next()
+    } else {
+      None
+    };
+    player
+      .player_view
+      .show_chaos_mix_result(outcome, product)?;
+    player.player_view.show_chaos_box_items(&player.chaos_box)?;
+    player
+      .player_view
+      .show_money(player.character()?.inventory.money)
+  }
+
+  /// Closes a character's chaos box, leaving its items within the inventory.
+  pub fn close(&self, player: &mut Player) -> Result<()> {
+    let items = player
+      .chaos_box
+      .into_iter()
+      .map(|(_, item)| item.id)
+      .collect::<Vec<_>>();
+    player.chaos_box.clear();
+    player.npc = None;
+
+    {
+      let inventory = &mut player.character_mut()?.inventory;
+      for item_id in items {
+        inventory.unlock_item(item_id);
+      }
+    }
+    player.player_view.update_inventory_list(player)
+  }
+
+  /// Places an inventory item within the chaos box.
+  fn insert_item(player: &mut Player, source: u8, target: u8) -> Result<()> {
+    let item = {
+      let inventory = &player.character()?.inventory;
+      match inventory.get_item_at_slot(source) {
+        Some(item) if !inventory.is_item_locked(item.id) => Some(item.clone()),
+        _ => None,
+      }
+    };
+
+    let item_id = match item {
+      Some(item) => {
+        let item_id = item.id;
+        player
+          .chaos_box
+          .add_item_at_slot(target, item)
+          .ok()
+          .map(|_| item_id)
+      },
+      None => None,
+    };
+
+    let item_id = match item_id {
+      Some(item_id) => item_id,
+      None => {
+        return player
+          .player_view
+          .show_item_move_result(ItemMoveResult::Failure)
+      },
+    };
+
+    player.character_mut()?.inventory.lock_item(item_id);
+    let item = player
+      .chaos_box
+      .get_item_at_slot(target)
+      .expect("retrieving boxed item");
+    player
+      .player_view
+      .show_item_move_result(ItemMoveResult::ChaosBox(target, item))
+  }
+
+  /// Withdraws an item from the chaos box, leaving it at its original
+  /// inventory slot.
+  fn withdraw_item(player: &mut Player, source: u8) -> Result<()> {
+    let item = match player.chaos_box.remove_item_at_slot(source) {
+      Some(item) => item,
+      None => {
+        return player
+          .player_view
+          .show_item_move_result(ItemMoveResult::Failure)
+      },
+    };
+
+    player.character_mut()?.inventory.unlock_item(item.id);
+    let inventory = &player.character()?.inventory;
+    let slot = inventory
+      .get_item_slot(&item)
+      .expect("retrieving withdrawn item");
+    let item = inventory
+      .get_item_at_slot(slot)
+      .expect("retrieving withdrawn item");
+
+    let slot = slot + ItemSlot::SIZE as u8;
+    player
+      .player_view
+      .show_item_move_result(ItemMoveResult::Success(slot, item))
+  }
+
+  /// Moves an item within the chaos box.
+  fn move_boxed_item(player: &mut Player, source: u8, target: u8) -> Result<()> {
+    let moved = player
+      .chaos_box
+      .move_item_between_slots(source, target)
+      .is_some();
+
+    let result = match player.chaos_box.get_item_at_slot(target) {
+      Some(item) if moved => ItemMoveResult::ChaosBox(target, item),
+      _ => ItemMoveResult::Failure,
+    };
+    player.player_view.show_item_move_result(result)
+  }
+}
+
+/// Returns whether a player has opened the chaos machine at a nearby Chaos
+/// Goblin.
+fn is_chaos_machine_open(player: &Player) -> Result<bool> {
+  let definition = opened_npc(player)?;
+  Ok(definition.map_or(false, |definition| definition.kind == NpcKind::ChaosGoblin))
+}
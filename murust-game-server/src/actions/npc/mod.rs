@@ -0,0 +1,11 @@
+pub use self::behaviours::{NpcBehaviour, NpcBehaviours};
+pub use self::chaos::NpcChaosAction;
+pub use self::shop::NpcShopAction;
+pub use self::talk::NpcTalkAction;
+pub use self::vault::NpcVaultAction;
+
+mod behaviours;
+mod chaos;
+mod shop;
+mod talk;
+mod vault;
@@ -0,0 +1,153 @@
+use super::talk::opened_npc;
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::entities::Shop;
+use murust_data_model::types::{ItemLocation, ItemSlot, NpcKind};
+use murust_service::ShopService;
+use player::{Player, PlayerState};
+use protocol::game::client::ItemRepair;
+use std::sync::Arc;
+
+pub struct NpcShopAction {
+  shop_service: ShopService,
+}
+
+impl NpcShopAction {
+  pub fn new(shop_service: ShopService) -> Self { NpcShopAction { shop_service } }
+
+  /// Lets a character buy an item from the shop it has opened.
+  pub fn buy(&self, player: &mut Player, slot: u8) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let shop = match open_shop(player)? {
+      Some(shop) => shop,
+      None => return player.player_view.show_item_buy_result(None),
+    };
+
+    let result = self
+      .shop_service
+      .buy(player.character_mut()?, &shop, slot)
+      .context("Shop service failed to buy item")?;
+
+    match result {
+      Ok(slot) => {
+        let character = player.character()?;
+        let item = character
+          .inventory
+          .get_item_at_slot(slot)
+          .expect("retrieving bought item");
+
+        let slot = slot + ItemSlot::SIZE as u8;
+        player
+          .player_view
+          .show_item_buy_result(Some((slot, item)))?;
+        player.player_view.show_money(character.inventory.money)
+      },
+      Err(error) => {
+        debug!("Player {} could not buy item {}: {:?}", player.id, slot, error);
+        player.player_view.show_item_buy_result(None)
+      },
+    }
+  }
+
+  /// Lets a character sell an inventory item to the shop it has opened.
+  pub fn sell(&self, player: &mut Player, slot: u8) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    // Equipped items cannot be sold directly
+    let money = player.character()?.inventory.money;
+    let slot = match slot.checked_sub(ItemSlot::SIZE as u8) {
+      Some(slot) if open_shop(player)?.is_some() => slot,
+      _ => return player.player_view.show_item_sell_result(false, money),
+    };
+
+    let result = self
+      .shop_service
+      .sell(player.character_mut()?, slot)
+      .context("Shop service failed to sell item")?;
+
+    match result {
+      Ok(money) => player.player_view.show_item_sell_result(true, money),
+      Err(error) => {
+        debug!("Player {} could not sell item {}: {:?}", player.id, slot, error);
+        player.player_view.show_item_sell_result(false, money)
+      },
+    }
+  }
+
+  /// Lets a character repair an item, or all of its items, either at the shop
+  /// it has opened or from its inventory.
+  pub fn repair(&self, player: &mut Player, slot: u8, at_npc: bool) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let money = player.character()?.inventory.money;
+    if at_npc && open_shop(player)?.is_none() {
+      return player.player_view.show_item_repair_result(money);
+    }
+
+    let location = match slot {
+      ItemRepair::ALL => None,
+      slot if slot >= ItemSlot::SIZE as u8 => {
+        Some(ItemLocation::Inventory(slot - ItemSlot::SIZE as u8))
+      },
+      slot => match ItemSlot::values().find(|&&item_slot| item_slot as u8 == slot) {
+        Some(&item_slot) => Some(ItemLocation::Equipment(item_slot)),
+        None => return player.player_view.show_item_repair_result(money),
+      },
+    };
+
+    let result = self
+      .shop_service
+      .repair(player.character_mut()?, location, at_npc)
+      .context("Shop service failed to repair items")?;
+
+    let locations = match result {
+      Ok(locations) => locations,
+      Err(error) => {
+        debug!("Player {} could not repair item {}: {:?}", player.id, slot, error);
+        return player.player_view.show_item_repair_result(money);
+      },
+    };
+
+    let character = player.character()?;
+    for &location in &locations {
+      let (slot, item) = match location {
+        ItemLocation::Equipment(slot) => (slot as u8, character.equipment[slot].as_ref()),
+        ItemLocation::Inventory(slot) => (
+          slot + ItemSlot::SIZE as u8,
+          character.inventory.get_item_at_slot(slot),
+        ),
+      };
+      let item = item.expect("retrieving repaired item");
+      player
+        .player_view
+        .show_item_durability(slot, item.durability)?;
+    }
+
+    let equipment_repaired = locations.iter().any(|location| match *location {
+      ItemLocation::Equipment(_) => true,
+      ItemLocation::Inventory(_) => false,
+    });
+
+    // Any broken equipment contributes stats once again
+    if equipment_repaired {
+      player
+        .context
+        .worlds()
+        .refresh_player(player.id, character, false);
+    }
+    player
+      .player_view
+      .show_item_repair_result(character.inventory.money)
+  }
+}
+
+/// Returns the shop opened by a player, unless it has been left behind.
+fn open_shop(player: &Player) -> Result<Option<Arc<Shop>>> {
+  match opened_npc(player)? {
+    Some(ref definition) if definition.kind == NpcKind::Shop => {
+      Ok(player.context.shops().get(definition.id))
+    },
+    _ => Ok(None),
+  }
+}
@@ -0,0 +1,82 @@
+use super::NpcBehaviours;
+use error::Result;
+use murust_data_model::entities::NpcDefinition;
+use murust_data_model::types::ObjectId;
+use player::{Player, PlayerState};
+use std::sync::Arc;
+
+pub struct NpcTalkAction {
+  behaviours: NpcBehaviours,
+}
+
+impl NpcTalkAction {
+  pub fn new(behaviours: NpcBehaviours) -> Self { NpcTalkAction { behaviours } }
+
+  /// Lets a character talk to a nearby NPC, acting upon its kind.
+  pub fn talk(&self, player: &mut Player, npc: ObjectId) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let (id, map) = (player.id, player.character()?.map);
+    let worlds = player.context.worlds().clone();
+    let definition = {
+      let world = match worlds.get(map) {
+        Some(world) => world,
+        None => return Ok(()),
+      };
+
+      let entity = match world.player(id) {
+        Some(entity) => entity,
+        None => return Ok(()),
+      };
+
+      match world.talk(entity, npc) {
+        Ok(definition) => definition,
+        Err(error) => {
+          debug!("Player {} could not talk to NPC {}: {:?}", id, npc, error);
+          return Ok(());
+        },
+      }
+    };
+
+    match self.behaviours.get(definition.kind) {
+      Some(behaviour) => {
+        behaviour.talk(player, &definition)?;
+        player.npc = Some(npc);
+        Ok(())
+      },
+      None => {
+        debug!("NPC {} of kind {:?} has no behaviour", npc, definition.kind);
+        Ok(())
+      },
+    }
+  }
+
+  /// Closes the window of the NPC a character has talked to.
+  pub fn close(&self, player: &mut Player) -> Result<()> {
+    player.npc = None;
+    Ok(())
+  }
+}
+
+/// Returns the NPC a player has talked to, unless it has been left behind.
+pub fn opened_npc(player: &Player) -> Result<Option<Arc<NpcDefinition>>> {
+  let npc = match player.npc {
+    Some(npc) => npc,
+    None => return Ok(None),
+  };
+
+  let (id, map) = (player.id, player.character()?.map);
+  let worlds = player.context.worlds().clone();
+  let world = match worlds.get(map) {
+    Some(world) => world,
+    None => return Ok(None),
+  };
+
+  let entity = match world.player(id) {
+    Some(entity) => entity,
+    None => return Ok(None),
+  };
+
+  let definition = world.talk(entity, npc).ok();
+  Ok(definition)
+}
@@ -0,0 +1,205 @@
+use super::talk::opened_npc;
+use error::{cxerr, Result};
+use failure::ResultExt;
+use murust_data_model::types::{ItemSlot, NpcKind};
+use murust_service::{AccountService, VaultError, VaultService};
+use player::{Player, PlayerState};
+use protocol::game::client::{StorageType, VaultPassword};
+use protocol::game::server::VaultProtectionState;
+use views::ItemMoveResult;
+
+/// The number of invalid vault passwords or security codes a player may enter
+/// before being disconnected.
+const MAX_VAULT_ATTEMPTS: u8 = 5;
+
+pub struct NpcVaultAction {
+  vault_service: VaultService,
+  account_service: AccountService,
+}
+
+impl NpcVaultAction {
+  pub fn new(vault_service: VaultService, account_service: AccountService) -> Self {
+    NpcVaultAction {
+      vault_service,
+      account_service,
+    }
+  }
+
+  /// Moves an item between a character's inventory and its account's vault,
+  /// or within the vault itself.
+  pub fn move_item(
+    &self,
+    player: &mut Player,
+    source: (StorageType, u8),
+    target: (StorageType, u8),
+  ) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    if !is_vault_open(player)? {
+      return player
+        .player_view
+        .show_item_move_result(ItemMoveResult::Failure);
+    }
+
+    let result = {
+      let (character, vault) = player.character_and_vault_mut()?;
+      let result = match (source, target) {
+        ((StorageType::Inventory, source), (StorageType::Warehouse, target)) => {
+          self
+            .vault_service
+            .deposit_item(character, vault, source, target)
+        },
+        ((StorageType::Warehouse, source), (StorageType::Inventory, target)) => {
+          self
+            .vault_service
+            .withdraw_item(character, vault, source, target)
+        },
+        ((StorageType::Warehouse, source), (StorageType::Warehouse, target)) => {
+          self.vault_service.move_item(vault, source, target)
+        },
+        _ => Ok(Err(VaultError::InvalidMove)),
+      };
+      result.context("Vault service failed to move item")?
+    };
+
+    if let Err(error) = result {
+      debug!("Player {} could not move vault item: {:?}", player.id, error);
+      return player
+        .player_view
+        .show_item_move_result(ItemMoveResult::Failure);
+    }
+
+    let result = match target {
+      (StorageType::Warehouse, slot) => player
+        .account()?
+        .vault
+        .get_item_at_slot(slot)
+        .map(|item| ItemMoveResult::Vault(slot, item)),
+      (_, slot) => player
+        .character()?
+        .inventory
+        .get_item_at_slot(slot)
+        .map(|item| ItemMoveResult::Success(slot + ItemSlot::SIZE as u8, item)),
+    };
+    let result = result.expect("retrieving moved item");
+    player.player_view.show_item_move_result(result)
+  }
+
+  /// Deposits or withdraws zen from the vault of a character's account.
+  pub fn transfer_money(&self, player: &mut Player, deposit: bool, money: u32) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let result = if is_vault_open(player)? {
+      let (character, vault) = player.character_and_vault_mut()?;
+      let result = if deposit {
+        self.vault_service.deposit_money(character, vault, money)
+      } else {
+        self.vault_service.withdraw_money(character, vault, money)
+      };
+      result.context("Vault service failed to transfer zen")?
+    } else {
+      Err(VaultError::Locked)
+    };
+
+    if let Err(ref error) = result {
+      debug!("Player {} could not transfer zen: {:?}", player.id, error);
+    }
+
+    let vault = player.account()?.vault.money;
+    let money = player.character()?.inventory.money;
+    player
+      .player_view
+      .show_vault_money(result.is_ok(), vault, money)
+  }
+
+  /// Unlocks the vault of a character's account, or changes its password.
+  ///
+  /// Changing the password requires the account's security code. Players
+  /// entering too many invalid passwords or codes are disconnected.
+  pub fn protect(
+    &self,
+    player: &mut Player,
+    kind: u8,
+    password: u16,
+    security_code: &str,
+  ) -> Result<()> {
+    player.ensure_state(PlayerState::InGame)?;
+
+    let valid_code = player.account()?.security_code.to_string() == security_code;
+    if !valid_code && kind != VaultPassword::UNLOCK {
+      info!("Client entered an invalid security code for the vault password");
+    }
+
+    let state = {
+      let account = player.account_mut()?;
+      match kind {
+        VaultPassword::UNLOCK => {
+          if account.vault.unlock(password) {
+            VaultProtectionState::Unlocked
+          } else {
+            VaultProtectionState::InvalidPassword
+          }
+        },
+        VaultPassword::SET => {
+          if valid_code && account.vault.set_password(password) {
+            self
+              .account_service
+              .update(account)
+              .context("Account service failed to save vault password")?;
+            VaultProtectionState::Unlocked
+          } else {
+            VaultProtectionState::SetFailed
+          }
+        },
+        VaultPassword::REMOVE => {
+          if valid_code && account.vault.remove_password() {
+            self
+              .account_service
+              .update(account)
+              .context("Account service failed to remove vault password")?;
+            VaultProtectionState::Unprotected
+          } else {
+            VaultProtectionState::RemoveFailed
+          }
+        },
+        _ => return Ok(()),
+      }
+    };
+
+    let failed = match state {
+      VaultProtectionState::InvalidPassword => true,
+      VaultProtectionState::SetFailed | VaultProtectionState::RemoveFailed => !valid_code,
+      _ => false,
+    };
+
+    player.player_view.show_vault_protection(state)?;
+    if failed {
+      player.vault_attempts += 1;
+      if player.vault_attempts >= MAX_VAULT_ATTEMPTS {
+        return Err(cxerr(format!(
+          "Player {} entered too many invalid vault passwords",
+          player.id
+        )));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Returns the protection state of a player's vault.
+pub fn protection_state(player: &Player) -> Result<VaultProtectionState> {
+  let vault = &player.account()?.vault;
+  Ok(if vault.password.is_none() {
+    VaultProtectionState::Unprotected
+  } else if vault.is_locked() {
+    VaultProtectionState::Locked
+  } else {
+    VaultProtectionState::Unlocked
+  })
+}
+
+/// Returns whether a player has opened the vault at a nearby warehouse NPC.
+fn is_vault_open(player: &Player) -> Result<bool> {
+  let definition = opened_npc(player)?;
+  Ok(definition.map_or(false, |definition| definition.kind == NpcKind::Warehouse))
+}
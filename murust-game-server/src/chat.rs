@@ -0,0 +1,166 @@
+use murust_data_model::types::ObjectId;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// The number of messages a player may send within the rate limit window.
+const RATE_LIMIT_MESSAGES: usize = 5;
+
+/// The window, in seconds, chat messages are rate limited over.
+const RATE_LIMIT_WINDOW_SECS: u64 = 10;
+
+/// A collection of channels a chat message can be sent to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ChatChannel {
+  /// Any nearby players.
+  Normal,
+  /// The sender's party, for messages prefixed with `~`.
+  Party,
+  /// The sender's guild, for messages prefixed with `@`.
+  Guild,
+}
+
+impl ChatChannel {
+  /// Returns the channel a message is sent to, according to its prefix.
+  pub fn of(message: &str) -> Self {
+    if message.starts_with('~') {
+      ChatChannel::Party
+    } else if message.starts_with('@') {
+      ChatChannel::Guild
+    } else {
+      ChatChannel::Normal
+    }
+  }
+}
+
+/// A filter applied to every chat message before it is delivered.
+pub trait ChatFilter: Send + Sync {
+  /// Returns the message to deliver, or none if it should be dropped.
+  fn filter(&self, message: &str) -> Option<String>;
+}
+
+/// A filter masking banned words, regardless of their case.
+pub struct BannedWordFilter {
+  words: Vec<String>,
+}
+
+impl BannedWordFilter {
+  /// Constructs a new filter from a list of banned words.
+  pub fn new<I, S>(words: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+  {
+    let words = words
+      .into_iter()
+      .map(|word| word.as_ref().trim().to_ascii_lowercase())
+      .filter(|word| !word.is_empty())
+      .collect();
+    BannedWordFilter { words }
+  }
+}
+
+impl ChatFilter for BannedWordFilter {
+  fn filter(&self, message: &str) -> Option<String> {
+    // ASCII lowercasing keeps the byte offsets of the original message
+    let lowercase = message.to_ascii_lowercase();
+    let mut masked = vec![false; message.len()];
+
+    for word in &self.words {
+      for (start, _) in lowercase.match_indices(word.as_str()) {
+        for flag in &mut masked[start..start + word.len()] {
+          *flag = true;
+        }
+      }
+    }
+
+    Some(
+      message
+        .char_indices()
+        .map(|(index, c)| if masked[index] { '*' } else { c })
+        .collect(),
+    )
+  }
+}
+
+/// The groups players may chat within.
+pub trait ChatGroups: Send + Sync {
+  /// Returns the members of a player's party, including itself.
+  fn party_members(&self, id: ObjectId) -> Vec<ObjectId>;
+
+  /// Returns the online members of a player's guild, including itself.
+  fn guild_members(&self, id: ObjectId) -> Vec<ObjectId>;
+}
+
+/// Groups to which no player belongs, until parties and guilds are
+/// supported.
+pub struct NoGroups;
+
+impl ChatGroups for NoGroups {
+  fn party_members(&self, _id: ObjectId) -> Vec<ObjectId> { Vec::new() }
+
+  fn guild_members(&self, _id: ObjectId) -> Vec<ObjectId> { Vec::new() }
+}
+
+/// A registry of the chat filter, groups and each player's recent messages.
+#[derive(Clone)]
+pub struct ChatRegistry {
+  filter: Arc<ChatFilter>,
+  groups: Arc<ChatGroups>,
+  messages: Arc<Mutex<HashMap<ObjectId, VecDeque<Instant>>>>,
+}
+
+impl ChatRegistry {
+  /// Constructs a new registry using a filter and the groups players belong
+  /// to.
+  pub fn new<F, G>(filter: F, groups: G) -> Self
+  where
+    F: ChatFilter + 'static,
+    G: ChatGroups + 'static,
+  {
+    ChatRegistry {
+      filter: Arc::new(filter),
+      groups: Arc::new(groups),
+      messages: Arc::new(Mutex::new(HashMap::new())),
+    }
+  }
+
+  /// Registers a message sent by a player, returning whether it is within
+  /// the rate limit.
+  ///
+  /// Messages refused by the rate limit are not counted towards it.
+  pub fn allow(&self, id: ObjectId, now: Instant) -> bool {
+    let window = Duration::from_secs(RATE_LIMIT_WINDOW_SECS);
+    let mut messages = self.messages();
+    let sent = messages.entry(id).or_insert_with(VecDeque::new);
+
+    while sent
+      .front()
+      .map_or(false, |&time| now.duration_since(time) >= window)
+    {
+      sent.pop_front();
+    }
+
+    if sent.len() >= RATE_LIMIT_MESSAGES {
+      return false;
+    }
+
+    sent.push_back(now);
+    true
+  }
+
+  /// Returns a message as it should be delivered, or none if it is dropped by
+  /// the filter.
+  pub fn filter(&self, message: &str) -> Option<String> { self.filter.filter(message) }
+
+  /// Returns the groups players may chat within.
+  pub fn groups(&self) -> &ChatGroups { &*self.groups }
+
+  /// Removes a player's recent messages, e.g after it has disconnected.
+  pub fn remove(&self, id: ObjectId) { self.messages().remove(&id); }
+
+  /// Returns the recent messages of each player.
+  fn messages(&self) -> MutexGuard<HashMap<ObjectId, VecDeque<Instant>>> {
+    self.messages.lock().expect("locking chat messages")
+  }
+}
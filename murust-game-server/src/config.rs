@@ -1,9 +1,24 @@
 use GameServerId;
 use std::net::SocketAddrV4;
+use std::path::PathBuf;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct GameServerConfig {
   pub id: GameServerId,
   pub socket: SocketAddrV4,
   pub maximum_players: usize,
+  /// The maps hosted by the server.
+  pub maps: Vec<u8>,
+  /// The number of world updates per second.
+  pub tick_rate: u32,
+  /// The directory containing terrain attribute files.
+  ///
+  /// If unspecified, the terrain of every map is considered walkable.
+  pub terrain_path: Option<PathBuf>,
+  /// The directory containing NPC shop files.
+  ///
+  /// If unspecified, every shop is empty.
+  pub shop_path: Option<PathBuf>,
+  /// The words masked within chat messages.
+  pub banned_words: Vec<String>,
 }
@@ -1,15 +1,35 @@
 use GameServerConfig;
+use chat::{BannedWordFilter, ChatRegistry, NoGroups};
+use error::Result;
+use futures::sync::mpsc;
 use handlers::{self, PacketHandlerCore};
 use murust_data_model::types::ObjectId;
+use murust_game_world::MONSTER_ID_START;
 use murust_service::ServiceManager;
+use player::PlayerEvent;
+use protocol::game::server::{TradeOutcome, ViewportPlayer};
+use shops::ShopRegistry;
+use skills::SkillRegistry;
 use std::collections::HashMap;
 use std::net::SocketAddrV4;
 use std::sync::{Arc, Mutex, MutexGuard};
+use trades::TradeRegistry;
+use views::PlayerView;
+use world::WorldManager;
+
+/// A player's presence within the worlds.
+struct PlayerEntry {
+  name: String,
+  view: PlayerView,
+  events: mpsc::UnboundedSender<PlayerEvent>,
+  appearance: ViewportPlayer,
+}
 
 /// The inner game server context.
 struct InnerContext {
   clients_idx: ObjectId,
   clients: HashMap<ObjectId, SocketAddrV4>,
+  players: HashMap<ObjectId, PlayerEntry>,
   socket: SocketAddrV4,
 }
 
@@ -19,43 +39,169 @@ pub struct GameServerContext {
   config: GameServerConfig,
   services: ServiceManager,
   handler: Arc<PacketHandlerCore>,
+  chat: ChatRegistry,
+  skills: SkillRegistry,
+  shops: ShopRegistry,
+  trades: TradeRegistry,
+  worlds: WorldManager,
   inner: Arc<Mutex<InnerContext>>,
 }
 
 impl GameServerContext {
   /// Constructs a new server context.
-  pub fn new(config: GameServerConfig, services: ServiceManager) -> Self {
+  pub fn new(config: GameServerConfig, services: ServiceManager) -> Result<Self> {
     let socket = config.socket;
     let handler = Arc::new(handlers::default(&services));
-    GameServerContext {
+    let chat = ChatRegistry::new(BannedWordFilter::new(&config.banned_words), NoGroups);
+    let skills = SkillRegistry::new(&services.skill_service())?;
+    let shops = ShopRegistry::new(
+      config.maps.iter().cloned(),
+      config.shop_path.as_ref().map(AsRef::as_ref),
+      &services.npc_service(),
+      &services.shop_service(),
+    )?;
+    let worlds = WorldManager::new(
+      config.maps.iter().cloned(),
+      config.terrain_path.as_ref().map(AsRef::as_ref),
+      &services.monster_service(),
+      &services.npc_service(),
+    )?;
+    Ok(GameServerContext {
       config,
       services,
       handler,
+      chat,
+      skills,
+      shops,
+      trades: TradeRegistry::default(),
+      worlds,
       inner: Arc::new(Mutex::new(InnerContext {
         socket,
         clients: HashMap::new(),
+        players: HashMap::new(),
         clients_idx: 0,
       })),
-    }
+    })
   }
 
-  /// Adds a new client.
+  /// Adds a new client, unless the server is full.
+  ///
+  /// Client IDs are allocated below those of monsters and NPCs, reusing the
+  /// IDs of disconnected clients once all others have been used.
   pub fn add_client(&self, socket: SocketAddrV4) -> Option<ObjectId> {
     let mut inner = self.inner();
+    if inner.clients.len() >= self.config.maximum_players {
+      return None;
+    }
+
+    let last = inner.clients_idx;
+    let id = (last + 1..MONSTER_ID_START)
+      .chain(1..last + 1)
+      .find(|id| !inner.clients.contains_key(id))?;
+    inner.clients_idx = id;
+    inner.clients.insert(id, socket);
+    Some(id)
+  }
+
+  /// Removes a client, including its presence in any world, trade or chat.
+  pub fn remove_client(&self, id: ObjectId) {
+    self.remove_player(id);
+    self.inner().clients.remove(&id);
+  }
 
-    // TODO: Use an ID pool for this.
-    if inner.clients.len() < self.config.maximum_players {
-      inner.clients_idx += 1;
-      let id = inner.clients_idx;
-      inner.clients.insert(id, socket);
-      Some(id)
-    } else {
-      None
+  /// Removes a player leaving its world, along with its view, any trade and
+  /// its chat history.
+  pub fn remove_player(&self, id: ObjectId) {
+    self.worlds.remove_player(id);
+    self.chat.remove(id);
+
+    if let Some((side, other)) = self.trades.cancel(id) {
+      let event = PlayerEvent::TradeClosed {
+        outcome: TradeOutcome::Cancelled,
+        offered: other.offer.item_ids(),
+      };
+      self.notify_player(side.partner, event);
     }
+
+    self.trades.settle(id);
+    self.inner().players.remove(&id);
   }
 
-  /// Removes a client.
-  pub fn remove_client(&self, id: ObjectId) { self.inner().clients.remove(&id); }
+  /// Registers the character name, view, event channel and appearance of a
+  /// player entering a world.
+  pub fn add_player_view(
+    &self,
+    id: ObjectId,
+    name: String,
+    view: PlayerView,
+    events: mpsc::UnboundedSender<PlayerEvent>,
+    appearance: ViewportPlayer,
+  ) {
+    self.inner().players.insert(
+      id,
+      PlayerEntry {
+        name,
+        view,
+        events,
+        appearance,
+      },
+    );
+  }
+
+  /// Replaces the appearance of a registered player, e.g after its equipment
+  /// has changed.
+  pub fn update_player_appearance(&self, id: ObjectId, appearance: ViewportPlayer) {
+    if let Some(entry) = self.inner().players.get_mut(&id) {
+      entry.appearance = appearance;
+    }
+  }
+
+  /// Notifies a player's session of an event, returning whether it was
+  /// delivered or not.
+  pub fn notify_player(&self, id: ObjectId, event: PlayerEvent) -> bool {
+    self
+      .inner()
+      .players
+      .get(&id)
+      .map_or(false, |entry| entry.events.unbounded_send(event).is_ok())
+  }
+
+  /// Returns the views of a set of players, skipping any unregistered.
+  pub fn player_views<'a, I>(&self, ids: I) -> Vec<(ObjectId, PlayerView)>
+  where
+    I: IntoIterator<Item = &'a ObjectId>,
+  {
+    let inner = self.inner();
+    ids
+      .into_iter()
+      .filter_map(|id| inner.players.get(id).map(|entry| (*id, entry.view.clone())))
+      .collect()
+  }
+
+  /// Returns the view of a player, if registered.
+  pub fn player_view(&self, id: ObjectId) -> Option<PlayerView> {
+    self.inner().players.get(&id).map(|entry| entry.view.clone())
+  }
+
+  /// Returns the ID and view of the player playing a character, regardless of
+  /// the name's case.
+  pub fn find_player_by_name(&self, name: &str) -> Option<(ObjectId, PlayerView)> {
+    self
+      .inner()
+      .players
+      .iter()
+      .find(|&(_, entry)| entry.name.eq_ignore_ascii_case(name))
+      .map(|(id, entry)| (*id, entry.view.clone()))
+  }
+
+  /// Returns the appearance of a player, if registered.
+  pub fn player_appearance(&self, id: ObjectId) -> Option<ViewportPlayer> {
+    self
+      .inner()
+      .players
+      .get(&id)
+      .map(|entry| entry.appearance.clone())
+  }
 
   /// Returns the number of clients connected.
   pub fn clients_connected(&self) -> usize { self.inner().clients.len() }
@@ -63,6 +209,21 @@ impl GameServerContext {
   /// Returns the service manager.
   pub fn services(&self) -> &ServiceManager { &self.services }
 
+  /// Returns the chat filter, groups and rate limit.
+  pub fn chat(&self) -> &ChatRegistry { &self.chat }
+
+  /// Returns the skills available on the server.
+  pub fn skills(&self) -> &SkillRegistry { &self.skills }
+
+  /// Returns the NPC shops available on the server.
+  pub fn shops(&self) -> &ShopRegistry { &self.shops }
+
+  /// Returns the ongoing trades between players.
+  pub fn trades(&self) -> &TradeRegistry { &self.trades }
+
+  /// Returns the hosted worlds.
+  pub fn worlds(&self) -> &WorldManager { &self.worlds }
+
   /// Returns the packet handler.
   pub fn packet_handler(&self) -> Arc<PacketHandlerCore> { self.handler.clone() }
 
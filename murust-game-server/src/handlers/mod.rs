@@ -1,7 +1,7 @@
 use error::Result;
 use muonline_packet::Packet;
 use murust_service::ServiceManager;
-use player::Player;
+use player::{Player, PlayerEvent};
 
 mod season2;
 
@@ -11,6 +11,9 @@ pub trait PacketHandlerCore: Send + Sync {
 
   /// Processes an handles an incoming packet.
   fn handle_packet(&self, player: &mut Player, packet: Packet) -> Result<()>;
+
+  /// Processes an event produced outside of the player's session.
+  fn handle_event(&self, player: &mut Player, event: PlayerEvent) -> Result<()>;
 }
 
 pub fn default(service_manager: &ServiceManager) -> impl PacketHandlerCore {
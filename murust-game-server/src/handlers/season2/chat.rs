@@ -0,0 +1,32 @@
+use super::PacketHandler;
+use actions::CharacterChatAction;
+use error::Result;
+use player::Player;
+use protocol::game::Client;
+
+pub struct ChatHandler {
+  chat_action: CharacterChatAction,
+}
+
+impl ChatHandler {
+  pub fn new() -> Self {
+    ChatHandler {
+      chat_action: CharacterChatAction,
+    }
+  }
+}
+
+impl PacketHandler for ChatHandler {
+  fn handle_packet(&self, player: &mut Player, packet: &Client) -> Result<bool> {
+    match packet {
+      Client::ChatMessage(request) => self.chat_action.chat(player, &request.message)?,
+      Client::Whisper(request) => {
+        self
+          .chat_action
+          .whisper(player, &request.name, &request.message)?
+      },
+      _ => return Ok(false),
+    }
+    Ok(true)
+  }
+}
@@ -0,0 +1,73 @@
+use super::PacketHandler;
+use actions::{CharacterAttackAction, CharacterDurabilityAction, CharacterLootAction,
+              CharacterProgressAction, CharacterSkillAction};
+use error::Result;
+use murust_data_model::types::Position;
+use murust_service::ServiceManager;
+use player::Player;
+use protocol::game::Client;
+
+pub struct CombatHandler {
+  attack_action: CharacterAttackAction,
+  skill_action: CharacterSkillAction,
+}
+
+impl CombatHandler {
+  pub fn new(service_manager: &ServiceManager) -> Self {
+    let progress_action = || {
+      CharacterProgressAction::new(
+        service_manager.experience_service(),
+        service_manager.character_service(),
+      )
+    };
+
+    let loot_action = || {
+      CharacterLootAction::new(
+        service_manager.drop_service(),
+        service_manager.item_service(),
+        service_manager.character_service(),
+      )
+    };
+
+    let durability_action = || CharacterDurabilityAction::new(service_manager.item_service());
+
+    CombatHandler {
+      attack_action: CharacterAttackAction::new(
+        progress_action(),
+        loot_action(),
+        durability_action(),
+      ),
+      skill_action: CharacterSkillAction::new(
+        progress_action(),
+        loot_action(),
+        durability_action(),
+      ),
+    }
+  }
+}
+
+impl PacketHandler for CombatHandler {
+  fn handle_packet(&self, player: &mut Player, packet: &Client) -> Result<bool> {
+    match packet {
+      Client::CharacterAttack(request) => self.attack_action.attack(
+        player,
+        request.target,
+        request.animation,
+        request.direction,
+      )?,
+      Client::SkillAttack(request) => {
+        self
+          .skill_action
+          .use_on_target(player, request.skill, request.target)?
+      },
+      Client::AreaSkill(request) => self.skill_action.use_on_area(
+        player,
+        request.skill,
+        Position::new(request.x, request.y),
+        request.rotation,
+      )?,
+      _ => return Ok(false),
+    }
+    Ok(true)
+  }
+}
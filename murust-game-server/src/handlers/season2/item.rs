@@ -0,0 +1,86 @@
+use super::PacketHandler;
+use actions::{CharacterConsumeAction, CharacterItemAction, CharacterLootAction,
+              CharacterTradeAction, NpcChaosAction, NpcVaultAction};
+use error::Result;
+use murust_service::ServiceManager;
+use player::Player;
+use protocol::game::{client::StorageType, Client};
+
+pub struct ItemHandler {
+  item_action: CharacterItemAction,
+  loot_action: CharacterLootAction,
+  vault_action: NpcVaultAction,
+  trade_action: CharacterTradeAction,
+  chaos_action: NpcChaosAction,
+  consume_action: CharacterConsumeAction,
+}
+
+impl ItemHandler {
+  pub fn new(service_manager: &ServiceManager) -> Self {
+    ItemHandler {
+      item_action: CharacterItemAction::new(service_manager.character_service()),
+      loot_action: CharacterLootAction::new(
+        service_manager.drop_service(),
+        service_manager.item_service(),
+        service_manager.character_service(),
+      ),
+      vault_action: NpcVaultAction::new(
+        service_manager.vault_service(),
+        service_manager.account_service(),
+      ),
+      trade_action: CharacterTradeAction::new(
+        service_manager.trade_service(),
+        service_manager.character_service(),
+      ),
+      chaos_action: NpcChaosAction::new(service_manager.chaos_service()),
+      consume_action: CharacterConsumeAction::new(
+        service_manager.jewel_service(),
+        service_manager.potion_service(),
+        service_manager.skill_service(),
+      ),
+    }
+  }
+}
+
+impl PacketHandler for ItemHandler {
+  fn handle_packet(&self, player: &mut Player, packet: &Client) -> Result<bool> {
+    match packet {
+      Client::ItemPickup(request) => self.loot_action.pick_up(player, request.item)?,
+      Client::ItemMove(request)
+        if request.source.0 == StorageType::Warehouse
+          || request.target.0 == StorageType::Warehouse =>
+      {
+        self
+          .vault_action
+          .move_item(player, request.source, request.target)?
+      },
+      Client::ItemMove(request)
+        if request.source.0 == StorageType::Trade || request.target.0 == StorageType::Trade =>
+      {
+        self
+          .trade_action
+          .move_item(player, request.source, request.target)?
+      },
+      Client::ItemMove(request)
+        if request.source.0 == StorageType::ChaosBox
+          || request.target.0 == StorageType::ChaosBox =>
+      {
+        self
+          .chaos_action
+          .move_item(player, request.source, request.target)?
+      },
+      Client::ItemMove(request) => {
+        self
+          .item_action
+          .move_item(player, request.source, request.target)?
+      },
+      Client::ItemUse(request) => {
+        self
+          .consume_action
+          .use_item(player, request.source, request.target)?
+      },
+      _ => return Ok(false),
+    }
+    Ok(true)
+  }
+}
@@ -1,14 +1,22 @@
+use actions::{CharacterDeathAction, CharacterDurabilityAction, CharacterTradeAction};
 use error::Result;
 use failure::ResultExt;
 use handlers::PacketHandlerCore;
 use muonline_packet::Packet;
 use murust_service::ServiceManager;
-use player::Player;
+use player::{Player, PlayerEvent, PlayerState};
 use protocol::game::Client;
 use protocol::game::VERSION;
 
 mod account;
+mod chat;
+mod combat;
+mod item;
 mod lobby;
+mod movement;
+mod npc;
+mod progress;
+mod trade;
 
 trait PacketHandler {
   /// Analyzes an incoming packet and returns whether it was handled or not.
@@ -17,6 +25,9 @@ trait PacketHandler {
 
 pub struct Season2PacketHandler {
   handlers: Vec<Box<PacketHandler + Send + Sync>>,
+  death_action: CharacterDeathAction,
+  durability_action: CharacterDurabilityAction,
+  trade_action: CharacterTradeAction,
 }
 
 impl Season2PacketHandler {
@@ -26,7 +37,23 @@ impl Season2PacketHandler {
       handlers: vec![
         Box::new(account::AccountHandler::new(service_manager)),
         Box::new(lobby::CharacterLobbyHandler::new(service_manager)),
+        Box::new(movement::MovementHandler::new()),
+        Box::new(chat::ChatHandler::new()),
+        Box::new(combat::CombatHandler::new(service_manager)),
+        Box::new(item::ItemHandler::new(service_manager)),
+        Box::new(progress::ProgressHandler::new(service_manager)),
+        Box::new(npc::NpcHandler::new(service_manager)),
+        Box::new(trade::TradeHandler::new(service_manager)),
       ],
+      death_action: CharacterDeathAction::new(
+        service_manager.experience_service(),
+        service_manager.character_service(),
+      ),
+      durability_action: CharacterDurabilityAction::new(service_manager.item_service()),
+      trade_action: CharacterTradeAction::new(
+        service_manager.trade_service(),
+        service_manager.character_service(),
+      ),
     }
   }
 }
@@ -39,6 +66,18 @@ impl PacketHandlerCore for Season2PacketHandler {
   fn handle_packet(&self, player: &mut Player, packet: Packet) -> Result<()> {
     let client = Client::from_packet(&packet).context("Client sent a corrupted network packet")?;
 
+    // Dead players cannot act until they have respawned
+    if player.state == PlayerState::Dead {
+      debug!("Rejected packet from dead player {}: {:?}", player.id, client);
+      return Ok(());
+    }
+
+    // Inventories cannot change until a completed trade has been reflected
+    if is_inventory_change(&client) && !player.context.trades().is_settled(player.id) {
+      debug!("Rejected packet from unsettled player {}: {:?}", player.id, client);
+      return Ok(());
+    }
+
     for handler in &self.handlers {
       if handler.handle_packet(player, &client)? {
         break;
@@ -52,4 +91,32 @@ impl PacketHandlerCore for Season2PacketHandler {
 
     Ok(())
   }
+
+  /// Dispatches an event to the appropriate action.
+  fn handle_event(&self, player: &mut Player, event: PlayerEvent) -> Result<()> {
+    match event {
+      PlayerEvent::Died { killer } => self.death_action.die(player, killer),
+      PlayerEvent::Hit => self.durability_action.wear_armor(player),
+      PlayerEvent::Respawned { position } => self.death_action.respawn(player, position),
+      PlayerEvent::TradeClosed { outcome, offered } => {
+        self.trade_action.close(player, outcome, &offered)
+      },
+    }
+  }
+}
+
+/// Returns whether a packet may change the items or zen of an inventory.
+fn is_inventory_change(client: &Client) -> bool {
+  match client {
+    Client::ItemPickup(_)
+    | Client::ItemMove(_)
+    | Client::ItemUse(_)
+    | Client::ItemBuy(_)
+    | Client::ItemSell(_)
+    | Client::ItemRepair(_)
+    | Client::VaultMoney(_)
+    | Client::ChaosMix(_)
+    | Client::TradeMoney(_) => true,
+    _ => false,
+  }
 }
@@ -0,0 +1,31 @@
+use super::PacketHandler;
+use actions::CharacterMoveAction;
+use error::Result;
+use player::Player;
+use protocol::game::Client;
+
+pub struct MovementHandler {
+  move_action: CharacterMoveAction,
+}
+
+impl MovementHandler {
+  pub fn new() -> Self {
+    MovementHandler {
+      move_action: CharacterMoveAction,
+    }
+  }
+}
+
+impl PacketHandler for MovementHandler {
+  fn handle_packet(&self, player: &mut Player, packet: &Client) -> Result<bool> {
+    match packet {
+      Client::CharacterMove(request) => {
+        self
+          .move_action
+          .walk(player, request.direction, &request.path)?
+      },
+      _ => return Ok(false),
+    }
+    Ok(true)
+  }
+}
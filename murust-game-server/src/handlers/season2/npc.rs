@@ -0,0 +1,59 @@
+use super::PacketHandler;
+use actions::{NpcBehaviours, NpcChaosAction, NpcShopAction, NpcTalkAction, NpcVaultAction};
+use error::Result;
+use murust_service::ServiceManager;
+use player::Player;
+use protocol::game::Client;
+
+pub struct NpcHandler {
+  talk_action: NpcTalkAction,
+  shop_action: NpcShopAction,
+  vault_action: NpcVaultAction,
+  chaos_action: NpcChaosAction,
+}
+
+impl NpcHandler {
+  pub fn new(service_manager: &ServiceManager) -> Self {
+    NpcHandler {
+      talk_action: NpcTalkAction::new(NpcBehaviours::with_defaults()),
+      shop_action: NpcShopAction::new(service_manager.shop_service()),
+      vault_action: NpcVaultAction::new(
+        service_manager.vault_service(),
+        service_manager.account_service(),
+      ),
+      chaos_action: NpcChaosAction::new(service_manager.chaos_service()),
+    }
+  }
+}
+
+impl PacketHandler for NpcHandler {
+  fn handle_packet(&self, player: &mut Player, packet: &Client) -> Result<bool> {
+    match packet {
+      Client::NpcTalk(request) => self.talk_action.talk(player, request.npc)?,
+      Client::NpcClose => self.talk_action.close(player)?,
+      Client::ItemBuy(request) => self.shop_action.buy(player, request.slot)?,
+      Client::ItemSell(request) => self.shop_action.sell(player, request.slot)?,
+      Client::ItemRepair(request) => {
+        self
+          .shop_action
+          .repair(player, request.slot, request.at_npc())?
+      },
+      Client::VaultMoney(request) => {
+        self
+          .vault_action
+          .transfer_money(player, request.is_deposit(), request.money)?
+      },
+      Client::VaultClose => self.talk_action.close(player)?,
+      Client::VaultPassword(request) => self.vault_action.protect(
+        player,
+        request.kind,
+        request.password,
+        &request.security_code,
+      )?,
+      Client::ChaosMix(_) => self.chaos_action.mix(player)?,
+      Client::ChaosClose => self.chaos_action.close(player)?,
+      _ => return Ok(false),
+    }
+    Ok(true)
+  }
+}
@@ -0,0 +1,33 @@
+use super::PacketHandler;
+use actions::CharacterProgressAction;
+use error::Result;
+use murust_service::ServiceManager;
+use player::Player;
+use protocol::game::Client;
+
+pub struct ProgressHandler {
+  progress_action: CharacterProgressAction,
+}
+
+impl ProgressHandler {
+  pub fn new(service_manager: &ServiceManager) -> Self {
+    ProgressHandler {
+      progress_action: CharacterProgressAction::new(
+        service_manager.experience_service(),
+        service_manager.character_service(),
+      ),
+    }
+  }
+}
+
+impl PacketHandler for ProgressHandler {
+  fn handle_packet(&self, player: &mut Player, packet: &Client) -> Result<bool> {
+    match packet {
+      Client::CharacterStatIncrease(request) => {
+        self.progress_action.add_stat_point(player, request.stat)?
+      },
+      _ => return Ok(false),
+    }
+    Ok(true)
+  }
+}
@@ -0,0 +1,43 @@
+use super::PacketHandler;
+use actions::CharacterTradeAction;
+use error::Result;
+use murust_service::ServiceManager;
+use player::Player;
+use protocol::game::Client;
+
+pub struct TradeHandler {
+  trade_action: CharacterTradeAction,
+}
+
+impl TradeHandler {
+  pub fn new(service_manager: &ServiceManager) -> Self {
+    TradeHandler {
+      trade_action: CharacterTradeAction::new(
+        service_manager.trade_service(),
+        service_manager.character_service(),
+      ),
+    }
+  }
+}
+
+impl PacketHandler for TradeHandler {
+  fn handle_packet(&self, player: &mut Player, packet: &Client) -> Result<bool> {
+    match packet {
+      Client::TradeRequest(request) => self.trade_action.request(player, request.player)?,
+      Client::TradeResponse(response) => {
+        self
+          .trade_action
+          .respond(player, response.is_accepted())?
+      },
+      Client::TradeMoney(request) => self.trade_action.offer_money(player, request.money)?,
+      Client::TradeButton(request) => {
+        self
+          .trade_action
+          .confirm(player, request.is_confirmed())?
+      },
+      Client::TradeCancel => self.trade_action.cancel(player)?,
+      _ => return Ok(false),
+    }
+    Ok(true)
+  }
+}
@@ -9,6 +9,7 @@ extern crate futures_await as futures;
 extern crate muonline_packet;
 extern crate muonline_packet_codec;
 extern crate murust_data_model;
+extern crate murust_game_world;
 extern crate murust_protocol as protocol;
 extern crate murust_service;
 extern crate tokio;
@@ -32,6 +33,7 @@ pub use server::GameServer;
 #[macro_use]
 mod macros;
 mod actions;
+mod chat;
 mod config;
 mod context;
 mod error;
@@ -40,8 +42,12 @@ mod listener;
 mod player;
 pub mod rpc;
 mod server;
+mod shops;
+mod skills;
+mod trades;
 mod util;
 mod views;
+mod world;
 
 /// The type of a server ID.
 pub type GameServerId = u16;
@@ -1,13 +1,20 @@
 use context::GameServerContext;
-use failure::{Error, Fail};
-use futures::{prelude::*, sync::mpsc};
+use failure::{Context, Error, Fail};
+use futures::{prelude::*, stream, sync::mpsc};
 use listener::traits::{PacketSink, PacketStream};
 use muonline_packet::Packet;
 use murust_data_model::types::ObjectId;
-use player::Player;
+use player::{Player, PlayerEvent};
 use std::io;
 use views::PlayerView;
 
+/// An input processed by a player's session.
+enum SessionInput {
+  Packet(Packet),
+  Event(PlayerEvent),
+  Closed,
+}
+
 #[async(boxed_send)]
 pub fn serve<S: PacketStream + PacketSink + Send + 'static>(
   player_id: ObjectId,
@@ -15,6 +22,7 @@ pub fn serve<S: PacketStream + PacketSink + Send + 'static>(
   stream: S,
 ) -> Result<(), Error> {
   let (server_sender, server_receiver) = mpsc::unbounded::<Packet>();
+  let (event_sender, event_receiver) = mpsc::unbounded::<PlayerEvent>();
   let (client_writer, client_reader) = stream.split();
 
   // All packets to the client are sent via a channel
@@ -25,18 +33,38 @@ pub fn serve<S: PacketStream + PacketSink + Send + 'static>(
     .map(|_| ());
 
   // Construct the player instance that will last throughout the session
-  let mut player = Player::new(player_id, context, PlayerView::new(server_sender));
+  let mut player = Player::new(
+    player_id,
+    context,
+    PlayerView::new(server_sender),
+    event_sender,
+  );
 
-  // Process each incoming packet using the default client packet handler
-  // TODO: Ugly clone for each incoming packet...
-  let packets_to_server = client_reader
+  // Events produced outside the session are interleaved with the packets, until
+  // the client closes its stream.
+  let events = event_receiver
+    .map(SessionInput::Event)
+    .map_err(|_| Error::from(Context::new("Player event channel closed abruptly")));
+  let inputs = client_reader
+    .map(SessionInput::Packet)
     .map_err(|error| Error::from(error.context("Server receiver stream closed abruptly")))
-    .for_each(move |packet| {
-      player
-        .packet_handler
-        .clone()
-        .handle_packet(&mut player, packet)
-    });
+    .chain(stream::once(Ok(SessionInput::Closed)))
+    .select(events)
+    .take_while(|input| Ok(match input {
+      SessionInput::Closed => false,
+      _ => true,
+    }));
+
+  // Process each input using the default client packet handler
+  // TODO: Ugly clone for each incoming packet...
+  let packets_to_server = inputs.for_each(move |input| {
+    let handler = player.packet_handler.clone();
+    match input {
+      SessionInput::Packet(packet) => handler.handle_packet(&mut player, packet),
+      SessionInput::Event(event) => handler.handle_event(&mut player, event),
+      SessionInput::Closed => Ok(()),
+    }
+  });
 
   let session = packets_to_client.select(packets_to_server);
   let session = session.map(|_| ()).map_err(|(error, _)| error);
@@ -0,0 +1,20 @@
+use murust_data_model::entities::item;
+use murust_data_model::types::{ObjectId, Position};
+use protocol::game::server::TradeOutcome;
+
+/// An event concerning a player, produced outside of its session.
+#[derive(Debug, Clone)]
+pub enum PlayerEvent {
+  /// The player's character has been killed.
+  Died { killer: ObjectId },
+  /// The player's character has been hit, wearing down its armor.
+  Hit,
+  /// The player's character has been revived at a position.
+  Respawned { position: Position },
+  /// The player's trade has been ended by its partner, along with the items
+  /// the player had offered.
+  TradeClosed {
+    outcome: TradeOutcome,
+    offered: Vec<item::Id>,
+  },
+}
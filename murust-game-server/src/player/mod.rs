@@ -1,5 +1,7 @@
+pub use self::event::PlayerEvent;
 pub use self::player::Player;
 pub use self::state::PlayerState;
 
+mod event;
 mod player;
 mod state;
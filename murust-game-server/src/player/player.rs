@@ -1,9 +1,11 @@
 use context::GameServerContext;
 use error::{cxerr, Result};
+use futures::sync::mpsc;
 use handlers::PacketHandlerCore;
-use murust_data_model::entities::{Account, Character};
-use murust_data_model::types::ObjectId;
-use player::PlayerState;
+use murust_data_model::entities::{Account, Character, Vault};
+use murust_data_model::types::{ItemStorage, ObjectId};
+use player::{PlayerEvent, PlayerState};
+use protocol::game::server::ViewportPlayer;
 use std::sync::Arc;
 use views::PlayerView;
 
@@ -16,13 +18,25 @@ pub struct Player {
   // TODO: Abstract away the 'server' part of this?
   pub context: GameServerContext,
   pub state: PlayerState,
+  /// The NPC whose window is open, if any.
+  pub npc: Option<ObjectId>,
+  /// The items placed within the chaos box, each locked within the inventory.
+  pub chaos_box: ItemStorage,
+  /// The number of invalid vault passwords or security codes entered.
+  pub vault_attempts: u8,
   pub player_view: PlayerView,
   pub packet_handler: Arc<PacketHandlerCore>,
+  pub events: mpsc::UnboundedSender<PlayerEvent>,
 }
 
 // TODO: On player state → CharacterSelection, send MOTD.
 impl Player {
-  pub fn new(id: ObjectId, context: GameServerContext, player_view: PlayerView) -> Player {
+  pub fn new(
+    id: ObjectId,
+    context: GameServerContext,
+    player_view: PlayerView,
+    events: mpsc::UnboundedSender<PlayerEvent>,
+  ) -> Player {
     let packet_handler = context.packet_handler();
     Player {
       id,
@@ -31,8 +45,12 @@ impl Player {
       character_index: None,
       context,
       state: PlayerState::LoginScreen,
+      npc: None,
+      chaos_box: ItemStorage::new(8, 4),
+      vault_attempts: 0,
       packet_handler,
       player_view,
+      events,
     }
   }
 
@@ -44,6 +62,30 @@ impl Player {
       .ok_or(cxerr("Invalid access to account when not available"))
   }
 
+  /// Returns the player's account, mutably.
+  pub fn account_mut(&mut self) -> Result<&mut Account> {
+    self
+      .account
+      .as_mut()
+      .ok_or(cxerr("Invalid access to account when not available"))
+  }
+
+  /// Returns the player's selected character and its account's vault,
+  /// mutably.
+  pub fn character_and_vault_mut(&mut self) -> Result<(&mut Character, &mut Vault)> {
+    let vault = match self.account {
+      Some(ref mut account) => &mut account.vault,
+      None => return Err(cxerr("Invalid access to account when not available")),
+    };
+
+    let characters = &mut self.characters;
+    let character = self
+      .character_index
+      .and_then(move |i| characters.get_mut(i))
+      .ok_or(cxerr("Invalid access to character when none selected"))?;
+    Ok((character, vault))
+  }
+
   /// Returns the player's selected character.
   pub fn character(&self) -> Result<&Character> {
     self
@@ -52,11 +94,63 @@ impl Player {
       .ok_or(cxerr("Invalid access to character when none selected"))
   }
 
+  /// Returns the player's selected character, mutably.
+  pub fn character_mut(&mut self) -> Result<&mut Character> {
+    let characters = &mut self.characters;
+    self
+      .character_index
+      .and_then(move |i| characters.get_mut(i))
+      .ok_or(cxerr("Invalid access to character when none selected"))
+  }
+
   pub fn select_character(&mut self, character_index: usize) -> Result<()> {
+    let mut state = self.state;
+    if !state.try_advance_to(PlayerState::InGame) {
+      return Err(cxerr(format!("Invalid player state {} for entering a world", self.state)));
+    }
+
     self.character_index = Some(character_index);
+    {
+      let character = self.character()?;
+      let (context, id) = (&self.context, self.id);
+      let (view, events) = (self.player_view.clone(), self.events.clone());
+
+      // The view is only registered once the player has been added to a world
+      context.worlds().add_player(id, character, || {
+        let appearance = ViewportPlayer::new(id, character);
+        context.add_player_view(id, character.name.clone(), view, events, appearance);
+      })?;
+    }
+
+    self.state = state;
     self.player_entered_world()
   }
 
+  /// Removes the player from its world, closing any trade or NPC window.
+  ///
+  /// Every item locked within the inventory is released, since neither a
+  /// trade nor the chaos box survives leaving the world.
+  pub fn leave_world(&mut self) -> Result<()> {
+    // The character is left wherever it has walked to
+    let (id, map) = (self.id, self.character()?.map);
+    let position = self.context.worlds().get(map).and_then(|world| {
+      world
+        .player(id)
+        .and_then(|entity| world.location(entity))
+        .map(|location| location.position)
+    });
+
+    if let Some(position) = position {
+      self.character_mut()?.position = position;
+    }
+
+    self.context.remove_player(id);
+    self.npc = None;
+    self.chaos_box.clear();
+    self.character_mut()?.inventory.unlock_all();
+    Ok(())
+  }
+
   pub fn ensure_state(&self, state: PlayerState) -> Result<()> {
     if self.state != state {
       Err(cxerr(format!(
@@ -71,6 +165,7 @@ impl Player {
   fn player_entered_world(&mut self) -> Result<()> {
     self.player_view.update_character_info(self)?;
     self.player_view.update_inventory_list(self)?;
+    self.player_view.update_skill_list(self)?;
     Ok(())
   }
 }
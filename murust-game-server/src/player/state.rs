@@ -6,14 +6,32 @@ pub enum PlayerState {
   LoginScreen,
   Authenticated,
   CharacterSelection,
+  InGame,
   Teleporting,
   Dead,
 }
 
 impl PlayerState {
+  /// Advances to another state, returning whether the transition is allowed.
+  ///
+  /// The state is left untouched if the transition is not allowed.
   pub fn try_advance_to(&mut self, other: PlayerState) -> bool {
-    *self = other;
-    true
+    let allowed = match (*self, other) {
+      (PlayerState::LoginScreen, PlayerState::Authenticated)
+      | (PlayerState::Authenticated, PlayerState::CharacterSelection)
+      | (PlayerState::CharacterSelection, PlayerState::InGame)
+      | (PlayerState::InGame, PlayerState::CharacterSelection)
+      | (PlayerState::InGame, PlayerState::Teleporting)
+      | (PlayerState::Teleporting, PlayerState::InGame)
+      | (PlayerState::InGame, PlayerState::Dead)
+      | (PlayerState::Dead, PlayerState::InGame) => true,
+      _ => false,
+    };
+
+    if allowed {
+      *self = other;
+    }
+    allowed
   }
 }
 
@@ -26,6 +44,7 @@ impl fmt::Display for PlayerState {
         PlayerState::LoginScreen => "Login Screen",
         PlayerState::Authenticated => "Authenticated",
         PlayerState::CharacterSelection => "Character Selection",
+        PlayerState::InGame => "In Game",
         PlayerState::Teleporting => "Teleporting",
         PlayerState::Dead => "Dead",
       }
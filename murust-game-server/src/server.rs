@@ -2,32 +2,44 @@ use context::GameServerContext;
 use error::Result;
 use failure::Context;
 use futures::{Future, Sink, sync::mpsc};
+use murust_game_world::SystemClock;
 use murust_service::ServiceManager;
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
 use std::{thread::{self, JoinHandle}, time::{Duration, Instant}};
 use tap::TapResultOps;
-use {listener, GameServerConfig};
+use {listener, world, GameServerConfig};
 
 /// An implementation of a Game Server.
 pub struct GameServer {
   context: GameServerContext,
   listener_close: mpsc::Sender<()>,
   listener_thread: Option<JoinHandle<Result<()>>>,
+  world_running: Arc<AtomicBool>,
+  world_thread: Option<JoinHandle<()>>,
   start_time: Instant,
 }
 
 impl GameServer {
   /// Spawns a new Game Server instance.
-  pub fn spawn(config: GameServerConfig, service_manager: ServiceManager) -> Self {
+  pub fn spawn(config: GameServerConfig, service_manager: ServiceManager) -> Result<Self> {
     let (listener_close, close_rx) = mpsc::channel(1);
-    let context = GameServerContext::new(config, service_manager);
+    let context = GameServerContext::new(config, service_manager)?;
     let thread = thread::spawn(closet!([context] move || listener::listen(context, close_rx)));
 
-    GameServer {
+    // Each hosted world is simulated on a dedicated thread
+    let world_running = Arc::new(AtomicBool::new(true));
+    let world_thread = thread::spawn(closet!([context, world_running] move || {
+      world::run(context, world_running, SystemClock)
+    }));
+
+    Ok(GameServer {
       context,
       listener_close,
       listener_thread: Some(thread),
+      world_running,
+      world_thread: Some(world_thread),
       start_time: Instant::now(),
-    }
+    })
   }
 
   /// Returns the server's current uptime.
@@ -45,6 +57,16 @@ impl GameServer {
   /// Will block, waiting for the server to finish.
   pub fn wait(self) -> Result<()> { self.join_listener_thread() }
 
+  /// Stops the world simulation and joins its thread.
+  fn stop_worlds(&mut self) -> Result<()> {
+    self.world_running.store(false, Ordering::SeqCst);
+    self
+      .world_thread
+      .take()
+      .map_or(Ok(()), |thread| thread.join())
+      .map_err(|_| Context::new("World simulation thread panicked").into())
+  }
+
   /// Sends a close message to the listener thread.
   fn stop_listener(&self) -> Result<()> {
     self
@@ -58,18 +80,24 @@ impl GameServer {
 
   /// Joins the server thread with the current thread.
   fn join_listener_thread(mut self) -> Result<()> {
-    self
+    let result = self
       .listener_thread
       .take()
       .expect("extracting game server thread handle")
       .join()
       .tap_err(|any| debug!("{:#?}", any))
       .map_err(|_| Context::new("Listener server thread panicked").into())
-      .and_then(|result| result)
+      .and_then(|result| result);
+
+    // The worlds are of no use once the listener has closed
+    self.stop_worlds().and(result)
   }
 }
 
 /// Closes the server upon destruction.
 impl Drop for GameServer {
-  fn drop(&mut self) { let _ = self.stop_listener(); }
+  fn drop(&mut self) {
+    let _ = self.stop_listener();
+    self.world_running.store(false, Ordering::SeqCst);
+  }
 }
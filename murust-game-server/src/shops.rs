@@ -0,0 +1,62 @@
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::entities::Shop;
+use murust_data_model::types::NpcKind;
+use murust_service::{NpcService, ShopService};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A registry of the wares sold by each NPC shop on a server.
+#[derive(Clone)]
+pub struct ShopRegistry {
+  shops: Arc<HashMap<i32, Arc<Shop>>>,
+}
+
+impl ShopRegistry {
+  /// Constructs a new registry, loading the shop of each NPC on the hosted
+  /// maps.
+  ///
+  /// Shops are read from `ShopN.txt` files, where `N` is the NPC's ID. If no
+  /// directory is specified, every shop is empty.
+  pub fn new<I: IntoIterator<Item = u8>>(
+    maps: I,
+    shop_path: Option<&Path>,
+    npc_service: &NpcService,
+    shop_service: &ShopService,
+  ) -> Result<Self> {
+    let mut shops = HashMap::new();
+
+    for map in maps {
+      let npcs = npc_service
+        .find_by_map(map)
+        .with_context(|_| format!("Failed to load NPCs for map {}", map))?;
+
+      for npc in npcs.into_iter().filter(|npc| npc.kind == NpcKind::Shop) {
+        let shop = match shop_path {
+          Some(path) => {
+            let path = path.join(format!("Shop{}.txt", npc.id));
+            let mut source = String::new();
+            File::open(&path)
+              .and_then(|mut file| file.read_to_string(&mut source))
+              .with_context(|_| format!("Failed to read shop file {}", path.display()))?;
+            shop_service
+              .parse(npc.id, &source)
+              .with_context(|_| format!("Failed to parse shop of NPC {}", npc.id))?
+          },
+          None => Shop::new(npc.id),
+        };
+        shops.insert(npc.id, Arc::new(shop));
+      }
+    }
+
+    Ok(ShopRegistry {
+      shops: Arc::new(shops),
+    })
+  }
+
+  /// Returns the shop of an NPC, if it exists.
+  pub fn get(&self, npc: i32) -> Option<Arc<Shop>> { self.shops.get(&npc).cloned() }
+}
@@ -0,0 +1,32 @@
+use error::Result;
+use failure::ResultExt;
+use murust_data_model::entities::SkillDefinition;
+use murust_data_model::types::SkillId;
+use murust_service::SkillService;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A registry of all skills available on a server.
+#[derive(Clone)]
+pub struct SkillRegistry {
+  skills: Arc<HashMap<SkillId, Arc<SkillDefinition>>>,
+}
+
+impl SkillRegistry {
+  /// Constructs a new registry, loading all skill definitions.
+  pub fn new(skill_service: &SkillService) -> Result<Self> {
+    let skills = skill_service
+      .find_all()
+      .context("Failed to load skill definitions")?
+      .into_iter()
+      .map(|skill| (skill.id, Arc::new(skill)))
+      .collect();
+
+    Ok(SkillRegistry {
+      skills: Arc::new(skills),
+    })
+  }
+
+  /// Returns the definition of a skill, if it exists.
+  pub fn get(&self, id: SkillId) -> Option<Arc<SkillDefinition>> { self.skills.get(&id).cloned() }
+}
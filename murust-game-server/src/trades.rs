@@ -0,0 +1,167 @@
+use murust_data_model::entities::{inventory, TradeOffer};
+use murust_data_model::types::ObjectId;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A pending request for trading with a player.
+#[derive(Debug, Clone)]
+pub struct TradeRequest {
+  /// The requesting player.
+  pub from: ObjectId,
+  /// The requesting character's name.
+  pub name: String,
+  /// The requesting character's level.
+  pub level: u16,
+  /// The requesting character's inventory.
+  pub inventory: inventory::Id,
+}
+
+/// One side of an ongoing trade.
+#[derive(Debug)]
+pub struct TradeSide {
+  pub partner: ObjectId,
+  pub inventory: inventory::Id,
+  pub offer: TradeOffer,
+}
+
+/// The inner trade registry.
+#[derive(Default)]
+struct InnerRegistry {
+  /// Pending requests, by the requested player.
+  requests: HashMap<ObjectId, TradeRequest>,
+  /// Ongoing trades, by each of their participants.
+  sides: HashMap<ObjectId, TradeSide>,
+  /// Participants of a completed trade, until their characters reflect it.
+  unsettled: HashSet<ObjectId>,
+}
+
+impl InnerRegistry {
+  /// Returns whether a player is trading, or involved in a trade request.
+  fn is_busy(&self, id: ObjectId) -> bool {
+    self.sides.contains_key(&id)
+      || self.unsettled.contains(&id)
+      || self.requests.contains_key(&id)
+      || self.requests.values().any(|request| request.from == id)
+  }
+}
+
+/// A registry of the trades between players on a server.
+///
+/// Each side of a trade is shared by both participants, so the offers are
+/// kept here rather than with either player.
+#[derive(Clone, Default)]
+pub struct TradeRegistry {
+  inner: Arc<Mutex<InnerRegistry>>,
+}
+
+impl TradeRegistry {
+  /// Registers a request for trading with a player, returning whether neither
+  /// of them is busy.
+  pub fn request(&self, target: ObjectId, request: TradeRequest) -> bool {
+    let mut inner = self.inner();
+    if request.from == target || inner.is_busy(request.from) || inner.is_busy(target) {
+      return false;
+    }
+
+    inner.requests.insert(target, request);
+    true
+  }
+
+  /// Accepts the request made to a player, opening a trade between it and the
+  /// requesting player.
+  pub fn accept(&self, target: ObjectId, inventory: inventory::Id) -> Option<TradeRequest> {
+    let mut inner = self.inner();
+    let request = inner.requests.remove(&target)?;
+
+    let sides = [
+      (target, request.from, inventory),
+      (request.from, target, request.inventory),
+    ];
+    for &(id, partner, inventory) in &sides {
+      inner.sides.insert(
+        id,
+        TradeSide {
+          partner,
+          inventory,
+          offer: TradeOffer::default(),
+        },
+      );
+    }
+    Some(request)
+  }
+
+  /// Declines the request made to a player.
+  pub fn decline(&self, target: ObjectId) -> Option<TradeRequest> {
+    self.inner().requests.remove(&target)
+  }
+
+  /// Applies a change to a player's offer, given its partner's offer as well,
+  /// returning the partner's ID along with the result.
+  pub fn update<F, R>(&self, id: ObjectId, change: F) -> Option<(ObjectId, R)>
+  where
+    F: FnOnce(&mut TradeOffer, &mut TradeOffer) -> R,
+  {
+    let mut inner = self.inner();
+    let mut side = inner.sides.remove(&id)?;
+    let partner = side.partner;
+
+    let result = inner
+      .sides
+      .get_mut(&partner)
+      .map(|other| change(&mut side.offer, &mut other.offer));
+    inner.sides.insert(id, side);
+    result.map(|result| (partner, result))
+  }
+
+  /// Ends a player's trade once both sides have confirmed the offers,
+  /// returning the player's side and its partner's.
+  ///
+  /// Both participants remain unsettled until their characters have been
+  /// refreshed, since the exchange is only persisted.
+  pub fn complete(&self, id: ObjectId) -> Option<(TradeSide, TradeSide)> {
+    let mut inner = self.inner();
+    let partner = match inner.sides.get(&id) {
+      Some(side) if side.offer.confirmed => side.partner,
+      _ => return None,
+    };
+
+    let confirmed = inner
+      .sides
+      .get(&partner)
+      .map_or(false, |side| side.offer.confirmed);
+    if !confirmed {
+      return None;
+    }
+
+    let side = inner.sides.remove(&id)?;
+    let other = inner.sides.remove(&partner)?;
+    inner.unsettled.insert(id);
+    inner.unsettled.insert(partner);
+    Some((side, other))
+  }
+
+  /// Returns whether a player's character reflects any trade it completed.
+  pub fn is_settled(&self, id: ObjectId) -> bool { !self.inner().unsettled.contains(&id) }
+
+  /// Marks a player's character as reflecting the trade it completed, e.g
+  /// once refreshed or disconnected.
+  pub fn settle(&self, id: ObjectId) { self.inner().unsettled.remove(&id); }
+
+  /// Ends a player's trade, and any request involving it, returning the
+  /// player's side and its partner's if a trade was ongoing.
+  pub fn cancel(&self, id: ObjectId) -> Option<(TradeSide, TradeSide)> {
+    let mut inner = self.inner();
+    inner
+      .requests
+      .retain(|&target, request| target != id && request.from != id);
+
+    let side = inner.sides.remove(&id)?;
+    let other = inner.sides.remove(&side.partner)?;
+    Some((side, other))
+  }
+
+  /// Returns the inner registry.
+  fn inner(&self) -> MutexGuard<InnerRegistry> {
+    self.inner.lock().expect("locking inner trade registry")
+  }
+}
@@ -2,9 +2,13 @@ use error::Result;
 use failure::ResultExt;
 use futures::sync::mpsc;
 use muonline_packet::{Packet, PacketEncodable};
-use murust_data_model::entities::Character;
-use murust_data_model::types::Class;
+use murust_data_model::entities::{Character, Item, Shop, Vault};
+use murust_data_model::types::{CharacterStat, Class, Direction, ItemStorage, ObjectId, Position,
+                               SkillId};
+use murust_game_world::combat::{AttackOutcome, DamageKind};
 use player::Player;
+use protocol::game::server::{ChaosMixOutcome, NpcWindow, TradeOutcome, VaultProtectionState,
+                             ViewportItem, ViewportMonster, ViewportPlayer};
 
 #[derive(Debug, Copy, Clone)]
 pub enum LoginResult {
@@ -30,6 +34,31 @@ pub enum CharacterDeleteResult {
   Blocked,
 }
 
+#[derive(Debug, Copy, Clone)]
+pub enum StatIncreaseResult<'a> {
+  Success(&'a Character, CharacterStat),
+  Failure,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum ItemPickupResult<'a> {
+  Item(u8, &'a Item),
+  Money(u32),
+  Failure,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum ItemMoveResult<'a> {
+  Success(u8, &'a Item),
+  Vault(u8, &'a Item),
+  Trade(u8, &'a Item),
+  ChaosBox(u8, &'a Item),
+  /// A stack merged onto another, whose counts are shown separately.
+  Merged,
+  Failure,
+}
+
+#[derive(Clone)]
 pub struct PlayerView {
   // TODO: Abstract this to a stream.
   output: mpsc::UnboundedSender<Packet>,
@@ -82,7 +111,41 @@ impl PlayerView {
 
   pub fn update_character_info(&self, player: &Player) -> Result<()> {
     use protocol::game::server::CharacterInfo;
-    self.send_packet(CharacterInfo::new(player.character()?))
+    let character = player.character()?;
+    let experience_level = player
+      .context
+      .services()
+      .experience_service()
+      .experience_for_level(character.level + 1);
+    self.send_packet(CharacterInfo::new(character, experience_level))
+  }
+
+  pub fn show_experience_gain(&self, id: ObjectId, experience: u32, damage: u32) -> Result<()> {
+    use protocol::game::server::ExperienceGain;
+    self.send_packet(ExperienceGain {
+      id,
+      experience: experience.min(u16::max_value() as u32) as u16,
+      damage: damage.min(u16::max_value() as u32) as u16,
+    })
+  }
+
+  pub fn show_respawn(&self, character: &Character) -> Result<()> {
+    use protocol::game::server::CharacterRespawn;
+    self.send_packet(CharacterRespawn::new(character))
+  }
+
+  pub fn show_level_up(&self, character: &Character) -> Result<()> {
+    use protocol::game::server::CharacterLevelUp;
+    self.send_packet(CharacterLevelUp::new(character))
+  }
+
+  pub fn show_stat_increase_result(&self, result: StatIncreaseResult) -> Result<()> {
+    use protocol::game::server::CharacterStatResult;
+    let packet = match result {
+      StatIncreaseResult::Success(character, stat) => CharacterStatResult::success(character, stat),
+      StatIncreaseResult::Failure => CharacterStatResult::failure(),
+    };
+    self.send_packet(packet)
   }
 
   // TODO: Move this somewhere else?
@@ -91,6 +154,399 @@ impl PlayerView {
     self.send_packet(InventoryList::new(player.character()?))
   }
 
+  pub fn update_skill_list(&self, player: &Player) -> Result<()> {
+    use protocol::game::server::SkillList;
+    self.send_packet(SkillList::new(player.character()?))
+  }
+
+  pub fn update_health(&self, health: u32) -> Result<()> {
+    use protocol::game::server::CharacterHealth;
+    self.send_packet(CharacterHealth {
+      health: health.min(u16::max_value() as u32) as u16,
+    })
+  }
+
+  pub fn update_mana_ability(&self, mana: u32, ability: u32) -> Result<()> {
+    use protocol::game::server::CharacterMana;
+    self.send_packet(CharacterMana {
+      mana: mana.min(u16::max_value() as u32) as u16,
+      ability: ability.min(u16::max_value() as u32) as u16,
+    })
+  }
+
+  pub fn show_object_move(
+    &self,
+    id: ObjectId,
+    destination: Position,
+    direction: Direction,
+  ) -> Result<()> {
+    use protocol::game::server::ObjectMove;
+    self.send_packet(ObjectMove {
+      id,
+      destination,
+      direction,
+    })
+  }
+
+  pub fn show_object_position(&self, id: ObjectId, position: Position) -> Result<()> {
+    use protocol::game::server::ObjectPosition;
+    self.send_packet(ObjectPosition::new(id, position))
+  }
+
+  pub fn show_object_animation(
+    &self,
+    id: ObjectId,
+    direction: Direction,
+    animation: u8,
+    target: ObjectId,
+  ) -> Result<()> {
+    use protocol::game::server::ObjectAnimation;
+    self.send_packet(ObjectAnimation {
+      id,
+      direction,
+      animation,
+      target,
+    })
+  }
+
+  pub fn show_object_hit(&self, id: ObjectId, outcome: AttackOutcome) -> Result<()> {
+    use protocol::game::server::ObjectHit;
+    let (damage, kind) = match outcome {
+      AttackOutcome::Miss => (0, ObjectHit::NORMAL),
+      AttackOutcome::Hit { damage, kind } => (damage, match kind {
+        DamageKind::Normal => ObjectHit::NORMAL,
+        DamageKind::Critical => ObjectHit::CRITICAL,
+        DamageKind::Excellent => ObjectHit::EXCELLENT,
+      }),
+    };
+
+    self.send_packet(ObjectHit {
+      id,
+      damage: damage.min(u16::max_value() as u32) as u16,
+      kind,
+    })
+  }
+
+  pub fn show_object_killed(&self, id: ObjectId, skill: SkillId, killer: ObjectId) -> Result<()> {
+    use protocol::game::server::ObjectKilled;
+    self.send_packet(ObjectKilled { id, skill, killer })
+  }
+
+  pub fn show_object_skill(
+    &self,
+    skill: SkillId,
+    caster: ObjectId,
+    target: ObjectId,
+  ) -> Result<()> {
+    use protocol::game::server::ObjectSkill;
+    self.send_packet(ObjectSkill {
+      skill,
+      caster,
+      target,
+    })
+  }
+
+  pub fn show_object_area_skill(
+    &self,
+    skill: SkillId,
+    caster: ObjectId,
+    position: Position,
+    rotation: u8,
+  ) -> Result<()> {
+    use protocol::game::server::ObjectAreaSkill;
+    self.send_packet(ObjectAreaSkill {
+      skill,
+      caster,
+      x: position.x,
+      y: position.y,
+      rotation,
+    })
+  }
+
+  pub fn show_viewport_players(&self, players: Vec<ViewportPlayer>) -> Result<()> {
+    use protocol::game::server::ViewportPlayers;
+    for players in players.chunks(ViewportPlayers::MAX_PLAYERS) {
+      self.send_packet(ViewportPlayers::new(players.iter().cloned()))?;
+    }
+    Ok(())
+  }
+
+  pub fn show_viewport_monsters(&self, monsters: Vec<ViewportMonster>) -> Result<()> {
+    use protocol::game::server::ViewportMonsters;
+    for monsters in monsters.chunks(ViewportMonsters::MAX_MONSTERS) {
+      self.send_packet(ViewportMonsters::new(monsters.iter().cloned()))?;
+    }
+    Ok(())
+  }
+
+  pub fn show_viewport_destroy(&self, ids: &[ObjectId]) -> Result<()> {
+    use protocol::game::server::ViewportDestroy;
+    for ids in ids.chunks(ViewportDestroy::MAX_OBJECTS) {
+      self.send_packet(ViewportDestroy::new(ids.iter().cloned()))?;
+    }
+    Ok(())
+  }
+
+  pub fn show_viewport_items(&self, items: Vec<ViewportItem>) -> Result<()> {
+    use protocol::game::server::ViewportItems;
+    for items in items.chunks(ViewportItems::MAX_ITEMS) {
+      self.send_packet(ViewportItems::new(items.iter().cloned()))?;
+    }
+    Ok(())
+  }
+
+  pub fn show_viewport_item_destroy(&self, ids: &[ObjectId]) -> Result<()> {
+    use protocol::game::server::ViewportItemDestroy;
+    for ids in ids.chunks(ViewportItemDestroy::MAX_ITEMS) {
+      self.send_packet(ViewportItemDestroy::new(ids.iter().cloned()))?;
+    }
+    Ok(())
+  }
+
+  pub fn show_item_move_result(&self, result: ItemMoveResult) -> Result<()> {
+    use protocol::game::models::ItemInfo;
+    use protocol::game::server::ItemMoveResult as MoveResult;
+    let packet = match result {
+      ItemMoveResult::Success(slot, item) => MoveResult::Success {
+        storage: 0,
+        slot,
+        item_info: ItemInfo::new(item),
+      },
+      ItemMoveResult::Vault(slot, item) => MoveResult::Success {
+        storage: 2,
+        slot,
+        item_info: ItemInfo::new(item),
+      },
+      ItemMoveResult::Trade(slot, item) => MoveResult::Success {
+        storage: 1,
+        slot,
+        item_info: ItemInfo::new(item),
+      },
+      ItemMoveResult::ChaosBox(slot, item) => MoveResult::Success {
+        storage: 3,
+        slot,
+        item_info: ItemInfo::new(item),
+      },
+      // The client cannot merge stacks by itself, so the moved stack is
+      // returned to its slot before the resulting counts are shown.
+      ItemMoveResult::Merged | ItemMoveResult::Failure => MoveResult::Failure,
+    };
+    self.send_packet(packet)
+  }
+
+  pub fn show_item_pickup_result(&self, result: ItemPickupResult) -> Result<()> {
+    use protocol::game::models::ItemInfo;
+    use protocol::game::server::ItemPickupResult as PickupResult;
+    let packet = match result {
+      ItemPickupResult::Item(slot, item) => PickupResult::Item {
+        slot,
+        item_info: ItemInfo::new(item),
+      },
+      ItemPickupResult::Money(money) => PickupResult::Money(money),
+      ItemPickupResult::Failure => PickupResult::Failure,
+    };
+    self.send_packet(packet)
+  }
+
+  pub fn show_item_durability(&self, slot: u8, durability: u8) -> Result<()> {
+    use protocol::game::server::ItemDurability;
+    self.send_packet(ItemDurability {
+      slot,
+      durability,
+      consumed: false,
+    })
+  }
+
+  /// Removes an item from the inventory, e.g once consumed.
+  pub fn show_item_removed(&self, slot: u8, consumed: bool) -> Result<()> {
+    use protocol::game::server::ItemRemove;
+    self.send_packet(ItemRemove { slot, consumed })
+  }
+
+  /// Shows the remaining count of a stack, after one of its items was used.
+  pub fn show_stack_consumed(&self, slot: u8, count: u8) -> Result<()> {
+    use protocol::game::server::ItemDurability;
+    self.send_packet(ItemDurability {
+      slot,
+      durability: count,
+      consumed: true,
+    })
+  }
+
+  /// Shows an inventory item which has been modified in place.
+  pub fn show_item_update(&self, slot: u8, item: &Item) -> Result<()> {
+    use protocol::game::models::ItemInfo;
+    use protocol::game::server::ItemUpdate;
+    self.send_packet(ItemUpdate {
+      slot,
+      item_info: ItemInfo::new(item),
+    })
+  }
+
+  pub fn show_item_repair_result(&self, money: u32) -> Result<()> {
+    use protocol::game::server::ItemRepairResult;
+    self.send_packet(ItemRepairResult { money })
+  }
+
+  pub fn show_npc_window(&self, window: NpcWindow) -> Result<()> {
+    use protocol::game::server::NpcTalkResult;
+    self.send_packet(NpcTalkResult { window })
+  }
+
+  pub fn show_shop_items(&self, shop: &Shop) -> Result<()> {
+    use protocol::game::server::ShopItemList;
+    self.send_packet(ShopItemList::new(shop))
+  }
+
+  /// Shows a bought item at its inventory slot, or nothing on failure.
+  pub fn show_item_buy_result(&self, result: Option<(u8, &Item)>) -> Result<()> {
+    use protocol::game::models::ItemInfo;
+    use protocol::game::server::ItemBuyResult;
+    let packet = match result {
+      Some((slot, item)) => ItemBuyResult::Success {
+        slot,
+        item_info: ItemInfo::new(item),
+      },
+      None => ItemBuyResult::Failure,
+    };
+    self.send_packet(packet)
+  }
+
+  pub fn show_item_sell_result(&self, success: bool, money: u32) -> Result<()> {
+    use protocol::game::server::ItemSellResult;
+    self.send_packet(ItemSellResult { success, money })
+  }
+
+  /// Updates the amount of zen carried by the character.
+  pub fn show_money(&self, money: u32) -> Result<()> {
+    use protocol::game::server::ItemPickupResult;
+    self.send_packet(ItemPickupResult::Money(money))
+  }
+
+  pub fn show_vault_items(&self, vault: &Vault) -> Result<()> {
+    use protocol::game::server::ShopItemList;
+    self.send_packet(ShopItemList::vault(vault))
+  }
+
+  pub fn show_vault_money(&self, success: bool, vault: u32, money: u32) -> Result<()> {
+    use protocol::game::server::VaultMoneyResult;
+    self.send_packet(VaultMoneyResult {
+      success,
+      vault,
+      money,
+    })
+  }
+
+  pub fn show_vault_protection(&self, state: VaultProtectionState) -> Result<()> {
+    use protocol::game::server::VaultProtection;
+    self.send_packet(VaultProtection { state })
+  }
+
+  pub fn show_trade_request(&self, name: &str) -> Result<()> {
+    use protocol::game::server::TradeRequested;
+    self.send_packet(TradeRequested {
+      name: name.to_owned(),
+    })
+  }
+
+  /// Shows the response to a trade request, along with the partner's details.
+  pub fn show_trade_response(&self, accepted: bool, name: &str, level: u16) -> Result<()> {
+    use protocol::game::server::TradeResponseResult;
+    self.send_packet(TradeResponseResult {
+      accepted,
+      name: name.to_owned(),
+      level,
+      guild: 0,
+    })
+  }
+
+  pub fn show_trade_item_added(&self, slot: u8, item: &Item) -> Result<()> {
+    use protocol::game::models::ItemInfo;
+    use protocol::game::server::TradeItemAdded;
+    self.send_packet(TradeItemAdded {
+      slot,
+      item_info: ItemInfo::new(item),
+    })
+  }
+
+  pub fn show_trade_item_removed(&self, slot: u8) -> Result<()> {
+    use protocol::game::server::TradeItemRemoved;
+    self.send_packet(TradeItemRemoved { slot })
+  }
+
+  pub fn show_trade_money_result(&self, success: bool) -> Result<()> {
+    use protocol::game::server::TradeMoneyResult;
+    self.send_packet(TradeMoneyResult { success })
+  }
+
+  pub fn show_trade_partner_money(&self, money: u32) -> Result<()> {
+    use protocol::game::server::TradePartnerMoney;
+    self.send_packet(TradePartnerMoney { money })
+  }
+
+  /// Shows whether the trade partner has confirmed the offers.
+  pub fn show_trade_button(&self, confirmed: bool) -> Result<()> {
+    use protocol::game::server::TradeButtonState;
+    self.send_packet(TradeButtonState { confirmed })
+  }
+
+  pub fn show_trade_result(&self, outcome: TradeOutcome) -> Result<()> {
+    use protocol::game::server::TradeResult;
+    self.send_packet(TradeResult { outcome })
+  }
+
+  pub fn show_chaos_box_items(&self, chaos_box: &ItemStorage) -> Result<()> {
+    use protocol::game::server::ShopItemList;
+    self.send_packet(ShopItemList::chaos_box(chaos_box))
+  }
+
+  /// Shows the outcome of a chaos mix, along with its product on success.
+  pub fn show_chaos_mix_result(
+    &self,
+    outcome: ChaosMixOutcome,
+    product: Option<&Item>,
+  ) -> Result<()> {
+    use protocol::game::server::ChaosMixResult;
+    self.send_packet(match product {
+      Some(item) => ChaosMixResult::success(item),
+      None => ChaosMixResult::failure(outcome),
+    })
+  }
+
+  pub fn show_notice<S: Into<String>>(&self, text: S) -> Result<()> {
+    use protocol::game::server::Message;
+    self.send_packet(Message::Notice(text.into()))
+  }
+
+  pub fn show_guild_master_question(&self) -> Result<()> {
+    use protocol::game::server::GuildMasterQuestion;
+    self.send_packet(GuildMasterQuestion)
+  }
+
+  /// Shows a chat message sent by a character.
+  pub fn show_chat_message(&self, name: &str, message: &str) -> Result<()> {
+    use protocol::game::server::ChatMessage;
+    self.send_packet(ChatMessage {
+      name: name.into(),
+      message: message.into(),
+    })
+  }
+
+  /// Shows a message whispered by a character.
+  pub fn show_whisper(&self, name: &str, message: &str) -> Result<()> {
+    use protocol::game::server::WhisperMessage;
+    self.send_packet(WhisperMessage {
+      name: name.into(),
+      message: message.into(),
+    })
+  }
+
+  /// Shows that a whisper could not be delivered.
+  pub fn show_whisper_failure(&self) -> Result<()> {
+    use protocol::game::server::WhisperFailure;
+    self.send_packet(WhisperFailure::default())
+  }
+
   fn send_packet<P: PacketEncodable>(&self, packet: P) -> Result<()> {
     let packet = packet
       .to_packet()
@@ -0,0 +1,268 @@
+use context::GameServerContext;
+use error::{cxerr, Result};
+use failure::ResultExt;
+use murust_data_model::entities::{Character, CharacterAttributes};
+use murust_data_model::types::{ObjectId, Position};
+use murust_game_world::combat::{AttackOutcome, CombatStats};
+use murust_game_world::resources::{Terrain, WorldEvent};
+use murust_game_world::{Clock, Entity, FixedTimestep, GameWorld};
+use murust_service::{MonsterService, NpcService};
+use player::PlayerEvent;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+mod viewport;
+
+/// A collection of all worlds hosted by a server.
+#[derive(Clone)]
+pub struct WorldManager {
+  worlds: Arc<HashMap<u8, Mutex<GameWorld>>>,
+}
+
+impl WorldManager {
+  /// Constructs a new manager, hosting one world per map.
+  pub fn new<I: IntoIterator<Item = u8>>(
+    maps: I,
+    terrain_path: Option<&Path>,
+    monster_service: &MonsterService,
+    npc_service: &NpcService,
+  ) -> Result<Self> {
+    let worlds = maps
+      .into_iter()
+      .map(|map| {
+        let terrain = match terrain_path {
+          Some(path) => Terrain::load(path, map)
+            .with_context(|_| format!("Failed to load terrain for map {}", map))?,
+          None => Terrain::new(map),
+        };
+
+        let mut world = GameWorld::new(terrain);
+        let spawns = monster_service
+          .find_spawns_by_map(map)
+          .with_context(|_| format!("Failed to load monster spawns for map {}", map))?;
+        for spawn in &spawns {
+          world.spawn_monsters(spawn);
+        }
+
+        let npcs = npc_service
+          .find_by_map(map)
+          .with_context(|_| format!("Failed to load NPCs for map {}", map))?;
+        for npc in npcs {
+          world.add_npc(Arc::new(npc));
+        }
+
+        Ok((map, Mutex::new(world)))
+      })
+      .collect::<Result<HashMap<_, _>>>()?;
+
+    Ok(WorldManager {
+      worlds: Arc::new(worlds),
+    })
+  }
+
+  /// Returns the world of a map, if it is hosted.
+  pub fn get(&self, map: u8) -> Option<MutexGuard<GameWorld>> {
+    self
+      .worlds
+      .get(&map)
+      .map(|world| world.lock().expect("locking game world"))
+  }
+
+  /// Adds a player to the world of its character's map, able to fight with
+  /// the character's stats.
+  ///
+  /// Once added, the player is registered using `register` while the world
+  /// is still locked, so no tick can observe it beforehand.
+  pub fn add_player<F: FnOnce()>(
+    &self,
+    id: ObjectId,
+    character: &Character,
+    register: F,
+  ) -> Result<()> {
+    let map = character.map;
+    let mut world = self
+      .get(map)
+      .ok_or_else(|| cxerr(format!("Map {} is not hosted by the server", map)))?;
+    let entity = world
+      .add_player(id, character.position)
+      .ok_or_else(|| cxerr(format!("Player {} has already been added to map {}", id, map)))?;
+    world.set_combat_stats(entity, CombatStats::from_character(character));
+    let attributes = CharacterAttributes::new(character);
+    world.set_health(entity, attributes.max_health);
+    world.set_mana_and_ability(entity, attributes.max_mana, attributes.max_ability);
+    register();
+    Ok(())
+  }
+
+  /// Refreshes a player's attributes after its character's stats changed.
+  ///
+  /// Health, mana and AG are refilled if requested, otherwise only their
+  /// maximum changes.
+  pub fn refresh_player(&self, id: ObjectId, character: &Character, refill: bool) {
+    let mut world = match self.get(character.map) {
+      Some(world) => world,
+      None => return,
+    };
+
+    let entity = match world.player(id) {
+      Some(entity) => entity,
+      None => return,
+    };
+
+    let attributes = CharacterAttributes::new(character);
+    let (health, mana, ability) = (
+      attributes.max_health,
+      attributes.max_mana,
+      attributes.max_ability,
+    );
+    world.set_combat_stats(entity, CombatStats::from_character(character));
+    if refill {
+      world.set_health(entity, health);
+      world.set_mana_and_ability(entity, mana, ability);
+    } else {
+      world.set_max_health(entity, health);
+      world.set_max_mana_and_ability(entity, mana, ability);
+    }
+  }
+
+  /// Removes a player from whichever world it resides in.
+  pub fn remove_player(&self, id: ObjectId) {
+    for world in self.worlds.values() {
+      if world.lock().expect("locking game world").remove_player(id) {
+        break;
+      }
+    }
+  }
+
+  /// Processes a single tick of each world, passing on any produced events.
+  pub fn update<F>(&self, mut handler: F)
+  where
+    F: FnMut(&GameWorld, Vec<WorldEvent>),
+  {
+    for world in self.worlds.values() {
+      let mut world = world.lock().expect("locking game world");
+      world.update();
+
+      let events = world.drain_events();
+      if !events.is_empty() {
+        handler(&world, events);
+      }
+    }
+  }
+}
+
+/// Updates the worlds at a fixed rate until the server is stopped.
+pub fn run<C: Clock>(context: GameServerContext, running: Arc<AtomicBool>, clock: C) {
+  let mut timestep = FixedTimestep::new(context.config().tick_rate, clock.now());
+
+  while running.load(Ordering::SeqCst) {
+    for _ in 0..timestep.advance(clock.now()) {
+      context
+        .worlds()
+        .update(|world, events| handle_events(&context, world, events));
+    }
+    thread::sleep(timestep.remaining());
+  }
+}
+
+/// Routes the events of a world to the affected players.
+fn handle_events(context: &GameServerContext, world: &GameWorld, events: Vec<WorldEvent>) {
+  for event in events {
+    let result = match event {
+      WorldEvent::ObjectsEntered { observer, objects } => {
+        viewport::show_objects(context, world, observer, &objects)
+      },
+      WorldEvent::ObjectsLeft { observer, objects } => {
+        viewport::hide_objects(context, observer, &objects)
+      },
+      WorldEvent::ItemsEntered { observer, items } => {
+        viewport::show_items(context, world, observer, &items)
+      },
+      WorldEvent::ItemsLeft { observer, items } => viewport::hide_items(context, observer, &items),
+      WorldEvent::ObjectMoved {
+        object,
+        destination,
+        direction,
+      } => viewport::show_object_move(context, world, object, destination, direction),
+      WorldEvent::MonsterAttack {
+        monster,
+        target,
+        outcome,
+      } => monster_attacked(context, world, monster, target, outcome),
+      WorldEvent::PlayerDied { player, killer } => player_died(context, world, player, killer),
+      WorldEvent::PlayerRespawned { player, position } => {
+        player_respawned(context, world, player, position)
+      },
+      WorldEvent::PlayerRecovered { player } => player_recovered(context, world, player),
+    };
+
+    if let Err(error) = result {
+      warn!("Failed to process world event on map {}: {}", world.map(), error);
+    }
+  }
+}
+
+/// Shows a monster's attack and lets the session of any player hit wear down
+/// its armor.
+fn monster_attacked(
+  context: &GameServerContext,
+  world: &GameWorld,
+  monster: Entity,
+  target: Entity,
+  outcome: AttackOutcome,
+) -> Result<()> {
+  if let (AttackOutcome::Hit { .. }, Some(id)) = (outcome, world.object_id(target)) {
+    context.notify_player(id, PlayerEvent::Hit);
+  }
+
+  viewport::show_monster_attack(context, world, monster, target, outcome)
+}
+
+/// Shows a player's death and lets its session apply the consequences.
+fn player_died(
+  context: &GameServerContext,
+  world: &GameWorld,
+  player: Entity,
+  killer: Entity,
+) -> Result<()> {
+  let (id, killer) = match (world.object_id(player), world.object_id(killer)) {
+    (Some(id), Some(killer)) => (id, killer),
+    _ => return Ok(()),
+  };
+
+  context.notify_player(id, PlayerEvent::Died { killer });
+  viewport::show_player_death(context, world, player, killer)
+}
+
+/// Shows a player being revived and lets its session resume.
+fn player_respawned(
+  context: &GameServerContext,
+  world: &GameWorld,
+  player: Entity,
+  position: Position,
+) -> Result<()> {
+  let id = match world.object_id(player) {
+    Some(id) => id,
+    None => return Ok(()),
+  };
+
+  context.notify_player(id, PlayerEvent::Respawned { position });
+  viewport::show_player_respawn(context, world, player)
+}
+
+/// Shows a player its health, mana and AG restored over time.
+fn player_recovered(context: &GameServerContext, world: &GameWorld, player: Entity) -> Result<()> {
+  let view = match world.object_id(player).and_then(|id| context.player_view(id)) {
+    Some(view) => view,
+    None => return Ok(()),
+  };
+
+  let health = world.health(player).map_or(0, |health| health.current);
+  let mana = world.mana(player).map_or(0, |mana| mana.current);
+  let ability = world.ability(player).map_or(0, |ability| ability.current);
+  view.update_health(health)?;
+  view.update_mana_ability(mana, ability)
+}
@@ -0,0 +1,224 @@
+use context::GameServerContext;
+use error::Result;
+use murust_data_model::entities::Loot;
+use murust_data_model::types::{Direction, ObjectId, Position};
+use murust_game_world::combat::AttackOutcome;
+use murust_game_world::{Entity, GameWorld};
+use protocol::game::models::ItemInfo;
+use protocol::game::server::{ObjectAnimation, ViewportItem, ViewportMonster};
+
+/// Shows objects that have come into view of a player.
+pub fn show_objects(
+  context: &GameServerContext,
+  world: &GameWorld,
+  observer: ObjectId,
+  objects: &[Entity],
+) -> Result<()> {
+  let view = match context.player_view(observer) {
+    Some(view) => view,
+    None => return Ok(()),
+  };
+
+  let monsters = objects
+    .iter()
+    .filter_map(|&entity| {
+      // NPCs are presented to the client as monsters
+      let class = match world.monster_definition(entity) {
+        Some(definition) => definition.class,
+        None => world.npc_definition(entity)?.class,
+      };
+      let id = world.object_id(entity)?;
+      let location = world.location(entity)?;
+      let target = world.destination(entity)?;
+      Some(ViewportMonster::new(
+        id,
+        class,
+        location.position,
+        target,
+        location.direction,
+      ))
+    })
+    .collect::<Vec<_>>();
+
+  let players = objects
+    .iter()
+    .filter(|&&entity| world.is_player(entity))
+    .filter_map(|&entity| {
+      let id = world.object_id(entity)?;
+      let location = world.location(entity)?;
+      let target = world.destination(entity)?;
+      context
+        .player_appearance(id)
+        .map(|player| player.with_location(location.position, target, location.direction))
+    })
+    .collect::<Vec<_>>();
+
+  if !monsters.is_empty() {
+    view.show_viewport_monsters(monsters)?;
+  }
+
+  if !players.is_empty() {
+    view.show_viewport_players(players)?;
+  }
+
+  Ok(())
+}
+
+/// Hides objects that have left the view of a player.
+pub fn hide_objects(
+  context: &GameServerContext,
+  observer: ObjectId,
+  objects: &[ObjectId],
+) -> Result<()> {
+  match context.player_view(observer) {
+    Some(view) => view.show_viewport_destroy(objects),
+    None => Ok(()),
+  }
+}
+
+/// Shows ground items that have come into view of a player.
+pub fn show_items(
+  context: &GameServerContext,
+  world: &GameWorld,
+  observer: ObjectId,
+  items: &[Entity],
+) -> Result<()> {
+  let view = match context.player_view(observer) {
+    Some(view) => view,
+    None => return Ok(()),
+  };
+
+  let items = {
+    let ground_items = world.ground_items();
+    items
+      .iter()
+      .filter_map(|&entity| {
+        let item = ground_items.get(entity)?;
+        let location = world.location(entity)?;
+        let item_info = match item.loot {
+          Loot::Item(ref item) => ItemInfo::new(item),
+          Loot::Money(amount) => ItemInfo::money(amount),
+        };
+        Some(ViewportItem::new(item.id, location.position, item_info))
+      })
+      .collect::<Vec<_>>()
+  };
+
+  if items.is_empty() {
+    return Ok(());
+  }
+  view.show_viewport_items(items)
+}
+
+/// Hides ground items that have left the view of a player.
+pub fn hide_items(
+  context: &GameServerContext,
+  observer: ObjectId,
+  items: &[ObjectId],
+) -> Result<()> {
+  match context.player_view(observer) {
+    Some(view) => view.show_viewport_item_destroy(items),
+    None => Ok(()),
+  }
+}
+
+/// Shows a world controlled object walking to the players observing it.
+pub fn show_object_move(
+  context: &GameServerContext,
+  world: &GameWorld,
+  object: Entity,
+  destination: Position,
+  direction: Direction,
+) -> Result<()> {
+  let id = match world.object_id(object) {
+    Some(id) => id,
+    None => return Ok(()),
+  };
+
+  for (observer, view) in context.player_views(&world.observers(object)) {
+    if let Err(error) = view.show_object_move(id, destination, direction) {
+      warn!("Failed to show movement to player {}: {}", observer, error);
+    }
+  }
+  Ok(())
+}
+
+/// Shows a monster's attack to the players observing it, and its outcome to
+/// the target.
+pub fn show_monster_attack(
+  context: &GameServerContext,
+  world: &GameWorld,
+  monster: Entity,
+  target: Entity,
+  outcome: AttackOutcome,
+) -> Result<()> {
+  let (id, target, direction) = match (
+    world.object_id(monster),
+    world.object_id(target),
+    world.location(monster),
+  ) {
+    (Some(id), Some(target), Some(location)) => (id, target, location.direction),
+    _ => return Ok(()),
+  };
+
+  for (observer, view) in context.player_views(&world.observers(monster)) {
+    let animation = ObjectAnimation::MONSTER_ATTACK;
+    if let Err(error) = view.show_object_animation(id, direction, animation, target) {
+      warn!("Failed to show monster attack to player {}: {}", observer, error);
+    }
+  }
+
+  match context.player_view(target) {
+    Some(view) => view.show_object_hit(target, outcome),
+    None => Ok(()),
+  }
+}
+
+/// Shows a player's death to itself and the players observing it.
+///
+/// Deaths by the hand of another player have already been shown to the
+/// observers along with the killing blow.
+pub fn show_player_death(
+  context: &GameServerContext,
+  world: &GameWorld,
+  player: Entity,
+  killer: ObjectId,
+) -> Result<()> {
+  let id = match world.object_id(player) {
+    Some(id) => id,
+    None => return Ok(()),
+  };
+
+  let mut viewers = vec![id];
+  if world.player(killer).is_none() {
+    viewers.extend(world.observers(player));
+  }
+
+  for (viewer, view) in context.player_views(&viewers) {
+    if let Err(error) = view.show_object_killed(id, 0, killer) {
+      warn!("Failed to show death to player {}: {}", viewer, error);
+    }
+  }
+  Ok(())
+}
+
+/// Shows a revived player anew to the players observing it.
+pub fn show_player_respawn(
+  context: &GameServerContext,
+  world: &GameWorld,
+  player: Entity,
+) -> Result<()> {
+  let id = match world.object_id(player) {
+    Some(id) => id,
+    None => return Ok(()),
+  };
+
+  for observer in world.observers(player) {
+    let shown = hide_objects(context, observer, &[id])
+      .and_then(|_| show_objects(context, world, observer, &[player]));
+    if let Err(error) = shown {
+      warn!("Failed to show respawn to player {}: {}", observer, error);
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,38 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of time for the game world.
+pub trait Clock: Send + Sync {
+  /// Returns the current instant.
+  fn now(&self) -> Instant;
+}
+
+/// A clock using the system's monotonic time.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  fn now(&self) -> Instant { Instant::now() }
+}
+
+/// A clock that only advances when told to.
+///
+/// This allows a world to be stepped deterministically.
+#[derive(Debug, Clone)]
+pub struct ManualClock(Arc<Mutex<Instant>>);
+
+impl ManualClock {
+  /// Constructs a new manual clock, starting at the current instant.
+  pub fn new() -> Self { ManualClock(Arc::new(Mutex::new(Instant::now()))) }
+
+  /// Advances the clock by a duration.
+  pub fn advance(&self, duration: Duration) { *self.instant() += duration; }
+
+  fn instant(&self) -> ::std::sync::MutexGuard<Instant> {
+    self.0.lock().expect("locking manual clock")
+  }
+}
+
+impl Clock for ManualClock {
+  fn now(&self) -> Instant { *self.instant() }
+}
@@ -0,0 +1,241 @@
+//! Pure combat formulas, independent of any world state.
+//!
+//! The values follow the season 2 server formulas, with class specific
+//! formulas kept in a table and randomness supplied by the caller.
+
+pub use self::stats::CombatStats;
+use rand::Rng;
+
+mod stats;
+mod table;
+
+/// The scale of a hit chance, i.e one hundredth of a percent.
+pub const HIT_CHANCE_SCALE: u32 = 10_000;
+
+/// The lowest chance of hitting, regardless of the defender.
+const MIN_HIT_CHANCE: u32 = 500;
+
+/// The multiplier (in percent) of excellent damage.
+const EXCELLENT_DAMAGE_PERCENT: u32 = 120;
+
+/// The kind of combatants involved in an attack.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CombatMode {
+  /// An attack between a player and a monster (PvM).
+  Monster,
+  /// An attack between two players (PvP).
+  Player,
+}
+
+/// The kind of damage dealt by a hit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DamageKind {
+  Normal,
+  Critical,
+  Excellent,
+}
+
+/// The outcome of a single attack.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AttackOutcome {
+  Miss,
+  Hit { damage: u32, kind: DamageKind },
+}
+
+/// Returns the chance of an attack hitting, scaled by `HIT_CHANCE_SCALE`.
+pub fn hit_chance(attacker: &CombatStats, defender: &CombatStats, mode: CombatMode) -> u32 {
+  let chance = match mode {
+    CombatMode::Monster => {
+      if attacker.attack_rate < defender.defense_rate || attacker.attack_rate == 0 {
+        0
+      } else {
+        HIT_CHANCE_SCALE - defender.defense_rate * HIT_CHANCE_SCALE / attacker.attack_rate
+      }
+    },
+    CombatMode::Player => {
+      let rates = attacker.pvp_attack_rate + defender.pvp_defense_rate;
+      let levels = u32::from(attacker.level) + u32::from(defender.level);
+      if rates == 0 || levels == 0 {
+        0
+      } else {
+        let rate = attacker.pvp_attack_rate * HIT_CHANCE_SCALE / rates;
+        let level = u32::from(attacker.level) * HIT_CHANCE_SCALE / levels;
+        2 * rate * level / HIT_CHANCE_SCALE
+      }
+    },
+  };
+
+  chance.max(MIN_HIT_CHANCE).min(HIT_CHANCE_SCALE)
+}
+
+/// Returns the damage dealt by a hit, after the defender's defense.
+///
+/// The roll is the attacker's random damage, only used by normal hits, since
+/// critical and excellent hits are based on the maximum damage.
+pub fn damage(attacker: &CombatStats, defender: &CombatStats, kind: DamageKind, roll: u32) -> u32 {
+  let damage = match kind {
+    DamageKind::Normal => roll,
+    DamageKind::Critical => attacker.max_damage,
+    DamageKind::Excellent => attacker.max_damage * EXCELLENT_DAMAGE_PERCENT / 100,
+  };
+
+  // Hits always deal a tenth of the attacker's level
+  damage
+    .saturating_sub(defender.defense)
+    .max(u32::from(attacker.level) / 10)
+}
+
+/// Resolves an attack, using a random generator for all rolls.
+pub fn attack<R: Rng>(
+  attacker: &CombatStats,
+  defender: &CombatStats,
+  mode: CombatMode,
+  rng: &mut R,
+) -> AttackOutcome {
+  if rng.gen_range(0, HIT_CHANCE_SCALE) >= hit_chance(attacker, defender, mode) {
+    return AttackOutcome::Miss;
+  }
+
+  let kind = if rng.gen_range(0, 100) < attacker.excellent_rate {
+    DamageKind::Excellent
+  } else if rng.gen_range(0, 100) < attacker.critical_rate {
+    DamageKind::Critical
+  } else {
+    DamageKind::Normal
+  };
+
+  let roll = rng.gen_range(attacker.min_damage, attacker.max_damage + 1);
+  AttackOutcome::Hit {
+    damage: damage(attacker, defender, kind, roll),
+    kind,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rand::{SeedableRng, XorShiftRng};
+
+  fn combatant(level: u16, min_damage: u32, max_damage: u32, defense: u32) -> CombatStats {
+    CombatStats {
+      level,
+      min_damage,
+      max_damage,
+      defense,
+      ..CombatStats::default()
+    }
+  }
+
+  #[test]
+  fn monster_hit_chance_depends_on_rates() {
+    let attacker = CombatStats {
+      attack_rate: 100,
+      ..CombatStats::default()
+    };
+    let defender = CombatStats {
+      defense_rate: 25,
+      ..CombatStats::default()
+    };
+
+    assert_eq!(hit_chance(&attacker, &defender, CombatMode::Monster), 7_500);
+    assert_eq!(hit_chance(&defender, &attacker, CombatMode::Monster), MIN_HIT_CHANCE);
+  }
+
+  #[test]
+  fn player_hit_chance_depends_on_rates_and_levels() {
+    // A level 1 Dark Knight attacking another one with base stats
+    let knight = CombatStats {
+      level: 1,
+      pvp_attack_rate: 93,
+      pvp_defense_rate: 12,
+      ..CombatStats::default()
+    };
+    assert_eq!(hit_chance(&knight, &knight, CombatMode::Player), 8_857);
+
+    let veteran = CombatStats {
+      level: 9,
+      ..knight
+    };
+    assert_eq!(hit_chance(&veteran, &knight, CombatMode::Player), HIT_CHANCE_SCALE);
+    assert_eq!(hit_chance(&knight, &veteran, CombatMode::Player), 1_771);
+  }
+
+  #[test]
+  fn damage_is_reduced_by_defense() {
+    let attacker = combatant(1, 10, 20, 0);
+    let defender = combatant(1, 0, 0, 6);
+
+    assert_eq!(damage(&attacker, &defender, DamageKind::Normal, 15), 9);
+    assert_eq!(damage(&attacker, &defender, DamageKind::Critical, 15), 14);
+    assert_eq!(damage(&attacker, &defender, DamageKind::Excellent, 15), 18);
+    assert_eq!(damage(&attacker, &combatant(1, 0, 0, 50), DamageKind::Normal, 15), 0);
+  }
+
+  #[test]
+  fn damage_has_level_based_minimum() {
+    let attacker = combatant(55, 10, 20, 0);
+    let defender = combatant(1, 0, 0, 100);
+    assert_eq!(damage(&attacker, &defender, DamageKind::Normal, 20), 5);
+  }
+
+  #[test]
+  fn attacks_roll_within_damage_range() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let attacker = CombatStats {
+      attack_rate: 1_000,
+      ..combatant(10, 16, 24, 0)
+    };
+    let defender = combatant(2, 4, 7, 1);
+
+    let outcomes = (0..100)
+      .map(|_| attack(&attacker, &defender, CombatMode::Monster, &mut rng))
+      .collect::<Vec<_>>();
+
+    assert!(outcomes.iter().all(|outcome| match *outcome {
+      AttackOutcome::Hit {
+        damage,
+        kind: DamageKind::Normal,
+      } => damage >= 15 && damage <= 23,
+      _ => false,
+    }));
+  }
+
+  #[test]
+  fn attacks_deal_excellent_damage() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let attacker = CombatStats {
+      attack_rate: 1_000,
+      excellent_rate: 100,
+      ..combatant(10, 16, 24, 0)
+    };
+
+    let outcome = attack(&attacker, &combatant(2, 4, 7, 1), CombatMode::Monster, &mut rng);
+    assert_eq!(
+      outcome,
+      AttackOutcome::Hit {
+        damage: 27,
+        kind: DamageKind::Excellent,
+      }
+    );
+  }
+
+  #[test]
+  fn attacks_miss_against_superior_defense() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let attacker = CombatStats {
+      attack_rate: 10,
+      ..combatant(1, 4, 7, 0)
+    };
+    let defender = CombatStats {
+      defense_rate: 1_000,
+      ..CombatStats::default()
+    };
+
+    let hits = (0..1_000)
+      .filter(|_| {
+        attack(&attacker, &defender, CombatMode::Monster, &mut rng) != AttackOutcome::Miss
+      })
+      .count();
+    assert!(hits > 0 && hits < 100);
+  }
+}
@@ -0,0 +1,376 @@
+use super::table::{self, evaluate};
+use murust_data_model::entities::{Character, CharacterAttributes, Item, MonsterDefinition,
+                                  SkillDefinition};
+use murust_data_model::types::{ExcellentOptions, ItemAttribute, ItemSlot};
+use std::time::Duration;
+
+/// The offensive and defensive values of a combatant.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CombatStats {
+  pub level: u16,
+  pub min_damage: u32,
+  pub max_damage: u32,
+  pub min_magic_damage: u32,
+  pub max_magic_damage: u32,
+  pub defense: u32,
+  /// The attack success rate against monsters.
+  pub attack_rate: u32,
+  /// The defense success rate against monsters.
+  pub defense_rate: u32,
+  /// The attack success rate against players.
+  pub pvp_attack_rate: u32,
+  /// The defense success rate against players.
+  pub pvp_defense_rate: u32,
+  /// The chance, in percent, of dealing critical damage.
+  pub critical_rate: u8,
+  /// The chance, in percent, of dealing excellent damage.
+  pub excellent_rate: u8,
+  /// The speed of attacks without skills.
+  pub attack_speed: u32,
+}
+
+/// The interval, in milliseconds, between attacks without any attack speed.
+const BASE_ATTACK_INTERVAL_MS: u64 = 600;
+
+/// The critical damage chance, in percent, granted by each lucky weapon.
+const LUCK_CRITICAL_RATE: u32 = 5;
+
+/// The excellent damage chance, in percent, granted by each excellent damage
+/// option.
+const EXCELLENT_DAMAGE_RATE: u32 = 10;
+
+/// The damage increase, in percent, granted by each excellent damage option.
+const EXCELLENT_DAMAGE_PERCENT: u32 = 2;
+
+/// The character levels per point of damage granted by each excellent damage
+/// level option.
+const EXCELLENT_DAMAGE_LEVELS: u32 = 20;
+
+/// The defense rate increase, in percent, granted by each excellent defense
+/// rate option.
+const EXCELLENT_DEFENSE_RATE_PERCENT: u32 = 10;
+
+/// The equipment slots that contribute damage.
+const WEAPON_SLOTS: [ItemSlot; 2] = [ItemSlot::WeaponRight, ItemSlot::WeaponLeft];
+
+/// The equipment slots that contribute defense rate.
+const ARMOR_SLOTS: [ItemSlot; 6] = [
+  ItemSlot::WeaponLeft,
+  ItemSlot::Helm,
+  ItemSlot::Armor,
+  ItemSlot::Pants,
+  ItemSlot::Gloves,
+  ItemSlot::Boots,
+];
+
+impl CombatStats {
+  /// Computes the stats of a character from its class, attributes and equipment.
+  pub fn from_character(character: &Character) -> Self {
+    let formulas = table::formulas(character.class);
+    let attributes = CharacterAttributes::new(character);
+    let mut stats = CombatStats {
+      level: character.level,
+      min_damage: evaluate(formulas.min_damage, character),
+      max_damage: evaluate(formulas.max_damage, character),
+      min_magic_damage: evaluate(formulas.min_magic_damage, character),
+      max_magic_damage: evaluate(formulas.max_magic_damage, character),
+      defense: attributes.defense,
+      attack_rate: evaluate(formulas.attack_rate, character),
+      defense_rate: evaluate(formulas.defense_rate, character),
+      pvp_attack_rate: evaluate(formulas.pvp_attack_rate, character),
+      pvp_defense_rate: evaluate(formulas.pvp_defense_rate, character),
+      critical_rate: 0,
+      excellent_rate: 0,
+      attack_speed: attributes.attack_speed,
+    };
+
+    let mut critical_rate = 0;
+    for item in equipped(character, &WEAPON_SLOTS).filter(|item| item.is_weapon()) {
+      let bonus = item.level_bonus() + item.option_bonus();
+      stats.min_damage += u32::from(item.boost(ItemAttribute::MinDamage)) + bonus;
+      stats.max_damage += u32::from(item.boost(ItemAttribute::MaxDamage)) + bonus;
+      if item.luck {
+        critical_rate += LUCK_CRITICAL_RATE;
+      }
+    }
+
+    for item in equipped(character, &ARMOR_SLOTS).filter(|item| !item.is_weapon()) {
+      stats.defense_rate += u32::from(item.boost(ItemAttribute::DefenseRate));
+    }
+
+    let equipment = &character.equipment;
+    stats.min_damage += equipment.set_bonus(ItemAttribute::MinDamage);
+    stats.max_damage += equipment.set_bonus(ItemAttribute::MaxDamage);
+    stats.min_magic_damage += equipment.set_bonus(ItemAttribute::MagicPower);
+    stats.max_magic_damage += equipment.set_bonus(ItemAttribute::MagicPower);
+    stats.defense_rate += equipment.set_bonus(ItemAttribute::DefenseRate);
+
+    let offensive = |option| equipment.excellent_count(Item::offensive_options, option);
+    let defensive = |option| equipment.excellent_count(Item::defensive_options, option);
+
+    let level_damage = offensive(ExcellentOptions::DamageLevel) * u32::from(character.level)
+      / EXCELLENT_DAMAGE_LEVELS;
+    let damage_percent =
+      100 + offensive(ExcellentOptions::DamagePercent) * EXCELLENT_DAMAGE_PERCENT;
+    stats.min_damage = (stats.min_damage + level_damage) * damage_percent / 100;
+    stats.max_damage = (stats.max_damage + level_damage) * damage_percent / 100;
+
+    let defense_rate_percent =
+      100 + defensive(ExcellentOptions::DefenseRate) * EXCELLENT_DEFENSE_RATE_PERCENT;
+    stats.defense_rate = stats.defense_rate * defense_rate_percent / 100;
+    stats.pvp_defense_rate = stats.pvp_defense_rate * defense_rate_percent / 100;
+
+    let excellent_rate = offensive(ExcellentOptions::ExcellentDamage) * EXCELLENT_DAMAGE_RATE;
+    stats.critical_rate = critical_rate.min(100) as u8;
+    stats.excellent_rate = excellent_rate.min(100) as u8;
+
+    stats.max_damage = stats.max_damage.max(stats.min_damage);
+    stats
+  }
+
+  /// Returns the shortest interval allowed between attacks without skills.
+  pub fn attack_interval(&self) -> Duration {
+    Duration::from_millis(BASE_ATTACK_INTERVAL_MS * 100 / (100 + u64::from(self.attack_speed)))
+  }
+
+  /// Returns the stats used when attacking with a skill.
+  ///
+  /// A skill's damage is added on top of the caster's magic or weapon damage,
+  /// increasing the maximum by an additional half.
+  pub fn with_skill(&self, skill: &SkillDefinition) -> Self {
+    let (min_damage, max_damage) = if skill.magic {
+      (self.min_magic_damage, self.max_magic_damage)
+    } else {
+      (self.min_damage, self.max_damage)
+    };
+
+    let damage = u32::from(skill.damage);
+    CombatStats {
+      min_damage: min_damage + damage,
+      max_damage: max_damage + damage + damage / 2,
+      ..*self
+    }
+  }
+
+  /// Returns the stats of a monster, which are the same against all targets.
+  pub fn from_monster(definition: &MonsterDefinition) -> Self {
+    CombatStats {
+      level: definition.level,
+      min_damage: u32::from(definition.min_damage),
+      max_damage: u32::from(definition.max_damage.max(definition.min_damage)),
+      min_magic_damage: 0,
+      max_magic_damage: 0,
+      defense: u32::from(definition.defense),
+      attack_rate: u32::from(definition.attack_rate),
+      defense_rate: u32::from(definition.defense_rate),
+      pvp_attack_rate: u32::from(definition.attack_rate),
+      pvp_defense_rate: u32::from(definition.defense_rate),
+      critical_rate: 0,
+      excellent_rate: 0,
+      attack_speed: 0,
+    }
+  }
+}
+
+/// Returns the unbroken items equipped in any of the slots.
+fn equipped<'a>(character: &'a Character, slots: &'a [ItemSlot]) -> impl Iterator<Item = &'a Item> {
+  slots
+    .iter()
+    .filter_map(move |&slot| character.equipment[slot].as_ref())
+    .filter(|item| !item.is_broken())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use murust_data_model::entities::{Equipment, Inventory, ItemDefinition};
+  use murust_data_model::types::{Class, Position, SkillArea};
+  use std::time::Duration;
+
+  fn character(
+    class: Class,
+    level: u16,
+    [strength, agility, vitality, energy]: [u16; 4],
+  ) -> Character {
+    Character {
+      id: 1,
+      slot: 0,
+      name: "Tester".into(),
+      level,
+      class,
+      experience: 0,
+      points: 0,
+      strength,
+      agility,
+      vitality,
+      energy,
+      command: 0,
+      map: 0,
+      position: Position::new(130, 130),
+      player_kills: 0,
+      equipment: Equipment::default(),
+      inventory: Inventory::new(8, 8),
+      skills: Vec::new(),
+    }
+  }
+
+  fn item(code: u16, slot: ItemSlot, level: u8, boosts: &[(ItemAttribute, u16)]) -> Item {
+    let mut definition = ItemDefinition::new(code, "Item");
+    definition.equippable_slot = Some(slot);
+    definition.attribute_boosts = boosts.iter().cloned().collect();
+
+    let mut item = Item::with_definition(definition);
+    item.level = level;
+    item
+  }
+
+  #[test]
+  fn dark_knight_base_stats() {
+    let stats = CombatStats::from_character(&character(Class::DarkKnight, 1, [28, 20, 25, 10]));
+    assert_eq!(
+      stats,
+      CombatStats {
+        level: 1,
+        min_damage: 4,
+        max_damage: 7,
+        min_magic_damage: 0,
+        max_magic_damage: 0,
+        defense: 6,
+        attack_rate: 42,
+        defense_rate: 6,
+        pvp_attack_rate: 93,
+        pvp_defense_rate: 12,
+        critical_rate: 0,
+        excellent_rate: 0,
+        attack_speed: 1,
+      }
+    );
+  }
+
+  #[test]
+  fn dark_wizard_base_stats() {
+    let stats = CombatStats::from_character(&character(Class::DarkWizard, 1, [18, 18, 15, 30]));
+    assert_eq!((stats.min_damage, stats.max_damage), (2, 4));
+    assert_eq!((stats.min_magic_damage, stats.max_magic_damage), (3, 7));
+    assert_eq!((stats.defense, stats.defense_rate), (4, 6));
+    assert_eq!((stats.attack_rate, stats.pvp_attack_rate, stats.pvp_defense_rate), (36, 75, 6));
+  }
+
+  #[test]
+  fn evolved_classes_share_formulas() {
+    let stats = [28, 20, 25, 10];
+    assert_eq!(
+      CombatStats::from_character(&character(Class::BladeKnight, 150, stats)),
+      CombatStats::from_character(&character(Class::DarkKnight, 150, stats))
+    );
+  }
+
+  #[test]
+  fn equipment_adds_damage_and_defense() {
+    let mut knight = character(Class::DarkKnight, 1, [28, 20, 25, 10]);
+    knight.equipment[ItemSlot::WeaponRight] = Some(item(
+      0,
+      ItemSlot::WeaponRight,
+      2,
+      &[(ItemAttribute::MinDamage, 6), (ItemAttribute::MaxDamage, 11)],
+    ));
+    knight.equipment[ItemSlot::Helm] =
+      Some(item(3585, ItemSlot::Helm, 3, &[(ItemAttribute::Defense, 24)]));
+    knight.equipment[ItemSlot::WeaponLeft] = Some(item(
+      3072,
+      ItemSlot::WeaponLeft,
+      0,
+      &[(ItemAttribute::Defense, 3), (ItemAttribute::DefenseRate, 4)],
+    ));
+
+    let stats = CombatStats::from_character(&knight);
+    assert_eq!((stats.min_damage, stats.max_damage), (16, 24));
+    assert_eq!((stats.defense, stats.defense_rate), (42, 10));
+  }
+
+  #[test]
+  fn item_options_add_damage_and_rates() {
+    let mut knight = character(Class::DarkKnight, 40, [28, 20, 25, 10]);
+    let mut sword = item(
+      0,
+      ItemSlot::WeaponRight,
+      0,
+      &[(ItemAttribute::MinDamage, 6), (ItemAttribute::MaxDamage, 11)],
+    );
+    sword.luck = true;
+    sword.option = 1;
+    sword.excellent = ExcellentOptions::ExcellentDamage | ExcellentOptions::DamageLevel;
+    knight.equipment[ItemSlot::WeaponRight] = Some(sword);
+
+    let mut helm = item(3585, ItemSlot::Helm, 0, &[(ItemAttribute::Defense, 24)]);
+    helm.excellent = ExcellentOptions::DefenseRate;
+    knight.equipment[ItemSlot::Helm] = Some(helm);
+
+    let base = CombatStats::from_character(&character(Class::DarkKnight, 40, [28, 20, 25, 10]));
+    let stats = CombatStats::from_character(&knight);
+    assert_eq!(stats.min_damage, base.min_damage + 6 + 4 + 2);
+    assert_eq!(stats.max_damage, base.max_damage + 11 + 4 + 2);
+    assert_eq!(stats.defense_rate, base.defense_rate * 110 / 100);
+    assert_eq!((stats.critical_rate, stats.excellent_rate), (5, 10));
+  }
+
+  #[test]
+  fn attack_speed_shortens_attack_interval() {
+    let mut stats = CombatStats::from_character(&character(Class::DarkKnight, 1, [28, 20, 25, 10]));
+    assert_eq!(stats.attack_interval(), Duration::from_millis(594));
+
+    stats.attack_speed = 100;
+    assert_eq!(stats.attack_interval(), Duration::from_millis(300));
+  }
+
+  #[test]
+  fn skills_add_damage_to_magic_or_weapons() {
+    let stats = CombatStats::from_character(&character(Class::DarkWizard, 1, [18, 18, 15, 30]));
+    let mut skill = SkillDefinition {
+      id: 4,
+      name: "Fire Ball".into(),
+      damage: 8,
+      magic: true,
+      mana_cost: 3,
+      ability_cost: 0,
+      cooldown: Duration::from_secs(0),
+      range: 6,
+      area: SkillArea::Single,
+      min_level: 0,
+      min_energy: 40,
+      eligible_classes: vec![Class::DarkWizard],
+    };
+
+    let magic = stats.with_skill(&skill);
+    assert_eq!((magic.min_damage, magic.max_damage), (11, 19));
+
+    skill.magic = false;
+    let physical = stats.with_skill(&skill);
+    assert_eq!((physical.min_damage, physical.max_damage), (10, 16));
+  }
+
+  #[test]
+  fn monster_stats_from_definition() {
+    let spider = MonsterDefinition {
+      class: 3,
+      name: "Spider".into(),
+      level: 2,
+      max_health: 30,
+      min_damage: 4,
+      max_damage: 7,
+      defense: 1,
+      attack_rate: 8,
+      defense_rate: 1,
+      move_range: 2,
+      attack_range: 1,
+      view_range: 4,
+      move_delay: Duration::from_millis(600),
+      attack_delay: Duration::from_millis(1800),
+      respawn_time: Duration::from_secs(10),
+    };
+
+    let stats = CombatStats::from_monster(&spider);
+    assert_eq!((stats.level, stats.min_damage, stats.max_damage), (2, 4, 7));
+    assert_eq!((stats.defense, stats.attack_rate, stats.defense_rate), (1, 8, 1));
+  }
+}
@@ -0,0 +1,119 @@
+use self::Stat::*;
+use murust_data_model::entities::Character;
+use murust_data_model::types::Class;
+
+/// A character attribute used by the class formulas.
+#[derive(Debug, Copy, Clone)]
+pub enum Stat {
+  Level,
+  Strength,
+  Agility,
+  Energy,
+  Command,
+}
+
+impl Stat {
+  /// Returns a character's value of the attribute.
+  fn of(self, character: &Character) -> u32 {
+    u32::from(match self {
+      Stat::Level => character.level,
+      Stat::Strength => character.strength,
+      Stat::Agility => character.agility,
+      Stat::Energy => character.energy,
+      Stat::Command => character.command,
+    })
+  }
+}
+
+/// A sum of fractions of character attributes.
+///
+/// Each term is `stat * numerator / denominator`, rounded down on its own like
+/// the original server formulas.
+pub type Formula = &'static [(Stat, u32, u32)];
+
+/// Evaluates a formula for a character.
+pub fn evaluate(formula: Formula, character: &Character) -> u32 {
+  formula
+    .iter()
+    .map(|&(stat, numerator, denominator)| stat.of(character) * numerator / denominator)
+    .sum()
+}
+
+/// The combat formulas of a character class.
+#[derive(Debug)]
+pub struct ClassFormulas {
+  pub min_damage: Formula,
+  pub max_damage: Formula,
+  pub min_magic_damage: Formula,
+  pub max_magic_damage: Formula,
+  pub attack_rate: Formula,
+  pub defense_rate: Formula,
+  pub pvp_attack_rate: Formula,
+  pub pvp_defense_rate: Formula,
+}
+
+static WIZARD: ClassFormulas = ClassFormulas {
+  min_damage: &[(Strength, 1, 8)],
+  max_damage: &[(Strength, 1, 4)],
+  min_magic_damage: &[(Energy, 1, 9)],
+  max_magic_damage: &[(Energy, 1, 4)],
+  attack_rate: &[(Level, 5, 1), (Agility, 3, 2), (Strength, 1, 4)],
+  defense_rate: &[(Agility, 1, 3)],
+  pvp_attack_rate: &[(Level, 3, 1), (Agility, 4, 1)],
+  pvp_defense_rate: &[(Level, 2, 1), (Agility, 1, 4)],
+};
+
+static KNIGHT: ClassFormulas = ClassFormulas {
+  min_damage: &[(Strength, 1, 6)],
+  max_damage: &[(Strength, 1, 4)],
+  min_magic_damage: &[],
+  max_magic_damage: &[],
+  attack_rate: &[(Level, 5, 1), (Agility, 3, 2), (Strength, 1, 4)],
+  defense_rate: &[(Agility, 1, 3)],
+  pvp_attack_rate: &[(Level, 3, 1), (Agility, 9, 2)],
+  pvp_defense_rate: &[(Level, 2, 1), (Agility, 1, 2)],
+};
+
+static ELF: ClassFormulas = ClassFormulas {
+  min_damage: &[(Strength, 1, 7), (Agility, 1, 7)],
+  max_damage: &[(Strength, 1, 4), (Agility, 1, 4)],
+  min_magic_damage: &[(Energy, 1, 9)],
+  max_magic_damage: &[(Energy, 1, 4)],
+  attack_rate: &[(Level, 5, 1), (Agility, 3, 2), (Strength, 1, 4)],
+  defense_rate: &[(Agility, 1, 4)],
+  pvp_attack_rate: &[(Level, 3, 1), (Agility, 3, 5)],
+  pvp_defense_rate: &[(Level, 2, 1), (Agility, 1, 10)],
+};
+
+static GLADIATOR: ClassFormulas = ClassFormulas {
+  min_damage: &[(Strength, 1, 6), (Energy, 1, 12)],
+  max_damage: &[(Strength, 1, 4), (Energy, 1, 8)],
+  min_magic_damage: &[(Energy, 1, 9)],
+  max_magic_damage: &[(Energy, 1, 4)],
+  attack_rate: &[(Level, 5, 1), (Agility, 3, 2), (Strength, 1, 4)],
+  defense_rate: &[(Agility, 1, 3)],
+  pvp_attack_rate: &[(Level, 3, 1), (Agility, 7, 2)],
+  pvp_defense_rate: &[(Level, 2, 1), (Agility, 1, 4)],
+};
+
+static LORD: ClassFormulas = ClassFormulas {
+  min_damage: &[(Strength, 1, 7), (Energy, 1, 14)],
+  max_damage: &[(Strength, 1, 5), (Energy, 1, 10)],
+  min_magic_damage: &[],
+  max_magic_damage: &[],
+  attack_rate: &[(Level, 5, 1), (Agility, 5, 2), (Strength, 1, 6), (Command, 1, 10)],
+  defense_rate: &[(Agility, 1, 7)],
+  pvp_attack_rate: &[(Level, 3, 1), (Agility, 4, 1)],
+  pvp_defense_rate: &[(Level, 2, 1), (Agility, 1, 2)],
+};
+
+/// Returns the combat formulas of a class, shared with its evolutions.
+pub fn formulas(class: Class) -> &'static ClassFormulas {
+  match class {
+    Class::DarkWizard | Class::SoulMaster => &WIZARD,
+    Class::DarkKnight | Class::BladeKnight => &KNIGHT,
+    Class::FairyElf | Class::MuseElf => &ELF,
+    Class::MagicGladiator => &GLADIATOR,
+    Class::DarkLord => &LORD,
+  }
+}
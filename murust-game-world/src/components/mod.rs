@@ -1,6 +1,285 @@
+use combat::CombatStats;
+use murust_data_model::entities::{Loot, MonsterDefinition, NpcDefinition};
+use murust_data_model::types::{Direction, ObjectId, Position, SkillId};
+use specs::{Entity, HashMapStorage, NullStorage, VecStorage};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// An object that is visible to players.
+#[derive(Component, Debug, Copy, Clone)]
+#[component(VecStorage)]
+pub struct Object {
+  pub id: ObjectId,
+}
+
+/// The location of an object within a map.
+#[derive(Component, Debug, Copy, Clone)]
+#[component(VecStorage)]
+pub struct Location {
+  pub position: Position,
+  pub direction: Direction,
+}
+
+impl Location {
+  /// Constructs a new location, facing the default direction.
+  pub fn new(position: Position) -> Self {
+    Location {
+      position,
+      direction: Direction::default(),
+    }
+  }
+}
+
+/// The default delay between each step, in milliseconds.
+const STEP_DELAY_MS: u64 = 400;
+
+/// A queued path an object is walking along.
 #[derive(Component, Debug)]
+#[component(HashMapStorage)]
+pub struct Movement {
+  /// The remaining steps, in the order they are taken.
+  pub path: VecDeque<Position>,
+  /// The delay between each step.
+  pub step_delay: Duration,
+  /// The instant the previous step was taken.
+  pub last_step: Instant,
+  /// The instant the path was started.
+  pub started: Instant,
+}
+
+impl Movement {
+  /// Constructs a new movement, starting at an instant.
+  pub fn new<I: IntoIterator<Item = Position>>(path: I, start: Instant) -> Self {
+    Self::with_delay(path, start, Duration::from_millis(STEP_DELAY_MS))
+  }
+
+  /// Constructs a new movement with a specific delay between each step.
+  pub fn with_delay<I>(path: I, start: Instant, step_delay: Duration) -> Self
+  where
+    I: IntoIterator<Item = Position>,
+  {
+    Movement {
+      path: path.into_iter().collect(),
+      step_delay,
+      last_step: start,
+      started: start,
+    }
+  }
+
+  /// Returns the final position of the path, if any.
+  pub fn destination(&self) -> Option<Position> { self.path.back().cloned() }
+}
+
+/// A player controlled object.
+#[derive(Component, Debug, Default, Copy, Clone)]
+#[component(NullStorage)]
+pub struct Player;
+
+/// The objects and ground items currently visible to an observer.
+#[derive(Component, Debug, Default)]
+#[component(HashMapStorage)]
+pub struct Viewport {
+  pub visible: HashMap<Entity, ObjectId>,
+  pub items: HashMap<Entity, ObjectId>,
+}
+
+/// A monster controlled by the world.
+#[derive(Component, Debug, Clone)]
+#[component(HashMapStorage)]
+pub struct Monster {
+  pub definition: Arc<MonsterDefinition>,
+}
+
+/// A non-player character players may talk to.
+#[derive(Component, Debug, Clone)]
+#[component(HashMapStorage)]
+pub struct Npc {
+  pub definition: Arc<NpcDefinition>,
+}
+
+/// The health of a living object.
+#[derive(Component, Debug, Copy, Clone)]
+#[component(VecStorage)]
+pub struct Health {
+  pub current: u32,
+  pub maximum: u32,
+}
+
+impl Health {
+  /// Constructs a new health, starting at its maximum.
+  pub fn new(maximum: u32) -> Self {
+    Health {
+      current: maximum,
+      maximum,
+    }
+  }
+}
+
+/// The mana of an object, spent on skills.
+#[derive(Component, Debug, Copy, Clone)]
+#[component(HashMapStorage)]
+pub struct Mana {
+  pub current: u32,
+  pub maximum: u32,
+}
+
+impl Mana {
+  /// Constructs a new mana, starting at its maximum.
+  pub fn new(maximum: u32) -> Self {
+    Mana {
+      current: maximum,
+      maximum,
+    }
+  }
+}
+
+/// The ability gauge (AG) of an object, spent on skills.
+#[derive(Component, Debug, Copy, Clone)]
+#[component(HashMapStorage)]
+pub struct Ability {
+  pub current: u32,
+  pub maximum: u32,
+}
+
+impl Ability {
+  /// Constructs a new ability gauge, starting at its maximum.
+  pub fn new(maximum: u32) -> Self {
+    Ability {
+      current: maximum,
+      maximum,
+    }
+  }
+}
+
+/// The gradual recovery of a player's health, mana and AG.
+///
+/// Besides regenerating naturally, any health and mana restored by potions is
+/// spread over several steps.
+#[derive(Component, Debug, Copy, Clone)]
+#[component(HashMapStorage)]
+pub struct Regeneration {
+  /// The instant health, mana and AG are next regenerated naturally.
+  pub next_regeneration: Instant,
+  /// The health yet to be restored.
+  pub health: u32,
+  /// The mana yet to be restored.
+  pub mana: u32,
+  /// The number of steps the remaining health and mana are restored over.
+  pub steps: u32,
+  /// The instant the next step is taken.
+  pub next_step: Instant,
+}
+
+impl Regeneration {
+  /// Constructs a new regeneration, first taking effect at an instant.
+  pub fn new(start: Instant) -> Self {
+    Regeneration {
+      next_regeneration: start,
+      health: 0,
+      mana: 0,
+      steps: 0,
+      next_step: start,
+    }
+  }
+}
+
+/// The instants at which an object's attacks and skills are ready to be used
+/// again.
+#[derive(Component, Debug, Default)]
+#[component(HashMapStorage)]
+pub struct Cooldowns {
+  pub attack: Option<Instant>,
+  pub ready: HashMap<SkillId, Instant>,
+}
+
+/// An object that can attack and be attacked.
+#[derive(Component, Debug, Default, Copy, Clone)]
 #[component(VecStorage)]
-struct Movement {
-  path: Vec<Position>,
-  last_movement: Instant,
+pub struct Combatant {
+  pub stats: CombatStats,
+}
+
+/// The area an object is (re)spawned within.
+#[derive(Component, Debug, Copy, Clone)]
+#[component(HashMapStorage)]
+pub struct Spawn {
+  pub area_start: Position,
+  pub area_end: Position,
+  /// The direction faced when spawned, random if unspecified.
+  pub direction: Option<Direction>,
+  /// The time until the object is respawned after death.
+  pub respawn_delay: Duration,
+}
+
+impl Spawn {
+  /// Returns the number of steps from a position to the spawn area.
+  pub fn distance_to(&self, position: Position) -> u8 {
+    let distance = |value: u8, start: u8, end: u8| {
+      start.saturating_sub(value).max(value.saturating_sub(end))
+    };
+
+    distance(position.x, self.area_start.x, self.area_end.x).max(distance(
+      position.y,
+      self.area_start.y,
+      self.area_end.y,
+    ))
+  }
+}
+
+/// The state of a monster's behaviour.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AiState {
+  /// Standing still, looking for targets.
+  Idle,
+  /// Walking randomly around its spawn.
+  Wander,
+  /// Pursuing a target until within attack range.
+  Chase(Entity),
+  /// Attacking a target within range.
+  Attack(Entity),
+  /// Returning to its spawn after losing its target.
+  Return,
+}
+
+/// The behaviour of a world controlled monster.
+#[derive(Component, Debug, Copy, Clone)]
+#[component(HashMapStorage)]
+pub struct MonsterAi {
+  pub state: AiState,
+  /// The instant the monster next acts.
+  pub next_action: Instant,
+}
+
+impl MonsterAi {
+  /// Constructs a new idle behaviour, acting at an instant.
+  pub fn new(start: Instant) -> Self {
+    MonsterAi {
+      state: AiState::Idle,
+      next_action: start,
+    }
+  }
+}
+
+/// A dead object, awaiting a respawn.
+#[derive(Component, Debug, Copy, Clone)]
+#[component(HashMapStorage)]
+pub struct Dead {
+  /// The instant the object died.
+  pub since: Instant,
+}
+
+/// Loot lying on the ground, waiting to be picked up.
+///
+/// Ground items use their own ID space, separate from objects.
+#[derive(Component, Debug)]
+#[component(HashMapStorage)]
+pub struct GroundItem {
+  pub id: ObjectId,
+  pub loot: Loot,
+  /// The entity allowed to pick up the loot until its ownership expires.
+  pub owner: Option<Entity>,
+  pub owned_until: Instant,
+  /// The instant the loot disappears from the ground.
+  pub expires: Instant,
 }
@@ -1,9 +1,25 @@
 #[macro_use]
-extern crate specs;
+extern crate bitflags;
 extern crate murust_data_model;
+extern crate rand;
+extern crate specs;
+#[macro_use]
+extern crate specs_derive;
 
-use self::components::Movement;
-use self::systems::{MovementSystem, MovementPostSystem};
+pub use self::clock::{Clock, ManualClock, SystemClock};
+pub use self::pathfinding::find_path;
+pub use self::systems::VIEW_RANGE;
+pub use self::timestep::FixedTimestep;
+pub use self::world::{GameWorld, PickupError, SkillError, SkillTarget, TalkError,
+                      LOOT_LIFETIME_SECS, LOOT_OWNERSHIP_SECS, MONSTER_ID_START, PICKUP_RANGE,
+                      TALK_RANGE};
+pub use specs::Entity;
 
-mod components;
+mod clock;
+pub mod combat;
+pub mod components;
+mod pathfinding;
+pub mod resources;
 mod systems;
+mod timestep;
+mod world;
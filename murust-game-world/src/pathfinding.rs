@@ -0,0 +1,189 @@
+use murust_data_model::types::{Direction, Position};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// A position awaiting expansion, ordered by its estimated total cost.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Node {
+  position: Position,
+  cost: usize,
+  estimate: usize,
+}
+
+impl Ord for Node {
+  fn cmp(&self, other: &Self) -> Ordering {
+    // The heap is a max-heap, so the lowest estimate must compare greatest.
+    // Ties prefer the node furthest along its path.
+    other
+      .estimate
+      .cmp(&self.estimate)
+      .then_with(|| self.cost.cmp(&other.cost))
+  }
+}
+
+impl PartialOrd for Node {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+/// Returns the number of steps between two positions, ignoring obstacles.
+pub(crate) fn steps_between(from: Position, to: Position) -> usize {
+  let dx = (from.x as isize - to.x as isize).abs();
+  let dy = (from.y as isize - to.y as isize).abs();
+  dx.max(dy) as usize
+}
+
+/// Finds the shortest walkable path between two positions using A*.
+///
+/// Only positions accepted by `is_walkable` are entered. The path excludes the
+/// start and ends at the goal. Paths longer than `max_steps` are never
+/// returned, which also bounds the area searched.
+pub fn find_path<F>(
+  is_walkable: F,
+  start: Position,
+  goal: Position,
+  max_steps: usize,
+) -> Option<Vec<Position>>
+where
+  F: Fn(Position) -> bool,
+{
+  if start == goal {
+    return Some(Vec::new());
+  }
+
+  if !is_walkable(goal) || steps_between(start, goal) > max_steps {
+    return None;
+  }
+
+  let mut open = BinaryHeap::new();
+  let mut costs = HashMap::new();
+  let mut parents = HashMap::new();
+
+  costs.insert(start, 0);
+  open.push(Node {
+    position: start,
+    cost: 0,
+    estimate: steps_between(start, goal),
+  });
+
+  while let Some(node) = open.pop() {
+    if node.position == goal {
+      let mut path = vec![goal];
+      while let Some(&parent) = parents.get(path.last().unwrap()) {
+        if parent == start {
+          break;
+        }
+        path.push(parent);
+      }
+      path.reverse();
+      return Some(path);
+    }
+
+    // Skip any stale entries and nodes that cannot reach the goal in time
+    if node.cost > costs[&node.position] || node.cost >= max_steps {
+      continue;
+    }
+
+    for &direction in Direction::values() {
+      let next = node.position.advance(direction);
+      if next == node.position || !is_walkable(next) {
+        continue;
+      }
+
+      let cost = node.cost + 1;
+      if costs.get(&next).map_or(true, |&previous| cost < previous) {
+        costs.insert(next, cost);
+        parents.insert(next, node.position);
+        open.push(Node {
+          position: next,
+          cost,
+          estimate: cost + steps_between(next, goal),
+        });
+      }
+    }
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use resources::{Terrain, TerrainAttribute};
+
+  /// Returns a terrain with a vertical wall at `x`, spanning `y` to `y_end`.
+  fn terrain_with_wall(x: u8, y: u8, y_end: u8) -> Terrain {
+    let mut terrain = Terrain::new(0);
+    for y in y..(y_end + 1) {
+      terrain.set_attributes(Position::new(x, y), TerrainAttribute::Blocked);
+    }
+    terrain
+  }
+
+  fn assert_continuous(start: Position, path: &[Position]) {
+    let mut previous = start;
+    for position in path {
+      assert!(previous.direction_to(position).is_some());
+      previous = *position;
+    }
+  }
+
+  #[test]
+  fn find_straight_path() {
+    let terrain = Terrain::new(0);
+    let (start, goal) = (Position::new(10, 10), Position::new(15, 10));
+
+    let path = find_path(|position| terrain.is_walkable(position), start, goal, 10).unwrap();
+    assert_eq!(path.len(), 5);
+    assert_eq!(path.last(), Some(&goal));
+    assert_continuous(start, &path);
+  }
+
+  #[test]
+  fn find_path_around_wall() {
+    let terrain = terrain_with_wall(12, 5, 15);
+    let (start, goal) = (Position::new(10, 10), Position::new(14, 10));
+
+    let path = find_path(|position| terrain.is_walkable(position), start, goal, 20).unwrap();
+    assert_eq!(path.last(), Some(&goal));
+    assert!(path.len() > 4);
+    assert!(path.iter().all(|&position| terrain.is_walkable(position)));
+    assert_continuous(start, &path);
+  }
+
+  #[test]
+  fn reject_unreachable_goals() {
+    let mut terrain = Terrain::new(0);
+    let goal = Position::new(20, 20);
+    for &direction in Direction::values() {
+      terrain.set_attributes(goal.advance(direction), TerrainAttribute::Blocked);
+    }
+
+    let is_walkable = |position| terrain.is_walkable(position);
+    assert!(find_path(&is_walkable, Position::new(10, 10), goal, 30).is_none());
+    let beside = goal.advance(Direction::North);
+    assert!(find_path(&is_walkable, Position::new(10, 10), beside, 30).is_none());
+  }
+
+  #[test]
+  fn reject_paths_exceeding_limit() {
+    let terrain = terrain_with_wall(12, 0, 30);
+    let (start, goal) = (Position::new(10, 10), Position::new(14, 10));
+
+    let is_walkable = |position| terrain.is_walkable(position);
+    assert!(find_path(&is_walkable, start, Position::new(30, 10), 10).is_none());
+    assert!(find_path(&is_walkable, start, goal, 10).is_none());
+    assert!(find_path(&is_walkable, start, goal, 50).is_some());
+  }
+
+  #[test]
+  fn avoid_rejected_positions() {
+    let (start, goal) = (Position::new(10, 10), Position::new(14, 10));
+    let is_walkable = |position: Position| position.x != 12 || position.y > 15;
+
+    let path = find_path(&is_walkable, start, goal, 20).unwrap();
+    assert_eq!(path.last(), Some(&goal));
+    assert!(path.iter().all(|&position| is_walkable(position)));
+    assert_continuous(start, &path);
+    assert!(find_path(&is_walkable, start, Position::new(12, 10), 20).is_none());
+  }
+}
@@ -0,0 +1,72 @@
+use combat::AttackOutcome;
+use murust_data_model::types::{Direction, ObjectId, Position};
+use specs::Entity;
+use std::mem;
+
+/// An event produced by a world, for the server to act upon.
+#[derive(Debug, Clone)]
+pub enum WorldEvent {
+  /// Objects have come into view of a player.
+  ObjectsEntered {
+    observer: ObjectId,
+    objects: Vec<Entity>,
+  },
+  /// Objects have left the view of a player.
+  ObjectsLeft {
+    observer: ObjectId,
+    objects: Vec<ObjectId>,
+  },
+  /// Ground items have come into view of a player.
+  ItemsEntered {
+    observer: ObjectId,
+    items: Vec<Entity>,
+  },
+  /// Ground items have left the view of a player.
+  ItemsLeft {
+    observer: ObjectId,
+    items: Vec<ObjectId>,
+  },
+  /// A world controlled object has started walking.
+  ObjectMoved {
+    object: Entity,
+    destination: Position,
+    direction: Direction,
+  },
+  /// A monster has attacked a target.
+  MonsterAttack {
+    monster: Entity,
+    target: Entity,
+    outcome: AttackOutcome,
+  },
+  /// A player has been killed.
+  PlayerDied { player: Entity, killer: Entity },
+  /// A dead player has been revived at a position.
+  PlayerRespawned { player: Entity, position: Position },
+  /// A player's health, mana or AG has been restored over time.
+  PlayerRecovered { player: Entity },
+}
+
+/// A queue of events produced during a world's ticks.
+#[derive(Debug, Default)]
+pub struct WorldEvents(Vec<WorldEvent>);
+
+impl WorldEvents {
+  /// Queues an event.
+  pub fn push(&mut self, event: WorldEvent) { self.0.push(event); }
+
+  /// Removes and returns all queued events.
+  pub fn drain(&mut self) -> Vec<WorldEvent> { mem::replace(&mut self.0, Vec::new()) }
+}
+
+/// A queue of attacks declared by monsters during a tick, resolved by the
+/// world once all systems have run.
+#[derive(Debug, Default)]
+pub struct MonsterAttacks(Vec<(Entity, Entity)>);
+
+impl MonsterAttacks {
+  /// Queues an attack by a monster on a target.
+  pub fn push(&mut self, monster: Entity, target: Entity) { self.0.push((monster, target)); }
+
+  /// Removes and returns all queued attacks.
+  pub fn drain(&mut self) -> Vec<(Entity, Entity)> { mem::replace(&mut self.0, Vec::new()) }
+}
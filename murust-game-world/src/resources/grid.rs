@@ -0,0 +1,153 @@
+use murust_data_model::types::Position;
+use specs::Entity;
+use std::collections::HashMap;
+
+/// The width and height of each cell.
+const CELL_SIZE: usize = 16;
+
+/// The number of cells along each axis.
+const CELLS_PER_AXIS: usize = 256 / CELL_SIZE;
+
+/// A spatial index of objects, partitioned into cells of bounded capacity.
+pub struct SpatialGrid {
+  capacity: usize,
+  cells: Vec<Vec<(Entity, Position)>>,
+  entities: HashMap<Entity, usize>,
+}
+
+impl SpatialGrid {
+  /// The default maximum number of entities within a single cell.
+  pub const CELL_CAPACITY: usize = 64;
+
+  /// Constructs a new grid, using the default cell capacity.
+  pub fn new() -> Self { Self::with_capacity(Self::CELL_CAPACITY) }
+
+  /// Constructs a new grid, allowing a specific number of entities per cell.
+  pub fn with_capacity(capacity: usize) -> Self {
+    SpatialGrid {
+      capacity,
+      cells: vec![Vec::new(); CELLS_PER_AXIS * CELLS_PER_AXIS],
+      entities: HashMap::new(),
+    }
+  }
+
+  /// Inserts or moves an entity, returning whether it is tracked or not.
+  ///
+  /// An entity that does not fit within its cell is left untracked until its
+  /// next update.
+  pub fn update(&mut self, entity: Entity, position: Position) -> bool {
+    let index = Self::cell_index(position);
+
+    if self.entities.get(&entity) == Some(&index) {
+      let entry = self.cells[index]
+        .iter_mut()
+        .find(|&&mut (other, _)| other == entity)
+        .expect("retrieving tracked grid entity");
+      entry.1 = position;
+      return true;
+    }
+
+    self.remove(entity);
+    if self.cells[index].len() >= self.capacity {
+      return false;
+    }
+
+    self.cells[index].push((entity, position));
+    self.entities.insert(entity, index);
+    true
+  }
+
+  /// Removes an entity, returning whether it was tracked or not.
+  pub fn remove(&mut self, entity: Entity) -> bool {
+    match self.entities.remove(&entity) {
+      Some(index) => {
+        self.cells[index].retain(|&(other, _)| other != entity);
+        true
+      },
+      None => false,
+    }
+  }
+
+  /// Returns whether an entity is tracked or not.
+  pub fn contains(&self, entity: Entity) -> bool { self.entities.contains_key(&entity) }
+
+  /// Returns all entities within range of a position.
+  pub fn query(&self, position: Position, range: u8) -> Vec<Entity> {
+    let (min, max) = (
+      Position::new(
+        position.x.saturating_sub(range),
+        position.y.saturating_sub(range),
+      ),
+      Position::new(
+        position.x.saturating_add(range),
+        position.y.saturating_add(range),
+      ),
+    );
+
+    let (min_cell, max_cell) = (Self::cell_index(min), Self::cell_index(max));
+    let (min_x, min_y) = (min_cell % CELLS_PER_AXIS, min_cell / CELLS_PER_AXIS);
+    let (max_x, max_y) = (max_cell % CELLS_PER_AXIS, max_cell / CELLS_PER_AXIS);
+
+    let mut entities = Vec::new();
+    for y in min_y..(max_y + 1) {
+      for x in min_x..(max_x + 1) {
+        entities.extend(
+          self.cells[y * CELLS_PER_AXIS + x]
+            .iter()
+            .filter(|&&(_, other)| other.is_within(&position, range))
+            .map(|&(entity, _)| entity),
+        );
+      }
+    }
+    entities
+  }
+
+  /// Returns the index of the cell containing a position.
+  fn cell_index(position: Position) -> usize {
+    (position.y as usize / CELL_SIZE) * CELLS_PER_AXIS + position.x as usize / CELL_SIZE
+  }
+}
+
+impl Default for SpatialGrid {
+  fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use specs::World;
+
+  #[test]
+  fn query_entities_within_range() {
+    let world = World::new();
+    let (near, far) = (world.create_entity().build(), world.create_entity().build());
+
+    let mut grid = SpatialGrid::new();
+    assert!(grid.update(near, Position::new(20, 20)));
+    assert!(grid.update(far, Position::new(50, 20)));
+    assert_eq!(grid.query(Position::new(10, 14), 15), vec![near]);
+
+    assert!(grid.update(near, Position::new(40, 20)));
+    let mut entities = grid.query(Position::new(45, 20), 15);
+    entities.sort();
+    assert_eq!(entities, vec![near, far]);
+  }
+
+  #[test]
+  fn cells_are_bounded() {
+    let world = World::new();
+    let entities = (0..3)
+      .map(|_| world.create_entity().build())
+      .collect::<Vec<_>>();
+
+    let mut grid = SpatialGrid::with_capacity(2);
+    assert!(grid.update(entities[0], Position::new(1, 1)));
+    assert!(grid.update(entities[1], Position::new(2, 2)));
+    assert!(!grid.update(entities[2], Position::new(3, 3)));
+    assert!(!grid.contains(entities[2]));
+
+    assert!(grid.remove(entities[0]));
+    assert!(grid.update(entities[2], Position::new(3, 3)));
+    assert_eq!(grid.query(Position::new(0, 0), 5).len(), 2);
+  }
+}
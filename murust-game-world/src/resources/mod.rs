@@ -0,0 +1,11 @@
+pub use self::events::{MonsterAttacks, WorldEvent, WorldEvents};
+pub use self::grid::SpatialGrid;
+pub use self::random::Random;
+pub use self::terrain::{Terrain, TerrainAttribute};
+pub use self::time::Time;
+
+mod events;
+mod grid;
+mod random;
+mod terrain;
+mod time;
@@ -0,0 +1,23 @@
+use rand::{self, SeedableRng, XorShiftRng};
+use std::ops::{Deref, DerefMut};
+
+/// A world's source of randomness.
+pub struct Random(XorShiftRng);
+
+impl Random {
+  /// Constructs a new generator, seeded by the operating system.
+  pub fn new() -> Self { Random(rand::weak_rng()) }
+
+  /// Constructs a new generator from a seed, producing a reproducible sequence.
+  pub fn from_seed(seed: [u32; 4]) -> Self { Random(XorShiftRng::from_seed(seed)) }
+}
+
+impl Deref for Random {
+  type Target = XorShiftRng;
+
+  fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl DerefMut for Random {
+  fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
@@ -0,0 +1,206 @@
+#![allow(non_upper_case_globals)]
+
+use murust_data_model::types::Position;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+bitflags! {
+  /// A collection of attributes for a terrain cell.
+  pub struct TerrainAttribute: u8 {
+    const None      = 0;
+    const SafeZone  = (1 << 0);
+    const Character = (1 << 1);
+    const Blocked   = (1 << 2);
+    const NoGround  = (1 << 3);
+    const Water     = (1 << 4);
+  }
+}
+
+impl Default for TerrainAttribute {
+  fn default() -> Self { TerrainAttribute::None }
+}
+
+/// The width and height of a terrain.
+const TERRAIN_SIZE: usize = 256;
+
+/// The number of cells within a terrain.
+const TERRAIN_CELLS: usize = TERRAIN_SIZE * TERRAIN_SIZE;
+
+/// The key used for the first pass of client attribute file decryption.
+const MAP_XOR_KEY: [u8; 16] = [
+  0xD1, 0x73, 0x52, 0xF6, 0xD2, 0x9A, 0xCB, 0x27, 0x3E, 0xAF, 0x59, 0x31, 0x37, 0xB3, 0xE7, 0xA2,
+];
+
+/// The key used for the second pass of client attribute file decryption.
+const BUX_XOR_KEY: [u8; 3] = [0xFC, 0xCF, 0xAB];
+
+/// The attribute map of a world's terrain.
+#[derive(Debug, Clone)]
+pub struct Terrain {
+  map: u8,
+  cells: Vec<TerrainAttribute>,
+}
+
+impl Terrain {
+  /// Constructs a new terrain without any attributes.
+  pub fn new(map: u8) -> Self {
+    Terrain {
+      map,
+      cells: vec![TerrainAttribute::None; TERRAIN_CELLS],
+    }
+  }
+
+  /// Loads a map's terrain from a directory.
+  ///
+  /// Server side attribute files (`TerrainN.att`) are preferred, with the
+  /// client's encrypted files (`EncTerrainN.att`) used as a fallback.
+  pub fn load<P: AsRef<Path>>(directory: P, map: u8) -> io::Result<Self> {
+    let directory = directory.as_ref();
+    let index = map as u16 + 1;
+
+    let plain = directory.join(format!("Terrain{}.att", index));
+    if plain.exists() {
+      return Self::from_bytes(map, &read_file(plain)?);
+    }
+
+    let encrypted = directory.join(format!("EncTerrain{}.att", index));
+    Self::from_client_bytes(map, read_file(encrypted)?)
+  }
+
+  /// Parses a terrain from a decrypted attribute file.
+  pub fn from_bytes(map: u8, bytes: &[u8]) -> io::Result<Self> {
+    // The attributes are preceded by a short header (version & dimensions)
+    let header = bytes.len().checked_sub(TERRAIN_CELLS);
+    if header.map_or(true, |size| size > 4) {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid terrain attribute file size ({})", bytes.len()),
+      ));
+    }
+
+    let cells = bytes[header.unwrap()..]
+      .iter()
+      .map(|&byte| TerrainAttribute::from_bits_truncate(byte))
+      .collect();
+    Ok(Terrain { map, cells })
+  }
+
+  /// Parses a terrain from an encrypted client attribute file.
+  pub fn from_client_bytes(map: u8, mut bytes: Vec<u8>) -> io::Result<Self> {
+    decrypt(&mut bytes);
+    Self::from_bytes(map, &bytes)
+  }
+
+  /// Returns the map the terrain belongs to.
+  pub fn map(&self) -> u8 { self.map }
+
+  /// Returns the attributes of a position.
+  pub fn attributes(&self, position: Position) -> TerrainAttribute {
+    self.cells[Self::index(position)]
+  }
+
+  /// Sets the attributes of a position.
+  pub fn set_attributes(&mut self, position: Position, attributes: TerrainAttribute) {
+    self.cells[Self::index(position)] = attributes;
+  }
+
+  /// Returns whether a position can be walked upon or not.
+  pub fn is_walkable(&self, position: Position) -> bool {
+    !self
+      .attributes(position)
+      .intersects(TerrainAttribute::Blocked | TerrainAttribute::NoGround)
+  }
+
+  /// Returns whether a position is within a safe zone or not.
+  pub fn is_safe_zone(&self, position: Position) -> bool {
+    self.attributes(position).contains(TerrainAttribute::SafeZone)
+  }
+
+  /// Returns the cell index of a position.
+  fn index(position: Position) -> usize {
+    position.y as usize * TERRAIN_SIZE + position.x as usize
+  }
+}
+
+/// Reads the entire contents of a file.
+fn read_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+  let mut bytes = Vec::new();
+  File::open(path)?.read_to_end(&mut bytes)?;
+  Ok(bytes)
+}
+
+/// Decrypts a client attribute file in place.
+fn decrypt(bytes: &mut [u8]) {
+  let mut key = 0x5Eu8;
+  for (index, byte) in bytes.iter_mut().enumerate() {
+    let encrypted = *byte;
+    *byte = (encrypted ^ MAP_XOR_KEY[index % MAP_XOR_KEY.len()]).wrapping_sub(key);
+    key = encrypted.wrapping_add(0x3D);
+  }
+
+  for (byte, xor) in bytes.iter_mut().zip(BUX_XOR_KEY.iter().cycle()) {
+    *byte ^= *xor;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn attribute_file(cells: &[(Position, TerrainAttribute)]) -> Vec<u8> {
+    let mut bytes = vec![0x00, 0xFF, 0xFF];
+    bytes.extend(vec![0u8; TERRAIN_CELLS]);
+    for &(position, attributes) in cells {
+      bytes[3 + Terrain::index(position)] = attributes.bits();
+    }
+    bytes
+  }
+
+  fn encrypt(bytes: &mut [u8]) {
+    for (byte, xor) in bytes.iter_mut().zip(BUX_XOR_KEY.iter().cycle()) {
+      *byte ^= *xor;
+    }
+
+    let mut key = 0x5Eu8;
+    for (index, byte) in bytes.iter_mut().enumerate() {
+      *byte = (byte.wrapping_add(key)) ^ MAP_XOR_KEY[index % MAP_XOR_KEY.len()];
+      key = byte.wrapping_add(0x3D);
+    }
+  }
+
+  #[test]
+  fn parse_attribute_file() {
+    let bytes = attribute_file(&[
+      (Position::new(10, 20), TerrainAttribute::SafeZone),
+      (Position::new(11, 20), TerrainAttribute::Blocked),
+      (Position::new(12, 20), TerrainAttribute::NoGround),
+      (Position::new(13, 20), TerrainAttribute::Water),
+    ]);
+
+    let terrain = Terrain::from_bytes(0, &bytes).unwrap();
+    assert!(terrain.is_safe_zone(Position::new(10, 20)));
+    assert!(terrain.is_walkable(Position::new(10, 20)));
+    assert!(!terrain.is_walkable(Position::new(11, 20)));
+    assert!(!terrain.is_walkable(Position::new(12, 20)));
+    assert!(terrain.is_walkable(Position::new(13, 20)));
+    assert!(!terrain.is_safe_zone(Position::new(255, 255)));
+  }
+
+  #[test]
+  fn parse_encrypted_attribute_file() {
+    let mut bytes = attribute_file(&[(Position::new(255, 0), TerrainAttribute::Blocked)]);
+    encrypt(&mut bytes);
+
+    let terrain = Terrain::from_client_bytes(2, bytes).unwrap();
+    assert_eq!(terrain.map(), 2);
+    assert!(!terrain.is_walkable(Position::new(255, 0)));
+    assert!(terrain.is_walkable(Position::new(0, 255)));
+  }
+
+  #[test]
+  fn parse_fails_with_invalid_size() {
+    assert!(Terrain::from_bytes(0, &[0u8; 1024]).is_err());
+    assert!(Terrain::from_bytes(0, &vec![0u8; TERRAIN_CELLS * 2]).is_err());
+  }
+}
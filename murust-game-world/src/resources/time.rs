@@ -0,0 +1,30 @@
+use std::time::{Duration, Instant};
+
+/// The timing of the current world tick.
+#[derive(Debug, Copy, Clone)]
+pub struct Time {
+  /// The instant the current tick started.
+  pub now: Instant,
+  /// The time elapsed since the previous tick.
+  pub delta: Duration,
+  /// The number of ticks processed.
+  pub tick: u64,
+}
+
+impl Time {
+  /// Constructs a new time resource, starting at an instant.
+  pub fn new(now: Instant) -> Self {
+    Time {
+      now,
+      delta: Duration::from_secs(0),
+      tick: 0,
+    }
+  }
+
+  /// Advances the time to a new tick.
+  pub fn advance(&mut self, now: Instant) {
+    self.delta = now.duration_since(self.now);
+    self.now = now;
+    self.tick += 1;
+  }
+}
@@ -0,0 +1,272 @@
+use components::{AiState, Dead, Health, Location, Monster, MonsterAi, Movement, Player, Spawn,
+                 Viewport};
+use murust_data_model::types::{Direction, Position};
+use pathfinding::{find_path, steps_between};
+use rand::Rng;
+use resources::{MonsterAttacks, Random, Terrain, Time, WorldEvent, WorldEvents};
+use specs::{Entities, Entity, Fetch, FetchMut, Join, ReadStorage, System, WriteStorage};
+use std::collections::HashMap;
+use std::time::Duration;
+use systems::spawn_location;
+
+/// The percentage chance of an idle monster starting to wander.
+const WANDER_CHANCE: u32 = 30;
+
+/// The range of time, in milliseconds, an idle monster waits before acting.
+const IDLE_DELAY_MS: (u64, u64) = (1000, 3000);
+
+/// The maximum distance a monster pursues a target from its spawn.
+const MAX_CHASE_DISTANCE: u8 = 15;
+
+/// The maximum length of a path towards a target.
+const MAX_CHASE_STEPS: usize = 15;
+
+/// The maximum length of a path back to a spawn.
+const MAX_RETURN_STEPS: usize = 40;
+
+/// Moves monsters through their idle, wander, chase, attack and return states.
+pub struct MonsterAiSystem;
+
+impl<'a> System<'a> for MonsterAiSystem {
+  type SystemData = (
+    Entities<'a>,
+    Fetch<'a, Time>,
+    Fetch<'a, Terrain>,
+    FetchMut<'a, Random>,
+    FetchMut<'a, WorldEvents>,
+    FetchMut<'a, MonsterAttacks>,
+    ReadStorage<'a, Monster>,
+    ReadStorage<'a, Spawn>,
+    ReadStorage<'a, Player>,
+    ReadStorage<'a, Viewport>,
+    ReadStorage<'a, Dead>,
+    WriteStorage<'a, MonsterAi>,
+    WriteStorage<'a, Health>,
+    WriteStorage<'a, Location>,
+    WriteStorage<'a, Movement>,
+  );
+
+  fn run(&mut self, data: Self::SystemData) {
+    let (
+      entities,
+      time,
+      terrain,
+      mut random,
+      mut events,
+      mut attacks,
+      monsters,
+      spawns,
+      players,
+      viewports,
+      deads,
+      mut ais,
+      mut healths,
+      mut locations,
+      mut movements,
+    ) = data;
+
+    // Targets are limited to living players outside of safe zones that can see
+    // the monster
+    let mut observers = HashMap::<Entity, Vec<Entity>>::new();
+    for (player, viewport, location, _, _) in
+      (&*entities, &viewports, &locations, &players, !&deads).join()
+    {
+      if terrain.is_safe_zone(location.position) {
+        continue;
+      }
+
+      for &monster in viewport.visible.keys() {
+        observers
+          .entry(monster)
+          .or_insert_with(Vec::new)
+          .push(player);
+      }
+    }
+
+    for (entity, monster, spawn, ai, _) in
+      (&*entities, &monsters, &spawns, &mut ais, !&deads).join()
+    {
+      if time.now < ai.next_action {
+        continue;
+      }
+
+      let location = match locations.get(entity) {
+        Some(location) => *location,
+        None => continue,
+      };
+
+      let definition = &monster.definition;
+      let is_walkable = |position| is_roamable(&terrain, position);
+      let candidates = observers
+        .get(&entity)
+        .map_or(&[][..], |players| players.as_slice());
+      let target_position = match ai.state {
+        AiState::Chase(target) | AiState::Attack(target) => candidates
+          .iter()
+          .find(|&&candidate| candidate == target)
+          .and_then(|&candidate| locations.get(candidate))
+          .map(|location| location.position),
+        _ => None,
+      };
+
+      let nearest_target = candidates
+        .iter()
+        .filter_map(|&candidate| locations.get(candidate).map(|l| (candidate, l.position)))
+        .filter(|(_, position)| position.is_within(&location.position, definition.view_range))
+        .min_by_key(|(_, position)| steps_between(location.position, *position))
+        .map(|(candidate, _)| candidate);
+
+      let mut walk: Option<Vec<Position>> = None;
+      let (state, delay) = match ai.state {
+        AiState::Idle | AiState::Wander => match nearest_target {
+          Some(target) => (AiState::Chase(target), Duration::from_millis(0)),
+          None if ai.state == AiState::Wander && movements.get(entity).is_some() => {
+            (AiState::Wander, definition.move_delay)
+          },
+          None if random.gen_range(0, 100) < WANDER_CHANCE => {
+            let destination =
+              wander_destination(spawn, location.position, definition.move_range, &mut random);
+            walk = find_path(
+              &is_walkable,
+              location.position,
+              destination,
+              definition.move_range as usize * 2,
+            );
+            (AiState::Wander, definition.move_delay)
+          },
+          None => (AiState::Idle, idle_delay(&mut random)),
+        },
+        AiState::Chase(target) | AiState::Attack(target) => {
+          match target_position {
+            Some(position) if spawn.distance_to(location.position) <= MAX_CHASE_DISTANCE => {
+              if location
+                .position
+                .is_within(&position, definition.attack_range)
+              {
+                movements.remove(entity);
+                if let Some(direction) = direction_towards(location.position, position) {
+                  locations.get_mut(entity).unwrap().direction = direction;
+                }
+                attacks.push(entity, target);
+                (AiState::Attack(target), definition.attack_delay)
+              } else {
+                let pursuing = movements
+                  .get(entity)
+                  .and_then(Movement::destination)
+                  .map_or(false, |end| {
+                    end.is_within(&position, definition.attack_range)
+                  });
+
+                if !pursuing {
+                  walk = find_path(&is_walkable, location.position, position, MAX_CHASE_STEPS)
+                    .map(|mut path| {
+                      // Stop as soon as the target is within attack range
+                      let end = path
+                        .iter()
+                        .position(|step| step.is_within(&position, definition.attack_range))
+                        .map_or(path.len(), |index| index + 1);
+                      path.truncate(end);
+                      path
+                    });
+                }
+
+                if pursuing || walk.is_some() {
+                  (AiState::Chase(target), definition.move_delay)
+                } else {
+                  (AiState::Return, Duration::from_millis(0))
+                }
+              }
+            },
+            _ => (AiState::Return, Duration::from_millis(0)),
+          }
+        },
+        AiState::Return => {
+          if movements.get(entity).is_some() {
+            (AiState::Return, definition.move_delay)
+          } else if spawn.distance_to(location.position) == 0 {
+            if let Some(health) = healths.get_mut(entity) {
+              health.current = health.maximum;
+            }
+            (AiState::Idle, idle_delay(&mut random))
+          } else {
+            let destination = spawn_location(spawn, &terrain, &mut random);
+            walk = find_path(
+              &is_walkable,
+              location.position,
+              destination.position,
+              MAX_RETURN_STEPS,
+            );
+            if walk.is_none() {
+              // Monsters unable to find their way back are placed at their spawn
+              locations.insert(entity, destination);
+            }
+            (AiState::Return, definition.move_delay)
+          }
+        },
+      };
+
+      if let Some(path) = walk {
+        let destination = path.last().cloned();
+        if let Some(destination) = destination {
+          let direction = location
+            .position
+            .direction_to(&path[0])
+            .unwrap_or(location.direction);
+
+          locations.get_mut(entity).unwrap().direction = direction;
+          movements.insert(
+            entity,
+            Movement::with_delay(path, time.now, definition.move_delay),
+          );
+          events.push(WorldEvent::ObjectMoved {
+            object: entity,
+            destination,
+            direction,
+          });
+        }
+      }
+
+      ai.state = state;
+      ai.next_action = time.now + delay;
+    }
+  }
+}
+
+/// Returns whether a monster may walk onto a position.
+fn is_roamable(terrain: &Terrain, position: Position) -> bool {
+  terrain.is_walkable(position) && !terrain.is_safe_zone(position)
+}
+
+/// Returns the direction most closely facing a position.
+fn direction_towards(from: Position, to: Position) -> Option<Direction> {
+  let step = Position::new(
+    (from.x as i16 + (to.x as i16 - from.x as i16).signum()) as u8,
+    (from.y as i16 + (to.y as i16 - from.y as i16).signum()) as u8,
+  );
+  from.direction_to(&step)
+}
+
+/// Returns a random position to wander towards, staying close to the spawn.
+fn wander_destination(
+  spawn: &Spawn,
+  position: Position,
+  range: u8,
+  random: &mut Random,
+) -> Position {
+  let mut shift = |value: u8| {
+    let offset = random.gen_range(-(range as i16), range as i16 + 1);
+    (value as i16 + offset).max(0).min(0xFF) as u8
+  };
+
+  let destination = Position::new(shift(position.x), shift(position.y));
+  if spawn.distance_to(destination) <= range {
+    destination
+  } else {
+    position
+  }
+}
+
+/// Returns a random delay for an idle monster.
+fn idle_delay(random: &mut Random) -> Duration {
+  Duration::from_millis(random.gen_range(IDLE_DELAY_MS.0, IDLE_DELAY_MS.1))
+}
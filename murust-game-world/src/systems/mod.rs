@@ -1,3 +1,11 @@
+pub use self::ai::*;
 pub use self::movement::*;
+pub use self::regeneration::*;
+pub use self::spawn::*;
+pub use self::viewport::*;
 
+mod ai;
 mod movement;
+mod regeneration;
+mod spawn;
+mod viewport;
@@ -1,32 +1,46 @@
-struct MovementSystem;
+use components::{Location, Movement};
+use resources::Time;
+use specs::{Entities, Fetch, Join, System, WriteStorage};
 
-impl<'a> System for MovementSystem {
-  type SystemData = (WriteStorage<'a, Position>, WriteStorage<'a, Movement>);
+/// Advances each moving object one step along its path.
+pub struct MovementSystem;
 
-  fn run(&mut self, (mut position, mut movement): Self::SystemData) {
-    let time = Instant::now();
+impl<'a> System<'a> for MovementSystem {
+  type SystemData = (
+    Fetch<'a, Time>,
+    WriteStorage<'a, Location>,
+    WriteStorage<'a, Movement>,
+  );
 
-    for (position, movement) in (&mut position, &mut movement).join() {
-      if time.duration_since(movement.last_movement) <= step_delay {
+  fn run(&mut self, (time, mut locations, mut movements): Self::SystemData) {
+    for (location, movement) in (&mut locations, &mut movements).join() {
+      if time.now.duration_since(movement.last_step) < movement.step_delay {
         continue;
       }
 
-      if let Some(new_position) = movement.path.pop() {
-        movement.last_movement = time;
-        position = new_position;
+      if let Some(position) = movement.path.pop_front() {
+        movement.last_step = time.now;
+        location.position = position;
       }
     }
   }
 }
 
-struct MovementPostSystem;
+/// Removes any movement that has reached its destination.
+pub struct MovementPostSystem;
 
-impl<'a> System for MovementPostSystem {
-  fn run() {
-    for (entity, movement) in (entities, movement).join() {
-      if movement.path.is_empty() {
-        movement.remove(entity)
-      }
+impl<'a> System<'a> for MovementPostSystem {
+  type SystemData = (Entities<'a>, WriteStorage<'a, Movement>);
+
+  fn run(&mut self, (entities, mut movements): Self::SystemData) {
+    let finished = (&*entities, &movements)
+      .join()
+      .filter(|(_, movement)| movement.path.is_empty())
+      .map(|(entity, _)| entity)
+      .collect::<Vec<_>>();
+
+    for entity in finished {
+      movements.remove(entity);
     }
   }
 }
@@ -0,0 +1,122 @@
+use components::{Ability, Dead, Health, Mana, Regeneration};
+use resources::{Time, WorldEvent, WorldEvents};
+use specs::{Entities, Fetch, FetchMut, Join, ReadStorage, System, WriteStorage};
+use std::mem;
+use std::time::Duration;
+
+/// The time, in seconds, between each natural regeneration.
+pub const REGENERATION_DELAY_SECS: u64 = 3;
+
+/// The share (in percent) of the maximum health regenerated naturally.
+const HEALTH_REGENERATION: u32 = 2;
+
+/// The share (in percent) of the maximum mana regenerated naturally.
+const MANA_REGENERATION: u32 = 4;
+
+/// The share (in percent) of the maximum AG regenerated naturally.
+const ABILITY_REGENERATION: u32 = 6;
+
+/// The number of steps health and mana restored by potions are spread over.
+pub const RECOVERY_STEPS: u32 = 5;
+
+/// The delay between each recovery step, in milliseconds.
+const RECOVERY_STEP_MS: u64 = 400;
+
+/// Regenerates the health, mana and AG of living players, and restores any
+/// health and mana recovered by potions one step at a time.
+pub struct RegenerationSystem;
+
+impl<'a> System<'a> for RegenerationSystem {
+  type SystemData = (
+    Entities<'a>,
+    Fetch<'a, Time>,
+    FetchMut<'a, WorldEvents>,
+    ReadStorage<'a, Dead>,
+    WriteStorage<'a, Regeneration>,
+    WriteStorage<'a, Health>,
+    WriteStorage<'a, Mana>,
+    WriteStorage<'a, Ability>,
+  );
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      time,
+      mut events,
+      deads,
+      mut regenerations,
+      mut healths,
+      mut manas,
+      mut abilities,
+    ): Self::SystemData,
+  ) {
+    for (entity, regeneration) in (&*entities, &mut regenerations).join() {
+      // Any recovery in progress is lost upon death
+      if deads.get(entity).is_some() {
+        regeneration.health = 0;
+        regeneration.mana = 0;
+        regeneration.steps = 0;
+        continue;
+      }
+
+      let (mut health, mut mana, mut ability) = (0, 0, 0);
+      if time.now >= regeneration.next_regeneration {
+        regeneration.next_regeneration = time.now + Duration::from_secs(REGENERATION_DELAY_SECS);
+        if let Some(current) = healths.get(entity) {
+          health = share(current.maximum, HEALTH_REGENERATION);
+        }
+
+        if let Some(current) = manas.get(entity) {
+          mana = share(current.maximum, MANA_REGENERATION);
+        }
+
+        if let Some(current) = abilities.get(entity) {
+          ability = share(current.maximum, ABILITY_REGENERATION);
+        }
+      }
+
+      if regeneration.steps > 0 && time.now >= regeneration.next_step {
+        let steps = regeneration.steps;
+        let (health_step, mana_step) = (
+          (regeneration.health + steps - 1) / steps,
+          (regeneration.mana + steps - 1) / steps,
+        );
+
+        regeneration.health -= health_step;
+        regeneration.mana -= mana_step;
+        regeneration.steps -= 1;
+        regeneration.next_step = time.now + Duration::from_millis(RECOVERY_STEP_MS);
+        health += health_step;
+        mana += mana_step;
+      }
+
+      let mut recovered = false;
+      if let Some(current) = healths.get_mut(entity) {
+        recovered |= restore(&mut current.current, current.maximum, health);
+      }
+
+      if let Some(current) = manas.get_mut(entity) {
+        recovered |= restore(&mut current.current, current.maximum, mana);
+      }
+
+      if let Some(current) = abilities.get_mut(entity) {
+        recovered |= restore(&mut current.current, current.maximum, ability);
+      }
+
+      if recovered {
+        events.push(WorldEvent::PlayerRecovered { player: entity });
+      }
+    }
+  }
+}
+
+/// Returns a share (in percent) of a maximum, of at least one.
+fn share(maximum: u32, percent: u32) -> u32 { (maximum * percent / 100).max(1) }
+
+/// Restores an amount of a value, up to its maximum, returning whether it
+/// changed.
+fn restore(current: &mut u32, maximum: u32, amount: u32) -> bool {
+  let restored = current.saturating_add(amount).min(maximum).max(*current);
+  mem::replace(current, restored) != restored
+}
@@ -0,0 +1,211 @@
+use components::{Ability, Dead, Health, Location, Mana, Movement, Player, Spawn};
+use murust_data_model::types::{Direction, Position};
+use rand::Rng;
+use resources::{Random, Terrain, Time, WorldEvent, WorldEvents};
+use specs::{Entities, Fetch, FetchMut, Join, ReadStorage, System, WriteStorage};
+use std::time::Duration;
+
+/// The number of attempts made to find a walkable spawn position.
+const SPAWN_ATTEMPTS: usize = 10;
+
+/// The time, in seconds, a player stays dead before being respawned.
+pub const PLAYER_RESPAWN_DELAY_SECS: u64 = 3;
+
+/// The maximum distance searched for a safe zone on maps without a town.
+const SAFE_ZONE_SEARCH_DISTANCE: u8 = 60;
+
+/// Returns the area of a map's town, if it has one.
+fn town_area(map: u8) -> Option<(Position, Position)> {
+  match map {
+    // Lorencia
+    0 => Some((Position::new(133, 118), Position::new(151, 135))),
+    // Devias
+    2 => Some((Position::new(197, 35), Position::new(218, 48))),
+    // Noria
+    3 => Some((Position::new(174, 101), Position::new(187, 125))),
+    _ => None,
+  }
+}
+
+/// Returns a random location within a spawn area.
+///
+/// Safe zones and unwalkable positions are avoided, falling back to the start
+/// of the area if no other position is found.
+pub fn spawn_location(spawn: &Spawn, terrain: &Terrain, random: &mut Random) -> Location {
+  let direction = spawn.direction.unwrap_or_else(|| {
+    *random
+      .choose(Direction::values().as_slice())
+      .expect("choosing a direction")
+  });
+
+  let position = (0..SPAWN_ATTEMPTS)
+    .map(|_| {
+      Position::new(
+        random.gen_range(spawn.area_start.x as u16, spawn.area_end.x as u16 + 1) as u8,
+        random.gen_range(spawn.area_start.y as u16, spawn.area_end.y as u16 + 1) as u8,
+      )
+    })
+    .find(|&position| terrain.is_walkable(position) && !terrain.is_safe_zone(position))
+    .unwrap_or(spawn.area_start);
+
+  Location {
+    position,
+    direction,
+  }
+}
+
+/// Returns the position a dead player is respawned at.
+///
+/// Players are respawned within the town of their map, or at the safe zone
+/// nearest to where they died if the map has none. If no safe zone is found
+/// either, they are respawned where they died.
+pub fn player_respawn_position(
+  terrain: &Terrain,
+  death: Position,
+  random: &mut Random,
+) -> Position {
+  if let Some((start, end)) = town_area(terrain.map()) {
+    return (0..SPAWN_ATTEMPTS)
+      .map(|_| {
+        Position::new(
+          random.gen_range(start.x as u16, end.x as u16 + 1) as u8,
+          random.gen_range(start.y as u16, end.y as u16 + 1) as u8,
+        )
+      })
+      .find(|&position| terrain.is_walkable(position))
+      .unwrap_or(start);
+  }
+
+  let is_safe =
+    |position: Position| terrain.is_walkable(position) && terrain.is_safe_zone(position);
+  if is_safe(death) {
+    return death;
+  }
+
+  (1..=SAFE_ZONE_SEARCH_DISTANCE)
+    .filter_map(|distance| {
+      death
+        .neighbors(distance)
+        .filter(|position| !position.is_within(&death, distance - 1))
+        .find(|&position| is_safe(position))
+    })
+    .next()
+    .unwrap_or(death)
+}
+
+/// Respawns dead objects once their respawn delay has elapsed.
+pub struct RespawnSystem;
+
+impl<'a> System<'a> for RespawnSystem {
+  type SystemData = (
+    Entities<'a>,
+    Fetch<'a, Time>,
+    Fetch<'a, Terrain>,
+    FetchMut<'a, Random>,
+    ReadStorage<'a, Spawn>,
+    WriteStorage<'a, Dead>,
+    WriteStorage<'a, Health>,
+    WriteStorage<'a, Location>,
+    WriteStorage<'a, Movement>,
+  );
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      time,
+      terrain,
+      mut random,
+      spawns,
+      mut deads,
+      mut healths,
+      mut locations,
+      mut movements,
+    ): Self::SystemData,
+  ) {
+    let respawned = (&*entities, &spawns, &deads)
+      .join()
+      .filter(|(_, spawn, dead)| time.now.duration_since(dead.since) >= spawn.respawn_delay)
+      .map(|(entity, spawn, _)| (entity, *spawn))
+      .collect::<Vec<_>>();
+
+    for (entity, spawn) in respawned {
+      deads.remove(entity);
+      movements.remove(entity);
+
+      if let Some(health) = healths.get_mut(entity) {
+        health.current = health.maximum;
+      }
+
+      locations.insert(entity, spawn_location(&spawn, &terrain, &mut random));
+    }
+  }
+}
+
+/// Respawns dead players once their respawn delay has elapsed, fully restoring
+/// their health, mana and AG.
+pub struct PlayerRespawnSystem;
+
+impl<'a> System<'a> for PlayerRespawnSystem {
+  type SystemData = (
+    Entities<'a>,
+    Fetch<'a, Time>,
+    Fetch<'a, Terrain>,
+    FetchMut<'a, Random>,
+    FetchMut<'a, WorldEvents>,
+    ReadStorage<'a, Player>,
+    WriteStorage<'a, Dead>,
+    WriteStorage<'a, Health>,
+    WriteStorage<'a, Mana>,
+    WriteStorage<'a, Ability>,
+    WriteStorage<'a, Location>,
+  );
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      time,
+      terrain,
+      mut random,
+      mut events,
+      players,
+      mut deads,
+      mut healths,
+      mut manas,
+      mut abilities,
+      mut locations,
+    ): Self::SystemData,
+  ) {
+    let delay = Duration::from_secs(PLAYER_RESPAWN_DELAY_SECS);
+    let respawned = (&*entities, &players, &deads)
+      .join()
+      .filter(|(_, _, dead)| time.now.duration_since(dead.since) >= delay)
+      .map(|(entity, _, _)| entity)
+      .collect::<Vec<_>>();
+
+    for entity in respawned {
+      deads.remove(entity);
+
+      if let Some(health) = healths.get_mut(entity) {
+        health.current = health.maximum;
+      }
+
+      if let Some(mana) = manas.get_mut(entity) {
+        mana.current = mana.maximum;
+      }
+
+      if let Some(ability) = abilities.get_mut(entity) {
+        ability.current = ability.maximum;
+      }
+
+      if let Some(location) = locations.get_mut(entity) {
+        location.position = player_respawn_position(&terrain, location.position, &mut random);
+        events.push(WorldEvent::PlayerRespawned {
+          player: entity,
+          position: location.position,
+        });
+      }
+    }
+  }
+}
@@ -0,0 +1,126 @@
+use components::{Dead, GroundItem, Location, Object, Viewport};
+use murust_data_model::types::ObjectId;
+use resources::{SpatialGrid, WorldEvent, WorldEvents};
+use specs::{Entities, Entity, FetchMut, Join, ReadStorage, System, WriteStorage};
+use std::collections::{HashMap, HashSet};
+
+/// The range within which a player observes other objects.
+pub const VIEW_RANGE: u8 = 15;
+
+/// Tracks which objects each player can see, queueing any changes as events.
+pub struct ViewportSystem;
+
+impl<'a> System<'a> for ViewportSystem {
+  type SystemData = (
+    Entities<'a>,
+    ReadStorage<'a, Object>,
+    ReadStorage<'a, GroundItem>,
+    ReadStorage<'a, Location>,
+    ReadStorage<'a, Dead>,
+    WriteStorage<'a, Viewport>,
+    FetchMut<'a, SpatialGrid>,
+    FetchMut<'a, WorldEvents>,
+  );
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      objects,
+      items,
+      locations,
+      deads,
+      mut viewports,
+      mut grid,
+      mut events,
+    ): Self::SystemData,
+  ) {
+    // Dead objects are hidden until they are respawned
+    for (entity, _) in (&*entities, &deads).join() {
+      grid.remove(entity);
+    }
+
+    for (entity, _, location, _) in (&*entities, &objects, &locations, !&deads).join() {
+      grid.update(entity, location.position);
+    }
+
+    for (entity, object, location, viewport) in
+      (&*entities, &objects, &locations, &mut viewports).join()
+    {
+      let nearby = grid.query(location.position, VIEW_RANGE);
+
+      let visible = nearby
+        .iter()
+        .cloned()
+        .filter(|&other| other != entity && objects.get(other).is_some())
+        .collect::<HashSet<_>>();
+      let (entered, left) = update_visible(&mut viewport.visible, visible, |other| {
+        objects.get(other).unwrap().id
+      });
+
+      if !left.is_empty() {
+        events.push(WorldEvent::ObjectsLeft {
+          observer: object.id,
+          objects: left,
+        });
+      }
+
+      if !entered.is_empty() {
+        events.push(WorldEvent::ObjectsEntered {
+          observer: object.id,
+          objects: entered,
+        });
+      }
+
+      let visible = nearby
+        .into_iter()
+        .filter(|&other| items.get(other).is_some())
+        .collect::<HashSet<_>>();
+      let (entered, left) = update_visible(&mut viewport.items, visible, |other| {
+        items.get(other).unwrap().id
+      });
+
+      if !left.is_empty() {
+        events.push(WorldEvent::ItemsLeft {
+          observer: object.id,
+          items: left,
+        });
+      }
+
+      if !entered.is_empty() {
+        events.push(WorldEvent::ItemsEntered {
+          observer: object.id,
+          items: entered,
+        });
+      }
+    }
+  }
+}
+
+/// Replaces a set of tracked entities, returning those that entered and the
+/// IDs of those that left.
+fn update_visible<F>(
+  tracked: &mut HashMap<Entity, ObjectId>,
+  visible: HashSet<Entity>,
+  id: F,
+) -> (Vec<Entity>, Vec<ObjectId>)
+where
+  F: Fn(Entity) -> ObjectId,
+{
+  let left = tracked
+    .iter()
+    .filter(|&(other, _)| !visible.contains(other))
+    .map(|(_, &id)| id)
+    .collect::<Vec<ObjectId>>();
+  tracked.retain(|other, _| visible.contains(other));
+
+  let entered = visible
+    .into_iter()
+    .filter(|other| !tracked.contains_key(other))
+    .collect::<Vec<_>>();
+  for &other in &entered {
+    tracked.insert(other, id(other));
+  }
+
+  (entered, left)
+}
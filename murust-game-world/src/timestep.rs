@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+/// The maximum number of ticks processed to catch up with a lagging clock.
+const MAX_CATCH_UP_TICKS: u32 = 5;
+
+/// A fixed timestep accumulator.
+///
+/// Elapsed time is accumulated until a whole tick is due, ensuring a world is
+/// updated at a constant rate regardless of how often it is polled.
+#[derive(Debug, Copy, Clone)]
+pub struct FixedTimestep {
+  step: Duration,
+  previous: Instant,
+  lag: Duration,
+}
+
+impl FixedTimestep {
+  /// Constructs a new timestep with a number of ticks per second.
+  pub fn new(tick_rate: u32, start: Instant) -> Self {
+    FixedTimestep {
+      step: Duration::from_secs(1) / tick_rate.max(1),
+      previous: start,
+      lag: Duration::from_secs(0),
+    }
+  }
+
+  /// Returns the duration of a single tick.
+  pub fn step(&self) -> Duration { self.step }
+
+  /// Accumulates the time elapsed and returns the number of ticks due.
+  pub fn advance(&mut self, now: Instant) -> u32 {
+    self.lag += now.duration_since(self.previous);
+    self.previous = now;
+
+    let mut ticks = 0;
+    while self.lag >= self.step {
+      self.lag -= self.step;
+      ticks += 1;
+    }
+
+    // Skip ahead instead of spiraling when the server cannot keep up
+    if ticks > MAX_CATCH_UP_TICKS {
+      ticks = MAX_CATCH_UP_TICKS;
+    }
+    ticks
+  }
+
+  /// Returns the time remaining until the next tick is due.
+  pub fn remaining(&self) -> Duration { self.step - self.lag }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use clock::{Clock, ManualClock};
+
+  #[test]
+  fn advance_accumulates_partial_ticks() {
+    let clock = ManualClock::new();
+    let mut timestep = FixedTimestep::new(20, clock.now());
+
+    clock.advance(Duration::from_millis(30));
+    assert_eq!(timestep.advance(clock.now()), 0);
+    assert_eq!(timestep.remaining(), Duration::from_millis(20));
+
+    clock.advance(Duration::from_millis(30));
+    assert_eq!(timestep.advance(clock.now()), 1);
+    assert_eq!(timestep.remaining(), Duration::from_millis(40));
+  }
+
+  #[test]
+  fn advance_limits_catch_up() {
+    let clock = ManualClock::new();
+    let mut timestep = FixedTimestep::new(10, clock.now());
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(timestep.advance(clock.now()), MAX_CATCH_UP_TICKS);
+  }
+}
@@ -1,31 +1,1445 @@
+use clock::{Clock, SystemClock};
+use combat::{self, AttackOutcome, CombatMode, CombatStats};
+use components::{Ability, AiState, Combatant, Cooldowns, Dead, GroundItem, Health, Location, Mana,
+                 Monster, MonsterAi, Movement, Npc, Object, Player, Regeneration, Spawn,
+                 Viewport};
+use murust_data_model::entities::{Loot, MonsterDefinition, MonsterSpawn, NpcDefinition,
+                                  SkillDefinition};
+use murust_data_model::types::{Direction, ObjectId, Position, SkillArea};
+use resources::{MonsterAttacks, Random, SpatialGrid, Terrain, Time, WorldEvent, WorldEvents};
+use specs::{Dispatcher, DispatcherBuilder, Entity, Fetch, FetchMut, Join, ReadStorage, World};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use systems::{self, MonsterAiSystem, MovementPostSystem, MovementSystem, PlayerRespawnSystem,
+              RegenerationSystem, RespawnSystem, ViewportSystem, RECOVERY_STEPS,
+              REGENERATION_DELAY_SECS};
+
+/// The first object ID allocated to monsters and NPCs, above any player IDs.
+pub const MONSTER_ID_START: ObjectId = 10_000;
+
+/// The time, in seconds, during which only the owner may pick up loot.
+pub const LOOT_OWNERSHIP_SECS: u64 = 10;
+
+/// The time, in seconds, loot lies on the ground before disappearing.
+pub const LOOT_LIFETIME_SECS: u64 = 60;
+
+/// The range within which a player can pick up loot.
+pub const PICKUP_RANGE: u8 = 3;
+
+/// The range within which a player can talk to an NPC.
+pub const TALK_RANGE: u8 = 5;
+
+/// The target of a skill.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SkillTarget {
+  /// An object, which is also the center of any area.
+  Object(Entity),
+  /// A position, used as the center of an area.
+  Position(Position),
+}
+
+/// A collection of reasons for a skill not being usable.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SkillError {
+  Dead,
+  InvalidTarget,
+  OutOfRange,
+  Cooldown,
+  InsufficientMana,
+  InsufficientAbility,
+}
+
+/// A collection of reasons for loot not being picked up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PickupError {
+  Dead,
+  NotFound,
+  OutOfRange,
+  NotOwner,
+  /// The loot could not be stored, e.g due to a full inventory.
+  Refused,
+}
+
+/// A collection of reasons for an NPC not being talked to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TalkError {
+  Dead,
+  NotFound,
+  OutOfRange,
+}
+
+/// A simulation of a single map.
 pub struct GameWorld {
-  dispatcher: Dispatcher,
-  world: World;
+  map: u8,
+  clock: Box<Clock>,
+  dispatcher: Dispatcher<'static, 'static>,
+  players: HashMap<ObjectId, Entity>,
+  monsters: HashMap<ObjectId, Entity>,
+  npcs: HashMap<ObjectId, Entity>,
+  next_monster_id: ObjectId,
+  items: HashMap<ObjectId, Entity>,
+  next_item_id: ObjectId,
+  world: World,
 }
 
 impl GameWorld {
-  pub fn new() -> Self {
+  /// Constructs a new world for a map's terrain, using the system clock.
+  pub fn new(terrain: Terrain) -> Self { Self::with_clock(terrain, SystemClock) }
+
+  /// Constructs a new world for a map's terrain, using a specific clock.
+  pub fn with_clock<C: Clock + 'static>(terrain: Terrain, clock: C) -> Self {
+    let map = terrain.map();
     let mut world = World::new();
-    world.register::<Position>();
+    world.register::<Ability>();
+    world.register::<Combatant>();
+    world.register::<Cooldowns>();
+    world.register::<Dead>();
+    world.register::<GroundItem>();
+    world.register::<Health>();
+    world.register::<Location>();
+    world.register::<Mana>();
+    world.register::<Monster>();
+    world.register::<MonsterAi>();
     world.register::<Movement>();
+    world.register::<Npc>();
+    world.register::<Object>();
+    world.register::<Player>();
+    world.register::<Regeneration>();
+    world.register::<Spawn>();
+    world.register::<Viewport>();
 
     world.add_resource(terrain);
+    world.add_resource(Time::new(clock.now()));
+    world.add_resource(Random::new());
+    world.add_resource(SpatialGrid::new());
+    world.add_resource(WorldEvents::default());
+    world.add_resource(MonsterAttacks::default());
 
-    let mut dispatcher = DispatcherBuilder::new()
+    let dispatcher = DispatcherBuilder::new()
       .add(MovementSystem, "movement_system", &[])
       .add(MovementPostSystem, "movement_post_system", &["movement_system"])
+      .add(RespawnSystem, "respawn_system", &[])
+      .add(PlayerRespawnSystem, "player_respawn_system", &[])
+      .add(RegenerationSystem, "regeneration_system", &["player_respawn_system"])
+      .add(
+        MonsterAiSystem,
+        "monster_ai_system",
+        &["movement_post_system", "respawn_system"],
+      )
+      .add(
+        ViewportSystem,
+        "viewport_system",
+        &[
+          "movement_system",
+          "respawn_system",
+          "player_respawn_system",
+          "monster_ai_system",
+        ],
+      )
+      .build();
+
+    GameWorld {
+      map,
+      clock: Box::new(clock),
+      dispatcher,
+      players: HashMap::new(),
+      monsters: HashMap::new(),
+      npcs: HashMap::new(),
+      next_monster_id: MONSTER_ID_START,
+      items: HashMap::new(),
+      next_item_id: 0,
+      world,
+    }
+  }
+
+  /// Returns the map this world simulates.
+  pub fn map(&self) -> u8 { self.map }
+
+  /// Adds a player to the world and returns its entity, unless a player with
+  /// the same ID has already been added.
+  pub fn add_player(&mut self, id: ObjectId, position: Position) -> Option<Entity> {
+    if self.players.contains_key(&id) {
+      return None;
+    }
+
+    let regeneration = self.time().now + Duration::from_secs(REGENERATION_DELAY_SECS);
+    let entity = self
+      .world
+      .create_entity()
+      .with(Object { id })
+      .with(Player)
+      .with(Combatant::default())
+      .with(Cooldowns::default())
+      .with(Location::new(position))
+      .with(Regeneration::new(regeneration))
+      .with(Viewport::default())
+      .build();
+    self.players.insert(id, entity);
+    Some(entity)
+  }
+
+  /// Removes a player from the world, returning whether it existed or not.
+  pub fn remove_player(&mut self, id: ObjectId) -> bool {
+    match self.players.remove(&id) {
+      Some(entity) => {
+        self.world.write_resource::<SpatialGrid>().remove(entity);
+        let _ = self.world.delete_entity(entity);
+        true
+      },
+      None => false,
+    }
+  }
+
+  /// Returns a player's entity.
+  pub fn player(&self, id: ObjectId) -> Option<Entity> { self.players.get(&id).cloned() }
+
+  /// Returns the number of players within the world.
+  pub fn players(&self) -> usize { self.players.len() }
+
+  /// Spawns the monsters of a spawn area and returns their entities.
+  pub fn spawn_monsters(&mut self, spawn: &MonsterSpawn) -> Vec<Entity> {
+    let area = Spawn {
+      area_start: spawn.area_start,
+      area_end: spawn.area_end,
+      direction: spawn.direction,
+      respawn_delay: spawn.definition.respawn_time,
+    };
+
+    let now = self.time().now;
+    (0..spawn.count)
+      .map(|_| {
+        let id = self.next_monster_id;
+        self.next_monster_id += 1;
+
+        let location = systems::spawn_location(
+          &area,
+          &self.world.read_resource::<Terrain>(),
+          &mut self.world.write_resource::<Random>(),
+        );
+
+        let entity = self
+          .world
+          .create_entity()
+          .with(Object { id })
+          .with(Monster {
+            definition: spawn.definition.clone(),
+          })
+          .with(MonsterAi::new(now))
+          .with(Health::new(spawn.definition.max_health))
+          .with(Combatant {
+            stats: CombatStats::from_monster(&spawn.definition),
+          })
+          .with(area)
+          .with(location)
+          .build();
+        self.monsters.insert(id, entity);
+        entity
+      })
+      .collect()
+  }
+
+  /// Returns a monster's entity.
+  pub fn monster(&self, id: ObjectId) -> Option<Entity> { self.monsters.get(&id).cloned() }
+
+  /// Returns the number of monsters within the world, including dead ones.
+  pub fn monsters(&self) -> usize { self.monsters.len() }
+
+  /// Returns the definition of a monster entity.
+  pub fn monster_definition(&self, entity: Entity) -> Option<Arc<MonsterDefinition>> {
+    self
+      .world
+      .read::<Monster>()
+      .get(entity)
+      .map(|monster| monster.definition.clone())
+  }
+
+  /// Adds an NPC to the world and returns its entity.
+  pub fn add_npc(&mut self, definition: Arc<NpcDefinition>) -> Entity {
+    let id = self.next_monster_id;
+    self.next_monster_id += 1;
+
+    let location = Location {
+      position: definition.position,
+      direction: definition.direction,
+    };
+
+    let entity = self
+      .world
+      .create_entity()
+      .with(Object { id })
+      .with(Npc { definition })
+      .with(location)
+      .build();
+    self.npcs.insert(id, entity);
+    entity
+  }
+
+  /// Returns an NPC's entity.
+  pub fn npc(&self, id: ObjectId) -> Option<Entity> { self.npcs.get(&id).cloned() }
+
+  /// Returns the definition of an NPC entity.
+  pub fn npc_definition(&self, entity: Entity) -> Option<Arc<NpcDefinition>> {
+    self
+      .world
+      .read::<Npc>()
+      .get(entity)
+      .map(|npc| npc.definition.clone())
+  }
+
+  /// Lets a player talk to an NPC, returning its definition.
+  pub fn talk(&self, player: Entity, id: ObjectId) -> Result<Arc<NpcDefinition>, TalkError> {
+    if self.is_dead(player) {
+      return Err(TalkError::Dead);
+    }
+
+    let entity = self.npc(id).ok_or(TalkError::NotFound)?;
+    let (position, npc_position) = match (self.location(player), self.location(entity)) {
+      (Some(player), Some(npc)) => (player.position, npc.position),
+      _ => return Err(TalkError::NotFound),
+    };
+
+    if !position.is_within(&npc_position, TALK_RANGE) {
+      return Err(TalkError::OutOfRange);
+    }
+
+    self.npc_definition(entity).ok_or(TalkError::NotFound)
+  }
+
+  /// Returns an entity's health.
+  pub fn health(&self, entity: Entity) -> Option<Health> {
+    self.world.read::<Health>().get(entity).cloned()
+  }
+
+  /// Returns whether an entity is dead or not.
+  pub fn is_dead(&self, entity: Entity) -> bool { self.world.read::<Dead>().get(entity).is_some() }
+
+  /// Replaces the combat stats of an entity, e.g when a player's equipment changes.
+  pub fn set_combat_stats(&mut self, entity: Entity, stats: CombatStats) {
+    if let Some(combatant) = self.world.write::<Combatant>().get_mut(entity) {
+      combatant.stats = stats;
+    }
+  }
+
+  /// Sets the maximum health of an entity, refilling it.
+  pub fn set_health(&mut self, entity: Entity, health: u32) {
+    self.world.write::<Health>().insert(entity, Health::new(health));
+  }
+
+  /// Sets the maximum health of an entity, keeping its current health within
+  /// the new bound.
+  pub fn set_max_health(&mut self, entity: Entity, health: u32) {
+    if self.health(entity).is_none() {
+      return self.set_health(entity, health);
+    }
+
+    if let Some(current) = self.world.write::<Health>().get_mut(entity) {
+      current.maximum = health;
+      current.current = current.current.min(health);
+    }
+  }
+
+  /// Sets the maximum mana and AG of an entity, refilling both.
+  pub fn set_mana_and_ability(&mut self, entity: Entity, mana: u32, ability: u32) {
+    self.world.write::<Mana>().insert(entity, Mana::new(mana));
+    self.world.write::<Ability>().insert(entity, Ability::new(ability));
+  }
+
+  /// Sets the maximum mana and AG of an entity, keeping their current values
+  /// within the new bounds.
+  pub fn set_max_mana_and_ability(&mut self, entity: Entity, mana: u32, ability: u32) {
+    if self.mana(entity).is_none() {
+      return self.set_mana_and_ability(entity, mana, ability);
+    }
+
+    if let Some(current) = self.world.write::<Mana>().get_mut(entity) {
+      current.maximum = mana;
+      current.current = current.current.min(mana);
+    }
+
+    if let Some(current) = self.world.write::<Ability>().get_mut(entity) {
+      current.maximum = ability;
+      current.current = current.current.min(ability);
+    }
+  }
+
+  /// Returns an entity's mana.
+  pub fn mana(&self, entity: Entity) -> Option<Mana> {
+    self.world.read::<Mana>().get(entity).cloned()
+  }
+
+  /// Returns an entity's ability gauge.
+  pub fn ability(&self, entity: Entity) -> Option<Ability> {
+    self.world.read::<Ability>().get(entity).cloned()
+  }
+
+  /// Restores an amount of health and mana to a living player gradually,
+  /// returning whether it's recovering.
+  ///
+  /// Any recovery already in progress is combined with it, and spread anew
+  /// over all steps.
+  pub fn recover(&mut self, entity: Entity, health: u32, mana: u32) -> bool {
+    if self.is_dead(entity) {
+      return false;
+    }
+
+    let now = self.time().now;
+    match self.world.write::<Regeneration>().get_mut(entity) {
+      Some(regeneration) => {
+        if regeneration.steps == 0 {
+          regeneration.next_step = now;
+        }
+
+        regeneration.health += health;
+        regeneration.mana += mana;
+        regeneration.steps = RECOVERY_STEPS;
+        true
+      },
+      None => false,
+    }
+  }
+
+  /// Lets an entity attack a target, returning the outcome.
+  ///
+  /// Any damage is subtracted from the target's health, killing it once
+  /// depleted. Nothing happens if either entity is dead or cannot fight, or
+  /// if the attacker's previous attack is too recent for its attack speed.
+  pub fn attack(&mut self, attacker: Entity, target: Entity) -> Option<AttackOutcome> {
+    let now = self.time().now;
+    let ready = self
+      .world
+      .read::<Cooldowns>()
+      .get(attacker)
+      .and_then(|cooldowns| cooldowns.attack);
+    if ready.map_or(false, |ready| ready > now) {
+      return None;
+    }
+
+    let outcome = self.strike(attacker, target, None)?;
+    let interval = self.world.read::<Combatant>().get(attacker)?.stats.attack_interval();
+    if let Some(cooldowns) = self.world.write::<Cooldowns>().get_mut(attacker) {
+      cooldowns.attack = Some(now + interval);
+    }
+    Some(outcome)
+  }
+
+  /// Lets an entity use a skill, returning the outcome of each hit target.
+  ///
+  /// The skill's costs are only paid once it is known to be usable. Area
+  /// skills hit all living monsters within the area, but never the caster.
+  pub fn use_skill(
+    &mut self,
+    caster: Entity,
+    skill: &SkillDefinition,
+    target: SkillTarget,
+  ) -> Result<Vec<(Entity, AttackOutcome)>, SkillError> {
+    if self.is_dead(caster) {
+      return Err(SkillError::Dead);
+    }
+
+    let origin = self
+      .location(caster)
+      .ok_or(SkillError::InvalidTarget)?
+      .position;
+    let center = match target {
+      SkillTarget::Object(entity) if entity != caster && !self.is_dead(entity) => {
+        self
+          .location(entity)
+          .ok_or(SkillError::InvalidTarget)?
+          .position
+      },
+      SkillTarget::Object(_) => return Err(SkillError::InvalidTarget),
+      SkillTarget::Position(position) => position,
+    };
+
+    if !origin.is_within(&center, skill.range) {
+      return Err(SkillError::OutOfRange);
+    }
+
+    let now = self.time().now;
+    let ready = self
+      .world
+      .read::<Cooldowns>()
+      .get(caster)
+      .and_then(|cooldowns| cooldowns.ready.get(&skill.id).cloned());
+    if ready.map_or(false, |ready| ready > now) {
+      return Err(SkillError::Cooldown);
+    }
+
+    self.pay_skill_costs(caster, skill)?;
+    if let Some(cooldowns) = self.world.write::<Cooldowns>().get_mut(caster) {
+      cooldowns.ready.insert(skill.id, now + skill.cooldown);
+    }
+
+    let targets = match (skill.area, target) {
+      (SkillArea::Single, SkillTarget::Object(entity)) => vec![entity],
+      (SkillArea::Single, SkillTarget::Position(_)) => Vec::new(),
+      (area, _) => self.monsters_within(area, center),
+    };
+
+    Ok(
+      targets
+        .into_iter()
+        .filter(|&target| target != caster)
+        .filter_map(|target| {
+          self
+            .strike(caster, target, Some(skill))
+            .map(|outcome| (target, outcome))
+        })
+        .collect(),
+    )
+  }
+
+  /// Resolves an attack, optionally using a skill, and applies its damage.
+  ///
+  /// Attacks by monsters and the deaths of players are queued as events.
+  fn strike(
+    &mut self,
+    attacker: Entity,
+    target: Entity,
+    skill: Option<&SkillDefinition>,
+  ) -> Option<AttackOutcome> {
+    if self.is_dead(attacker) || self.is_dead(target) {
+      return None;
+    }
+
+    let (attacker_stats, target_stats) = {
+      let combatants = self.world.read::<Combatant>();
+      (combatants.get(attacker)?.stats, combatants.get(target)?.stats)
+    };
+
+    let attacker_stats = match skill {
+      Some(skill) => attacker_stats.with_skill(skill),
+      None => attacker_stats,
+    };
+
+    let mode = if self.is_player(attacker) && self.is_player(target) {
+      CombatMode::Player
+    } else {
+      CombatMode::Monster
+    };
+
+    // Players within a safe zone can neither be struck nor strike other players
+    let pvp = mode == CombatMode::Player;
+    if (self.is_player(target) && self.is_in_safe_zone(target))
+      || (pvp && self.is_in_safe_zone(attacker))
+    {
+      return None;
+    }
+
+    let outcome = {
+      let mut random = self.world.write_resource::<Random>();
+      combat::attack(&attacker_stats, &target_stats, mode, &mut **random)
+    };
+
+    if self.monster_definition(attacker).is_some() {
+      self.world.write_resource::<WorldEvents>().push(WorldEvent::MonsterAttack {
+        monster: attacker,
+        target,
+        outcome,
+      });
+    }
+
+    if let AttackOutcome::Hit { damage, .. } = outcome {
+      let depleted = match self.world.write::<Health>().get_mut(target) {
+        Some(health) => {
+          health.current = health.current.saturating_sub(damage);
+          health.current == 0
+        },
+        None => false,
+      };
+
+      if depleted {
+        self.kill(target);
+
+        if self.is_player(target) {
+          self.world.write_resource::<WorldEvents>().push(WorldEvent::PlayerDied {
+            player: target,
+            killer: attacker,
+          });
+        }
+      }
+    }
+
+    Some(outcome)
+  }
+
+  /// Returns whether an entity stands within a safe zone.
+  fn is_in_safe_zone(&self, entity: Entity) -> bool {
+    self
+      .location(entity)
+      .map_or(false, |location| self.terrain().is_safe_zone(location.position))
+  }
+
+  /// Subtracts the mana and AG required by a skill, if available.
+  fn pay_skill_costs(&mut self, caster: Entity, skill: &SkillDefinition) -> Result<(), SkillError> {
+    let mut manas = self.world.write::<Mana>();
+    let mut abilities = self.world.write::<Ability>();
+    let (mana_cost, ability_cost) = (u32::from(skill.mana_cost), u32::from(skill.ability_cost));
+
+    if manas.get(caster).map_or(0, |mana| mana.current) < mana_cost {
+      return Err(SkillError::InsufficientMana);
+    }
+
+    if abilities.get(caster).map_or(0, |ability| ability.current) < ability_cost {
+      return Err(SkillError::InsufficientAbility);
+    }
+
+    if let Some(mana) = manas.get_mut(caster) {
+      mana.current -= mana_cost;
+    }
+
+    if let Some(ability) = abilities.get_mut(caster) {
+      ability.current -= ability_cost;
+    }
+    Ok(())
+  }
+
+  /// Returns all living monsters within an area.
+  fn monsters_within(&self, area: SkillArea, center: Position) -> Vec<Entity> {
+    (
+      &*self.world.entities(),
+      &self.world.read::<Monster>(),
+      &self.world.read::<Location>(),
+      !&self.world.read::<Dead>(),
+    ).join()
+      .filter(|(_, _, location, _)| area.contains(center, location.position))
+      .map(|(entity, _, _, _)| entity)
+      .collect()
+  }
+
+  /// Marks an entity as dead, stopping any movement or behaviour.
+  fn kill(&mut self, entity: Entity) {
+    let since = self.time().now;
+    self.world.write::<Dead>().insert(entity, Dead { since });
+    self.world.write::<Movement>().remove(entity);
+
+    if let Some(ai) = self.world.write::<MonsterAi>().get_mut(entity) {
+      ai.state = AiState::Idle;
+    }
+  }
+
+  /// Drops loot on the ground and returns its entity.
+  ///
+  /// The owner, if any, has exclusive rights to the loot for a short while.
+  pub fn drop_loot(&mut self, loot: Loot, position: Position, owner: Option<Entity>) -> Entity {
+    let id = self.allocate_item_id();
+    let now = self.time().now;
+
+    let entity = self
+      .world
+      .create_entity()
+      .with(GroundItem {
+        id,
+        loot,
+        owner,
+        owned_until: now + Duration::from_secs(LOOT_OWNERSHIP_SECS),
+        expires: now + Duration::from_secs(LOOT_LIFETIME_SECS),
+      })
+      .with(Location::new(position))
       .build();
+    self.world.write_resource::<SpatialGrid>().update(entity, position);
+    self.items.insert(id, entity);
+    entity
+  }
+
+  /// Returns a ground item's entity.
+  pub fn ground_item(&self, id: ObjectId) -> Option<Entity> { self.items.get(&id).cloned() }
+
+  /// Returns the storage of all ground items.
+  pub fn ground_items(&self) -> ReadStorage<GroundItem> { self.world.read::<GroundItem>() }
+
+  /// Lets a player pick up a ground item, storing its loot.
+  ///
+  /// The loot is handed to `store`, which may give it back if it cannot be
+  /// stored, in which case it remains on the ground.
+  pub fn pick_up<F, R>(&mut self, player: Entity, id: ObjectId, store: F) -> Result<R, PickupError>
+  where
+    F: FnOnce(Loot) -> Result<R, Loot>,
+  {
+    if self.is_dead(player) {
+      return Err(PickupError::Dead);
+    }
+
+    let entity = self.ground_item(id).ok_or(PickupError::NotFound)?;
+    let (position, item_position) = match (self.location(player), self.location(entity)) {
+      (Some(player), Some(item)) => (player.position, item.position),
+      _ => return Err(PickupError::NotFound),
+    };
+
+    if !position.is_within(&item_position, PICKUP_RANGE) {
+      return Err(PickupError::OutOfRange);
+    }
+
+    let now = self.time().now;
+    let item = self
+      .world
+      .write::<GroundItem>()
+      .remove(entity)
+      .ok_or(PickupError::NotFound)?;
+
+    // TODO: Share ownership with the owner's party
+    if item.owner.map_or(false, |owner| owner != player) && item.owned_until > now {
+      self.world.write::<GroundItem>().insert(entity, item);
+      return Err(PickupError::NotOwner);
+    }
+
+    let GroundItem {
+      loot,
+      owner,
+      owned_until,
+      expires,
+      ..
+    } = item;
+    match store(loot) {
+      Ok(result) => {
+        self.remove_ground_item(id);
+        Ok(result)
+      },
+      Err(loot) => {
+        self.world.write::<GroundItem>().insert(
+          entity,
+          GroundItem {
+            id,
+            loot,
+            owner,
+            owned_until,
+            expires,
+          },
+        );
+        Err(PickupError::Refused)
+      },
+    }
+  }
+
+  /// Removes a ground item, returning whether it existed or not.
+  fn remove_ground_item(&mut self, id: ObjectId) -> bool {
+    match self.items.remove(&id) {
+      Some(entity) => {
+        self.world.write_resource::<SpatialGrid>().remove(entity);
+        let _ = self.world.delete_entity(entity);
+        true
+      },
+      None => false,
+    }
+  }
+
+  /// Returns the next unused ground item ID.
+  fn allocate_item_id(&mut self) -> ObjectId {
+    while self.items.contains_key(&self.next_item_id) {
+      self.next_item_id = self.next_item_id.wrapping_add(1);
+    }
+
+    let id = self.next_item_id;
+    self.next_item_id = self.next_item_id.wrapping_add(1);
+    id
+  }
+
+  /// Removes all ground items that have expired.
+  fn expire_ground_items(&mut self) {
+    let now = self.time().now;
+    let expired = self
+      .world
+      .read::<GroundItem>()
+      .join()
+      .filter(|item| item.expires <= now)
+      .map(|item| item.id)
+      .collect::<Vec<_>>();
+
+    for id in expired {
+      self.remove_ground_item(id);
+    }
+  }
+
+  /// Returns the IDs of all players within range of a position.
+  pub fn players_within(&self, position: Position, range: u8) -> Vec<ObjectId> {
+    (
+      &self.world.read::<Object>(),
+      &self.world.read::<Player>(),
+      &self.world.read::<Location>(),
+    ).join()
+      .filter(|(_, _, location)| location.position.is_within(&position, range))
+      .map(|(object, _, _)| object.id)
+      .collect()
+  }
+
+  /// Returns the IDs of all players that can see an entity.
+  pub fn observers(&self, entity: Entity) -> Vec<ObjectId> {
+    (&self.world.read::<Object>(), &self.world.read::<Viewport>())
+      .join()
+      .filter(|(_, viewport)| viewport.visible.contains_key(&entity))
+      .map(|(object, _)| object.id)
+      .collect()
+  }
+
+  /// Returns the object ID of an entity.
+  pub fn object_id(&self, entity: Entity) -> Option<ObjectId> {
+    self.world.read::<Object>().get(entity).map(|object| object.id)
   }
 
-  pub fn add_player(&mut self) {
-    const MoveDuration = Duration::from_millis(400);
+  /// Returns whether an entity is a player or not.
+  pub fn is_player(&self, entity: Entity) -> bool {
+    self.world.read::<Player>().get(entity).is_some()
+  }
+
+  /// Returns an entity's current location.
+  pub fn location(&self, entity: Entity) -> Option<Location> {
+    self.world.read::<Location>().get(entity).cloned()
+  }
+
+  /// Returns the position an entity is walking towards, or stands at.
+  pub fn destination(&self, entity: Entity) -> Option<Position> {
+    self
+      .world
+      .read::<Movement>()
+      .get(entity)
+      .and_then(Movement::destination)
+      .or_else(|| self.location(entity).map(|location| location.position))
+  }
+
+  /// Lets an entity walk along a path, starting at a position, returning
+  /// whether the path was accepted.
+  ///
+  /// A path replacing another is refused unless the latter was started at
+  /// least one step earlier, bounding how fast a path may be redirected.
+  pub fn walk(
+    &mut self,
+    entity: Entity,
+    start: Position,
+    direction: Direction,
+    path: Vec<Position>,
+  ) -> bool {
+    let now = self.time().now;
+    let mut movements = self.world.write::<Movement>();
+    let redirected_early = movements
+      .get(entity)
+      .map_or(false, |movement| now.duration_since(movement.started) < movement.step_delay);
+    if redirected_early {
+      return false;
+    }
+
+    if let Some(location) = self.world.write::<Location>().get_mut(entity) {
+      location.position = start;
+      location.direction = direction;
+    }
+
+    if path.is_empty() {
+      movements.remove(entity);
+    } else {
+      movements.insert(entity, Movement::new(path, now));
+    }
+    true
   }
 
-  pub fn remove_player(&mut self) {
+  /// Stops any movement of an entity.
+  pub fn stop(&mut self, entity: Entity) { self.world.write::<Movement>().remove(entity); }
+
+  /// Returns the world's terrain.
+  pub fn terrain(&self) -> Fetch<Terrain> { self.world.read_resource::<Terrain>() }
+
+  /// Returns the world's source of randomness.
+  pub fn random(&self) -> FetchMut<Random> { self.world.write_resource::<Random>() }
+
+  /// Returns the timing of the latest tick.
+  pub fn time(&self) -> Time { *self.world.read_resource::<Time>() }
+
+  /// Removes and returns all events produced since the previous call.
+  pub fn drain_events(&mut self) -> Vec<WorldEvent> {
+    self.world.write_resource::<WorldEvents>().drain()
   }
 
+  /// Processes a single tick of the world.
   pub fn update(&mut self) {
-    self.dispatcher.dispatch(&self.world);
-    world.maintain();
+    let now = self.clock.now();
+    self.world.write_resource::<Time>().advance(now);
+    self.expire_ground_items();
+    self.dispatcher.dispatch(&self.world.res);
+    self.world.maintain();
+    self.resolve_monster_attacks();
+  }
+
+  /// Resolves the attacks declared by monsters during the latest tick.
+  fn resolve_monster_attacks(&mut self) {
+    let attacks = self.world.write_resource::<MonsterAttacks>().drain();
+    for (monster, target) in attacks {
+      self.strike(monster, target, None);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use clock::ManualClock;
+  use combat::DamageKind;
+  use murust_data_model::types::NpcKind;
+  use resources::TerrainAttribute;
+  use std::time::Duration;
+  use systems::{PLAYER_RESPAWN_DELAY_SECS, RECOVERY_STEPS, REGENERATION_DELAY_SECS};
+
+  #[test]
+  fn add_and_remove_player() {
+    let mut world = GameWorld::new(Terrain::new(0));
+    world.add_player(1, Position::new(120, 60)).unwrap();
+    assert_eq!(world.players(), 1);
+    assert!(world.add_player(1, Position::new(120, 60)).is_none());
+
+    assert!(world.remove_player(1));
+    assert!(!world.remove_player(1));
+    assert_eq!(world.players(), 0);
+  }
+
+  #[test]
+  fn find_players_within_range() {
+    let mut world = GameWorld::new(Terrain::new(0));
+    world.add_player(1, Position::new(100, 100)).unwrap();
+    world.add_player(2, Position::new(110, 90)).unwrap();
+    world.add_player(3, Position::new(120, 100)).unwrap();
+
+    let mut players = world.players_within(Position::new(100, 100), 10);
+    players.sort();
+    assert_eq!(players, vec![1, 2]);
+  }
+
+  #[test]
+  fn players_enter_and_leave_view() {
+    let mut world = GameWorld::new(Terrain::new(0));
+    let first = world.add_player(1, Position::new(100, 100)).unwrap();
+    let second = world.add_player(2, Position::new(105, 100)).unwrap();
+    world.update();
+
+    let mut observers = world
+      .drain_events()
+      .into_iter()
+      .filter_map(|event| match event {
+        WorldEvent::ObjectsEntered { observer, objects } => Some((observer, objects)),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    observers.sort_by_key(|&(observer, _)| observer);
+    assert_eq!(observers, vec![(1, vec![second]), (2, vec![first])]);
+    assert_eq!(world.observers(first), vec![2]);
+
+    world.remove_player(2);
+    world.update();
+    match world.drain_events().as_slice() {
+      [WorldEvent::ObjectsLeft { observer, objects }] => {
+        assert_eq!(*observer, 1);
+        assert_eq!(*objects, vec![2]);
+      },
+      events => panic!("unexpected events {:?}", events),
+    }
+  }
+
+  fn spider() -> MonsterSpawn {
+    MonsterSpawn {
+      id: 1,
+      definition: Arc::new(MonsterDefinition {
+        class: 3,
+        name: "Spider".into(),
+        level: 2,
+        max_health: 30,
+        min_damage: 4,
+        max_damage: 7,
+        defense: 1,
+        attack_rate: 8,
+        defense_rate: 1,
+        move_range: 2,
+        attack_range: 1,
+        view_range: 4,
+        move_delay: Duration::from_millis(600),
+        attack_delay: Duration::from_millis(1800),
+        respawn_time: Duration::from_secs(10),
+      }),
+      map: 0,
+      area_start: Position::new(50, 50),
+      area_end: Position::new(60, 55),
+      direction: None,
+      count: 5,
+    }
+  }
+
+  #[test]
+  fn spawn_monsters_within_area() {
+    let mut world = GameWorld::new(Terrain::new(0));
+    let entities = world.spawn_monsters(&spider());
+    assert_eq!(world.monsters(), 5);
+
+    for entity in entities {
+      let position = world.location(entity).unwrap().position;
+      assert!(position.x >= 50 && position.x <= 60);
+      assert!(position.y >= 50 && position.y <= 55);
+      assert_eq!(world.health(entity).unwrap().current, 30);
+      assert!(world.object_id(entity).unwrap() >= MONSTER_ID_START);
+    }
+  }
+
+  #[test]
+  fn dead_monsters_respawn_after_delay() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+    let entity = world.spawn_monsters(&spider())[0];
+
+    world.world.write::<Health>().get_mut(entity).unwrap().current = 0;
+    world.world.write::<Dead>().insert(entity, Dead { since: clock.now() });
+
+    clock.advance(Duration::from_secs(5));
+    world.update();
+    assert!(world.is_dead(entity));
+
+    clock.advance(Duration::from_secs(5));
+    world.update();
+    assert!(!world.is_dead(entity));
+    assert_eq!(world.health(entity).unwrap().current, 30);
+  }
+
+  #[test]
+  fn players_attack_and_kill_monsters() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+    let monster = world.spawn_monsters(&spider())[0];
+    let player = world.add_player(1, Position::new(100, 100)).unwrap();
+    world.set_combat_stats(
+      player,
+      CombatStats {
+        level: 1,
+        min_damage: 10,
+        max_damage: 10,
+        attack_rate: 100_000,
+        ..CombatStats::default()
+      },
+    );
+
+    // Spiders have 30 health and a defense of 1
+    let hit = AttackOutcome::Hit {
+      damage: 9,
+      kind: DamageKind::Normal,
+    };
+    for _ in 0..3 {
+      assert_eq!(world.attack(player, monster), Some(hit));
+      assert_eq!(world.attack(player, monster), None);
+      clock.advance(Duration::from_millis(600));
+      world.update();
+    }
+    assert_eq!(world.health(monster).unwrap().current, 3);
+    assert!(!world.is_dead(monster));
+
+    assert_eq!(world.attack(player, monster), Some(hit));
+    assert!(world.is_dead(monster));
+
+    clock.advance(Duration::from_millis(600));
+    world.update();
+    assert_eq!(world.attack(player, monster), None);
+  }
+
+  #[test]
+  fn players_cannot_fight_within_safe_zones() {
+    let mut terrain = Terrain::new(0);
+    terrain.set_attributes(Position::new(100, 100), TerrainAttribute::SafeZone);
+
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(terrain, clock.clone());
+    let safe = world.add_player(1, Position::new(100, 100)).unwrap();
+    let other = world.add_player(2, Position::new(101, 100)).unwrap();
+    let stats = CombatStats {
+      level: 1,
+      min_damage: 10,
+      max_damage: 10,
+      pvp_attack_rate: 100_000,
+      ..CombatStats::default()
+    };
+    world.set_combat_stats(safe, stats);
+    world.set_combat_stats(other, stats);
+
+    assert_eq!(world.attack(safe, other), None);
+    assert_eq!(world.attack(other, safe), None);
+
+    world.walk(other, Position::new(101, 100), Direction::East, vec![Position::new(102, 100)]);
+    clock.advance(Duration::from_millis(400));
+    world.update();
+    assert_eq!(world.location(other).unwrap().position, Position::new(102, 100));
+    assert_eq!(world.attack(safe, other), None);
+
+    world.walk(safe, Position::new(100, 100), Direction::East, vec![Position::new(101, 100)]);
+    clock.advance(Duration::from_millis(400));
+    world.update();
+    assert!(world.attack(safe, other).is_some());
+  }
+
+  #[test]
+  fn monsters_cannot_strike_players_within_safe_zones() {
+    let mut terrain = Terrain::new(0);
+    terrain.set_attributes(Position::new(52, 50), TerrainAttribute::SafeZone);
+
+    let mut world = GameWorld::new(terrain);
+    let mut spawn = spider();
+    spawn.area_end = spawn.area_start;
+    spawn.count = 1;
+
+    let monster = world.spawn_monsters(&spawn)[0];
+    let safe = world.add_player(1, Position::new(52, 50)).unwrap();
+    let other = world.add_player(2, Position::new(53, 50)).unwrap();
+    assert_eq!(world.attack(monster, safe), None);
+    assert!(world.attack(monster, other).is_some());
+  }
+
+  #[test]
+  fn area_skills_hit_monsters_and_respect_costs() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+
+    let mut spawn = spider();
+    spawn.area_end = spawn.area_start;
+    spawn.count = 3;
+
+    let monsters = world.spawn_monsters(&spawn);
+    let player = world.add_player(1, Position::new(52, 50)).unwrap();
+    world.set_mana_and_ability(player, 15, 0);
+    world.set_combat_stats(
+      player,
+      CombatStats {
+        level: 1,
+        min_magic_damage: 5,
+        max_magic_damage: 5,
+        attack_rate: 100_000,
+        ..CombatStats::default()
+      },
+    );
+
+    let skill = SkillDefinition {
+      id: 9,
+      name: "Evil Spirit".into(),
+      damage: 5,
+      magic: true,
+      mana_cost: 10,
+      ability_cost: 0,
+      cooldown: Duration::from_secs(1),
+      range: 6,
+      area: SkillArea::Circle(3),
+      min_level: 0,
+      min_energy: 0,
+      eligible_classes: Vec::new(),
+    };
+
+    let center = SkillTarget::Position(Position::new(50, 50));
+    let hits = world.use_skill(player, &skill, center).unwrap();
+    assert_eq!(hits.len(), 3);
+    assert!(hits.iter().all(|(monster, _)| monsters.contains(monster)));
+    assert_eq!(world.mana(player).unwrap().current, 5);
+
+    assert_eq!(world.use_skill(player, &skill, center), Err(SkillError::Cooldown));
+    clock.advance(Duration::from_secs(1));
+    world.update();
+    assert_eq!(
+      world.use_skill(player, &skill, center),
+      Err(SkillError::InsufficientMana)
+    );
+
+    let distant = SkillTarget::Position(Position::new(60, 60));
+    assert_eq!(world.use_skill(player, &skill, distant), Err(SkillError::OutOfRange));
+  }
+
+  #[test]
+  fn monsters_chase_and_attack_nearby_players() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+
+    let mut spawn = spider();
+    spawn.area_end = spawn.area_start;
+    spawn.count = 1;
+
+    let monster = world.spawn_monsters(&spawn)[0];
+    let player = world.add_player(1, Position::new(52, 50)).unwrap();
+
+    let attacked = (0..20).any(|_| {
+      clock.advance(Duration::from_secs(1));
+      world.update();
+      world.drain_events().into_iter().any(|event| match event {
+        WorldEvent::MonsterAttack {
+          monster: m, target, ..
+        } => m == monster && target == player,
+        _ => false,
+      })
+    });
+    assert!(attacked);
+  }
+
+  #[test]
+  fn monsters_ignore_players_within_safe_zones() {
+    let mut terrain = Terrain::new(0);
+    terrain.set_attributes(Position::new(52, 50), TerrainAttribute::SafeZone);
+
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(terrain, clock.clone());
+
+    let mut spawn = spider();
+    spawn.area_end = spawn.area_start;
+    spawn.count = 1;
+
+    world.spawn_monsters(&spawn);
+    world.add_player(1, Position::new(52, 50)).unwrap();
+
+    let attacked = (0..20).any(|_| {
+      clock.advance(Duration::from_secs(1));
+      world.update();
+      world.drain_events().into_iter().any(|event| match event {
+        WorldEvent::MonsterAttack { .. } => true,
+        _ => false,
+      })
+    });
+    assert!(!attacked);
+  }
+
+  #[test]
+  fn players_die_and_respawn_in_town() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+
+    let mut spawn = spider();
+    spawn.area_end = spawn.area_start;
+    spawn.count = 1;
+
+    let monster = world.spawn_monsters(&spawn)[0];
+    let player = world.add_player(1, Position::new(51, 50)).unwrap();
+    world.set_health(player, 5);
+
+    let died = (0..60).any(|_| {
+      clock.advance(Duration::from_secs(1));
+      world.update();
+      world.drain_events().into_iter().any(|event| match event {
+        WorldEvent::PlayerDied { player: p, killer } => p == player && killer == monster,
+        _ => false,
+      })
+    });
+    assert!(died);
+    assert!(world.is_dead(player));
+    assert_eq!(world.health(player).unwrap().current, 0);
+
+    clock.advance(Duration::from_secs(PLAYER_RESPAWN_DELAY_SECS));
+    world.update();
+    let position = world
+      .drain_events()
+      .into_iter()
+      .filter_map(|event| match event {
+        WorldEvent::PlayerRespawned { player: p, position } if p == player => Some(position),
+        _ => None,
+      })
+      .next()
+      .expect("player respawn");
+
+    // Lorencia's town spans (133, 118) to (151, 135)
+    assert!(position.x >= 133 && position.x <= 151);
+    assert!(position.y >= 118 && position.y <= 135);
+    assert_eq!(world.location(player).unwrap().position, position);
+    assert!(!world.is_dead(player));
+    assert_eq!(world.health(player).unwrap().current, 5);
+  }
+
+  #[test]
+  fn players_regenerate_and_recover_over_time() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+    let player = world.add_player(1, Position::new(100, 100)).unwrap();
+    world.set_health(player, 100);
+    world.set_mana_and_ability(player, 100, 100);
+    world.world.write::<Health>().get_mut(player).unwrap().current = 10;
+    world.world.write::<Mana>().get_mut(player).unwrap().current = 10;
+
+    let recovered = |world: &mut GameWorld| {
+      world.drain_events().into_iter().any(|event| match event {
+        WorldEvent::PlayerRecovered { player: p } => p == player,
+        _ => false,
+      })
+    };
+
+    // Nothing is regenerated until the delay has elapsed
+    clock.advance(Duration::from_secs(1));
+    world.update();
+    assert!(!recovered(&mut world));
+    assert_eq!(world.health(player).unwrap().current, 10);
+
+    clock.advance(Duration::from_secs(REGENERATION_DELAY_SECS));
+    world.update();
+    assert!(recovered(&mut world));
+    assert_eq!(world.health(player).unwrap().current, 12);
+    assert_eq!(world.mana(player).unwrap().current, 14);
+    assert_eq!(world.ability(player).unwrap().current, 100);
+
+    // Potions restore their health over several steps
+    assert!(world.recover(player, 50, 0));
+    world.update();
+    assert!(recovered(&mut world));
+    assert_eq!(world.health(player).unwrap().current, 22);
+
+    for _ in 1..RECOVERY_STEPS {
+      clock.advance(Duration::from_millis(400));
+      world.update();
+    }
+    assert_eq!(world.health(player).unwrap().current, 62);
+    assert_eq!(world.mana(player).unwrap().current, 14);
+
+    world.world.write::<Dead>().insert(player, Dead { since: clock.now() });
+    assert!(!world.recover(player, 50, 0));
+  }
+
+  #[test]
+  fn players_respawn_at_nearest_safe_zone_without_town() {
+    let mut terrain = Terrain::new(1);
+    terrain.set_attributes(Position::new(20, 20), TerrainAttribute::SafeZone);
+    terrain.set_attributes(Position::new(40, 40), TerrainAttribute::SafeZone);
+
+    let mut random = Random::new();
+    let position = systems::player_respawn_position(&terrain, Position::new(35, 33), &mut random);
+    assert_eq!(position, Position::new(40, 40));
+
+    let position = systems::player_respawn_position(&terrain, Position::new(200, 200), &mut random);
+    assert_eq!(position, Position::new(200, 200));
+  }
+
+  #[test]
+  fn loot_is_owned_picked_up_and_expires() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+    let owner = world.add_player(1, Position::new(100, 100)).unwrap();
+    let other = world.add_player(2, Position::new(101, 100)).unwrap();
+
+    let entity = world.drop_loot(Loot::Money(100), Position::new(100, 101), Some(owner));
+    let id = world.ground_items().get(entity).unwrap().id;
+    world.update();
+
+    let mut observers = world
+      .drain_events()
+      .into_iter()
+      .filter_map(|event| match event {
+        WorldEvent::ItemsEntered { observer, items } => Some((observer, items)),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    observers.sort_by_key(|&(observer, _)| observer);
+    assert_eq!(observers, vec![(1, vec![entity]), (2, vec![entity])]);
+
+    fn store(loot: Loot) -> Result<u32, Loot> {
+      match loot {
+        Loot::Money(amount) => Ok(amount),
+        loot => Err(loot),
+      }
+    }
+
+    assert_eq!(world.pick_up(other, id, store), Err(PickupError::NotOwner));
+    assert_eq!(
+      world.pick_up(owner, id, |loot| Err::<(), _>(loot)),
+      Err(PickupError::Refused)
+    );
+    assert_eq!(world.pick_up(owner, id, store), Ok(100));
+    assert_eq!(world.pick_up(owner, id, store), Err(PickupError::NotFound));
+
+    world.update();
+    let left = world
+      .drain_events()
+      .into_iter()
+      .filter(|event| match event {
+        WorldEvent::ItemsLeft { items, .. } => *items == vec![id],
+        _ => false,
+      })
+      .count();
+    assert_eq!(left, 2);
+
+    // Others may pick up loot once ownership ends, until it expires
+    let entity = world.drop_loot(Loot::Money(50), Position::new(110, 100), Some(owner));
+    let id = world.ground_items().get(entity).unwrap().id;
+    assert_eq!(world.pick_up(other, id, store), Err(PickupError::OutOfRange));
+
+    clock.advance(Duration::from_secs(LOOT_OWNERSHIP_SECS));
+    world.update();
+    world.walk(other, Position::new(108, 100), Direction::East, Vec::new());
+    assert_eq!(
+      world.pick_up(other, id, |loot| Err::<(), _>(loot)),
+      Err(PickupError::Refused)
+    );
+
+    clock.advance(Duration::from_secs(LOOT_LIFETIME_SECS));
+    world.update();
+    assert!(world.ground_item(id).is_none());
+  }
+
+  #[test]
+  fn npcs_are_seen_and_talked_to() {
+    let mut world = GameWorld::new(Terrain::new(0));
+    let npc = world.add_npc(Arc::new(NpcDefinition {
+      id: 1,
+      class: 251,
+      name: "Hanzo the Blacksmith".into(),
+      kind: NpcKind::Shop,
+      map: 0,
+      position: Position::new(116, 141),
+      direction: Direction::South,
+    }));
+    let id = world.object_id(npc).unwrap();
+
+    let player = world.add_player(1, Position::new(110, 141)).unwrap();
+    world.update();
+    assert_eq!(world.observers(npc), vec![1]);
+    assert_eq!(world.talk(player, id).err(), Some(TalkError::OutOfRange));
+    assert_eq!(world.talk(player, id + 1).err(), Some(TalkError::NotFound));
+
+    world.walk(player, Position::new(112, 141), Direction::East, Vec::new());
+    assert_eq!(world.talk(player, id).unwrap().kind, NpcKind::Shop);
+
+    // NPCs cannot be fought
+    assert_eq!(world.attack(player, npc), None);
+  }
+
+  #[test]
+  fn update_advances_time() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+
+    clock.advance(Duration::from_millis(50));
+    world.update();
+
+    let time = world.time();
+    assert_eq!(time.tick, 1);
+    assert_eq!(time.delta, Duration::from_millis(50));
+  }
+
+  #[test]
+  fn movement_steps_once_per_delay() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+    let entity = world.add_player(1, Position::new(10, 10)).unwrap();
+
+    let path = vec![Position::new(11, 10), Position::new(12, 10)];
+    world.walk(entity, Position::new(10, 10), Direction::East, path);
+
+    clock.advance(Duration::from_millis(200));
+    world.update();
+    assert_eq!(world.location(entity).unwrap().position, Position::new(10, 10));
+
+    clock.advance(Duration::from_millis(200));
+    world.update();
+    assert_eq!(world.location(entity).unwrap().position, Position::new(11, 10));
+
+    clock.advance(Duration::from_millis(400));
+    world.update();
+    assert_eq!(world.location(entity).unwrap().position, Position::new(12, 10));
+    assert!(world.world.read::<Movement>().get(entity).is_none());
+  }
+
+  #[test]
+  fn paths_are_redirected_at_most_once_per_step() {
+    let clock = ManualClock::new();
+    let mut world = GameWorld::with_clock(Terrain::new(0), clock.clone());
+    let entity = world.add_player(1, Position::new(10, 10)).unwrap();
+
+    let path = vec![Position::new(11, 10), Position::new(12, 10)];
+    assert!(world.walk(entity, Position::new(10, 10), Direction::East, path.clone()));
+    assert!(!world.walk(entity, Position::new(10, 10), Direction::East, path.clone()));
+
+    clock.advance(Duration::from_millis(400));
+    world.update();
+    let current = world.location(entity).unwrap().position;
+    assert_eq!(current, Position::new(11, 10));
+    assert!(world.walk(entity, current, Direction::East, vec![Position::new(12, 10)]));
+    assert_eq!(world.destination(entity), Some(Position::new(12, 10)));
   }
 }
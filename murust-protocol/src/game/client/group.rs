@@ -5,15 +5,38 @@ use std::io;
 /// An aggregation of all possible client packets.
 #[derive(Debug)]
 pub enum Client {
+  ChatMessage(ChatMessage),
+  Whisper(Whisper),
   ClientTime(ClientTime),
+  CharacterAttack(CharacterAttack),
   CharacterAction(CharacterAction),
-  // ItemMove(ItemMove),
+  SkillAttack(SkillAttack),
+  AreaSkill(AreaSkill),
+  ItemPickup(ItemPickup),
+  ItemMove(ItemMove),
+  ItemUse(ItemUse),
+  NpcTalk(NpcTalk),
+  NpcClose,
+  ItemBuy(ItemBuy),
+  ItemSell(ItemSell),
+  ItemRepair(ItemRepair),
+  TradeRequest(TradeRequest),
+  TradeResponse(TradeResponse),
+  TradeMoney(TradeMoney),
+  TradeButton(TradeButton),
+  TradeCancel,
+  VaultMoney(VaultMoney),
+  VaultClose,
+  VaultPassword(VaultPassword),
+  ChaosMix(ChaosMix),
+  ChaosClose,
   CharacterMove(CharacterMove),
   AccountLoginRequest(AccountLoginRequest),
   CharacterListRequest,
   CharacterCreate(CharacterCreate),
   CharacterDelete(CharacterDelete),
   CharacterSelect(CharacterSelect),
+  CharacterStatIncrease(CharacterStatIncrease),
   Unknown,
 }
 
@@ -23,11 +46,35 @@ impl Client {
     // TODO: Box the largest packets to decrease total size?
     // TODO: Handle this boilerplate, subcodes should also be automatic
     match (packet.code(), packet.data()) {
+      (ChatMessage::CODE, _) => ChatMessage::from_packet(packet).map(Client::ChatMessage),
+      (Whisper::CODE, _) => Whisper::from_packet(packet).map(Client::Whisper),
       (ClientTime::CODE, &[0x00, _..]) => ClientTime::from_packet(packet).map(Client::ClientTime),
+      (CharacterAttack::CODE, _) => {
+        CharacterAttack::from_packet(packet).map(Client::CharacterAttack)
+      },
       (CharacterAction::CODE, _) => {
         CharacterAction::from_packet(packet).map(Client::CharacterAction)
       },
-      //(ItemMove::CODE, _) => ItemMove::from_packet(packet).map(Client::ItemMove),
+      (SkillAttack::CODE, _) => SkillAttack::from_packet(packet).map(Client::SkillAttack),
+      (AreaSkill::CODE, _) => AreaSkill::from_packet(packet).map(Client::AreaSkill),
+      (ItemPickup::CODE, _) => ItemPickup::from_packet(packet).map(Client::ItemPickup),
+      (ItemMove::CODE, _) => ItemMove::from_packet(packet).map(Client::ItemMove),
+      (ItemUse::CODE, _) => ItemUse::from_packet(packet).map(Client::ItemUse),
+      (NpcTalk::CODE, _) => NpcTalk::from_packet(packet).map(Client::NpcTalk),
+      (NpcClose::CODE, _) => NpcClose::from_packet(packet).map(|_| Client::NpcClose),
+      (ItemBuy::CODE, _) => ItemBuy::from_packet(packet).map(Client::ItemBuy),
+      (ItemSell::CODE, _) => ItemSell::from_packet(packet).map(Client::ItemSell),
+      (ItemRepair::CODE, _) => ItemRepair::from_packet(packet).map(Client::ItemRepair),
+      (TradeRequest::CODE, _) => TradeRequest::from_packet(packet).map(Client::TradeRequest),
+      (TradeResponse::CODE, _) => TradeResponse::from_packet(packet).map(Client::TradeResponse),
+      (TradeMoney::CODE, _) => TradeMoney::from_packet(packet).map(Client::TradeMoney),
+      (TradeButton::CODE, _) => TradeButton::from_packet(packet).map(Client::TradeButton),
+      (TradeCancel::CODE, _) => TradeCancel::from_packet(packet).map(|_| Client::TradeCancel),
+      (VaultMoney::CODE, _) => VaultMoney::from_packet(packet).map(Client::VaultMoney),
+      (VaultClose::CODE, _) => VaultClose::from_packet(packet).map(|_| Client::VaultClose),
+      (VaultPassword::CODE, _) => VaultPassword::from_packet(packet).map(Client::VaultPassword),
+      (ChaosMix::CODE, _) => ChaosMix::from_packet(packet).map(Client::ChaosMix),
+      (ChaosClose::CODE, _) => ChaosClose::from_packet(packet).map(|_| Client::ChaosClose),
       (CharacterMove::CODE, _) => CharacterMove::from_packet(packet).map(Client::CharacterMove),
       (AccountLoginRequest::CODE, &[0x01, _..]) => {
         AccountLoginRequest::from_packet(packet).map(Client::AccountLoginRequest)
@@ -44,6 +91,9 @@ impl Client {
       (CharacterSelect::CODE, &[0x03, _..]) => {
         CharacterSelect::from_packet(packet).map(Client::CharacterSelect)
       },
+      (CharacterStatIncrease::CODE, &[0x06, _..]) => {
+        CharacterStatIncrease::from_packet(packet).map(Client::CharacterStatIncrease)
+      },
       _ => Ok(Client::Unknown),
     }
   }
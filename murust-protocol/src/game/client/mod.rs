@@ -4,13 +4,50 @@ pub use self::group::Client;
 use super::{Serial, Version, util::deserialize_class};
 use game::visitors::CharacterMoveVisitor;
 use game::{models::ItemInfo, util::StringFixedCredentials};
-use muonline_packet_serialize::{IntegerLE, StringFixed};
-use murust_data_model::types::{Class, Direction, Position};
+use muonline_packet_serialize::{IntegerBE, IntegerLE, StringFixed};
+use murust_data_model::types::{CharacterStat, Class, Direction, Position};
 use serde::{Deserialize, Deserializer};
 use typenum;
 
 mod group;
 
+/// `C1:00` - A chat message sent by a character.
+///
+/// Messages prefixed with `~` are sent to the character's party, and those
+/// prefixed with `@` to its guild.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// name | `CHAR(10)` | The sending character's name. | -
+/// message | `CHAR(60)` | The message's content. | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "00")]
+pub struct ChatMessage {
+  #[serde(with = "StringFixed::<typenum::U10>")]
+  pub name: String,
+  #[serde(with = "StringFixed::<typenum::U60>")]
+  pub message: String,
+}
+
+/// `C1:02` - A private message whispered to a character.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// name | `CHAR(10)` | The receiving character's name. | -
+/// message | `CHAR(60)` | The message's content. | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "02")]
+pub struct Whisper {
+  #[serde(with = "StringFixed::<typenum::U10>")]
+  pub name: String,
+  #[serde(with = "StringFixed::<typenum::U60>")]
+  pub message: String,
+}
+
 /// `C1:0E:00` - Local client timing values.
 ///
 /// This is sent by default every 20th second.
@@ -33,6 +70,24 @@ pub struct ClientTime {
   pub magic_speed: u16,
 }
 
+/// `C1:11` - Describes a character's melee attack on a target.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// target | `U16` | The target's object ID. | BE
+/// animation | `U8` | The attack animation performed. | -
+/// direction | `U8` | The character's direction. | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "11")]
+pub struct CharacterAttack {
+  #[serde(with = "IntegerBE")]
+  pub target: u16,
+  pub animation: u8,
+  pub direction: Direction,
+}
+
 #[derive(Deserialize, MuPacket, Debug)]
 #[packet(kind = "C1", code = "18")]
 pub struct CharacterAction {
@@ -225,7 +280,55 @@ pub enum ActionType {
 
 primitive_serialize!(ActionType, u8);
 
-//
+/// `C1:19` - Describes a character's skill used on a target.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// skill | `U8` | The skill's ID. | -
+/// target | `U16` | The target's object ID. | BE
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "19")]
+pub struct SkillAttack {
+  pub skill: u8,
+  #[serde(with = "IntegerBE")]
+  pub target: u16,
+}
+
+/// `C1:1E` - Describes a character's skill used on an area.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// skill | `U8` | The skill's ID. | -
+/// x | `U8` | The X coordinate of the area's center. | -
+/// y | `U8` | The Y coordinate of the area's center. | -
+/// rotation | `U8` | The character's rotation. | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "1E")]
+pub struct AreaSkill {
+  pub skill: u8,
+  pub x: u8,
+  pub y: u8,
+  pub rotation: u8,
+}
+
+/// `C1:22` - Request for picking up a ground item.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// item | `U16` | The ground item's ID. | BE
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "22")]
+pub struct ItemPickup {
+  #[serde(with = "IntegerBE")]
+  pub item: u16,
+}
+
 /// `C1:24` - Describing the relocation of an inventory item.
 ///
 /// ## Layout
@@ -239,76 +342,335 @@ primitive_serialize!(ActionType, u8);
 /// slot | `U8` | The slot the item is moved to. | -
 ///
 /// Value | Type
-/// `0x00` | Inventory
+/// ----- | ----
+/// `0x00` | Inventory (including equipment and personal shop)
 /// `0x01` | Trade
 /// `0x02` | Warehouse
 /// `0x03` | Chaos box
 /// `0x05` | Dark trainer box
-// #[derive(MuPacket, Debug)]
-// #[packet(kind = "C1", code = "24")]
-// pub struct ItemMove {
-// pub source: (StorageType, u8),
-// pub target: (StorageType, u8),
-// TODO: What to do with diiis one?
-// pub item_info: ItemInfo,
-// }
-//
-// #[derive(Eq, PartialEq, Debug)]
-// pub enum StorageType {
-// Equipment,
-// Inventory,
-// PersonalShop,
-// Trade,
-// Warehouse,
-// ChaosBox,
-// DarkTrainerBox,
-// }
-//
-// impl<'de> Deserialize<'de> for ItemMove {
-// fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-// #[derive(Deserialize)]
-// struct ItemMovePacket {
-// source_storage: u8,
-// source_slot: u8,
-// item_info: ItemInfo,
-// target_storage: u8,
-// target_slot: u8,
-// }
-// fn parse_slot_type(storage: u8, slot: u8) -> Option<(StorageType, u8)> {
-// use std::ops::Range;
-// use murust_data_model::types::ItemSlot;
-// let result = if storage == 0 {
-// const EQ_RANGE: Range<usize> = 0..ItemSlot::SIZE;
-// const IV_RANGE: Range<usize> = ItemSlot::SIZE..76;
-// const PS_RANGE: Range<usize> = 76..108;
-// match slot {
-// EQ_RANGE => (StorageType::Equipment, slot.saturating_sub(EQ_RANGE.end as u8)),
-// IV_RANGE => (StorageType::Inventory, slot.saturating_sub(IV_RANGE.end as u8)),
-// PS_RANGE => (StorageType::PersonalShop, slot.saturating_sub(PS_RANGE.end as u8)),
-// _ => return None,
-// }
-// } else {
-// let storage = match storage {
-// 1 => StorageType::Trade,
-// 2 => StorageType::Warehouse,
-// 3 => StorageType::ChaosBox,
-// 5 => StorageType::DarkTrainerBox,
-// _ => return None,
-// };
-// (storage, slot)
-// };
-// Some(result)
-// }
-//
-// let item_move = ItemMovePacket::deserialize(deserializer)?;
-// Ok(ItemMove {
-// source: parse_slot_type(item_move.source_storage, item_move.source_slot),
-// target: parse_slot_type(item_move.target_storage, item_move.target_slot),
-// item_info: item_move.item_info,
-// })
-// }
-// }
-//
+#[derive(MuPacket, Debug)]
+#[packet(kind = "C1", code = "24")]
+pub struct ItemMove {
+  pub source: (StorageType, u8),
+  pub target: (StorageType, u8),
+  // TODO: What to do with diiis one?
+  pub item_info: ItemInfo,
+}
+
+/// A collection of storages an item can be moved between.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StorageType {
+  Equipment,
+  Inventory,
+  PersonalShop,
+  Trade,
+  Warehouse,
+  ChaosBox,
+  DarkTrainerBox,
+}
+
+impl StorageType {
+  /// The number of inventory slots, following the equipment.
+  const INVENTORY_SIZE: u8 = 64;
+
+  /// The number of personal shop slots, following the inventory.
+  const PERSONAL_SHOP_SIZE: u8 = 32;
+
+  /// Parses a storage and slot, relative to the storage's first slot.
+  fn parse(storage: u8, slot: u8) -> Option<(Self, u8)> {
+    use murust_data_model::types::ItemSlot;
+
+    let inventory_start = ItemSlot::SIZE as u8;
+    let shop_start = inventory_start + Self::INVENTORY_SIZE;
+
+    let result = match storage {
+      0 if slot < inventory_start => (StorageType::Equipment, slot),
+      0 if slot < shop_start => (StorageType::Inventory, slot - inventory_start),
+      0 if slot < shop_start + Self::PERSONAL_SHOP_SIZE => {
+        (StorageType::PersonalShop, slot - shop_start)
+      },
+      1 => (StorageType::Trade, slot),
+      2 => (StorageType::Warehouse, slot),
+      3 => (StorageType::ChaosBox, slot),
+      5 => (StorageType::DarkTrainerBox, slot),
+      _ => return None,
+    };
+    Some(result)
+  }
+}
+
+impl<'de> Deserialize<'de> for ItemMove {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    use serde::de::Error;
+
+    #[derive(Deserialize)]
+    struct ItemMovePacket {
+      source_storage: u8,
+      source_slot: u8,
+      item_info: ItemInfo,
+      target_storage: u8,
+      target_slot: u8,
+    }
+
+    let item_move = ItemMovePacket::deserialize(deserializer)?;
+    let parse = |storage, slot| {
+      StorageType::parse(storage, slot)
+        .ok_or_else(|| D::Error::custom(format!("invalid item storage {}:{}", storage, slot)))
+    };
+
+    Ok(ItemMove {
+      source: parse(item_move.source_storage, item_move.source_slot)?,
+      target: parse(item_move.target_storage, item_move.target_slot)?,
+      item_info: item_move.item_info,
+    })
+  }
+}
+
+/// `C1:26` - Request for using an inventory item, e.g applying a jewel onto
+/// another item.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// source | `U8` | The used item's inventory slot (including equipment). | -
+/// target | `U8` | The target item's inventory slot (including equipment). | -
+/// type | `U8` | The kind of use. | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "26")]
+pub struct ItemUse {
+  pub source: u8,
+  pub target: u8,
+  pub kind: u8,
+}
+
+/// `C1:30` - Request for talking to an NPC.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// npc | `U16` | The NPC's object ID. | BE
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "30")]
+pub struct NpcTalk {
+  #[serde(with = "IntegerBE")]
+  pub npc: u16,
+}
+
+/// `C1:31` - Notifies the server that an NPC window has been closed.
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "31")]
+pub struct NpcClose;
+
+/// `C1:32` - Request for buying an item from an NPC shop.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The item's slot in the shop. | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "32")]
+pub struct ItemBuy {
+  pub slot: u8,
+}
+
+/// `C1:33` - Request for selling an item to an NPC shop.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The item's inventory slot (including equipment). | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "33")]
+pub struct ItemSell {
+  pub slot: u8,
+}
+
+/// `C1:34` - Request for repairing an item, or all items.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The item's inventory slot, or `0xFF` for all items. | -
+/// type | `U8` | Where the items are repaired. | -
+///
+/// Value | Repaired at
+/// ----- | -----------
+/// `0x00` | An NPC
+/// `0x01` | The inventory
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "34")]
+pub struct ItemRepair {
+  pub slot: u8,
+  pub kind: u8,
+}
+
+impl ItemRepair {
+  /// The slot used to repair all items.
+  pub const ALL: u8 = 0xFF;
+
+  /// Returns whether the items are repaired at an NPC.
+  pub fn at_npc(&self) -> bool { self.kind == 0 }
+}
+
+/// `C1:36` - Request for trading with another player.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// player | `U16` | The player's object ID. | BE
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "36")]
+pub struct TradeRequest {
+  #[serde(with = "IntegerBE")]
+  pub player: u16,
+}
+
+/// `C1:37` - Response to a trade request from another player.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// response | `U8` | Whether the trade was accepted or not. | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "37")]
+pub struct TradeResponse {
+  pub response: u8,
+}
+
+impl TradeResponse {
+  /// Returns whether the trade was accepted.
+  pub fn is_accepted(&self) -> bool { self.response != 0 }
+}
+
+/// `C1:3A` - Request for offering zen in a trade.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// money | `U32` | The amount of zen offered. | LE
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "3A")]
+pub struct TradeMoney {
+  #[serde(with = "IntegerLE")]
+  pub money: u32,
+}
+
+/// `C1:3C` - Notifies the server that the trade button has been toggled.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// state | `U8` | Whether the current offers are confirmed or not. | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "3C")]
+pub struct TradeButton {
+  pub state: u8,
+}
+
+impl TradeButton {
+  /// Returns whether the current offers are confirmed.
+  pub fn is_confirmed(&self) -> bool { self.state == 1 }
+}
+
+/// `C1:3D` - Notifies the server that the trade window has been closed.
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "3D")]
+pub struct TradeCancel;
+
+/// `C1:81` - Request for depositing or withdrawing zen from a vault.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// type | `U8` | The direction of the transfer. | -
+/// money | `U32` | The amount of zen. | LE
+///
+/// Value | Transfer
+/// ----- | --------
+/// `0x00` | Deposit
+/// `0x01` | Withdrawal
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "81")]
+pub struct VaultMoney {
+  pub kind: u8,
+  #[serde(with = "IntegerLE")]
+  pub money: u32,
+}
+
+impl VaultMoney {
+  /// Returns whether the zen is deposited into the vault.
+  pub fn is_deposit(&self) -> bool { self.kind == 0 }
+}
+
+/// `C1:82` - Notifies the server that the vault window has been closed.
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "82")]
+pub struct VaultClose;
+
+/// `C1:83` - Request for unlocking a vault, or changing its password.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// type | `U8` | The requested action. | -
+/// password | `U16` | The vault's PIN. | LE
+/// code | `CHAR(10)` | The account's security code. | -
+///
+/// Value | Action
+/// ----- | ------
+/// `0x00` | Unlock the vault
+/// `0x01` | Set a new password
+/// `0x02` | Remove the password
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "83")]
+pub struct VaultPassword {
+  pub kind: u8,
+  #[serde(with = "IntegerLE")]
+  pub password: u16,
+  #[serde(with = "StringFixed::<typenum::U10>")]
+  pub security_code: String,
+}
+
+impl VaultPassword {
+  /// The action unlocking the vault.
+  pub const UNLOCK: u8 = 0x00;
+
+  /// The action setting a new password.
+  pub const SET: u8 = 0x01;
+
+  /// The action removing the password.
+  pub const REMOVE: u8 = 0x02;
+}
+
+/// `C1:86` - Request for mixing the items within the chaos box.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// type | `U8` | The kind of mix selected by the client. | -
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "86")]
+pub struct ChaosMix {
+  pub kind: u8,
+}
+
+/// `C1:87` - Notifies the server that the chaos box has been closed.
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "87")]
+pub struct ChaosClose;
+
 /// `C1:D4` - Describes a character's movement.
 ///
 /// ## Layout
@@ -429,3 +791,24 @@ pub struct CharacterSelect {
   #[serde(with = "StringFixed::<typenum::U10>")]
   pub name: String,
 }
+
+/// `C1:F3:06` - Request for spending a level-up point on a stat.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// stat | `U8` | The stat to increase. | -
+///
+/// Value | Stat
+/// ------ | -------
+/// `0x00` | Strength
+/// `0x01` | Agility
+/// `0x02` | Vitality
+/// `0x03` | Energy
+/// `0x04` | Command
+#[derive(Deserialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "F3", subcode = "06")]
+pub struct CharacterStatIncrease {
+  pub stat: CharacterStat,
+}
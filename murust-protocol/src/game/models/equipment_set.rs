@@ -5,7 +5,7 @@ use murust_data_model::types::{ItemCode, ItemGroup, ItemSlot};
 /// The size required by the protocol.
 const CHAR_SET_SIZE: usize = 17;
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Copy, Clone)]
 pub struct CharacterEquipmentSet([u8; CHAR_SET_SIZE]);
 
 impl CharacterEquipmentSet {
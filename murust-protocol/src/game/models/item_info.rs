@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 use murust_data_model::entities::Item;
-use murust_data_model::types::ItemCode;
+use murust_data_model::types::{ItemCode, ItemGroup};
 
 /// The size required by the protocol.
 const ITEM_INFO_SIZE: usize = 7;
@@ -16,9 +16,30 @@ impl ItemInfo {
       prot.set_code(item.code);
       prot.set_level(item.level);
       prot.set_durability(item.durability);
+      prot.set_option1(item.skill as u8);
+      prot.set_option2(item.luck as u8);
+      prot.set_option3(item.option & 0b11);
+      prot.set_option3ext((item.option >> 2) & 1);
+      prot.set_noption(item.excellent.bits());
+      prot.set_tier_option(item.tier());
     }
     ItemInfo(data)
   }
+
+  /// Constructs the info of an empty slot.
+  pub fn empty() -> Self { ItemInfo([0xFF; ITEM_INFO_SIZE]) }
+
+  /// Constructs the info of zen lying on the ground.
+  ///
+  /// The amount is spread across the level, durability and option bytes.
+  pub fn money(amount: u32) -> Self {
+    let mut data = [0u8; ITEM_INFO_SIZE];
+    ItemInfoView(&mut data).set_code(ItemCode::new(ItemGroup::Potion, 15));
+    data[1] = (amount >> 16) as u8;
+    data[2] = (amount >> 8) as u8;
+    data[4] = amount as u8;
+    ItemInfo(data)
+  }
 }
 
 bitfield! {
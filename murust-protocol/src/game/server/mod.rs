@@ -3,12 +3,64 @@
 use super::{Version, util::serialize_class, VERSION};
 use game::models::{CharacterEquipmentSet, Color, ItemInfo};
 use muonline_packet_serialize::{IntegerBE, IntegerLE, StringFixed, VectorLengthLE};
-use murust_data_model::entities::Character;
-use murust_data_model::types::{Class, CtlCode, Direction, GuildRole, HeroStatus, ItemSlot};
+use murust_data_model::entities::{Character, CharacterAttributes, Item, Shop, Vault};
+use murust_data_model::types::{CharacterStat, Class, CtlCode, Direction, GuildRole, HeroStatus,
+                               ItemSlot, ItemStorage, Position};
 use serde::{Serialize, Serializer};
 use std::iter::IntoIterator;
 use typenum;
 
+/// `C1:00` - A chat message sent by a character.
+///
+/// Party and guild messages keep their `~` and `@` prefixes, which the client
+/// uses for coloring them.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// name | `CHAR(10)` | The sending character's name. | -
+/// message | `CHAR(60)` | The message's content. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "00")]
+pub struct ChatMessage {
+  #[serde(with = "StringFixed::<typenum::U10>")]
+  pub name: String,
+  #[serde(with = "StringFixed::<typenum::U60>")]
+  pub message: String,
+}
+
+/// `C1:02` - A private message whispered by a character.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// name | `CHAR(10)` | The whispering character's name. | -
+/// message | `CHAR(60)` | The message's content. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "02")]
+pub struct WhisperMessage {
+  #[serde(with = "StringFixed::<typenum::U10>")]
+  pub name: String,
+  #[serde(with = "StringFixed::<typenum::U60>")]
+  pub message: String,
+}
+
+/// `C1:0C` - Informs a character that its whisper could not be delivered,
+/// since the receiving character is not online.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// result | `U8` | Always zero. | -
+#[derive(Serialize, MuPacket, Debug, Default)]
+#[packet(kind = "C1", code = "0C")]
+pub struct WhisperFailure {
+  pub result: u8,
+}
+
 /// `C1:0D` — Multicast text message sent from the server.
 ///
 /// ## Layout
@@ -72,101 +124,984 @@ impl Serialize for Message {
       padding2: u8,
     }
 
-    let mut data = MessageData::default();
-    match self {
-      // TODO: Refactor this somehowzzz
-      &Message::Alert(ref text) => {
-        data.kind = 0;
-        data.message = text.clone();
-      },
-      &Message::Notice(ref text) => {
-        data.kind = 1;
-        data.message = text.clone();
-      },
-      &Message::Guild(ref text) => {
-        data.kind = 2;
-        data.message = text.clone();
-      },
-      &Message::Custom {
-        kind,
-        color,
-        count,
-        delay,
-        speed,
-        ref message,
-      } => {
-        data.kind = kind + 10;
-        data.color = color;
-        data.count = count;
-        data.delay = delay;
-        data.speed = speed;
-        data.message = message.clone();
-      },
-    }
+    let mut data = MessageData::default();
+    match self {
+      // TODO: Refactor this somehowzzz
+      &Message::Alert(ref text) => {
+        data.kind = 0;
+        data.message = text.clone();
+      },
+      &Message::Notice(ref text) => {
+        data.kind = 1;
+        data.message = text.clone();
+      },
+      &Message::Guild(ref text) => {
+        data.kind = 2;
+        data.message = text.clone();
+      },
+      &Message::Custom {
+        kind,
+        color,
+        count,
+        delay,
+        speed,
+        ref message,
+      } => {
+        data.kind = kind + 10;
+        data.color = color;
+        data.count = count;
+        data.delay = delay;
+        data.speed = speed;
+        data.message = message.clone();
+      },
+    }
+
+    data.serialize(serializer)
+  }
+}
+
+/// `C1:11` - Describes damage dealt to an object.
+///
+/// A miss is described as a normal hit without any damage.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// id | `U16` | The damaged object's ID. | BE
+/// damage | `U16` | The damage dealt. | BE
+/// kind | `U8` | The kind of damage dealt. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "11")]
+pub struct ObjectHit {
+  #[serde(with = "IntegerBE")]
+  pub id: u16,
+  #[serde(with = "IntegerBE")]
+  pub damage: u16,
+  pub kind: u8,
+}
+
+impl ObjectHit {
+  /// The kind of a normal hit.
+  pub const NORMAL: u8 = 0x00;
+  /// The kind of an excellent hit.
+  pub const EXCELLENT: u8 = 0x02;
+  /// The kind of a critical hit.
+  pub const CRITICAL: u8 = 0x03;
+}
+
+/// `C1:12` - Describes players that have come into view.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// count | `U8` | The number of players in this packet. | -
+/// players | `Player[]` | An array of players. | -
+///
+/// ### Layout - Player
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// id | `U16` | The player's object ID. | BE
+/// x | `U8` | The player's X coordinate. | -
+/// y | `U8` | The player's Y coordinate. | -
+/// class | `U8` | The character's class. | -
+/// EQ | `U8(17)` | The character's equipment. | -
+/// padding | `U8(2)` | Ignored by the client. | -
+/// skill state | `U32` | The character's active effects. | -
+/// name | `CHAR(10)` | The character's name. | -
+/// target x | `U8` | The X coordinate the player is moving towards. | -
+/// target y | `U8` | The Y coordinate the player is moving towards. | -
+/// direction | `U4` | The player's direction (upper bits). | -
+/// PK level | `U4` | The player's hero status (lower bits). | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "12")]
+pub struct ViewportPlayers(#[serde(with = "VectorLengthLE::<u8>")] Vec<ViewportPlayer>);
+
+impl ViewportPlayers {
+  /// The maximum number of players that fit within a single packet.
+  pub const MAX_PLAYERS: usize = 5;
+
+  /// Constructs a new viewport packet from a set of players.
+  pub fn new<I: IntoIterator<Item = ViewportPlayer>>(players: I) -> Self {
+    ViewportPlayers(players.into_iter().collect())
+  }
+}
+
+/// A player entry of a viewport.
+#[derive(Serialize, Debug, Clone)]
+pub struct ViewportPlayer {
+  #[serde(with = "IntegerBE")]
+  player_id: u16, // 0
+  position_x: u8, // 2
+  position_y: u8, // 3
+  #[serde(serialize_with = "serialize_class")]
+  class: Class, // 4
+  equipment: CharacterEquipmentSet, // 5
+  padding: [u8; 2], // 22
+  skill_state: u32, // 24
+  #[serde(with = "StringFixed::<typenum::U10>")]
+  name: String, // 28
+  tx: u8,         // 38
+  ty: u8,         // 39
+  direction_pk_level: u8, // 40
+}
+
+impl ViewportPlayer {
+  /// Constructs a new viewport entry for a character, standing at its position.
+  pub fn new(id: u16, character: &Character) -> Self {
+    ViewportPlayer {
+      player_id: id,
+      position_x: character.position.x,
+      position_y: character.position.y,
+      class: character.class,
+      equipment: CharacterEquipmentSet::new(&character.equipment),
+      padding: [0; 2],
+      skill_state: 0,
+      name: character.name.clone(),
+      tx: character.position.x,
+      ty: character.position.y,
+      direction_pk_level: HeroStatus::default() as u8,
+    }
+  }
+
+  /// Returns the entry with an updated location.
+  pub fn with_location(self, position: Position, target: Position, direction: Direction) -> Self {
+    ViewportPlayer {
+      position_x: position.x,
+      position_y: position.y,
+      tx: target.x,
+      ty: target.y,
+      direction_pk_level: ((direction as u8) << 4) | (self.direction_pk_level & 0x0F),
+      ..self
+    }
+  }
+}
+
+/// `C1:13` - Describes monsters that have come into view.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// count | `U8` | The number of monsters in this packet. | -
+/// monsters | `Monster[]` | An array of monsters. | -
+///
+/// ### Layout - Monster
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// id | `U16` | The monster's object ID. | BE
+/// class | `U16` | The monster's class. | BE
+/// skill state | `U32` | The monster's active effects. | -
+/// x | `U8` | The monster's X coordinate. | -
+/// y | `U8` | The monster's Y coordinate. | -
+/// target x | `U8` | The X coordinate the monster is moving towards. | -
+/// target y | `U8` | The Y coordinate the monster is moving towards. | -
+/// direction | `U4` | The monster's direction (upper bits). | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "13")]
+pub struct ViewportMonsters(#[serde(with = "VectorLengthLE::<u8>")] Vec<ViewportMonster>);
+
+impl ViewportMonsters {
+  /// The maximum number of monsters that fit within a single packet.
+  pub const MAX_MONSTERS: usize = 15;
+
+  /// Constructs a new viewport packet from a set of monsters.
+  pub fn new<I: IntoIterator<Item = ViewportMonster>>(monsters: I) -> Self {
+    ViewportMonsters(monsters.into_iter().collect())
+  }
+}
+
+/// A monster entry of a viewport.
+#[derive(Serialize, Debug, Clone)]
+pub struct ViewportMonster {
+  #[serde(with = "IntegerBE")]
+  id: u16,
+  #[serde(with = "IntegerBE")]
+  class: u16,
+  skill_state: u32,
+  x: u8,
+  y: u8,
+  tx: u8,
+  ty: u8,
+  direction: u8,
+}
+
+impl ViewportMonster {
+  /// Constructs a new viewport entry for a monster.
+  pub fn new(
+    id: u16,
+    class: u16,
+    position: Position,
+    target: Position,
+    direction: Direction,
+  ) -> Self {
+    ViewportMonster {
+      id,
+      class,
+      skill_state: 0,
+      x: position.x,
+      y: position.y,
+      tx: target.x,
+      ty: target.y,
+      direction: (direction as u8) << 4,
+    }
+  }
+}
+
+/// `C1:14` - Describes objects that have left view.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// count | `U8` | The number of objects in this packet. | -
+/// ids | `U16[]` | An array of object IDs. | BE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "14")]
+pub struct ViewportDestroy(#[serde(with = "VectorLengthLE::<u8>")] Vec<ViewportObjectId>);
+
+impl ViewportDestroy {
+  /// The maximum number of objects that fit within a single packet.
+  pub const MAX_OBJECTS: usize = 120;
+
+  /// Constructs a new destroy packet from a set of object IDs.
+  pub fn new<I: IntoIterator<Item = u16>>(ids: I) -> Self {
+    ViewportDestroy(ids.into_iter().map(ViewportObjectId).collect())
+  }
+}
+
+/// An object ID entry of a viewport.
+#[derive(Serialize, Debug)]
+struct ViewportObjectId(#[serde(with = "IntegerBE")] u16);
+
+/// `C1:15` - Instantly sets the position of an object.
+///
+/// This is also used to correct a client's position when it has been
+/// desynchronized from the server.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// id | `U16` | The object's ID. | BE
+/// x | `U8` | The object's X coordinate. | -
+/// y | `U8` | The object's Y coordinate. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "15")]
+pub struct ObjectPosition {
+  #[serde(with = "IntegerBE")]
+  pub id: u16,
+  pub x: u8,
+  pub y: u8,
+}
+
+impl ObjectPosition {
+  /// Constructs a new position packet.
+  pub fn new(id: u16, position: Position) -> Self {
+    ObjectPosition {
+      id,
+      x: position.x,
+      y: position.y,
+    }
+  }
+}
+
+/// `C1:16` - Describes the experience gained from killing an object.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// id | `U16` | The killed object's ID. | BE
+/// experience | `U16` | The experience gained. | BE
+/// damage | `U16` | The damage of the killing blow. | BE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "16")]
+pub struct ExperienceGain {
+  #[serde(with = "IntegerBE")]
+  pub id: u16,
+  #[serde(with = "IntegerBE")]
+  pub experience: u16,
+  #[serde(with = "IntegerBE")]
+  pub damage: u16,
+}
+
+/// `C1:17` - Describes the death of an object.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// id | `U16` | The killed object's ID. | BE
+/// skill | `U8` | The skill used for the killing blow. | -
+/// killer | `U16` | The killer's object ID. | BE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "17")]
+pub struct ObjectKilled {
+  #[serde(with = "IntegerBE")]
+  pub id: u16,
+  pub skill: u8,
+  #[serde(with = "IntegerBE")]
+  pub killer: u16,
+}
+
+/// `C1:18` - Describes an animation performed by an object.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// id | `U16` | The object's ID. | BE
+/// direction | `U8` | The object's direction. | -
+/// animation | `U8` | The animation performed. | -
+/// target | `U16` | The ID of the animation's target. | BE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "18")]
+pub struct ObjectAnimation {
+  #[serde(with = "IntegerBE")]
+  pub id: u16,
+  pub direction: Direction,
+  pub animation: u8,
+  #[serde(with = "IntegerBE")]
+  pub target: u16,
+}
+
+impl ObjectAnimation {
+  /// The animation of a monster's attack.
+  pub const MONSTER_ATTACK: u8 = 0x78;
+}
+
+/// `C1:19` - Describes a skill used by an object on a target.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// skill | `U8` | The skill's ID. | -
+/// caster | `U16` | The caster's object ID. | BE
+/// target | `U16` | The target's object ID. | BE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "19")]
+pub struct ObjectSkill {
+  pub skill: u8,
+  #[serde(with = "IntegerBE")]
+  pub caster: u16,
+  #[serde(with = "IntegerBE")]
+  pub target: u16,
+}
+
+/// `C1:1E` - Describes a skill used by an object on an area.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// skill | `U8` | The skill's ID. | -
+/// caster | `U16` | The caster's object ID. | BE
+/// x | `U8` | The X coordinate of the area's center. | -
+/// y | `U8` | The Y coordinate of the area's center. | -
+/// rotation | `U8` | The caster's rotation. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "1E")]
+pub struct ObjectAreaSkill {
+  pub skill: u8,
+  #[serde(with = "IntegerBE")]
+  pub caster: u16,
+  pub x: u8,
+  pub y: u8,
+  pub rotation: u8,
+}
+
+/// `C2:20` - Describes ground items that have come into view.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// count | `U8` | The number of items in this packet. | -
+/// items | `Item[]` | An array of ground items. | -
+///
+/// ### Layout - Item
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// id | `U16` | The ground item's ID. | BE
+/// x | `U8` | The item's X coordinate. | -
+/// y | `U8` | The item's Y coordinate. | -
+/// item | `Item` | The item's info. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C2", code = "20")]
+pub struct ViewportItems(#[serde(with = "VectorLengthLE::<u8>")] Vec<ViewportItem>);
+
+impl ViewportItems {
+  /// The maximum number of items that fit within a single packet.
+  pub const MAX_ITEMS: usize = 80;
+
+  /// Constructs a new viewport packet from a set of ground items.
+  pub fn new<I: IntoIterator<Item = ViewportItem>>(items: I) -> Self {
+    ViewportItems(items.into_iter().collect())
+  }
+}
+
+/// A ground item entry of a viewport.
+#[derive(Serialize, Debug, Clone)]
+pub struct ViewportItem {
+  #[serde(with = "IntegerBE")]
+  id: u16,
+  x: u8,
+  y: u8,
+  item_info: ItemInfo,
+}
+
+impl ViewportItem {
+  /// Constructs a new viewport entry for a ground item.
+  pub fn new(id: u16, position: Position, item_info: ItemInfo) -> Self {
+    ViewportItem {
+      id,
+      x: position.x,
+      y: position.y,
+      item_info,
+    }
+  }
+}
+
+/// `C2:21` - Describes ground items that have left view.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// count | `U8` | The number of items in this packet. | -
+/// ids | `U16[]` | An array of ground item IDs. | BE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C2", code = "21")]
+pub struct ViewportItemDestroy(#[serde(with = "VectorLengthLE::<u8>")] Vec<ViewportObjectId>);
+
+impl ViewportItemDestroy {
+  /// The maximum number of items that fit within a single packet.
+  pub const MAX_ITEMS: usize = 120;
+
+  /// Constructs a new destroy packet from a set of ground item IDs.
+  pub fn new<I: IntoIterator<Item = u16>>(ids: I) -> Self {
+    ViewportItemDestroy(ids.into_iter().map(ViewportObjectId).collect())
+  }
+}
+
+/// `C1:22` - Describes the result of an item pickup request.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The inventory slot, `0xFE` for zen or `0xFF` on failure. | -
+/// item/money | `Item`/`U32` | The item's info or the new amount of zen. | -/BE
+#[derive(MuPacket, Debug)]
+#[packet(kind = "C1", code = "22")]
+pub enum ItemPickupResult {
+  Failure,
+  Item { slot: u8, item_info: ItemInfo },
+  Money(u32),
+}
+
+impl Serialize for ItemPickupResult {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize, Debug)]
+    struct ItemPickupItem {
+      slot: u8,
+      item_info: ItemInfo,
+    }
+
+    #[derive(Serialize, Debug)]
+    struct ItemPickupMoney {
+      slot: u8,
+      #[serde(with = "IntegerBE")]
+      money: u32,
+    }
+
+    match self {
+      &ItemPickupResult::Failure => 0xFFu8.serialize(serializer),
+      &ItemPickupResult::Item { slot, item_info } => {
+        ItemPickupItem { slot, item_info }.serialize(serializer)
+      },
+      &ItemPickupResult::Money(money) => {
+        ItemPickupMoney { slot: 0xFE, money }.serialize(serializer)
+      },
+    }
+  }
+}
+
+/// `C1:24` - Describes the result of an item move request.
+#[derive(MuPacket, Debug)]
+#[packet(kind = "C1", code = "24")]
+pub enum ItemMoveResult {
+  Failure,
+  Success {
+    storage: u8,
+    slot: u8,
+    item_info: ItemInfo,
+  },
+}
+
+impl Serialize for ItemMoveResult {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize, Debug)]
+    struct ItemMoveSuccess {
+      storage: u8,
+      slot: u8,
+      item_info: ItemInfo,
+    }
+
+    match self {
+      &ItemMoveResult::Failure => 0xFFu8.serialize(serializer),
+      &ItemMoveResult::Success {
+        storage,
+        slot,
+        item_info,
+      } => ItemMoveSuccess {
+        storage,
+        slot,
+        item_info,
+      }.serialize(serializer),
+    }
+  }
+}
+
+/// `C1:26:FF` - Describes a character's current health.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// health | `U16` | The character's health. | BE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "26", subcode = "FF")]
+pub struct CharacterHealth {
+  #[serde(with = "IntegerBE")]
+  pub health: u16,
+}
+
+/// `C1:27:FF` - Describes a character's current mana and AG.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// mana | `U16` | The character's mana. | BE
+/// ability | `U16` | The character's AG. | BE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "27", subcode = "FF")]
+pub struct CharacterMana {
+  #[serde(with = "IntegerBE")]
+  pub mana: u16,
+  #[serde(with = "IntegerBE")]
+  pub ability: u16,
+}
+
+/// `C1:28` - Removes an item from the inventory.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The item's inventory slot. | -
+/// consumed | `U8` | Whether the item was consumed by using it. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "28")]
+pub struct ItemRemove {
+  pub slot: u8,
+  pub consumed: bool,
+}
+
+/// `C1:2A` - Describes the durability of an item.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The item's inventory slot. | -
+/// durability | `U8` | The item's durability. | -
+/// consumed | `U8` | Whether the durability was consumed by using the item. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "2A")]
+pub struct ItemDurability {
+  pub slot: u8,
+  pub durability: u8,
+  pub consumed: bool,
+}
+
+/// `C3:30` - Opens the window of an NPC a player has talked to.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// window | `U8` | The type of window. | -
+///
+/// Value | Window
+/// ----- | ------
+/// `0x00` | Shop
+/// `0x02` | Warehouse
+/// `0x03` | Chaos machine
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C3", code = "30")]
+pub struct NpcTalkResult {
+  pub window: NpcWindow,
+}
+
+/// A collection of windows opened by NPCs.
+#[repr(u8)]
+#[derive(Primitive, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NpcWindow {
+  Shop = 0x00,
+  Warehouse = 0x02,
+  ChaosMachine = 0x03,
+}
+
+primitive_serialize!(NpcWindow, u8);
+
+/// `C2:31` - Describes the wares of an NPC shop, or the contents of a vault or
+/// chaos box.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// type | `U8` | The type of list, `0x00` for shops and vaults, `0x03` for chaos boxes. | -
+/// count | `U8` | The number of items in the list. | -
+/// items | `Item[]` | An array of items. | -
+///
+/// ### Layout - Item
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The item's slot in the shop or vault. | -
+/// item | `Item` | The item's info. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C2", code = "31")]
+pub struct ShopItemList {
+  kind: u8,
+  #[serde(with = "VectorLengthLE::<u8>")]
+  items: Vec<CharacterInventoryEntry>,
+}
+
+impl ShopItemList {
+  /// Constructs a new list from a shop's wares.
+  pub fn new(shop: &Shop) -> Self { Self::from_storage(&shop.storage) }
+
+  /// Constructs a new list from a vault's contents.
+  pub fn vault(vault: &Vault) -> Self { Self::from_storage(&vault.storage) }
+
+  /// Constructs a new list from a chaos box's contents.
+  pub fn chaos_box(storage: &ItemStorage) -> Self {
+    ShopItemList {
+      kind: 3,
+      ..Self::from_storage(storage)
+    }
+  }
+
+  fn from_storage(storage: &ItemStorage) -> Self {
+    let items = storage
+      .into_iter()
+      .map(|(slot, item)| CharacterInventoryEntry {
+        slot,
+        item: ItemInfo::new(item),
+      })
+      .collect();
+    ShopItemList { kind: 0, items }
+  }
+}
+
+/// `C1:32` - Describes the result of an item purchase.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The inventory slot, or `0xFF` on failure. | -
+/// item | `Item` | The item's info. | -
+#[derive(MuPacket, Debug)]
+#[packet(kind = "C1", code = "32")]
+pub enum ItemBuyResult {
+  Failure,
+  Success { slot: u8, item_info: ItemInfo },
+}
+
+impl Serialize for ItemBuyResult {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize, Debug)]
+    struct ItemBuySuccess {
+      slot: u8,
+      item_info: ItemInfo,
+    }
+
+    match self {
+      &ItemBuyResult::Failure => 0xFFu8.serialize(serializer),
+      &ItemBuyResult::Success { slot, item_info } => {
+        ItemBuySuccess { slot, item_info }.serialize(serializer)
+      },
+    }
+  }
+}
+
+/// `C1:33` - Describes the result of an item sale.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// success | `U8` | Whether the item was sold or not. | -
+/// money | `U32` | The character's amount of zen. | LE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "33")]
+pub struct ItemSellResult {
+  pub success: bool,
+  #[serde(with = "IntegerLE")]
+  pub money: u32,
+}
+
+/// `C1:34` - Describes the result of an item repair.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// money | `U32` | The character's amount of zen. | LE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "34")]
+pub struct ItemRepairResult {
+  #[serde(with = "IntegerLE")]
+  pub money: u32,
+}
+
+/// `C3:36` - Asks a player whether to trade with another player.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// name | `CHAR(10)` | The requesting character's name. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C3", code = "36")]
+pub struct TradeRequested {
+  #[serde(with = "StringFixed::<typenum::U10>")]
+  pub name: String,
+}
+
+/// `C1:37` - Describes the response to a trade request, opening the trade
+/// window if accepted.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// accepted | `U8` | Whether the trade was accepted or not. | -
+/// name | `CHAR(10)` | The partner's character name. | -
+/// level | `U16` | The partner's character level. | LE
+/// guild | `U32` | The partner's guild ID. | LE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "37")]
+pub struct TradeResponseResult {
+  pub accepted: bool,
+  #[serde(with = "StringFixed::<typenum::U10>")]
+  pub name: String,
+  #[serde(with = "IntegerLE")]
+  pub level: u16,
+  #[serde(with = "IntegerLE")]
+  pub guild: u32,
+}
+
+/// `C1:38` - Removes an item from the partner's side of a trade.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The item's trade slot. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "38")]
+pub struct TradeItemRemoved {
+  pub slot: u8,
+}
+
+/// `C1:39` - Adds an item to the partner's side of a trade.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The item's trade slot. | -
+/// item | `Item` | The item's info. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "39")]
+pub struct TradeItemAdded {
+  pub slot: u8,
+  pub item_info: ItemInfo,
+}
+
+/// `C1:3A` - Describes the result of offering zen in a trade.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// success | `U8` | Whether the zen was offered or not. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "3A")]
+pub struct TradeMoneyResult {
+  pub success: bool,
+}
+
+/// `C1:3B` - Describes the zen offered by the partner of a trade.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// money | `U32` | The amount of zen offered. | LE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "3B")]
+pub struct TradePartnerMoney {
+  #[serde(with = "IntegerLE")]
+  pub money: u32,
+}
+
+/// `C1:3C` - Describes the state of the partner's trade button.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// confirmed | `U8` | Whether the partner has confirmed the offers. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "3C")]
+pub struct TradeButtonState {
+  pub confirmed: bool,
+}
+
+/// `C1:3D` - Closes the trade window.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// outcome | `U8` | The outcome of the trade. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "3D")]
+pub struct TradeResult {
+  pub outcome: TradeOutcome,
+}
+
+/// A collection of trade outcomes.
+#[repr(u8)]
+#[derive(Primitive, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TradeOutcome {
+  Cancelled = 0x00,
+  Success = 0x01,
+  InventoryFull = 0x02,
+}
+
+primitive_serialize!(TradeOutcome, u8);
+
+/// `C1:54:01` - Asks a player whether to create a guild, once the guild
+/// master has been talked to.
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "54", subcode = "01")]
+pub struct GuildMasterQuestion;
 
-    data.serialize(serializer)
-  }
+/// `C1:81` - Describes the amount of zen in a vault and an inventory.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// success | `U8` | Whether the zen was transferred or not. | -
+/// vault | `U32` | The vault's amount of zen. | LE
+/// money | `U32` | The character's amount of zen. | LE
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "81")]
+pub struct VaultMoneyResult {
+  pub success: bool,
+  #[serde(with = "IntegerLE")]
+  pub vault: u32,
+  #[serde(with = "IntegerLE")]
+  pub money: u32,
 }
 
-/// `C2:12`
+/// `C1:83` - Describes the protection of a vault.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// state | `U8` | The vault's protection state. | -
 #[derive(Serialize, MuPacket, Debug)]
-#[packet(kind = "C1", code = "12")]
-pub struct ViewportPlayers(#[serde(with = "VectorLengthLE::<u8>")] Vec<PlayerView>);
+#[packet(kind = "C1", code = "83")]
+pub struct VaultProtection {
+  pub state: VaultProtectionState,
+}
 
-#[derive(Serialize, Debug)]
-struct PlayerView {
-  #[serde(with = "IntegerBE")]
-  player_id: u16, // 0
-  position_x: u8, // 2
-  position_y: u8, // 3
-  #[serde(serialize_with = "serialize_class")]
-  class: Class, // 4
-  equipment: CharacterEquipmentSet, // 5
-  padding: [u8; 2], // 22
-  skill_state: u32, // 24
-  #[serde(with = "StringFixed::<typenum::U10>")]
-  name: String, // 28
-  tx: u8,         // 38
-  ty: u8,         // 39
-  direction_pk_level: u8, // 40
+/// A collection of vault protection states.
+#[repr(u8)]
+#[derive(Primitive, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum VaultProtectionState {
+  Unprotected = 0x00,
+  Locked = 0x01,
+  InvalidPassword = 0x0A,
+  SetFailed = 0x0B,
+  Unlocked = 0x0C,
+  RemoveFailed = 0x0D,
 }
 
-/// `C1:24` - Describes the result of an item move request.
-#[derive(MuPacket, Debug)]
-#[packet(kind = "C1", code = "24")]
-pub enum ItemMoveResult {
-  Failure,
-  Success {
-    storage: u8,
-    slot: u8,
-    item_info: ItemInfo,
-  },
+primitive_serialize!(VaultProtectionState, u8);
+
+/// `C1:86` - Describes the result of a chaos mix.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// outcome | `U8` | The outcome of the mix. | -
+/// item | `Item` | The product's info, or empty unless successful. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "86")]
+pub struct ChaosMixResult {
+  pub outcome: ChaosMixOutcome,
+  pub item_info: ItemInfo,
 }
 
-impl Serialize for ItemMoveResult {
-  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-    #[derive(Serialize, Debug)]
-    struct ItemMoveSuccess {
-      storage: u8,
-      slot: u8,
-      item_info: ItemInfo,
+impl ChaosMixResult {
+  /// Constructs a new result for a successful mix.
+  pub fn success(item: &Item) -> Self {
+    ChaosMixResult {
+      outcome: ChaosMixOutcome::Success,
+      item_info: ItemInfo::new(item),
     }
+  }
 
-    match self {
-      &ItemMoveResult::Failure => 0xFFu8.serialize(serializer),
-      &ItemMoveResult::Success {
-        storage,
-        slot,
-        item_info,
-      } => ItemMoveSuccess {
-        storage,
-        slot,
-        item_info,
-      }.serialize(serializer),
+  /// Constructs a new result for a mix without a product.
+  pub fn failure(outcome: ChaosMixOutcome) -> Self {
+    ChaosMixResult {
+      outcome,
+      item_info: ItemInfo::empty(),
     }
   }
 }
 
+/// A collection of chaos mix outcomes.
+#[repr(u8)]
+#[derive(Primitive, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ChaosMixOutcome {
+  Failure = 0x00,
+  Success = 0x01,
+  InsufficientMoney = 0x02,
+  InvalidItems = 0x03,
+}
+
+primitive_serialize!(ChaosMixOutcome, u8);
+
 /// `C1:B8:01` - Send the client's kill count for the character.
 ///
 /// This is specific to the client's character only.
@@ -180,6 +1115,44 @@ impl Serialize for ItemMoveResult {
 #[packet(kind = "C1", code = "B8", subcode = "01")]
 pub struct CharacterKillCount(pub u8);
 
+/// `C1:D4` - Describes the movement of an object.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// id | `U16` | The object's ID. | BE
+/// x | `U8` | The X coordinate of the destination. | -
+/// y | `U8` | The Y coordinate of the destination. | -
+/// direction | `U4` | The object's direction (upper bits). | -
+#[derive(MuPacket, Debug)]
+#[packet(kind = "C1", code = "D4")]
+pub struct ObjectMove {
+  pub id: u16,
+  pub destination: Position,
+  pub direction: Direction,
+}
+
+impl Serialize for ObjectMove {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct ObjectMoveData {
+      #[serde(with = "IntegerBE")]
+      id: u16,
+      x: u8,
+      y: u8,
+      direction: u8,
+    }
+
+    ObjectMoveData {
+      id: self.id,
+      x: self.destination.x,
+      y: self.destination.y,
+      direction: (self.direction as u8) << 4,
+    }.serialize(serializer)
+  }
+}
+
 /// `C1:F1:00` — Describes the result of an attempt to join a Game Server.
 ///
 /// This can also be sent after a client has connected. If sent after the initial
@@ -548,14 +1521,195 @@ pub struct CharacterInfo {
 }
 
 impl CharacterInfo {
-  pub fn new(character: &Character) -> Self {
+  pub fn new(character: &Character, experience_level: u32) -> Self {
+    let attributes = CharacterAttributes::new(character);
     CharacterInfo {
       x: character.position.x,
       y: character.position.y,
       map: character.map,
+      experience: character.experience,
+      experience_level,
+      points: character.points,
+      strength: character.strength,
+      agility: character.agility,
+      vitality: character.vitality,
+      energy: character.energy,
+      command: character.command,
+      health: attributes.max_health as u16,
+      health_max: attributes.max_health as u16,
+      mana: attributes.max_mana as u16,
+      mana_max: attributes.max_mana as u16,
+      shield: attributes.max_shield as u16,
+      shield_max: attributes.max_shield as u16,
+      ag: attributes.max_ability as u16,
+      ag_max: attributes.max_ability as u16,
+      money: character.inventory.money,
+      hero_status: character.hero_status(),
+      ..Default::default()
+    }
+  }
+}
+
+/// `C1:F3:04` - Describes the respawn of a character after its death.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// x | `U8` | The character's X coordinate. | -
+/// y | `U8` | The character's Y coordinate. | -
+/// map | `U8` | The character's map. | -
+/// direction | `U8` | The character's direction. | -
+/// HP | `U16` | The character's HP. | LE
+/// MP | `U16` | The character's MP. | LE
+/// SD | `U16` | The character's SD. | LE
+/// AG | `U16` | The character's AG. | LE
+/// experience | `U32` | The character's experience. | LE
+/// money | `U32` | The amount of zen. | LE
+#[derive(Serialize, MuPacket, Debug, Default)]
+#[packet(kind = "C1", code = "F3", subcode = "04")]
+pub struct CharacterRespawn {
+  pub x: u8,
+  pub y: u8,
+  pub map: u8,
+  pub direction: Direction,
+  #[serde(with = "IntegerLE")]
+  pub health: u16,
+  #[serde(with = "IntegerLE")]
+  pub mana: u16,
+  #[serde(with = "IntegerLE")]
+  pub shield: u16,
+  #[serde(with = "IntegerLE")]
+  pub ag: u16,
+  #[serde(with = "IntegerLE")]
+  pub experience: u32,
+  #[serde(with = "IntegerLE")]
+  pub money: u32,
+}
+
+impl CharacterRespawn {
+  /// Constructs a new respawn, with the character's attributes fully restored.
+  pub fn new(character: &Character) -> Self {
+    let attributes = CharacterAttributes::new(character);
+    CharacterRespawn {
+      x: character.position.x,
+      y: character.position.y,
+      map: character.map,
+      health: attributes.max_health as u16,
+      mana: attributes.max_mana as u16,
+      shield: attributes.max_shield as u16,
+      ag: attributes.max_ability as u16,
+      experience: character.experience,
+      money: character.inventory.money,
+      ..Default::default()
+    }
+  }
+}
+
+/// `C1:F3:05` - Describes a character's new level and attributes.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// level | `U16` | The character's new level. | LE
+/// points | `U16` | The amount of level up points available. | LE
+/// HP (max) | `U16` | The character's max HP. | LE
+/// MP (max) | `U16` | The character's max MP. | LE
+/// SD (max) | `U16` | The character's max SD. | LE
+/// AG (max) | `U16` | The character's max AG. | LE
+/// FP⊕ | `U16` | The character's fruit points (increase). | LE
+/// FP⊕(max) | `U16` | The character's max fruit points (increase). | LE
+/// FP⊖ | `U16` | The character's fruit points (decrease). | LE
+/// FP⊖(max) | `U16` | The character's max fruit points (decrease). | LE
+#[derive(Serialize, MuPacket, Debug, Default)]
+#[packet(kind = "C1", code = "F3", subcode = "05")]
+pub struct CharacterLevelUp {
+  #[serde(with = "IntegerLE")]
+  pub level: u16,
+  #[serde(with = "IntegerLE")]
+  pub points: u16,
+  #[serde(with = "IntegerLE")]
+  pub health_max: u16,
+  #[serde(with = "IntegerLE")]
+  pub mana_max: u16,
+  #[serde(with = "IntegerLE")]
+  pub shield_max: u16,
+  #[serde(with = "IntegerLE")]
+  pub ag_max: u16,
+  #[serde(with = "IntegerLE")]
+  pub fruit_points_add: u16,
+  #[serde(with = "IntegerLE")]
+  pub fruit_points_add_max: u16,
+  #[serde(with = "IntegerLE")]
+  pub fruit_points_sub: u16,
+  #[serde(with = "IntegerLE")]
+  pub fruit_points_sub_max: u16,
+}
+
+impl CharacterLevelUp {
+  pub fn new(character: &Character) -> Self {
+    let attributes = CharacterAttributes::new(character);
+    CharacterLevelUp {
+      level: character.level,
+      points: character.points,
+      health_max: attributes.max_health as u16,
+      mana_max: attributes.max_mana as u16,
+      shield_max: attributes.max_shield as u16,
+      ag_max: attributes.max_ability as u16,
+      ..Default::default()
+    }
+  }
+}
+
+/// `C1:F3:06` - Describes the result of spending a level-up point.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// result | `U8` | The increased stat (`0x10` + stat) or zero on failure. | -
+/// padding | `U8` | Padding, ignored by the client. | -
+/// HP/MP (max) | `U16` | The max HP after vitality or max MP after energy. | LE
+/// SD (max) | `U16` | The character's max SD. | LE
+/// AG (max) | `U16` | The character's max AG. | LE
+#[derive(Serialize, MuPacket, Debug, Default)]
+#[packet(kind = "C1", code = "F3", subcode = "06")]
+pub struct CharacterStatResult {
+  pub result: u8,
+  pub padding: u8,
+  #[serde(with = "IntegerLE")]
+  pub maximum: u16,
+  #[serde(with = "IntegerLE")]
+  pub shield_max: u16,
+  #[serde(with = "IntegerLE")]
+  pub ag_max: u16,
+}
+
+impl CharacterStatResult {
+  /// The result of a successfully increased stat, offset by the stat.
+  pub const SUCCESS: u8 = 0x10;
+
+  /// Constructs a new result for a successfully increased stat.
+  pub fn success(character: &Character, stat: CharacterStat) -> Self {
+    let attributes = CharacterAttributes::new(character);
+    let maximum = match stat {
+      CharacterStat::Vitality => attributes.max_health,
+      CharacterStat::Energy => attributes.max_mana,
+      _ => 0,
+    };
+
+    CharacterStatResult {
+      result: Self::SUCCESS + stat as u8,
+      maximum: maximum as u16,
+      shield_max: attributes.max_shield as u16,
+      ag_max: attributes.max_ability as u16,
       ..Default::default()
     }
   }
+
+  /// Constructs a new result for a failed increase.
+  pub fn failure() -> Self { CharacterStatResult::default() }
 }
 
 /// `C1:F3:08` - Describes the hero status of a character.
@@ -612,6 +1766,67 @@ impl InventoryList {
   }
 }
 
+/// `C1:F3:11` - Describes the skills learned by a character.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// count | `U8` | The number of skills. | -
+/// skills | `Skill[]` | An array of skills. | -
+///
+/// ### Layout - Skill
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// index | `U8` | The skill's index in the skill bar. | -
+/// skill | `U8` | The skill's ID. | -
+/// level | `U8` | The skill's level. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "F3", subcode = "11")]
+pub struct SkillList(#[serde(with = "VectorLengthLE::<u8>")] Vec<SkillListEntry>);
+
+impl SkillList {
+  /// Constructs a new list of a character's skills.
+  pub fn new(character: &Character) -> Self {
+    let skills = character
+      .skills
+      .iter()
+      .enumerate()
+      .map(|(index, &skill)| SkillListEntry {
+        index: index as u8,
+        skill,
+        level: 0,
+      })
+      .collect();
+    SkillList(skills)
+  }
+}
+
+/// `C1:F3:14` - Describes an inventory item which has been modified, e.g by a
+/// jewel.
+///
+/// ## Layout
+///
+/// Field | Type | Description | Endianess
+/// ----- | ---- | ----------- | ---------
+/// slot | `U8` | The item's inventory slot. | -
+/// item | `Item` | The item's info. | -
+#[derive(Serialize, MuPacket, Debug)]
+#[packet(kind = "C1", code = "F3", subcode = "14")]
+pub struct ItemUpdate {
+  pub slot: u8,
+  pub item_info: ItemInfo,
+}
+
+/// A skill list entry.
+#[derive(Serialize, Debug)]
+struct SkillListEntry {
+  index: u8,
+  skill: u8,
+  level: u8,
+}
+
 /// An item inventory entry.
 #[derive(Serialize, Debug)]
 struct CharacterInventoryEntry {
@@ -1,11 +1,17 @@
 PRAGMA foreign_keys = ON;
 
+-- Create a default account vault with 8x15 space and 5000 in cash
+INSERT INTO inventory(id, width, height, money)
+VALUES
+  (X'c5a3e0f2b1d44f6e9a7b3c8d2e1f0a9b', 8, 15, 5000);
+
 -- Create a default account used for testing
 INSERT INTO account
-  (id, username, password_hash, security_code, email)
+  (id, username, password_hash, security_code, email, vault_id)
 VALUES
   -- The password is 'test'
-  (1, 'foobar', '$2y$07$zFM0q8YmKjaYW4Hig6AFz.wroa/eG5DSK4ST9Y0KS4hDw5Jepw31a', 111111, 'test@mail.com');
+  (1, 'foobar', '$2y$07$zFM0q8YmKjaYW4Hig6AFz.wroa/eG5DSK4ST9Y0KS4hDw5Jepw31a', 111111, 'test@mail.com',
+   X'c5a3e0f2b1d44f6e9a7b3c8d2e1f0a9b');
 
 -- Create a default character inventory with 8x8 space and 1337 in cash
 INSERT INTO inventory(id, width, height, money)
@@ -14,36 +20,102 @@ VALUES
 
 -- Create a default DK character named deadbeef at level 3
 INSERT INTO character
-  (id, slot, name, level, class, map, position_x, position_y, inventory_id, account_id)
+  (id, slot, name, level, class, strength, agility, vitality, energy, map, position_x, position_y,
+   inventory_id, account_id)
 VALUES
-  (1, 2, 'deadbeef', 3, 'DK', 0, 120, 60, X'587d12b748364673a0989476894283e4', 1);
+  (1, 2, 'deadbeef', 3, 'DK', 28, 20, 25, 10, 0, 120, 60, X'587d12b748364673a0989476894283e4', 1);
 
 -- Create item definitions for some test items.
 INSERT INTO item_definition
   (code, name, equippable_slot, max_durability, width, height, drop_from_monster, drop_level)
 VALUES
-  (0,    'Kris',           0,  20, 1, 2, 1, 6),
-  (1,    'Short Sword',    0,  22, 1, 3, 1, 3),
-  (2,    'Rapier',         0,  23, 1, 3, 1, 9),
-  (3585, 'Dragon Helm',    2,  68, 2, 2, 1, 57),
-  (4097, 'Dragon Armor',   3,  68, 2, 3, 1, 59),
-  (4609, 'Dragon Pants',   4,  68, 2, 2, 1, 55),
-  (5121, 'Dragon Gloves',  5,  68, 2, 2, 1, 52),
-  (5633, 'Dragon Boots',   6,  68, 2, 2, 1, 54),
-  (6656, 'Guardian Angel', 8, 255, 1, 1, 1, 23),
-  (6657, 'Imp',            8, 255, 1, 1, 1, 28);
+  (0,    'Kris',                  0,  20, 1, 2, 1, 6),
+  (1,    'Short Sword',           0,  22, 1, 3, 1, 3),
+  (2,    'Rapier',                0,  23, 1, 3, 1, 9),
+  (518,  'Chaos Dragon Axe',      0,  75, 2, 4, 0, 75),
+  (2054, 'Chaos Nature Bow',      1,  68, 2, 4, 0, 88),
+  (2567, 'Chaos Lightning Staff', 0,  60, 2, 4, 0, 75),
+  (3585, 'Dragon Helm',           2,  68, 2, 2, 1, 57),
+  (4097, 'Dragon Armor',          3,  68, 2, 3, 1, 59),
+  (4609, 'Dragon Pants',          4,  68, 2, 2, 1, 55),
+  (5121, 'Dragon Gloves',         5,  68, 2, 2, 1, 52),
+  (5633, 'Dragon Boots',          6,  68, 2, 2, 1, 54),
+  (6656, 'Guardian Angel',        8, 255, 1, 1, 1, 23),
+  (6657, 'Imp',                   8, 255, 1, 1, 1, 28);
+
+-- Create item definitions for jewels, and the ingredients and products of chaos
+-- mixes
+INSERT INTO item_definition
+  (code, name, equippable_slot, max_durability, width, height, drop_from_monster, drop_level)
+VALUES
+  (6144, 'Wings of Elf',        7,    200, 3, 2, 0, 100),
+  (6145, 'Wings of Heaven',     7,    200, 5, 3, 0, 100),
+  (6146, 'Wings of Satan',      7,    200, 5, 2, 0, 100),
+  (6159, 'Jewel of Chaos',      NULL,   0, 1, 1, 0, 12),
+  (6658, 'Horn of Uniria',      8,    255, 1, 1, 0, 25),
+  (6659, 'Horn of Dinorant',    8,    255, 1, 1, 0, 110),
+  (6672, 'Scroll of Archangel', NULL,   0, 1, 1, 0, 32),
+  (6673, 'Blood Bone',          NULL,   0, 1, 1, 0, 32),
+  (6674, 'Invisibility Cloak',  NULL,   0, 2, 2, 0, 32),
+  (7181, 'Jewel of Bless',      NULL,   0, 1, 1, 0, 25),
+  (7182, 'Jewel of Soul',       NULL,   0, 1, 1, 0, 30),
+  (7184, 'Jewel of Life',       NULL,   0, 1, 1, 0, 72),
+  (7185, 'Devil''s Eye',        NULL,   0, 1, 1, 0, 30),
+  (7186, 'Devil''s Key',        NULL,   0, 1, 1, 0, 30),
+  (7187, 'Devil''s Invitation', NULL,   0, 1, 1, 0, 30);
+
+-- Create item definitions for apples and potions, which keep their stack count
+-- in their durability
+INSERT INTO item_definition
+  (code, name, equippable_slot, max_durability, width, height, drop_from_monster, drop_level)
+VALUES
+  (7168, 'Apple',                NULL, 1, 1, 1, 0, 1),
+  (7169, 'Small Healing Potion', NULL, 1, 1, 1, 0, 10),
+  (7170, 'Healing Potion',       NULL, 1, 1, 1, 0, 25),
+  (7171, 'Large Healing Potion', NULL, 1, 1, 1, 0, 40),
+  (7172, 'Small Mana Potion',    NULL, 1, 1, 1, 0, 10),
+  (7173, 'Mana Potion',          NULL, 1, 1, 1, 0, 25),
+  (7174, 'Large Mana Potion',    NULL, 1, 1, 1, 0, 40);
+
+-- Create item definitions for the orbs and scrolls teaching skills
+INSERT INTO item_definition
+  (code, name, equippable_slot, max_durability, width, height, drop_from_monster, drop_level)
+VALUES
+  (6151, 'Orb of Twisting Slash',  NULL, 0, 1, 1, 1, 47),
+  (6152, 'Orb of Healing',         NULL, 0, 1, 1, 1, 8),
+  (6153, 'Orb of Greater Defense', NULL, 0, 1, 1, 1, 13),
+  (6154, 'Orb of Greater Damage',  NULL, 0, 1, 1, 1, 18),
+  (6155, 'Orb of Summoning',       NULL, 0, 1, 1, 1, 3),
+  (6156, 'Orb of Rageful Blow',    NULL, 0, 1, 1, 1, 78),
+  (6157, 'Orb of Impale',          NULL, 0, 1, 1, 1, 20),
+  (6160, 'Orb of Fire Slash',      NULL, 0, 1, 1, 1, 60),
+  (6161, 'Orb of Penetration',     NULL, 0, 1, 1, 1, 64),
+  (6162, 'Orb of Ice Arrow',       NULL, 0, 1, 1, 1, 81),
+  (6163, 'Orb of Death Stab',      NULL, 0, 1, 1, 1, 86),
+  (6164, 'Orb of Power Slash',     NULL, 0, 1, 1, 1, 70),
+  (7680, 'Scroll of Poison',       NULL, 0, 1, 2, 1, 30),
+  (7681, 'Scroll of Meteorite',    NULL, 0, 1, 2, 1, 21),
+  (7682, 'Scroll of Lightning',    NULL, 0, 1, 2, 1, 13),
+  (7683, 'Scroll of Fire Ball',    NULL, 0, 1, 2, 1, 7),
+  (7684, 'Scroll of Flame',        NULL, 0, 1, 2, 1, 35),
+  (7686, 'Scroll of Ice',          NULL, 0, 1, 2, 1, 25),
+  (7687, 'Scroll of Twister',      NULL, 0, 1, 2, 1, 40),
+  (7688, 'Scroll of Evil Spirit',  NULL, 0, 1, 2, 1, 50),
+  (7689, 'Scroll of Hellfire',     NULL, 0, 1, 2, 1, 60),
+  (7690, 'Scroll of Power Wave',   NULL, 0, 1, 2, 1, 9),
+  (7692, 'Scroll of Cometfall',    NULL, 0, 1, 2, 1, 80);
 
 -- Create an item instance of a Kris, Short Sword + Dragon Set
-INSERT INTO item(id, code, level, durability)
+INSERT INTO item(id, code, level, durability, skill, luck, option, excellent, tier)
 VALUES
-  (X'6606af63a93c11e4979700505690798f',    0,  2, 20),
-  (X'3f06af63a93c11e4979700505690773f',    1,  3, 22),
-  (X'a64f5979c8684d2eb6dc217dd2e5a009', 3585,  3, 55),
-  (X'b64f5979c8684d2eb6dc217dd2e5a009', 4097, 13, 55),
-  (X'c64f5979c8684d2eb6dc217dd2e5a009', 4609,  5, 55),
-  (X'd64f5979c8684d2eb6dc217dd2e5a009', 5121, 11, 54),
-  (X'e64f5979c8684d2eb6dc217dd2e5a009', 5633,  7, 55),
-  (X'ed38227dcf6a4a18bdb6721b7fb78f9e', 6657,  0, 10);
+  (X'6606af63a93c11e4979700505690798f',    0,  2, 20, 0, 0, 0,    0, 0),
+  (X'3f06af63a93c11e4979700505690773f',    1,  3, 22, 1, 1, 2,    0, 0),
+  (X'a64f5979c8684d2eb6dc217dd2e5a009', 3585,  3, 55, 0, 0, 0,    0, 1),
+  (X'b64f5979c8684d2eb6dc217dd2e5a009', 4097, 13, 55, 0, 1, 4, 0x21, 0),
+  (X'c64f5979c8684d2eb6dc217dd2e5a009', 4609,  5, 55, 0, 0, 0,    0, 0),
+  (X'd64f5979c8684d2eb6dc217dd2e5a009', 5121, 11, 54, 0, 0, 0,    0, 1),
+  (X'e64f5979c8684d2eb6dc217dd2e5a009', 5633,  7, 55, 0, 0, 0,    0, 0),
+  (X'ed38227dcf6a4a18bdb6721b7fb78f9e', 6657,  0, 10, 0, 0, 0,    0, 0);
 
 -- Equip the 'deadbeef' character with the Short Sword
 INSERT INTO equipment_item(character_id, item_id, slot)
@@ -66,10 +138,194 @@ VALUES
   (0, 'DW'), (0, 'DK'), (0, 'FE'), (0, 'MG'), (0, 'DL'),
   (1, 'DW'), (1, 'DK'), (1, 'FE'), (1, 'MG'), (1, 'DL'),
   (2, 'DK'), (2, 'FE'), (2, 'MG'), (2, 'DL'),
+  (518, 'DK'), (518, 'MG'),
+  (2054, 'FE'),
+  (2567, 'DW'), (2567, 'MG'),
   (3585, 'DK'),
   (4097, 'DK'), (4097, 'MG'),
   (4609, 'DK'), (4609, 'MG'),
   (5121, 'DK'), (5121, 'MG'),
   (5633, 'DK'), (5633, 'MG'),
   (6656, 'DW'), (6656, 'DK'), (6656, 'FE'), (6656, 'MG'), (6656, 'DL'),
-  (6657, 'DW'), (6657, 'DK'), (6657, 'FE'), (6657, 'MG'), (6657, 'DL');
\ No newline at end of file
+  (6657, 'DW'), (6657, 'DK'), (6657, 'FE'), (6657, 'MG'), (6657, 'DL'),
+  (6144, 'FE'),
+  (6145, 'DW'),
+  (6146, 'DK'),
+  (6658, 'DW'), (6658, 'DK'), (6658, 'FE'), (6658, 'MG'), (6658, 'DL'),
+  (6659, 'DW'), (6659, 'DK'), (6659, 'FE'), (6659, 'MG'), (6659, 'DL');
+
+-- Add the combat attributes of the weapons and the Dragon Set
+INSERT INTO item_attribute_boost(item_code, attribute, boost)
+VALUES
+  (0, 'MinDamage', 6), (0, 'MaxDamage', 11), (0, 'AttackSpeed', 50),
+  (1, 'MinDamage', 3), (1, 'MaxDamage', 7), (1, 'AttackSpeed', 20),
+  (2, 'MinDamage', 9), (2, 'MaxDamage', 15), (2, 'AttackSpeed', 40),
+  (518, 'MinDamage', 75), (518, 'MaxDamage', 130), (518, 'AttackSpeed', 25),
+  (2054, 'MinDamage', 88), (2054, 'MaxDamage', 106), (2054, 'AttackSpeed', 30),
+  (2567, 'MinDamage', 47), (2567, 'MaxDamage', 48), (2567, 'AttackSpeed', 25),
+  (3585, 'Defense', 24),
+  (4097, 'Defense', 37),
+  (4609, 'Defense', 30),
+  (5121, 'Defense', 20),
+  (5633, 'Defense', 22),
+  (6144, 'Defense', 10),
+  (6145, 'Defense', 10),
+  (6146, 'Defense', 20);
+
+-- Add the stat requirements of the weapons and the Dragon Set
+INSERT INTO item_attribute_requirement(item_code, attribute, requirement)
+VALUES
+  (0, 'Strength', 10), (0, 'Agility', 8),
+  (1, 'Strength', 20),
+  (2, 'Strength', 50), (2, 'Agility', 40),
+  (518, 'Strength', 140), (518, 'Agility', 50),
+  (2054, 'Strength', 40), (2054, 'Agility', 150),
+  (2567, 'Strength', 40), (2567, 'Energy', 150),
+  (3585, 'Strength', 120), (3585, 'Agility', 30),
+  (4097, 'Strength', 120), (4097, 'Agility', 30),
+  (4609, 'Strength', 120), (4609, 'Agility', 30),
+  (5121, 'Strength', 120), (5121, 'Agility', 30),
+  (5633, 'Strength', 120), (5633, 'Agility', 30),
+  (6657, 'Level', 28),
+  (6144, 'Level', 100),
+  (6145, 'Level', 100),
+  (6146, 'Level', 100),
+  (6659, 'Level', 110);
+
+-- Create the ancient Hyon set, of which the Dragon Helm and Gloves are equipped
+INSERT INTO item_set(id, name, tier)
+VALUES
+  (1, 'Hyon', 1);
+
+INSERT INTO item_set_member(item_set_id, item_code)
+VALUES
+  (1, 3585), (1, 5121), (1, 5633);
+
+INSERT INTO item_set_bonus(item_set_id, pieces, attribute, bonus)
+VALUES
+  (1, 2, 'Defense', 25),
+  (1, 3, 'Strength', 10), (1, 3, 'MaxDamage', 20);
+
+-- Create monster definitions for the monsters of Lorencia
+INSERT INTO monster_definition
+  (class, name, level, max_health, min_damage, max_damage, defense, attack_rate, defense_rate,
+   move_range, attack_range, view_range, move_delay, attack_delay, respawn_time)
+VALUES
+  (0,  'Bull Fighter',        6, 100, 16, 20,  6, 28,  6, 3, 1, 5, 400, 1600, 10000),
+  (1,  'Hound',               9, 140, 22, 27,  9, 39,  9, 3, 1, 5, 400, 1600, 10000),
+  (2,  'Budge Dragon',        4,  60, 10, 13,  3, 18,  3, 3, 1, 4, 400, 2000, 10000),
+  (3,  'Spider',              2,  30,  4,  7,  1,  8,  1, 2, 1, 4, 600, 1800, 10000),
+  (4,  'Elite Bull Fighter', 12, 190, 31, 36, 12, 50, 12, 3, 1, 4, 400, 1400, 10000),
+  (6,  'Lich',               14, 255, 41, 46, 14, 62, 14, 3, 4, 7, 400, 2000, 10000),
+  (7,  'Giant',              17, 400, 57, 62, 18, 80, 18, 2, 2, 3, 400, 2200, 10000);
+
+-- Spawn monsters in the fields surrounding Lorencia
+INSERT INTO monster_spawn
+  (id, monster_class, map, start_x, start_y, end_x, end_y, direction, count)
+VALUES
+  (1, 3, 0, 175, 100, 190, 115, NULL, 10),
+  (2, 2, 0, 100, 180, 120, 200, NULL, 8),
+  (3, 0, 0, 200,  60, 220,  80, NULL, 6),
+  (4, 1, 0,  30, 200,  50, 220, NULL, 6),
+  (5, 4, 0,  60,  30,  80,  50, NULL, 4),
+  (6, 6, 0, 220, 170, 235, 185, NULL, 3),
+  (7, 7, 0, 210, 210, 210, 210,    3, 1);
+
+-- Create the merchants and keepers of Lorencia and Noria
+INSERT INTO npc(id, class, name, kind, map, position_x, position_y, direction)
+VALUES
+  (1, 251, 'Hanzo the Blacksmith', 'Shop',        0, 116, 141, 3),
+  (2, 254, 'Pasi the Mage',        'Shop',        0, 118, 113, 3),
+  (3, 240, 'Safety Guardian',      'Warehouse',   0, 146, 110, 3),
+  (4, 241, 'Guild Master',         'GuildMaster', 0,  94, 125, 2),
+  (5, 238, 'Chaos Goblin',         'ChaosGoblin', 3, 180, 106, 3);
+
+-- Create the skills of the season 2 classes
+INSERT INTO skill_definition
+  (id, name, damage, magic, mana_cost, ability_cost, cooldown, range, area, area_size,
+   min_level, min_energy)
+VALUES
+  (1,  'Poison',             12, 1,  42,  0,    0, 6, 'Single', 0,   0, 140),
+  (2,  'Meteorite',          21, 1,  12,  0,    0, 6, 'Single', 0,   0, 104),
+  (3,  'Lightning',          17, 1,  15,  0,    0, 6, 'Single', 0,   0,  72),
+  (4,  'Fire Ball',           8, 1,   3,  0,    0, 6, 'Single', 0,   0,  40),
+  (5,  'Flame',              25, 1,  50,  0,  500, 6, 'Circle', 2,   0, 160),
+  (7,  'Ice',                10, 1,  38,  0,    0, 6, 'Single', 0,   0, 120),
+  (8,  'Twister',            35, 1,  60,  0,  500, 6, 'Circle', 2,   0, 180),
+  (9,  'Evil Spirit',        45, 1,  90,  0, 1000, 0, 'Circle', 3,   0, 220),
+  (10, 'Hellfire',           60, 1, 160,  0, 1000, 0, 'Circle', 3,   0, 260),
+  (11, 'Power Wave',         14, 1,   5,  0,    0, 6, 'Single', 0,   0,  56),
+  (13, 'Cometfall',          70, 1, 150,  0, 1000, 3, 'Circle', 2,   0, 436),
+  (17, 'Energy Ball',         3, 1,   1,  0,    0, 6, 'Single', 0,   0,   0),
+  (19, 'Falling Slash',      15, 0,   9,  0,    0, 2, 'Single', 0,   0,   0),
+  (20, 'Lunge',              15, 0,   9,  0,    0, 2, 'Single', 0,   0,   0),
+  (21, 'Uppercut',           15, 0,   8,  0,    0, 2, 'Single', 0,   0,   0),
+  (22, 'Cyclone',            18, 0,   9,  0,    0, 2, 'Single', 0,   0,   0),
+  (23, 'Slash',              20, 0,  10,  0,    0, 2, 'Single', 0,   0,   0),
+  (26, 'Heal',                0, 0,  20,  0,    0, 6, 'Single', 0,   8,  52),
+  (27, 'Greater Defense',     0, 0,  30,  0,    0, 6, 'Single', 0,  13,  72),
+  (28, 'Greater Damage',      0, 0,  40,  0,    0, 6, 'Single', 0,  18,  92),
+  (30, 'Summon Goblin',       0, 0,  40,  0,    0, 0, 'Single', 0,   3,  90),
+  (31, 'Summon Stone Golem',  0, 0,  70,  0,    0, 0, 'Single', 0,   0, 170),
+  (32, 'Summon Assassin',     0, 0, 110,  0,    0, 0, 'Single', 0,   0, 190),
+  (33, 'Summon Elite Yeti',   0, 0, 160,  0,    0, 0, 'Single', 0,   0, 230),
+  (34, 'Summon Dark Knight',  0, 0, 200,  0,    0, 0, 'Single', 0,   0, 250),
+  (35, 'Summon Bali',         0, 0, 250,  0,    0, 0, 'Single', 0,   0, 260),
+  (36, 'Summon Soldier',      0, 0, 350,  0,    0, 0, 'Single', 0,   0, 280),
+  (41, 'Twisting Slash',     25, 0,  10, 10,  500, 0, 'Circle', 2,  80,   0),
+  (42, 'Rageful Blow',       60, 0,  20, 20, 1000, 3, 'Circle', 2, 170,   0),
+  (43, 'Death Stab',         70, 0,  22, 12,    0, 2, 'Single', 0, 160,   0),
+  (47, 'Impale',             15, 0,   8,  0,    0, 3, 'Single', 0,  28,   0),
+  (51, 'Ice Arrow',         105, 0,  10, 12, 1000, 8, 'Single', 0,  81,   0),
+  (52, 'Penetration',        70, 0,   9,  9,    0, 6, 'Single', 0, 130,   0),
+  (24, 'Triple Shot',         0, 0,   5,  0,    0, 6, 'Single', 0,   0,   0),
+  (55, 'Fire Slash',         80, 0,  15, 20,    0, 2, 'Single', 0, 220,   0),
+  (56, 'Power Slash',        80, 0,  15, 20,  500, 5, 'Circle', 2,   0, 100),
+  (61, 'Fire Burst',        100, 0,  25, 25,    0, 6, 'Circle', 1,  74,   0),
+  (62, 'Earthshake',        150, 0,   0, 50, 1000, 0, 'Circle', 5,   0,   0);
+
+INSERT INTO skill_eligible_class(skill_id, class)
+VALUES
+  (1, 'DW'), (1, 'SM'), (1, 'MG'),
+  (2, 'DW'), (2, 'SM'), (2, 'MG'),
+  (3, 'DW'), (3, 'SM'), (3, 'MG'),
+  (4, 'DW'), (4, 'SM'), (4, 'MG'),
+  (5, 'DW'), (5, 'SM'), (5, 'MG'),
+  (7, 'DW'), (7, 'SM'), (7, 'MG'),
+  (8, 'DW'), (8, 'SM'), (8, 'MG'),
+  (9, 'DW'), (9, 'SM'),
+  (10, 'DW'), (10, 'SM'),
+  (11, 'DW'), (11, 'SM'), (11, 'MG'),
+  (13, 'DW'), (13, 'SM'),
+  (17, 'DW'), (17, 'SM'),
+  (19, 'DK'), (19, 'BK'), (19, 'MG'),
+  (20, 'DK'), (20, 'BK'), (20, 'MG'),
+  (21, 'DK'), (21, 'BK'), (21, 'MG'),
+  (22, 'DK'), (22, 'BK'), (22, 'MG'),
+  (23, 'DK'), (23, 'BK'), (23, 'MG'),
+  (41, 'DK'), (41, 'BK'), (41, 'MG'),
+  (42, 'DK'), (42, 'BK'),
+  (43, 'DK'), (43, 'BK'),
+  (24, 'FE'), (24, 'ME'),
+  (26, 'FE'), (26, 'ME'),
+  (27, 'FE'), (27, 'ME'),
+  (28, 'FE'), (28, 'ME'),
+  (30, 'FE'), (30, 'ME'),
+  (31, 'FE'), (31, 'ME'),
+  (32, 'FE'), (32, 'ME'),
+  (33, 'FE'), (33, 'ME'),
+  (34, 'FE'), (34, 'ME'),
+  (35, 'FE'), (35, 'ME'),
+  (36, 'FE'), (36, 'ME'),
+  (47, 'DK'), (47, 'BK'),
+  (51, 'FE'), (51, 'ME'),
+  (52, 'FE'), (52, 'ME'),
+  (55, 'MG'),
+  (56, 'MG'),
+  (61, 'DL'),
+  (62, 'DL');
+
+-- Teach the test character the basic sword skills
+INSERT INTO character_skill(character_id, skill_id)
+VALUES
+  (1, 19), (1, 20), (1, 21);
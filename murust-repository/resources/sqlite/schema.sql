@@ -7,6 +7,9 @@ CREATE TABLE IF NOT EXISTS account(
   logged_in TINYINT NOT NULL DEFAULT 0 CHECK(logged_in IN (0, 1)),
   failed_login_attempts INTEGER NOT NULL DEFAULT 0 CHECK(failed_login_attempts >= 0),
   failed_login_time BIGINT,
+  vault_id BINARY NOT NULL,
+  vault_password INTEGER CHECK(vault_password BETWEEN 0 AND 9999),
+  FOREIGN KEY(vault_id) REFERENCES inventory(id),
   PRIMARY KEY(id)
 );
 
@@ -17,6 +20,7 @@ CREATE TABLE IF NOT EXISTS character(
   level INTEGER NOT NULL DEFAULT 1 CHECK(level BETWEEN 1 AND 0xFFFF),
   class TEXT NOT NULL CHECK(class IN ('DW', 'DK', 'FE', 'MG', 'DL', 'SM', 'BK', 'ME')),
   experience INTEGER NOT NULL DEFAULT 0 CHECK(experience >= 0),
+  points INTEGER NOT NULL DEFAULT 0 CHECK(points BETWEEN 0 AND 0xFFFF),
   strength INTEGER NOT NULL DEFAULT 0 CHECK(strength BETWEEN 0 AND 0xFFFF),
   agility INTEGER NOT NULL DEFAULT 0 CHECK(agility BETWEEN 0 AND 0xFFFF),
   vitality INTEGER NOT NULL DEFAULT 0 CHECK(vitality BETWEEN 0 AND 0xFFFF),
@@ -66,11 +70,15 @@ CREATE TABLE IF NOT EXISTS item(
   code INTEGER NOT NULL CHECK(code BETWEEN 0 AND 0x1FFF),
   level INTEGER NOT NULL DEFAULT 0 CHECK(level BETWEEN 0 AND 15),
   durability INTEGER NOT NULL CHECK(durability BETWEEN 0 AND 0xFF),
+  skill BOOLEAN NOT NULL DEFAULT 0,
+  luck BOOLEAN NOT NULL DEFAULT 0,
+  option INTEGER NOT NULL DEFAULT 0 CHECK(option BETWEEN 0 AND 7),
+  excellent INTEGER NOT NULL DEFAULT 0 CHECK(excellent BETWEEN 0 AND 0x3F),
+  tier INTEGER NOT NULL DEFAULT 0 CHECK(tier BETWEEN 0 AND 2),
   FOREIGN KEY(code) REFERENCES item_definition(code),
   PRIMARY KEY(id)
 );
 
--- Add excellent, option, skill & luck
 CREATE TABLE IF NOT EXISTS item_definition(
   code INTEGER NOT NULL CHECK(code BETWEEN 0 AND 0x1FFF),
   name TEXT NOT NULL,
@@ -108,4 +116,112 @@ CREATE TABLE IF NOT EXISTS item_attribute_boost(
   boost INTEGER NOT NULL,
   FOREIGN KEY(item_code) REFERENCES item_definition(code),
   PRIMARY KEY(item_code, attribute)
-);
\ No newline at end of file
+);
+
+-- Ancient sets, of which an item may belong to one per tier
+CREATE TABLE IF NOT EXISTS item_set(
+  id INTEGER NOT NULL CHECK(id BETWEEN 0 AND 0xFFFF),
+  name TEXT NOT NULL,
+  tier INTEGER NOT NULL CHECK(tier IN (1, 2)),
+  UNIQUE(name),
+  PRIMARY KEY(id)
+);
+
+CREATE TABLE IF NOT EXISTS item_set_member(
+  item_set_id INTEGER NOT NULL,
+  item_code INTEGER NOT NULL CHECK(item_code BETWEEN 0 AND 0x1FFF),
+  FOREIGN KEY(item_set_id) REFERENCES item_set(id),
+  FOREIGN KEY(item_code) REFERENCES item_definition(code),
+  PRIMARY KEY(item_set_id, item_code)
+);
+
+-- Attribute bonuses granted once a number of set pieces are equipped
+CREATE TABLE IF NOT EXISTS item_set_bonus(
+  item_set_id INTEGER NOT NULL,
+  pieces INTEGER NOT NULL CHECK(pieces BETWEEN 2 AND 0xFF),
+  attribute TEXT NOT NULL,
+  bonus INTEGER NOT NULL,
+  FOREIGN KEY(item_set_id) REFERENCES item_set(id),
+  PRIMARY KEY(item_set_id, pieces, attribute)
+);
+
+-- Attributes shared by all monsters of a class (times in milliseconds)
+CREATE TABLE IF NOT EXISTS monster_definition(
+  class INTEGER NOT NULL CHECK(class BETWEEN 0 AND 0xFFFF),
+  name TEXT NOT NULL,
+  level INTEGER NOT NULL CHECK(level BETWEEN 0 AND 0xFFFF),
+  max_health INTEGER NOT NULL CHECK(max_health > 0),
+  min_damage INTEGER NOT NULL CHECK(min_damage BETWEEN 0 AND 0xFFFF),
+  max_damage INTEGER NOT NULL CHECK(max_damage BETWEEN min_damage AND 0xFFFF),
+  defense INTEGER NOT NULL CHECK(defense BETWEEN 0 AND 0xFFFF),
+  attack_rate INTEGER NOT NULL CHECK(attack_rate BETWEEN 0 AND 0xFFFF),
+  defense_rate INTEGER NOT NULL CHECK(defense_rate BETWEEN 0 AND 0xFFFF),
+  move_range INTEGER NOT NULL CHECK(move_range BETWEEN 0 AND 0xFF),
+  attack_range INTEGER NOT NULL CHECK(attack_range BETWEEN 0 AND 0xFF),
+  view_range INTEGER NOT NULL CHECK(view_range BETWEEN 0 AND 0xFF),
+  move_delay INTEGER NOT NULL CHECK(move_delay >= 0),
+  attack_delay INTEGER NOT NULL CHECK(attack_delay >= 0),
+  respawn_time INTEGER NOT NULL CHECK(respawn_time >= 0),
+  PRIMARY KEY(class)
+);
+
+-- Areas where monsters are spawned (a random direction if unspecified)
+CREATE TABLE IF NOT EXISTS monster_spawn(
+  id INTEGER NOT NULL,
+  monster_class INTEGER NOT NULL,
+  map INTEGER NOT NULL CHECK(map BETWEEN 0 AND 0xFF),
+  start_x INTEGER NOT NULL CHECK(start_x BETWEEN 0 AND 0xFF),
+  start_y INTEGER NOT NULL CHECK(start_y BETWEEN 0 AND 0xFF),
+  end_x INTEGER NOT NULL CHECK(end_x BETWEEN start_x AND 0xFF),
+  end_y INTEGER NOT NULL CHECK(end_y BETWEEN start_y AND 0xFF),
+  direction INTEGER CHECK(IFNULL(direction, 0) BETWEEN 0 AND 7),
+  count INTEGER NOT NULL DEFAULT 1 CHECK(count BETWEEN 1 AND 0xFF),
+  FOREIGN KEY(monster_class) REFERENCES monster_definition(class),
+  PRIMARY KEY(id)
+);
+
+-- Non-player characters standing at fixed locations
+CREATE TABLE IF NOT EXISTS npc(
+  id INTEGER NOT NULL,
+  class INTEGER NOT NULL CHECK(class BETWEEN 0 AND 0xFFFF),
+  name TEXT NOT NULL,
+  kind TEXT NOT NULL CHECK(kind IN ('Shop', 'Warehouse', 'ChaosGoblin', 'GuildMaster')),
+  map INTEGER NOT NULL CHECK(map BETWEEN 0 AND 0xFF),
+  position_x INTEGER NOT NULL CHECK(position_x BETWEEN 0 AND 0xFF),
+  position_y INTEGER NOT NULL CHECK(position_y BETWEEN 0 AND 0xFF),
+  direction INTEGER NOT NULL DEFAULT 0 CHECK(direction BETWEEN 0 AND 7),
+  PRIMARY KEY(id)
+);
+
+-- Costs, requirements and effects of skills (cooldowns in milliseconds)
+CREATE TABLE IF NOT EXISTS skill_definition(
+  id INTEGER NOT NULL CHECK(id BETWEEN 0 AND 0xFF),
+  name TEXT NOT NULL,
+  damage INTEGER NOT NULL DEFAULT 0 CHECK(damage BETWEEN 0 AND 0xFFFF),
+  magic BOOLEAN NOT NULL DEFAULT 0,
+  mana_cost INTEGER NOT NULL DEFAULT 0 CHECK(mana_cost BETWEEN 0 AND 0xFFFF),
+  ability_cost INTEGER NOT NULL DEFAULT 0 CHECK(ability_cost BETWEEN 0 AND 0xFFFF),
+  cooldown INTEGER NOT NULL DEFAULT 0 CHECK(cooldown >= 0),
+  range INTEGER NOT NULL CHECK(range BETWEEN 0 AND 0xFF),
+  area TEXT NOT NULL DEFAULT 'Single' CHECK(area IN ('Single', 'Circle')),
+  area_size INTEGER NOT NULL DEFAULT 0 CHECK(area_size BETWEEN 0 AND 0xFF),
+  min_level INTEGER NOT NULL DEFAULT 0 CHECK(min_level BETWEEN 0 AND 0xFFFF),
+  min_energy INTEGER NOT NULL DEFAULT 0 CHECK(min_energy BETWEEN 0 AND 0xFFFF),
+  PRIMARY KEY(id)
+);
+
+CREATE TABLE IF NOT EXISTS skill_eligible_class(
+  skill_id INTEGER NOT NULL,
+  class TEXT NOT NULL CHECK(class IN ('DW', 'DK', 'FE', 'MG', 'DL', 'SM', 'BK', 'ME')),
+  FOREIGN KEY(skill_id) REFERENCES skill_definition(id),
+  PRIMARY KEY(skill_id, class)
+);
+
+-- Skills learned by a character
+CREATE TABLE IF NOT EXISTS character_skill(
+  character_id INTEGER NOT NULL,
+  skill_id INTEGER NOT NULL,
+  FOREIGN KEY(character_id) REFERENCES character(id) ON DELETE CASCADE,
+  FOREIGN KEY(skill_id) REFERENCES skill_definition(id),
+  PRIMARY KEY(character_id, skill_id)
+);
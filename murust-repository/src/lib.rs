@@ -55,15 +55,26 @@ mod tests {
     let (_temp, db) = setup_test_db();
     let accounts = AccountRepository::new(&db);
 
+    let vault = models::Inventory {
+      id: Uuid::new_v4().into(),
+      width: 8,
+      height: 15,
+      money: 0,
+    };
     let account = accounts
       .create(
         "fajbar",
         "$2y$07$zFM0q8YmKjaYW4Hig6AFz.wroa/eG5DSK4ST9Y0KS4hDw5Jepw31a",
         123456,
         "fajbar@mail.com",
+        &vault,
       )
       .unwrap();
+    assert_eq!(account.vault_id, vault.id);
+
+    let inventories = InventoryRepository::new(&db);
     assert!(accounts.delete(&account.id).is_ok());
+    assert!(inventories.find_by_id(vault.id).unwrap().is_none());
   }
 
   #[test]
@@ -83,6 +94,29 @@ mod tests {
     assert_eq!(characters[0].name, "deadbeef");
   }
 
+  #[test]
+  fn save_character_progress() {
+    let (_temp, db) = setup_test_db();
+    let repository = CharacterRepository::new(&db);
+
+    let progress = models::CharacterProgress {
+      level: 4,
+      experience: 2000,
+      points: 5,
+      strength: 28,
+      agility: 20,
+      vitality: 25,
+      energy: 10,
+      command: 0,
+    };
+    repository.save_progress(1, &progress).unwrap();
+
+    let character = repository.find_by_id(1).unwrap().unwrap();
+    assert_eq!(character.level, 4);
+    assert_eq!(character.points, 5);
+    assert_eq!(character.strength, 28);
+  }
+
   #[test]
   fn find_item_by_id_and_update() {
     let (_temp, db) = setup_test_db();
@@ -95,10 +129,104 @@ mod tests {
     assert_eq!(item.durability, 20);
 
     item.level = 3;
+    item.luck = true;
+    item.option = 2;
     repository.save(&item).unwrap();
 
     let item = repository.find_by_id(id).unwrap().unwrap();
     assert_eq!(item.level, 3);
+    assert!(item.luck && !item.skill);
+    assert_eq!(item.option, 2);
+
+    // Saving an item must not remove it from its inventory
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let contents = repository
+      .find_inventory_contents_by_id(inventory_id)
+      .unwrap();
+    assert_eq!(contents.len(), 1);
+  }
+
+  #[test]
+  fn save_item_consuming_another() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let jewel = models::Item {
+      id: Uuid::new_v4().into(),
+      code: 7181,
+      level: 0,
+      durability: 0,
+      skill: false,
+      luck: false,
+      option: 0,
+      excellent: 0,
+      tier: 0,
+    };
+    let inventory_item = models::InventoryItem {
+      inventory_id: inventory_id.into(),
+      item_id: jewel.id.clone(),
+      slot: 8,
+    };
+    repository.add_to_inventory(&jewel, &inventory_item).unwrap();
+
+    let id = Uuid::parse_str("6606af63a93c11e4979700505690798f").unwrap();
+    let mut item = repository.find_by_id(id).unwrap().unwrap();
+    item.level = 3;
+    repository.save_consuming(&item, jewel.id.clone()).unwrap();
+
+    // The jewel is already consumed, so the item is left untouched
+    item.level = 4;
+    assert!(repository.save_consuming(&item, jewel.id.clone()).is_err());
+
+    let items = repository.find_inventory_contents_by_id(inventory_id).unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!((items[0].1.code, items[0].1.level), (0, 3));
+  }
+
+  #[test]
+  fn add_item_to_inventory() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let item = models::Item {
+      id: Uuid::new_v4().into(),
+      code: 2,
+      level: 1,
+      durability: 23,
+      skill: false,
+      luck: true,
+      option: 1,
+      excellent: 0x04,
+      tier: 0,
+    };
+    let inventory_item = models::InventoryItem {
+      inventory_id: inventory_id.into(),
+      item_id: item.id.clone(),
+      slot: 8,
+    };
+    repository.add_to_inventory(&item, &inventory_item).unwrap();
+
+    let items = repository.find_inventory_contents_by_id(inventory_id).unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(
+      items
+        .iter()
+        .any(|(entry, item)| entry.slot == 8 && item.luck && item.excellent == 0x04)
+    );
+
+    // The slot is already occupied, so neither the item nor its entry is added
+    let item = models::Item {
+      id: Uuid::new_v4().into(),
+      ..item
+    };
+    let inventory_item = models::InventoryItem {
+      item_id: item.id.clone(),
+      ..inventory_item
+    };
+    assert!(repository.add_to_inventory(&item, &inventory_item).is_err());
+    assert!(repository.find_by_id(item.id).unwrap().is_none());
   }
 
   #[test]
@@ -111,6 +239,49 @@ mod tests {
     assert_eq!(definition.name, "Rapier");
   }
 
+  #[test]
+  fn find_item_definitions_droppable_by_level() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemDefinitionRepository::new(&db);
+
+    let mut codes = repository
+      .find_droppable_by_level(6)
+      .unwrap()
+      .into_iter()
+      .map(|definition| definition.code)
+      .collect::<Vec<_>>();
+    codes.sort();
+    assert_eq!(codes, vec![0, 1]);
+  }
+
+  #[test]
+  fn find_item_attribute_requirements_from_item_code() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemAttributeRequirementRepository::new(&db);
+
+    let requirements = repository.find_by_item_code(2).unwrap();
+    assert_eq!(requirements.len(), 2);
+    assert!(
+      requirements
+        .iter()
+        .any(|entry| entry.attribute == "Strength" && entry.requirement == 50)
+    );
+  }
+
+  #[test]
+  fn find_item_set_with_members_and_bonuses() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemSetRepository::new(&db);
+
+    assert!(repository.find_by_item_code_and_tier(3585, 2).unwrap().is_none());
+    assert!(repository.find_by_item_code_and_tier(4097, 1).unwrap().is_none());
+
+    let set = repository.find_by_item_code_and_tier(3585, 1).unwrap().unwrap();
+    assert_eq!(set.name, "Hyon");
+    assert_eq!(repository.find_members_by_id(set.id).unwrap().len(), 3);
+    assert_eq!(repository.find_bonuses_by_id(set.id).unwrap().len(), 3);
+  }
+
   #[test]
   fn find_inventory_by_id() {
     let (_temp, db) = setup_test_db();
@@ -123,6 +294,138 @@ mod tests {
     assert_eq!(inventory.money, 1337);
   }
 
+  #[test]
+  fn save_inventory_money() {
+    let (_temp, db) = setup_test_db();
+    let repository = InventoryRepository::new(&db);
+
+    let id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    repository.add_money(id, -337).unwrap();
+    assert_eq!(repository.find_by_id(id).unwrap().unwrap().money, 1000);
+
+    // The zen of an inventory can never become negative
+    assert!(repository.add_money(id, -1001).is_err());
+    assert_eq!(repository.find_by_id(id).unwrap().unwrap().money, 1000);
+  }
+
+  #[test]
+  fn transfer_items_and_money_between_inventories() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+    let inventories = InventoryRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let vault_id = Uuid::parse_str("c5a3e0f2b1d44f6e9a7b3c8d2e1f0a9b").unwrap();
+    repository
+      .transfer_inventory_item(inventory_id, 0, vault_id, 8)
+      .unwrap();
+    assert!(repository.find_inventory_contents_by_id(inventory_id).unwrap().is_empty());
+    assert_eq!(repository.find_inventory_contents_by_id(vault_id).unwrap()[0].0.slot, 8);
+
+    // Unknown inventories must roll back the entire transfer
+    assert!(
+      inventories
+        .transfer_money(inventory_id, Uuid::new_v4(), 1337)
+        .is_err()
+    );
+    assert_eq!(inventories.find_by_id(inventory_id).unwrap().unwrap().money, 1337);
+
+    inventories
+      .transfer_money(inventory_id, vault_id, 1337)
+      .unwrap();
+    assert_eq!(inventories.find_by_id(inventory_id).unwrap().unwrap().money, 0);
+    assert_eq!(inventories.find_by_id(vault_id).unwrap().unwrap().money, 6337);
+  }
+
+  #[test]
+  fn exchange_items_between_inventories() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+    let inventories = InventoryRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let other = models::Inventory {
+      id: Uuid::new_v4().into(),
+      width: 8,
+      height: 8,
+      money: 0,
+    };
+    inventories.save(&other).unwrap();
+
+    let kris = || models::InventoryItem {
+      inventory_id: other.id,
+      item_id: Uuid::parse_str("6606af63a93c11e4979700505690798f").unwrap().into(),
+      slot: 0,
+    };
+    let money = [(inventory_id, -1000), (*other.id, 1000)];
+
+    // Unknown items must roll back the entire exchange
+    let unknown = models::InventoryItem {
+      inventory_id: inventory_id.into(),
+      item_id: Uuid::new_v4().into(),
+      slot: 8,
+    };
+    assert!(
+      repository
+        .exchange_inventory_items(&[kris(), unknown], &money)
+        .is_err()
+    );
+    assert_eq!(repository.find_inventory_contents_by_id(inventory_id).unwrap().len(), 1);
+
+    repository.exchange_inventory_items(&[kris()], &money).unwrap();
+    assert!(repository.find_inventory_contents_by_id(inventory_id).unwrap().is_empty());
+    assert_eq!(repository.find_inventory_contents_by_id(other.id).unwrap().len(), 1);
+    assert_eq!(inventories.find_by_id(inventory_id).unwrap().unwrap().money, 337);
+    assert_eq!(inventories.find_by_id(other.id).unwrap().unwrap().money, 1000);
+  }
+
+  #[test]
+  fn mix_items_within_inventory() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+    let inventories = InventoryRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let kris = Uuid::parse_str("6606af63a93c11e4979700505690798f").unwrap();
+    let product = || {
+      let item = models::Item {
+        id: Uuid::parse_str("7706af63a93c11e4979700505690798f").unwrap().into(),
+        code: 1,
+        level: 0,
+        durability: 22,
+        skill: false,
+        luck: false,
+        option: 0,
+        excellent: 0,
+        tier: 0,
+      };
+      let inventory_item = models::InventoryItem {
+        inventory_id: inventory_id.into(),
+        item_id: item.id,
+        slot: 0,
+      };
+      (item, inventory_item)
+    };
+
+    // Unknown items must roll back the entire mix
+    assert!(
+      repository
+        .mix_inventory_items(inventory_id, &[kris, Uuid::new_v4()], &[product()], 1300)
+        .is_err()
+    );
+    assert!(repository.find_by_id(kris).unwrap().is_some());
+
+    repository
+      .mix_inventory_items(inventory_id, &[kris], &[product()], 1300)
+      .unwrap();
+    assert!(repository.find_by_id(kris).unwrap().is_none());
+
+    let items = repository.find_inventory_contents_by_id(inventory_id).unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!((items[0].0.slot, items[0].1.code), (0, 1));
+    assert_eq!(inventories.find_by_id(inventory_id).unwrap().unwrap().money, 37);
+  }
+
   #[test]
   fn find_inventory_items_from_inventory() {
     let (_temp, db) = setup_test_db();
@@ -151,6 +454,129 @@ mod tests {
     );
   }
 
+  #[test]
+  fn buy_and_sell_items_with_inventory_money() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+    let inventories = InventoryRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let item = models::Item {
+      id: Uuid::new_v4().into(),
+      code: 2,
+      level: 0,
+      durability: 20,
+      skill: false,
+      luck: false,
+      option: 0,
+      excellent: 0,
+      tier: 0,
+    };
+    let inventory_item = models::InventoryItem {
+      inventory_id: inventory_id.into(),
+      item_id: item.id.clone(),
+      slot: 8,
+    };
+    repository
+      .buy_into_inventory(&item, &inventory_item, 337)
+      .unwrap();
+    assert_eq!(inventories.find_by_id(inventory_id).unwrap().unwrap().money, 1000);
+    assert_eq!(repository.find_inventory_contents_by_id(inventory_id).unwrap().len(), 2);
+
+    // A failed purchase must leave the zen untouched
+    let item_id = item.id.clone();
+    let other = models::Item {
+      id: Uuid::new_v4().into(),
+      ..item
+    };
+    let other_entry = models::InventoryItem {
+      item_id: other.id.clone(),
+      ..inventory_item
+    };
+    assert!(
+      repository
+        .buy_into_inventory(&other, &other_entry, 0)
+        .is_err()
+    );
+    assert_eq!(inventories.find_by_id(inventory_id).unwrap().unwrap().money, 1000);
+
+    repository
+      .sell_from_inventory(item_id.clone(), inventory_id, 500)
+      .unwrap();
+    assert_eq!(inventories.find_by_id(inventory_id).unwrap().unwrap().money, 1500);
+    assert!(repository.find_by_id(item_id).unwrap().is_none());
+  }
+
+  #[test]
+  fn repair_items_with_inventory_money() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+    let inventories = InventoryRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let item_id = Uuid::parse_str("a64f5979c8684d2eb6dc217dd2e5a009").unwrap();
+    repository.save_durability(item_id, 10).unwrap();
+    assert_eq!(repository.find_by_id(item_id).unwrap().unwrap().durability, 10);
+
+    // Unknown items must roll back the entire repair
+    let items = [(item_id, 68), (Uuid::new_v4(), 20)];
+    assert!(repository.repair_items(&items, inventory_id, 0).is_err());
+    assert_eq!(repository.find_by_id(item_id).unwrap().unwrap().durability, 10);
+
+    repository
+      .repair_items(&items[..1], inventory_id, 337)
+      .unwrap();
+    assert_eq!(repository.find_by_id(item_id).unwrap().unwrap().durability, 68);
+    assert_eq!(inventories.find_by_id(inventory_id).unwrap().unwrap().money, 1000);
+  }
+
+  #[test]
+  fn save_stack_durabilities_and_delete_items() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let potions = (16..18)
+      .map(|slot| {
+        let potion = models::Item {
+          id: Uuid::new_v4().into(),
+          code: 7169,
+          level: 0,
+          durability: 2,
+          skill: false,
+          luck: false,
+          option: 0,
+          excellent: 0,
+          tier: 0,
+        };
+        let inventory_item = models::InventoryItem {
+          inventory_id: inventory_id.into(),
+          item_id: potion.id.clone(),
+          slot,
+        };
+        repository.add_to_inventory(&potion, &inventory_item).unwrap();
+        *potion.id
+      })
+      .collect::<Vec<_>>();
+
+    // Unknown items must roll back the entire update
+    let items = [(potions[0], 3), (Uuid::new_v4(), 1)];
+    assert!(repository.save_durabilities(&items).is_err());
+    assert_eq!(repository.find_by_id(potions[0]).unwrap().unwrap().durability, 2);
+
+    repository
+      .save_durabilities(&[(potions[0], 3), (potions[1], 1)])
+      .unwrap();
+    assert_eq!(repository.find_by_id(potions[0]).unwrap().unwrap().durability, 3);
+
+    repository.delete(potions[1]).unwrap();
+    assert!(repository.delete(potions[1]).is_err());
+    assert!(repository.find_by_id(potions[1]).unwrap().is_none());
+
+    let items = repository.find_inventory_contents_by_id(inventory_id).unwrap();
+    assert_eq!(items.len(), 2);
+  }
+
   #[test]
   fn find_equipment_items_from_character() {
     let (_temp, db) = setup_test_db();
@@ -163,6 +589,83 @@ mod tests {
     assert!(items.iter().any(|(i, _)| *i.item_id == id));
   }
 
+  #[test]
+  fn move_items_between_inventory_and_equipment() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let item_id = Uuid::parse_str("6606af63a93c11e4979700505690798f").unwrap();
+    repository.move_inventory_item(inventory_id, 0, 4).unwrap();
+    assert!(repository.move_inventory_item(inventory_id, 0, 5).is_err());
+
+    let items = repository.find_inventory_contents_by_id(inventory_id).unwrap();
+    assert_eq!(items[0].0.slot, 4);
+
+    repository.delete_inventory_item(inventory_id, 4).unwrap();
+    repository
+      .insert_equipment_item(&models::EquipmentItem {
+        character_id: 1,
+        item_id: item_id.into(),
+        slot: 1,
+      })
+      .unwrap();
+    assert!(repository.find_by_id(item_id).unwrap().is_some());
+    assert!(repository.find_inventory_contents_by_id(inventory_id).unwrap().is_empty());
+
+    repository.move_equipment_item(1, 1, 7).unwrap();
+    repository.delete_equipment_item(1, 7).unwrap();
+    repository
+      .insert_inventory_item(&models::InventoryItem {
+        inventory_id: inventory_id.into(),
+        item_id: item_id.into(),
+        slot: 10,
+      })
+      .unwrap();
+
+    let equipment = repository.find_equipment_by_character_id(1).unwrap();
+    assert!(equipment.iter().all(|(entry, _)| *entry.item_id != item_id));
+    let items = repository.find_inventory_contents_by_id(inventory_id).unwrap();
+    assert_eq!(items[0].0.slot, 10);
+  }
+
+  #[test]
+  fn equip_and_unequip_items_as_transactions() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let item_id = Uuid::parse_str("6606af63a93c11e4979700505690798f").unwrap();
+    let occupied = repository.find_equipment_by_character_id(1).unwrap()[0].0.slot;
+
+    // Equipping to an occupied slot must leave the item in the inventory
+    let entry = models::EquipmentItem {
+      character_id: 1,
+      item_id: item_id.into(),
+      slot: occupied,
+    };
+    assert!(repository.equip_inventory_item(inventory_id, 0, &entry).is_err());
+    let items = repository.find_inventory_contents_by_id(inventory_id).unwrap();
+    assert_eq!(items[0].0.slot, 0);
+
+    let entry = models::EquipmentItem { slot: 1, ..entry };
+    repository.equip_inventory_item(inventory_id, 0, &entry).unwrap();
+    assert!(repository.find_inventory_contents_by_id(inventory_id).unwrap().is_empty());
+
+    let entry = models::InventoryItem {
+      inventory_id: inventory_id.into(),
+      item_id: item_id.into(),
+      slot: 10,
+    };
+    repository.unequip_equipment_item(1, 1, &entry).unwrap();
+    assert!(repository.unequip_equipment_item(1, 1, &entry).is_err());
+
+    let equipment = repository.find_equipment_by_character_id(1).unwrap();
+    assert!(equipment.iter().all(|(entry, _)| *entry.item_id != item_id));
+    let items = repository.find_inventory_contents_by_id(inventory_id).unwrap();
+    assert_eq!(items[0].0.slot, 10);
+  }
+
   #[test]
   fn delete_equipment_items_from_character() {
     let (_temp, db) = setup_test_db();
@@ -181,4 +684,120 @@ mod tests {
     let classes = repository.find_by_item_code(2).unwrap();
     assert_eq!(classes.len(), 4);
   }
+
+  #[test]
+  fn find_item_attribute_boosts_from_item_code() {
+    let (_temp, db) = setup_test_db();
+    let repository = ItemAttributeBoostRepository::new(&db);
+
+    let boosts = repository.find_by_item_code(0).unwrap();
+    assert_eq!(boosts.len(), 3);
+    assert!(repository.find_by_item_code(6656).unwrap().is_empty());
+  }
+
+  #[test]
+  fn find_monster_definition_by_class() {
+    let (_temp, db) = setup_test_db();
+    let repository = MonsterDefinitionRepository::new(&db);
+
+    let definition = repository.find_by_class(3).unwrap().unwrap();
+    assert_eq!(definition.name, "Spider");
+    assert_eq!(definition.max_health, 30);
+  }
+
+  #[test]
+  fn find_monster_spawns_by_map() {
+    let (_temp, db) = setup_test_db();
+    let repository = MonsterSpawnRepository::new(&db);
+
+    let spawns = repository.find_by_map(0).unwrap();
+    assert_eq!(spawns.len(), 7);
+    assert!(spawns.iter().all(|(s, d)| s.monster_class == d.class));
+    assert!(repository.find_by_map(0xFF).unwrap().is_empty());
+  }
+
+  #[test]
+  fn find_npcs_by_map() {
+    let (_temp, db) = setup_test_db();
+    let repository = NpcRepository::new(&db);
+
+    let npcs = repository.find_by_map(0).unwrap();
+    assert_eq!(npcs.len(), 4);
+    assert_eq!(npcs[0].name, "Hanzo the Blacksmith");
+    assert!(repository.find_by_map(0xFF).unwrap().is_empty());
+  }
+
+  #[test]
+  fn find_skill_definitions_and_classes() {
+    let (_temp, db) = setup_test_db();
+    let repository = SkillDefinitionRepository::new(&db);
+
+    let skills = repository.find_all().unwrap();
+    let twister = skills.iter().find(|skill| skill.name == "Twister").unwrap();
+    assert_eq!((twister.area.as_str(), twister.area_size), ("Circle", 2));
+    assert_eq!(repository.find_by_id(twister.id).unwrap().unwrap().name, "Twister");
+    assert!(repository.find_by_id(6).unwrap().is_none());
+
+    let classes = SkillEligibleClassRepository::new(&db)
+      .find_by_skill_id(twister.id)
+      .unwrap();
+    assert_eq!(classes.len(), 3);
+  }
+
+  #[test]
+  fn find_and_save_character_skills() {
+    let (_temp, db) = setup_test_db();
+    let repository = CharacterSkillRepository::new(&db);
+    assert_eq!(repository.find_by_character_id(1).unwrap().len(), 3);
+
+    let skill = models::CharacterSkill {
+      character_id: 1,
+      skill_id: 41,
+    };
+    repository.save(&skill).unwrap();
+    repository.save(&skill).unwrap();
+    assert_eq!(repository.find_by_character_id(1).unwrap().len(), 4);
+  }
+
+  #[test]
+  fn save_character_skill_consuming_an_item() {
+    let (_temp, db) = setup_test_db();
+    let repository = CharacterSkillRepository::new(&db);
+    let items = ItemRepository::new(&db);
+
+    let inventory_id = Uuid::parse_str("587d12b748364673a0989476894283e4").unwrap();
+    let orb = models::Item {
+      id: Uuid::new_v4().into(),
+      code: 6151,
+      level: 0,
+      durability: 0,
+      skill: false,
+      luck: false,
+      option: 0,
+      excellent: 0,
+      tier: 0,
+    };
+    let inventory_item = models::InventoryItem {
+      inventory_id: inventory_id.into(),
+      item_id: orb.id.clone(),
+      slot: 8,
+    };
+    items.add_to_inventory(&orb, &inventory_item).unwrap();
+
+    let skill = models::CharacterSkill {
+      character_id: 1,
+      skill_id: 41,
+    };
+    repository.save_consuming(&skill, orb.id.clone()).unwrap();
+    assert_eq!(repository.find_by_character_id(1).unwrap().len(), 4);
+    assert_eq!(items.find_inventory_contents_by_id(inventory_id).unwrap().len(), 1);
+
+    // The orb is already consumed, so the skill is not learned again
+    let skill = models::CharacterSkill {
+      character_id: 1,
+      skill_id: 42,
+    };
+    assert!(repository.save_consuming(&skill, orb.id.clone()).is_err());
+    assert_eq!(repository.find_by_character_id(1).unwrap().len(), 4);
+  }
 }
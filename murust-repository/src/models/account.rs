@@ -1,4 +1,5 @@
 use schema::account;
+use types::UuidWrapper;
 
 #[derive(Identifiable, Queryable, AsChangeset, Debug)]
 #[changeset_options(treat_none_as_null = "true")]
@@ -12,4 +13,6 @@ pub struct Account {
   pub logged_in: bool,
   pub failed_login_attempts: i32,
   pub failed_login_time: Option<i64>,
+  pub vault_id: UuidWrapper,
+  pub vault_password: Option<i32>,
 }
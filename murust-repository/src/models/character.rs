@@ -9,7 +9,8 @@ pub struct Character {
   pub name: String,
   pub level: i32,
   pub class: String,
-  pub experience: i32,
+  pub experience: i64,
+  pub points: i32,
   pub strength: i32,
   pub agility: i32,
   pub vitality: i32,
@@ -22,3 +23,17 @@ pub struct Character {
   pub inventory_id: UuidWrapper,
   pub account_id: i32,
 }
+
+/// The progression of a character, updated as it levels and distributes points.
+#[derive(AsChangeset, Debug)]
+#[table_name = "character"]
+pub struct CharacterProgress {
+  pub level: i32,
+  pub experience: i64,
+  pub points: i32,
+  pub strength: i32,
+  pub agility: i32,
+  pub vitality: i32,
+  pub energy: i32,
+  pub command: i32,
+}
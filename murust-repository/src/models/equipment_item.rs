@@ -1,7 +1,7 @@
 use schema::equipment_item;
 use types::UuidWrapper;
 
-#[derive(Identifiable, Queryable, AsChangeset, Debug)]
+#[derive(Identifiable, Queryable, Insertable, AsChangeset, Debug)]
 #[primary_key(character_id, slot)]
 #[table_name = "equipment_item"]
 pub struct EquipmentItem {
@@ -10,7 +10,7 @@ pub struct Inventory {
   pub money: i32,
 }
 
-#[derive(Identifiable, Queryable, AsChangeset, Debug)]
+#[derive(Identifiable, Queryable, Insertable, AsChangeset, Debug)]
 #[primary_key(inventory_id, slot)]
 #[table_name = "inventory_item"]
 pub struct InventoryItem {
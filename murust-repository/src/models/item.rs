@@ -8,4 +8,9 @@ pub struct Item {
   pub code: i32,
   pub level: i32,
   pub durability: i32,
+  pub skill: bool,
+  pub luck: bool,
+  pub option: i32,
+  pub excellent: i32,
+  pub tier: i32,
 }
@@ -1,4 +1,7 @@
-use schema::{item_definition, item_eligible_class};
+use schema::{
+  item_attribute_boost, item_attribute_requirement, item_definition, item_eligible_class, item_set,
+  item_set_bonus, item_set_member,
+};
 
 #[derive(Identifiable, Queryable, AsChangeset, Debug)]
 #[primary_key(code)]
@@ -21,3 +24,47 @@ pub struct ItemEligibleClass {
   pub item_code: i32,
   pub class: String,
 }
+
+#[derive(Identifiable, Queryable, Debug)]
+#[table_name = "item_attribute_boost"]
+#[primary_key(item_code, attribute)]
+pub struct ItemAttributeBoost {
+  pub item_code: i32,
+  pub attribute: String,
+  pub boost: i32,
+}
+
+#[derive(Identifiable, Queryable, Debug)]
+#[table_name = "item_attribute_requirement"]
+#[primary_key(item_code, attribute)]
+pub struct ItemAttributeRequirement {
+  pub item_code: i32,
+  pub attribute: String,
+  pub requirement: i32,
+}
+
+#[derive(Identifiable, Queryable, Debug)]
+#[table_name = "item_set"]
+pub struct ItemSet {
+  pub id: i32,
+  pub name: String,
+  pub tier: i32,
+}
+
+#[derive(Identifiable, Queryable, Debug)]
+#[table_name = "item_set_member"]
+#[primary_key(item_set_id, item_code)]
+pub struct ItemSetMember {
+  pub item_set_id: i32,
+  pub item_code: i32,
+}
+
+#[derive(Identifiable, Queryable, Debug)]
+#[table_name = "item_set_bonus"]
+#[primary_key(item_set_id, pieces, attribute)]
+pub struct ItemSetBonus {
+  pub item_set_id: i32,
+  pub pieces: i32,
+  pub attribute: String,
+  pub bonus: i32,
+}
@@ -1,9 +1,15 @@
 pub use self::account::Account;
-pub use self::character::Character;
+pub use self::character::{Character, CharacterProgress};
 pub use self::equipment_item::EquipmentItem;
 pub use self::inventory::{Inventory, InventoryItem};
 pub use self::item::Item;
-pub use self::item_definition::{ItemDefinition, ItemEligibleClass};
+pub use self::item_definition::{
+  ItemAttributeBoost, ItemAttributeRequirement, ItemDefinition, ItemEligibleClass, ItemSet,
+  ItemSetBonus, ItemSetMember,
+};
+pub use self::monster::{MonsterDefinition, MonsterSpawn};
+pub use self::npc::Npc;
+pub use self::skill::{CharacterSkill, SkillDefinition, SkillEligibleClass};
 
 mod account;
 mod character;
@@ -11,3 +17,6 @@ mod equipment_item;
 mod inventory;
 mod item;
 mod item_definition;
+mod monster;
+mod npc;
+mod skill;
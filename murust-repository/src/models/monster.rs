@@ -0,0 +1,36 @@
+use schema::{monster_definition, monster_spawn};
+
+#[derive(Identifiable, Queryable, Debug)]
+#[primary_key(class)]
+#[table_name = "monster_definition"]
+pub struct MonsterDefinition {
+  pub class: i32,
+  pub name: String,
+  pub level: i32,
+  pub max_health: i32,
+  pub min_damage: i32,
+  pub max_damage: i32,
+  pub defense: i32,
+  pub attack_rate: i32,
+  pub defense_rate: i32,
+  pub move_range: i32,
+  pub attack_range: i32,
+  pub view_range: i32,
+  pub move_delay: i32,
+  pub attack_delay: i32,
+  pub respawn_time: i32,
+}
+
+#[derive(Identifiable, Queryable, Debug)]
+#[table_name = "monster_spawn"]
+pub struct MonsterSpawn {
+  pub id: i32,
+  pub monster_class: i32,
+  pub map: i32,
+  pub start_x: i32,
+  pub start_y: i32,
+  pub end_x: i32,
+  pub end_y: i32,
+  pub direction: Option<i32>,
+  pub count: i32,
+}
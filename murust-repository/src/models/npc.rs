@@ -0,0 +1,14 @@
+use schema::npc;
+
+#[derive(Identifiable, Queryable, Debug)]
+#[table_name = "npc"]
+pub struct Npc {
+  pub id: i32,
+  pub class: i32,
+  pub name: String,
+  pub kind: String,
+  pub map: i32,
+  pub position_x: i32,
+  pub position_y: i32,
+  pub direction: i32,
+}
@@ -0,0 +1,34 @@
+use schema::{character_skill, skill_definition, skill_eligible_class};
+
+#[derive(Identifiable, Queryable, Debug)]
+#[table_name = "skill_definition"]
+pub struct SkillDefinition {
+  pub id: i32,
+  pub name: String,
+  pub damage: i32,
+  pub magic: bool,
+  pub mana_cost: i32,
+  pub ability_cost: i32,
+  pub cooldown: i32,
+  pub range: i32,
+  pub area: String,
+  pub area_size: i32,
+  pub min_level: i32,
+  pub min_energy: i32,
+}
+
+#[derive(Identifiable, Queryable, Debug)]
+#[table_name = "skill_eligible_class"]
+#[primary_key(skill_id, class)]
+pub struct SkillEligibleClass {
+  pub skill_id: i32,
+  pub class: String,
+}
+
+#[derive(Identifiable, Insertable, Queryable, Debug)]
+#[table_name = "character_skill"]
+#[primary_key(character_id, skill_id)]
+pub struct CharacterSkill {
+  pub character_id: i32,
+  pub skill_id: i32,
+}
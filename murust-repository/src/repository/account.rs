@@ -2,8 +2,9 @@ use boolinator::Boolinator;
 use context::{DataContext, DataContextInner};
 use diesel::{self, prelude::*};
 use error::Result;
-use models::Account;
-use schema::account::dsl;
+use models::{Account, Inventory};
+use schema::{self, account::dsl};
+use types::UuidWrapper;
 
 /// A repository for accounts.
 #[derive(Clone)]
@@ -37,24 +38,32 @@ impl AccountRepository {
       .map_err(Into::into)
   }
 
-  /// Creates a new account and returns it.
+  /// Creates a new account along with its vault and returns it.
   pub fn create(
     &self,
     username: &str,
     password_hash: &str,
     security_code: i32,
     email: &str,
+    vault: &Inventory,
   ) -> Result<Account> {
-    let context = self.context.access();
-    diesel::insert_into(dsl::account)
-      .values((
-        dsl::username.eq(username),
-        dsl::password_hash.eq(password_hash),
-        dsl::security_code.eq(security_code),
-        dsl::email.eq(email),
-      ))
-      .execute(&*context)
-      .and_then(|_| dsl::account.order(dsl::id.desc()).first(&*context))
+    let conn = self.context.access();
+    conn
+      .transaction::<_, diesel::result::Error, _>(|| {
+        diesel::insert_into(schema::inventory::table)
+          .values(vault)
+          .execute(&*conn)?;
+        diesel::insert_into(dsl::account)
+          .values((
+            dsl::username.eq(username),
+            dsl::password_hash.eq(password_hash),
+            dsl::security_code.eq(security_code),
+            dsl::email.eq(email),
+            dsl::vault_id.eq(&vault.id),
+          ))
+          .execute(&*conn)?;
+        dsl::account.order(dsl::id.desc()).first(&*conn)
+      })
       .map_err(Into::into)
   }
 
@@ -66,11 +75,27 @@ impl AccountRepository {
     Ok(())
   }
 
-  /// Deletes an account by its ID.
+  /// Deletes an account by its ID, along with its vault.
   pub fn delete(&self, account_id: &i32) -> Result<()> {
-    diesel::delete(dsl::account.filter(dsl::id.eq(account_id)))
-      .execute(&*self.context.access())
-      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
-      .map_err(Into::into)
+    use schema::inventory_item::dsl as inventory_item;
+    let conn = self.context.access();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      let vault_id = dsl::account
+        .find(*account_id)
+        .select(dsl::vault_id)
+        .first::<UuidWrapper>(&*conn)?;
+      diesel::delete(dsl::account.find(*account_id)).execute(&*conn)?;
+
+      // Cascading delete's the inventory items automatically
+      let item_ids = inventory_item::inventory_item
+        .select(inventory_item::item_id)
+        .filter(inventory_item::inventory_id.eq(&vault_id));
+      diesel::delete(schema::item::table.filter(schema::item::dsl::id.eq_any(item_ids)))
+        .execute(&*conn)?;
+      diesel::delete(schema::inventory::table.find(&vault_id))
+        .execute(&*conn)
+        .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+    })?;
+    Ok(())
   }
 }
@@ -2,7 +2,7 @@ use boolinator::Boolinator;
 use context::{DataContext, DataContextInner};
 use diesel::{self, prelude::*};
 use error::Result;
-use models::Character;
+use models::{Character, CharacterProgress};
 use schema::character::dsl;
 use types::UuidWrapper;
 
@@ -75,6 +75,15 @@ impl CharacterRepository {
       .map_err(Into::into)
   }
 
+  /// Updates the progression of a character.
+  pub fn save_progress(&self, character_id: i32, progress: &CharacterProgress) -> Result<()> {
+    diesel::update(dsl::character.find(character_id))
+      .set(progress)
+      .execute(&*self.context.access())
+      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+      .map_err(Into::into)
+  }
+
   /// Deletes a character by its ID.
   pub fn delete(&self, character_id: &i32) -> Result<()> {
     diesel::delete(dsl::character.filter(dsl::id.eq(character_id)))
@@ -0,0 +1,60 @@
+use boolinator::Boolinator;
+use context::{DataContext, DataContextInner};
+use diesel::{self, prelude::*};
+use error::Result;
+use models::CharacterSkill;
+use schema::{self, character_skill::dsl};
+use types::UuidWrapper;
+
+/// A repository for the skills learned by characters.
+#[derive(Clone)]
+pub struct CharacterSkillRepository {
+  context: DataContextInner,
+}
+
+impl CharacterSkillRepository {
+  /// Creates a new character skill repository instance.
+  pub fn new(context: &DataContext) -> Self {
+    CharacterSkillRepository {
+      context: context.inner(),
+    }
+  }
+
+  /// Returns the skills learned by a character.
+  pub fn find_by_character_id(&self, character_id: i32) -> Result<Vec<CharacterSkill>> {
+    dsl::character_skill
+      .filter(dsl::character_id.eq(&character_id))
+      .order(dsl::skill_id)
+      .get_results::<CharacterSkill>(&*self.context.access())
+      .map_err(Into::into)
+  }
+
+  /// Saves a learned skill, ignoring skills already learned.
+  pub fn save(&self, skill: &CharacterSkill) -> Result<()> {
+    diesel::replace_into(dsl::character_skill)
+      .values(skill)
+      .execute(&*self.context.access())?;
+    Ok(())
+  }
+
+  /// Saves a learned skill, whilst deleting the item which taught it.
+  pub fn save_consuming<I: Into<UuidWrapper>>(
+    &self,
+    skill: &CharacterSkill,
+    consumed: I,
+  ) -> Result<()> {
+    let conn = self.context.access();
+    let consumed = consumed.into();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      // Cascading delete's the inventory item automatically
+      diesel::delete(schema::item::dsl::item.find(&consumed))
+        .execute(&*conn)
+        .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))?;
+      diesel::replace_into(dsl::character_skill)
+        .values(skill)
+        .execute(&*conn)?;
+      Ok(())
+    })?;
+    Ok(())
+  }
+}
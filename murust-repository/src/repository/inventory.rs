@@ -1,6 +1,6 @@
 use boolinator::Boolinator;
 use context::{DataContext, DataContextInner};
-use diesel::{self, prelude::*};
+use diesel::{self, prelude::*, sqlite::SqliteConnection};
 use error::Result;
 use models::Inventory;
 use schema::inventory::dsl;
@@ -37,6 +37,44 @@ impl InventoryRepository {
     Ok(())
   }
 
+  /// Adds an amount of zen to an inventory, or removes it if negative.
+  pub fn add_money<I: Into<UuidWrapper>>(&self, inventory_id: I, amount: i32) -> Result<()> {
+    let conn = self.context.access();
+    Self::add_money_with(&conn, &inventory_id.into(), amount).map_err(Into::into)
+  }
+
+  /// Moves an amount of zen from one inventory to another, as a single
+  /// transaction.
+  pub fn transfer_money<I: Into<UuidWrapper>, T: Into<UuidWrapper>>(
+    &self,
+    source_id: I,
+    target_id: T,
+    amount: i32,
+  ) -> Result<()> {
+    let conn = self.context.access();
+    let (source_id, target_id) = (source_id.into(), target_id.into());
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      Self::add_money_with(&conn, &source_id, -amount)?;
+      Self::add_money_with(&conn, &target_id, amount)
+    })?;
+    Ok(())
+  }
+
+  /// Adds an amount of zen to an inventory using an existing connection.
+  ///
+  /// The amount is applied relative to the stored zen, so concurrent changes
+  /// are never overwritten, whilst the schema refuses negative amounts.
+  pub(crate) fn add_money_with(
+    conn: &SqliteConnection,
+    inventory_id: &UuidWrapper,
+    amount: i32,
+  ) -> QueryResult<()> {
+    diesel::update(dsl::inventory.find(inventory_id))
+      .set(dsl::money.eq(dsl::money + amount))
+      .execute(conn)
+      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+  }
+
   /// Deletes an inventory by its ID.
   pub fn delete<I: Into<UuidWrapper>>(&self, inventory_id: I) -> Result<()> {
     diesel::delete(dsl::inventory.filter(dsl::id.eq(&inventory_id.into())))
@@ -1,9 +1,12 @@
+use boolinator::Boolinator;
 use context::{DataContext, DataContextInner};
-use diesel::{self, prelude::*};
+use diesel::{self, prelude::*, sqlite::SqliteConnection};
 use error::Result;
 use models::{EquipmentItem, InventoryItem, Item};
+use repository::InventoryRepository;
 use schema::{self, item::dsl};
 use types::UuidWrapper;
+use uuid::Uuid;
 
 /// A repository for items.
 #[derive(Clone)]
@@ -77,11 +80,337 @@ impl ItemRepository {
     Ok(())
   }
 
-  /// Saves an item by inserting or replacing it.
-  pub fn save(&self, item: &Item) -> Result<()> {
-    diesel::replace_into(dsl::item)
-      .values(item)
+  /// Adds an item to an inventory slot, inserting the item itself.
+  pub fn add_to_inventory(&self, item: &Item, inventory_item: &InventoryItem) -> Result<()> {
+    let conn = self.context.access();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      diesel::insert_into(dsl::item).values(item).execute(&*conn)?;
+      diesel::insert_into(schema::inventory_item::table)
+        .values(inventory_item)
+        .execute(&*conn)?;
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  /// Adds an item to an inventory slot and removes its price from the
+  /// inventory's zen, as a single transaction.
+  pub fn buy_into_inventory(
+    &self,
+    item: &Item,
+    inventory_item: &InventoryItem,
+    price: i32,
+  ) -> Result<()> {
+    let conn = self.context.access();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      diesel::insert_into(dsl::item).values(item).execute(&*conn)?;
+      diesel::insert_into(schema::inventory_item::table)
+        .values(inventory_item)
+        .execute(&*conn)?;
+      InventoryRepository::add_money_with(&conn, &inventory_item.inventory_id, -price)
+    })?;
+    Ok(())
+  }
+
+  /// Deletes an inventory item and adds its price to the inventory's zen, as
+  /// a single transaction.
+  pub fn sell_from_inventory<I: Into<UuidWrapper>, N: Into<UuidWrapper>>(
+    &self,
+    item_id: I,
+    inventory_id: N,
+    price: i32,
+  ) -> Result<()> {
+    let conn = self.context.access();
+    let (item_id, inventory_id) = (item_id.into(), inventory_id.into());
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      // Cascading delete's the inventory item automatically
+      diesel::delete(dsl::item.find(&item_id))
+        .execute(&*conn)
+        .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))?;
+      InventoryRepository::add_money_with(&conn, &inventory_id, price)
+    })?;
+    Ok(())
+  }
+
+  /// Adds an existing item to an inventory slot.
+  pub fn insert_inventory_item(&self, inventory_item: &InventoryItem) -> Result<()> {
+    diesel::insert_into(schema::inventory_item::table)
+      .values(inventory_item)
+      .execute(&*self.context.access())?;
+    Ok(())
+  }
+
+  /// Moves an inventory item from one slot to another.
+  pub fn move_inventory_item<I: Into<UuidWrapper>>(
+    &self,
+    inventory_id: I,
+    source: i32,
+    target: i32,
+  ) -> Result<()> {
+    use schema::inventory_item::dsl as inventory_item;
+    diesel::update(inventory_item::inventory_item.find((&inventory_id.into(), source)))
+      .set(inventory_item::slot.eq(target))
+      .execute(&*self.context.access())
+      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+      .map_err(Into::into)
+  }
+
+  /// Moves an inventory item to a slot within another inventory.
+  pub fn transfer_inventory_item<I: Into<UuidWrapper>, T: Into<UuidWrapper>>(
+    &self,
+    source_id: I,
+    source: i32,
+    target_id: T,
+    target: i32,
+  ) -> Result<()> {
+    use schema::inventory_item::dsl as inventory_item;
+    diesel::update(inventory_item::inventory_item.find((&source_id.into(), source)))
+      .set((
+        inventory_item::inventory_id.eq(&target_id.into()),
+        inventory_item::slot.eq(target),
+      ))
+      .execute(&*self.context.access())
+      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+      .map_err(Into::into)
+  }
+
+  /// Relocates items to new inventory slots and adds an amount of zen to each
+  /// inventory involved (or removes it, if negative), as a single
+  /// transaction.
+  pub fn exchange_inventory_items(
+    &self,
+    entries: &[InventoryItem],
+    money: &[(Uuid, i32)],
+  ) -> Result<()> {
+    use schema::inventory_item::dsl as inventory_item;
+    let conn = self.context.access();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      // Every item must be removed before any is added, since the items may
+      // take each other's slots.
+      for entry in entries {
+        let item_id = inventory_item::item_id.eq(&entry.item_id);
+        diesel::delete(inventory_item::inventory_item.filter(item_id))
+          .execute(&*conn)
+          .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))?;
+      }
+
+      for entry in entries {
+        diesel::insert_into(inventory_item::inventory_item)
+          .values(entry)
+          .execute(&*conn)?;
+      }
+
+      for &(inventory_id, amount) in money {
+        InventoryRepository::add_money_with(&conn, &inventory_id.into(), amount)?;
+      }
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  /// Deletes the items consumed by a chaos mix, adds the items it produced to
+  /// an inventory and removes its cost from the inventory's zen, as a single
+  /// transaction.
+  pub fn mix_inventory_items<I: Into<UuidWrapper>>(
+    &self,
+    inventory_id: I,
+    consumed: &[Uuid],
+    products: &[(Item, InventoryItem)],
+    cost: i32,
+  ) -> Result<()> {
+    let conn = self.context.access();
+    let inventory_id = inventory_id.into();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      // Cascading delete's the inventory items automatically
+      for &item_id in consumed {
+        diesel::delete(dsl::item.find(&UuidWrapper::from(item_id)))
+          .execute(&*conn)
+          .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))?;
+      }
+
+      for &(ref item, ref inventory_item) in products {
+        diesel::insert_into(dsl::item).values(item).execute(&*conn)?;
+        diesel::insert_into(schema::inventory_item::table)
+          .values(inventory_item)
+          .execute(&*conn)?;
+      }
+
+      InventoryRepository::add_money_with(&conn, &inventory_id, -cost)
+    })?;
+    Ok(())
+  }
+
+  /// Removes an item from an inventory slot, without deleting the item itself.
+  pub fn delete_inventory_item<I: Into<UuidWrapper>>(
+    &self,
+    inventory_id: I,
+    slot: i32,
+  ) -> Result<()> {
+    use schema::inventory_item::dsl as inventory_item;
+    diesel::delete(inventory_item::inventory_item.find((&inventory_id.into(), slot)))
+      .execute(&*self.context.access())
+      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+      .map_err(Into::into)
+  }
+
+  /// Adds an existing item to an equipment slot.
+  pub fn insert_equipment_item(&self, equipment_item: &EquipmentItem) -> Result<()> {
+    diesel::insert_into(schema::equipment_item::table)
+      .values(equipment_item)
       .execute(&*self.context.access())?;
     Ok(())
   }
+
+  /// Moves an equipment item from one slot to another.
+  pub fn move_equipment_item(&self, character_id: i32, source: i32, target: i32) -> Result<()> {
+    use schema::equipment_item::dsl as equipment_item;
+    diesel::update(equipment_item::equipment_item.find((character_id, source)))
+      .set(equipment_item::slot.eq(target))
+      .execute(&*self.context.access())
+      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+      .map_err(Into::into)
+  }
+
+  /// Removes an item from an equipment slot, without deleting the item itself.
+  pub fn delete_equipment_item(&self, character_id: i32, slot: i32) -> Result<()> {
+    use schema::equipment_item::dsl as equipment_item;
+    diesel::delete(equipment_item::equipment_item.find((character_id, slot)))
+      .execute(&*self.context.access())
+      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+      .map_err(Into::into)
+  }
+
+  /// Moves an inventory item to an equipment slot, as a single transaction.
+  pub fn equip_inventory_item<I: Into<UuidWrapper>>(
+    &self,
+    inventory_id: I,
+    source: i32,
+    equipment_item: &EquipmentItem,
+  ) -> Result<()> {
+    use schema::inventory_item::dsl as inventory_item;
+    let conn = self.context.access();
+    let inventory_id = inventory_id.into();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      diesel::delete(inventory_item::inventory_item.find((&inventory_id, source)))
+        .execute(&*conn)
+        .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))?;
+      diesel::insert_into(schema::equipment_item::table)
+        .values(equipment_item)
+        .execute(&*conn)?;
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  /// Moves an equipment item to an inventory slot, as a single transaction.
+  pub fn unequip_equipment_item(
+    &self,
+    character_id: i32,
+    source: i32,
+    inventory_item: &InventoryItem,
+  ) -> Result<()> {
+    use schema::equipment_item::dsl as equipment_item;
+    let conn = self.context.access();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      diesel::delete(equipment_item::equipment_item.find((character_id, source)))
+        .execute(&*conn)
+        .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))?;
+      diesel::insert_into(schema::inventory_item::table)
+        .values(inventory_item)
+        .execute(&*conn)?;
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  /// Updates the durability of an item.
+  pub fn save_durability<I: Into<UuidWrapper>>(&self, item_id: I, durability: i32) -> Result<()> {
+    diesel::update(dsl::item.find(&item_id.into()))
+      .set(dsl::durability.eq(durability))
+      .execute(&*self.context.access())
+      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+      .map_err(Into::into)
+  }
+
+  /// Updates the durability of items, as a single transaction.
+  pub fn save_durabilities(&self, items: &[(Uuid, i32)]) -> Result<()> {
+    let conn = self.context.access();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      for &(item_id, durability) in items {
+        diesel::update(dsl::item.find(&UuidWrapper::from(item_id)))
+          .set(dsl::durability.eq(durability))
+          .execute(&*conn)
+          .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))?;
+      }
+      Ok(())
+    })?;
+    Ok(())
+  }
+
+  /// Deletes an item, along with any inventory or equipment entry of it.
+  pub fn delete<I: Into<UuidWrapper>>(&self, item_id: I) -> Result<()> {
+    // Cascading delete's the inventory and equipment items automatically
+    diesel::delete(dsl::item.find(&item_id.into()))
+      .execute(&*self.context.access())
+      .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))
+      .map_err(Into::into)
+  }
+
+  /// Updates the durability of items and removes the price of their repair
+  /// from the inventory paying for them, as a single transaction.
+  pub fn repair_items<I: Into<UuidWrapper>>(
+    &self,
+    items: &[(Uuid, i32)],
+    inventory_id: I,
+    price: i32,
+  ) -> Result<()> {
+    let conn = self.context.access();
+    let inventory_id = inventory_id.into();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      for &(item_id, durability) in items {
+        diesel::update(dsl::item.find(&UuidWrapper::from(item_id)))
+          .set(dsl::durability.eq(durability))
+          .execute(&*conn)
+          .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))?;
+      }
+
+      InventoryRepository::add_money_with(&conn, &inventory_id, -price)
+    })?;
+    Ok(())
+  }
+
+  /// Saves an item by updating or inserting it.
+  ///
+  /// Existing items are updated in place, since replacing them would cascade
+  /// to their inventory and equipment entries.
+  pub fn save(&self, item: &Item) -> Result<()> {
+    let conn = self.context.access();
+    conn.transaction(|| Self::save_with(&conn, item))?;
+    Ok(())
+  }
+
+  /// Saves an item, whilst deleting another item consumed by it.
+  pub fn save_consuming<I: Into<UuidWrapper>>(&self, item: &Item, consumed: I) -> Result<()> {
+    let conn = self.context.access();
+    let consumed = consumed.into();
+    conn.transaction::<_, diesel::result::Error, _>(|| {
+      // Cascading delete's the inventory item automatically
+      diesel::delete(dsl::item.find(&consumed))
+        .execute(&*conn)
+        .and_then(|count| (count == 1).ok_or(diesel::result::Error::NotFound))?;
+      Self::save_with(&conn, item)
+    })?;
+    Ok(())
+  }
+
+  /// Saves an item using an existing connection.
+  fn save_with(conn: &SqliteConnection, item: &Item) -> QueryResult<()> {
+    let count = diesel::update(dsl::item.find(&item.id))
+      .set(item)
+      .execute(conn)?;
+    if count == 0 {
+      diesel::insert_into(dsl::item).values(item).execute(conn)?;
+    }
+    Ok(())
+  }
 }
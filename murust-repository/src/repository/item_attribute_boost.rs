@@ -0,0 +1,28 @@
+use context::{DataContext, DataContextInner};
+use diesel::prelude::*;
+use error::Result;
+use models::ItemAttributeBoost;
+use schema::item_attribute_boost::dsl;
+
+/// A repository for item attribute boosts.
+#[derive(Clone)]
+pub struct ItemAttributeBoostRepository {
+  context: DataContextInner,
+}
+
+impl ItemAttributeBoostRepository {
+  /// Creates a new item attribute boost repository instance.
+  pub fn new(context: &DataContext) -> Self {
+    ItemAttributeBoostRepository {
+      context: context.inner(),
+    }
+  }
+
+  /// Returns an item definition's attribute boosts.
+  pub fn find_by_item_code(&self, item_code: i32) -> Result<Vec<ItemAttributeBoost>> {
+    dsl::item_attribute_boost
+      .filter(dsl::item_code.eq(&item_code))
+      .get_results::<ItemAttributeBoost>(&*self.context.access())
+      .map_err(Into::into)
+  }
+}
@@ -0,0 +1,28 @@
+use context::{DataContext, DataContextInner};
+use diesel::prelude::*;
+use error::Result;
+use models::ItemAttributeRequirement;
+use schema::item_attribute_requirement::dsl;
+
+/// A repository for item attribute requirements.
+#[derive(Clone)]
+pub struct ItemAttributeRequirementRepository {
+  context: DataContextInner,
+}
+
+impl ItemAttributeRequirementRepository {
+  /// Creates a new item attribute requirement repository instance.
+  pub fn new(context: &DataContext) -> Self {
+    ItemAttributeRequirementRepository {
+      context: context.inner(),
+    }
+  }
+
+  /// Returns an item definition's attribute requirements.
+  pub fn find_by_item_code(&self, item_code: i32) -> Result<Vec<ItemAttributeRequirement>> {
+    dsl::item_attribute_requirement
+      .filter(dsl::item_code.eq(&item_code))
+      .get_results::<ItemAttributeRequirement>(&*self.context.access())
+      .map_err(Into::into)
+  }
+}
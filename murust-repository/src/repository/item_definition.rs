@@ -26,4 +26,13 @@ impl ItemDefinitionRepository {
       .optional()
       .map_err(Into::into)
   }
+
+  /// Returns all item definitions dropped by monsters of a level.
+  pub fn find_droppable_by_level(&self, level: i32) -> Result<Vec<ItemDefinition>> {
+    dsl::item_definition
+      .filter(dsl::drop_from_monster.eq(true))
+      .filter(dsl::drop_level.le(level))
+      .get_results::<ItemDefinition>(&*self.context.access())
+      .map_err(Into::into)
+  }
 }
@@ -0,0 +1,48 @@
+use context::{DataContext, DataContextInner};
+use diesel::prelude::*;
+use error::Result;
+use models::{ItemSet, ItemSetBonus, ItemSetMember};
+use schema::{self, item_set::dsl};
+
+/// A repository for ancient item sets.
+#[derive(Clone)]
+pub struct ItemSetRepository {
+  context: DataContextInner,
+}
+
+impl ItemSetRepository {
+  /// Creates a new item set repository instance.
+  pub fn new(context: &DataContext) -> Self {
+    ItemSetRepository {
+      context: context.inner(),
+    }
+  }
+
+  /// Returns the set of a tier that an item code belongs to.
+  pub fn find_by_item_code_and_tier(&self, item_code: i32, tier: i32) -> Result<Option<ItemSet>> {
+    dsl::item_set
+      .inner_join(schema::item_set_member::table)
+      .select(schema::item_set::all_columns)
+      .filter(schema::item_set_member::dsl::item_code.eq(&item_code))
+      .filter(dsl::tier.eq(&tier))
+      .first::<ItemSet>(&*self.context.access())
+      .optional()
+      .map_err(Into::into)
+  }
+
+  /// Returns a set's member items.
+  pub fn find_members_by_id(&self, item_set_id: i32) -> Result<Vec<ItemSetMember>> {
+    schema::item_set_member::table
+      .filter(schema::item_set_member::dsl::item_set_id.eq(&item_set_id))
+      .get_results::<ItemSetMember>(&*self.context.access())
+      .map_err(Into::into)
+  }
+
+  /// Returns a set's tiered bonuses.
+  pub fn find_bonuses_by_id(&self, item_set_id: i32) -> Result<Vec<ItemSetBonus>> {
+    schema::item_set_bonus::table
+      .filter(schema::item_set_bonus::dsl::item_set_id.eq(&item_set_id))
+      .get_results::<ItemSetBonus>(&*self.context.access())
+      .map_err(Into::into)
+  }
+}
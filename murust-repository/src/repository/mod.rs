@@ -1,13 +1,31 @@
 pub use self::account::AccountRepository;
 pub use self::character::CharacterRepository;
+pub use self::character_skill::CharacterSkillRepository;
 pub use self::inventory::InventoryRepository;
 pub use self::item::ItemRepository;
+pub use self::item_attribute_boost::ItemAttributeBoostRepository;
+pub use self::item_attribute_requirement::ItemAttributeRequirementRepository;
 pub use self::item_definition::ItemDefinitionRepository;
 pub use self::item_eligible_class::ItemEligibleClassRepository;
+pub use self::item_set::ItemSetRepository;
+pub use self::monster_definition::MonsterDefinitionRepository;
+pub use self::monster_spawn::MonsterSpawnRepository;
+pub use self::npc::NpcRepository;
+pub use self::skill_definition::SkillDefinitionRepository;
+pub use self::skill_eligible_class::SkillEligibleClassRepository;
 
 mod account;
 mod character;
+mod character_skill;
 mod inventory;
 mod item;
+mod item_attribute_boost;
+mod item_attribute_requirement;
 mod item_definition;
 mod item_eligible_class;
+mod item_set;
+mod monster_definition;
+mod monster_spawn;
+mod npc;
+mod skill_definition;
+mod skill_eligible_class;
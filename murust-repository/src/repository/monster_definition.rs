@@ -0,0 +1,29 @@
+use context::{DataContext, DataContextInner};
+use diesel::prelude::*;
+use error::Result;
+use models::MonsterDefinition;
+use schema::monster_definition::dsl;
+
+/// A repository for monster definitions.
+#[derive(Clone)]
+pub struct MonsterDefinitionRepository {
+  context: DataContextInner,
+}
+
+impl MonsterDefinitionRepository {
+  /// Creates a new monster definition repository instance.
+  pub fn new(context: &DataContext) -> Self {
+    MonsterDefinitionRepository {
+      context: context.inner(),
+    }
+  }
+
+  /// Returns a monster definition by its class.
+  pub fn find_by_class(&self, class: i32) -> Result<Option<MonsterDefinition>> {
+    dsl::monster_definition
+      .find(class)
+      .first::<MonsterDefinition>(&*self.context.access())
+      .optional()
+      .map_err(Into::into)
+  }
+}
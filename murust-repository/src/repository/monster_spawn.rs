@@ -0,0 +1,30 @@
+use context::{DataContext, DataContextInner};
+use diesel::prelude::*;
+use error::Result;
+use models::{MonsterDefinition, MonsterSpawn};
+use schema::{self, monster_spawn::dsl};
+
+/// A repository for monster spawns.
+#[derive(Clone)]
+pub struct MonsterSpawnRepository {
+  context: DataContextInner,
+}
+
+impl MonsterSpawnRepository {
+  /// Creates a new monster spawn repository instance.
+  pub fn new(context: &DataContext) -> Self {
+    MonsterSpawnRepository {
+      context: context.inner(),
+    }
+  }
+
+  /// Returns a map's monster spawns, including their definitions.
+  pub fn find_by_map(&self, map: i32) -> Result<Vec<(MonsterSpawn, MonsterDefinition)>> {
+    dsl::monster_spawn
+      .inner_join(schema::monster_definition::table)
+      .filter(dsl::map.eq(&map))
+      .order(dsl::id)
+      .load(&*self.context.access())
+      .map_err(Into::into)
+  }
+}
@@ -0,0 +1,29 @@
+use context::{DataContext, DataContextInner};
+use diesel::prelude::*;
+use error::Result;
+use models::Npc;
+use schema::npc::dsl;
+
+/// A repository for NPCs.
+#[derive(Clone)]
+pub struct NpcRepository {
+  context: DataContextInner,
+}
+
+impl NpcRepository {
+  /// Creates a new NPC repository instance.
+  pub fn new(context: &DataContext) -> Self {
+    NpcRepository {
+      context: context.inner(),
+    }
+  }
+
+  /// Returns a map's NPCs.
+  pub fn find_by_map(&self, map: i32) -> Result<Vec<Npc>> {
+    dsl::npc
+      .filter(dsl::map.eq(&map))
+      .order(dsl::id)
+      .get_results::<Npc>(&*self.context.access())
+      .map_err(Into::into)
+  }
+}
@@ -0,0 +1,37 @@
+use context::{DataContext, DataContextInner};
+use diesel::prelude::*;
+use error::Result;
+use models::SkillDefinition;
+use schema::skill_definition::dsl;
+
+/// A repository for skill definitions.
+#[derive(Clone)]
+pub struct SkillDefinitionRepository {
+  context: DataContextInner,
+}
+
+impl SkillDefinitionRepository {
+  /// Creates a new skill definition repository instance.
+  pub fn new(context: &DataContext) -> Self {
+    SkillDefinitionRepository {
+      context: context.inner(),
+    }
+  }
+
+  /// Returns a skill definition by its ID.
+  pub fn find_by_id(&self, id: i32) -> Result<Option<SkillDefinition>> {
+    dsl::skill_definition
+      .find(id)
+      .first::<SkillDefinition>(&*self.context.access())
+      .optional()
+      .map_err(Into::into)
+  }
+
+  /// Returns all skill definitions.
+  pub fn find_all(&self) -> Result<Vec<SkillDefinition>> {
+    dsl::skill_definition
+      .order(dsl::id)
+      .load::<SkillDefinition>(&*self.context.access())
+      .map_err(Into::into)
+  }
+}
@@ -0,0 +1,28 @@
+use context::{DataContext, DataContextInner};
+use diesel::prelude::*;
+use error::Result;
+use models::SkillEligibleClass;
+use schema::skill_eligible_class::dsl;
+
+/// A repository for eligible skill classes.
+#[derive(Clone)]
+pub struct SkillEligibleClassRepository {
+  context: DataContextInner,
+}
+
+impl SkillEligibleClassRepository {
+  /// Creates a new eligible skill class repository instance.
+  pub fn new(context: &DataContext) -> Self {
+    SkillEligibleClassRepository {
+      context: context.inner(),
+    }
+  }
+
+  /// Returns a skill definition's eligible classes.
+  pub fn find_by_skill_id(&self, skill_id: i32) -> Result<Vec<SkillEligibleClass>> {
+    dsl::skill_eligible_class
+      .filter(dsl::skill_id.eq(&skill_id))
+      .get_results::<SkillEligibleClass>(&*self.context.access())
+      .map_err(Into::into)
+  }
+}
@@ -14,6 +14,8 @@ table! {
         logged_in -> Bool,
         failed_login_attempts -> Integer,
         failed_login_time -> Nullable<BigInt>,
+        vault_id -> Binary,
+        vault_password -> Nullable<Integer>,
     }
 }
 
@@ -24,7 +26,8 @@ table! {
         name -> Text,
         level -> Integer,
         class -> Text,
-        experience -> Integer,
+        experience -> BigInt,
+        points -> Integer,
         strength -> Integer,
         agility -> Integer,
         vitality -> Integer,
@@ -39,6 +42,13 @@ table! {
     }
 }
 
+table! {
+    character_skill (character_id, skill_id) {
+        character_id -> Integer,
+        skill_id -> Integer,
+    }
+}
+
 table! {
     equipment_item (character_id, slot) {
         character_id -> Integer,
@@ -70,6 +80,11 @@ table! {
         code -> Integer,
         level -> Integer,
         durability -> Integer,
+        skill -> Bool,
+        luck -> Bool,
+        option -> Integer,
+        excellent -> Integer,
+        tier -> Integer,
     }
 }
 
@@ -109,8 +124,106 @@ table! {
     }
 }
 
+table! {
+    item_set (id) {
+        id -> Integer,
+        name -> Text,
+        tier -> Integer,
+    }
+}
+
+table! {
+    item_set_bonus (item_set_id, pieces, attribute) {
+        item_set_id -> Integer,
+        pieces -> Integer,
+        attribute -> Text,
+        bonus -> Integer,
+    }
+}
+
+table! {
+    item_set_member (item_set_id, item_code) {
+        item_set_id -> Integer,
+        item_code -> Integer,
+    }
+}
+
+table! {
+    monster_definition (class) {
+        class -> Integer,
+        name -> Text,
+        level -> Integer,
+        max_health -> Integer,
+        min_damage -> Integer,
+        max_damage -> Integer,
+        defense -> Integer,
+        attack_rate -> Integer,
+        defense_rate -> Integer,
+        move_range -> Integer,
+        attack_range -> Integer,
+        view_range -> Integer,
+        move_delay -> Integer,
+        attack_delay -> Integer,
+        respawn_time -> Integer,
+    }
+}
+
+table! {
+    monster_spawn (id) {
+        id -> Integer,
+        monster_class -> Integer,
+        map -> Integer,
+        start_x -> Integer,
+        start_y -> Integer,
+        end_x -> Integer,
+        end_y -> Integer,
+        direction -> Nullable<Integer>,
+        count -> Integer,
+    }
+}
+
+table! {
+    npc (id) {
+        id -> Integer,
+        class -> Integer,
+        name -> Text,
+        kind -> Text,
+        map -> Integer,
+        position_x -> Integer,
+        position_y -> Integer,
+        direction -> Integer,
+    }
+}
+
+table! {
+    skill_definition (id) {
+        id -> Integer,
+        name -> Text,
+        damage -> Integer,
+        magic -> Bool,
+        mana_cost -> Integer,
+        ability_cost -> Integer,
+        cooldown -> Integer,
+        range -> Integer,
+        area -> Text,
+        area_size -> Integer,
+        min_level -> Integer,
+        min_energy -> Integer,
+    }
+}
+
+table! {
+    skill_eligible_class (skill_id, class) {
+        skill_id -> Integer,
+        class -> Text,
+    }
+}
+
+joinable!(account -> inventory (vault_id));
 joinable!(character -> account (account_id));
 joinable!(character -> inventory (inventory_id));
+joinable!(character_skill -> character (character_id));
+joinable!(character_skill -> skill_definition (skill_id));
 joinable!(equipment_item -> character (character_id));
 joinable!(equipment_item -> item (item_id));
 joinable!(inventory_item -> inventory (inventory_id));
@@ -119,10 +232,16 @@ joinable!(item -> item_definition (code));
 joinable!(item_attribute_boost -> item_definition (item_code));
 joinable!(item_attribute_requirement -> item_definition (item_code));
 joinable!(item_eligible_class -> item_definition (item_code));
+joinable!(item_set_bonus -> item_set (item_set_id));
+joinable!(item_set_member -> item_definition (item_code));
+joinable!(item_set_member -> item_set (item_set_id));
+joinable!(monster_spawn -> monster_definition (monster_class));
+joinable!(skill_eligible_class -> skill_definition (skill_id));
 
 allow_tables_to_appear_in_same_query!(
   account,
   character,
+  character_skill,
   equipment_item,
   inventory,
   inventory_item,
@@ -131,4 +250,12 @@ allow_tables_to_appear_in_same_query!(
   item_attribute_requirement,
   item_definition,
   item_eligible_class,
+  item_set,
+  item_set_bonus,
+  item_set_member,
+  monster_definition,
+  monster_spawn,
+  npc,
+  skill_definition,
+  skill_eligible_class,
 );
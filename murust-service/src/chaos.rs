@@ -0,0 +1,310 @@
+//! Chaos machine recipes, as mixed at the Chaos Goblin.
+
+use murust_data_model::entities::{item, Item};
+use murust_data_model::types::{ItemCode, ItemGroup};
+use pricing;
+use rand::Rng;
+
+const JEWEL_OF_CHAOS: (ItemGroup, u16) = (ItemGroup::Wings, 15);
+const JEWEL_OF_BLESS: (ItemGroup, u16) = (ItemGroup::Potion, 13);
+const JEWEL_OF_SOUL: (ItemGroup, u16) = (ItemGroup::Potion, 14);
+const DEVILS_EYE: (ItemGroup, u16) = (ItemGroup::Potion, 17);
+const DEVILS_KEY: (ItemGroup, u16) = (ItemGroup::Potion, 18);
+const DEVILS_INVITATION: (ItemGroup, u16) = (ItemGroup::Potion, 19);
+const SCROLL_OF_ARCHANGEL: (ItemGroup, u16) = (ItemGroup::Helper, 16);
+const BLOOD_BONE: (ItemGroup, u16) = (ItemGroup::Helper, 17);
+const INVISIBILITY_CLOAK: (ItemGroup, u16) = (ItemGroup::Helper, 18);
+const HORN_OF_UNIRIA: (ItemGroup, u16) = (ItemGroup::Helper, 2);
+const HORN_OF_DINORANT: (ItemGroup, u16) = (ItemGroup::Helper, 3);
+const CHAOS_WEAPONS: [(ItemGroup, u16); 3] = [
+  (ItemGroup::Axe, 6),
+  (ItemGroup::Bow, 6),
+  (ItemGroup::Staff, 7),
+];
+const FIRST_WINGS: [(ItemGroup, u16); 3] = [
+  (ItemGroup::Wings, 0),
+  (ItemGroup::Wings, 1),
+  (ItemGroup::Wings, 2),
+];
+
+/// The zen charged for each Devil Square invitation, indexed by level.
+static DEVIL_SQUARE_COSTS: [u32; 6] = [100_000, 200_000, 400_000, 700_000, 1_100_000, 1_600_000];
+
+/// The zen charged for each Blood Castle cloak, indexed by level.
+static BLOOD_CASTLE_COSTS: [u32; 8] = [
+  50_000, 80_000, 150_000, 250_000, 400_000, 600_000, 850_000, 1_050_000,
+];
+
+/// A chaos mix, as described by the ingredients of a recipe.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ChaosMix {
+  /// The zen charged for attempting the mix.
+  pub cost: u32,
+  /// The chance (in percent) of the mix succeeding.
+  pub success_rate: u32,
+  /// The product of a successful mix.
+  pub product: ChaosProduct,
+}
+
+impl ChaosMix {
+  /// Rolls whether an attempt at the mix succeeds.
+  pub fn succeeds<R: Rng>(&self, random: &mut R) -> bool {
+    random.gen_range(0, 100) < self.success_rate
+  }
+}
+
+/// The product of a successful chaos mix.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ChaosProduct {
+  /// One of the ingredients raised to a level, consuming the rest.
+  Upgrade { item: item::Id, level: u8 },
+  /// A new item chosen among candidates, consuming all ingredients.
+  Create { candidates: Vec<ItemCode>, level: u8 },
+}
+
+impl ChaosProduct {
+  fn create(code: (ItemGroup, u16), level: u8) -> Self {
+    ChaosProduct::Create {
+      candidates: vec![code.into()],
+      level,
+    }
+  }
+}
+
+/// A chaos machine recipe.
+pub trait ChaosRecipe: Send + Sync {
+  /// Returns the mix described by a set of ingredients, unless they do not
+  /// match the recipe.
+  fn evaluate(&self, items: &[&Item]) -> Option<ChaosMix>;
+}
+
+/// A registry of all chaos machine recipes.
+#[derive(Default)]
+pub struct ChaosRecipes {
+  recipes: Vec<Box<ChaosRecipe>>,
+}
+
+impl ChaosRecipes {
+  /// Constructs a registry with all supported recipes.
+  pub fn with_defaults() -> Self {
+    let mut recipes = Self::default();
+    recipes.register(ItemUpgrade(10));
+    recipes.register(ItemUpgrade(11));
+    recipes.register(DevilSquareInvitation);
+    recipes.register(BloodCastleCloak);
+    recipes.register(Dinorant);
+    recipes.register(FirstWings);
+    recipes
+  }
+
+  /// Registers a recipe, after any previous ones.
+  pub fn register<R: ChaosRecipe + 'static>(&mut self, recipe: R) {
+    self.recipes.push(Box::new(recipe));
+  }
+
+  /// Returns the mix described by a set of ingredients, according to the
+  /// first recipe they match.
+  pub fn evaluate(&self, items: &[&Item]) -> Option<ChaosMix> {
+    self
+      .recipes
+      .iter()
+      .filter_map(|recipe| recipe.evaluate(items))
+      .next()
+  }
+}
+
+/// Raises an item to +10 or +11, using a Jewel of Chaos along with one
+/// Jewel of Bless and Soul for each level above +9.
+pub struct ItemUpgrade(pub u8);
+
+impl ChaosRecipe for ItemUpgrade {
+  fn evaluate(&self, items: &[&Item]) -> Option<ChaosMix> {
+    let level = self.0;
+    let jewels = level.checked_sub(9)? as usize;
+
+    let mut ingredients = Ingredients::new(items);
+    ingredients.take_exactly(JEWEL_OF_CHAOS, 1)?;
+    ingredients.take_exactly(JEWEL_OF_BLESS, jewels)?;
+    ingredients.take_exactly(JEWEL_OF_SOUL, jewels)?;
+
+    let item = ingredients.take_only()?;
+    if !is_upgradeable(item) || item.level != level - 1 {
+      return None;
+    }
+
+    Some(ChaosMix {
+      cost: 2_000_000 * jewels as u32,
+      success_rate: if item.luck { 75 } else { 50 },
+      product: ChaosProduct::Upgrade {
+        item: item.id,
+        level,
+      },
+    })
+  }
+}
+
+/// Creates a Devil Square invitation from a Devil's Eye and Key of the same
+/// level.
+pub struct DevilSquareInvitation;
+
+impl ChaosRecipe for DevilSquareInvitation {
+  fn evaluate(&self, items: &[&Item]) -> Option<ChaosMix> {
+    let mut ingredients = Ingredients::new(items);
+    ingredients.take_exactly(JEWEL_OF_CHAOS, 1)?;
+    let eye = ingredients.take_exactly(DEVILS_EYE, 1)?[0];
+    let key = ingredients.take_exactly(DEVILS_KEY, 1)?[0];
+
+    let cost = level_cost(&DEVIL_SQUARE_COSTS, eye.level)?;
+    if eye.level != key.level || !ingredients.is_empty() {
+      return None;
+    }
+
+    Some(ChaosMix {
+      cost,
+      success_rate: 80,
+      product: ChaosProduct::create(DEVILS_INVITATION, eye.level),
+    })
+  }
+}
+
+/// Creates a Blood Castle cloak from a Scroll of Archangel and a Blood Bone of
+/// the same level.
+pub struct BloodCastleCloak;
+
+impl ChaosRecipe for BloodCastleCloak {
+  fn evaluate(&self, items: &[&Item]) -> Option<ChaosMix> {
+    let mut ingredients = Ingredients::new(items);
+    ingredients.take_exactly(JEWEL_OF_CHAOS, 1)?;
+    let scroll = ingredients.take_exactly(SCROLL_OF_ARCHANGEL, 1)?[0];
+    let bone = ingredients.take_exactly(BLOOD_BONE, 1)?[0];
+
+    let cost = level_cost(&BLOOD_CASTLE_COSTS, scroll.level)?;
+    if scroll.level != bone.level || !ingredients.is_empty() {
+      return None;
+    }
+
+    Some(ChaosMix {
+      cost,
+      success_rate: 80,
+      product: ChaosProduct::create(INVISIBILITY_CLOAK, scroll.level),
+    })
+  }
+}
+
+/// Creates a Horn of Dinorant from ten Horns of Uniria.
+pub struct Dinorant;
+
+impl ChaosRecipe for Dinorant {
+  fn evaluate(&self, items: &[&Item]) -> Option<ChaosMix> {
+    let mut ingredients = Ingredients::new(items);
+    ingredients.take_exactly(JEWEL_OF_CHAOS, 1)?;
+    ingredients.take_exactly(HORN_OF_UNIRIA, 10)?;
+    if !ingredients.is_empty() {
+      return None;
+    }
+
+    Some(ChaosMix {
+      cost: 500_000,
+      success_rate: 70,
+      product: ChaosProduct::create(HORN_OF_DINORANT, 0),
+    })
+  }
+}
+
+/// Creates a random pair of first wings by sacrificing a chaos weapon along
+/// with items of at least +4 with an additional option.
+///
+/// The success rate grows with the value of the items sacrificed, and so does
+/// the cost.
+pub struct FirstWings;
+
+impl ChaosRecipe for FirstWings {
+  fn evaluate(&self, items: &[&Item]) -> Option<ChaosMix> {
+    let mut ingredients = Ingredients::new(items);
+    ingredients.take_exactly(JEWEL_OF_CHAOS, 1)?;
+
+    let weapons = ingredients.take(|item| CHAOS_WEAPONS.contains(&item.code.tuple()));
+    let sacrifices =
+      ingredients.take(|item| is_upgradeable(item) && item.level >= 4 && item.option > 0);
+    if weapons.len() != 1 || sacrifices.is_empty() || !ingredients.is_empty() {
+      return None;
+    }
+
+    let value = weapons
+      .iter()
+      .chain(&sacrifices)
+      .map(|item| u64::from(pricing::buy_price(item)))
+      .sum::<u64>();
+    let success_rate = (value / 20_000).min(100) as u32;
+    if success_rate == 0 {
+      return None;
+    }
+
+    Some(ChaosMix {
+      cost: success_rate * 10_000,
+      success_rate,
+      product: ChaosProduct::Create {
+        candidates: FIRST_WINGS.iter().map(|&code| code.into()).collect(),
+        level: 0,
+      },
+    })
+  }
+}
+
+/// The ingredients of a mix, yet to be accounted for by a recipe.
+struct Ingredients<'a> {
+  items: Vec<&'a Item>,
+}
+
+impl<'a> Ingredients<'a> {
+  fn new(items: &[&'a Item]) -> Self {
+    Ingredients {
+      items: items.to_vec(),
+    }
+  }
+
+  /// Takes all ingredients matching a predicate.
+  fn take<P: Fn(&Item) -> bool>(&mut self, predicate: P) -> Vec<&'a Item> {
+    let (taken, rest): (Vec<&'a Item>, Vec<&'a Item>) =
+      self.items.drain(..).partition(|item| predicate(item));
+    self.items = rest;
+    taken
+  }
+
+  /// Takes all ingredients of a kind, unless there are not exactly as many as
+  /// expected.
+  fn take_exactly(&mut self, code: (ItemGroup, u16), count: usize) -> Option<Vec<&'a Item>> {
+    let taken = self.take(|item| item.code.tuple() == code);
+    if taken.len() == count {
+      Some(taken)
+    } else {
+      None
+    }
+  }
+
+  /// Takes the only remaining ingredient, if there is exactly one.
+  fn take_only(&mut self) -> Option<&'a Item> {
+    if self.items.len() == 1 {
+      self.items.pop()
+    } else {
+      None
+    }
+  }
+
+  /// Returns whether all ingredients have been accounted for.
+  fn is_empty(&self) -> bool { self.items.is_empty() }
+}
+
+/// Returns whether an item can be upgraded, i.e weapons, armor and wings.
+pub(crate) fn is_upgradeable(item: &Item) -> bool {
+  item.equippable_slot.is_some() && (item.code.group() as u8) <= (ItemGroup::Wings as u8)
+}
+
+/// Returns the cost of mixing an item level, if it is within the range of a
+/// cost table.
+fn level_cost(costs: &[u32], level: u8) -> Option<u32> {
+  (level as usize)
+    .checked_sub(1)
+    .and_then(|index| costs.get(index))
+    .cloned()
+}
@@ -9,6 +9,8 @@ pub enum Error {
   MissingAssociation(String),
   #[fail(display = "The specified entity does not exist in the persistence storage")]
   MissingPersistence,
+  #[fail(display = "The shop entry '{}' is invalid", _0)]
+  InvalidShopEntry(String),
   #[fail(display = "A repository error occurred.")]
   Repository(#[cause] murust_repository::Error),
   #[fail(display = "An entity mapping error occurred.")]
@@ -13,23 +13,30 @@ extern crate bcrypt;
 extern crate murust_data_model;
 extern crate murust_repository;
 extern crate num_traits;
+extern crate rand;
 extern crate uuid;
 
 pub use self::error::Error;
 pub use self::manager::ServiceManager;
 pub use self::services::*;
 
+pub mod chaos;
 mod error;
 mod manager;
 mod mapping;
+pub mod pricing;
 mod services;
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use murust_data_model::entities::item;
-  use murust_data_model::types::{Class, ItemSlot};
+  use chaos::{ChaosProduct, ChaosRecipes};
+  use murust_data_model::entities::{item, Character, Item, Loot, TradeOffer};
+  use murust_data_model::types::{
+    CharacterStat, Class, ItemAttribute, ItemGroup, ItemLocation, ItemSlot, NpcKind, Position,
+  };
   use murust_repository::*;
+  use rand::{SeedableRng, XorShiftRng};
   use tempdir::TempDir;
 
   fn setup_test_env() -> (TempDir, ServiceManager) {
@@ -77,6 +84,16 @@ mod tests {
 
     let weapon = characters[0].equipment[ItemSlot::WeaponRight].as_ref();
     assert_eq!(weapon.unwrap().name, "Short Sword");
+    assert!(weapon.unwrap().skill && weapon.unwrap().luck);
+
+    let armor = characters[0].equipment[ItemSlot::Armor].as_ref().unwrap();
+    assert_eq!(armor.option, 4);
+    assert!(armor.is_excellent());
+
+    let helm = characters[0].equipment[ItemSlot::Helm].as_ref().unwrap();
+    assert_eq!(helm.set.as_ref().map(|set| set.name.as_str()), Some("Hyon"));
+    assert_eq!((helm.tier(), armor.tier()), (1, 0));
+    assert_eq!(characters[0].skills, vec![19, 20, 21]);
   }
 
   #[test]
@@ -101,6 +118,506 @@ mod tests {
     assert!(service.find_by_name("deadbeef").unwrap().is_none());
   }
 
+  #[test]
+  fn gain_experience_and_save_progress() {
+    let (_temp, manager) = setup_test_env();
+    let experience = manager.experience_service();
+    let characters = manager.character_service();
+
+    let mut character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(experience.experience_for_level(4), 1080);
+
+    let levels = experience.gain_experience(&mut character, 1080);
+    assert_eq!(levels, 1);
+    assert_eq!(character.level, 4);
+    assert_eq!(character.points, 5);
+
+    assert!(character.add_stat_point(CharacterStat::Strength));
+    assert!(!character.add_stat_point(CharacterStat::Command));
+    characters.save_progress(&character).unwrap();
+
+    let character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(character.level, 4);
+    assert_eq!(character.experience, 1080);
+    assert_eq!(character.points, 4);
+    assert_eq!(character.strength, 29);
+  }
+
+  #[test]
+  fn apply_death_penalty_and_save_money() {
+    let (_temp, manager) = setup_test_env();
+    let experience = manager.experience_service();
+    let characters = manager.character_service();
+
+    let mut character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    character.inventory.money = 1000;
+    assert_eq!(experience.apply_death_penalty(&mut character), DeathPenalty::default());
+
+    // Characters never lose a level, only the experience gained within it
+    character.level = 50;
+    character.experience = experience.experience_for_level(50) + 100;
+    let penalty = experience.apply_death_penalty(&mut character);
+    assert_eq!(penalty, DeathPenalty { experience: 100, money: 40 });
+    assert_eq!(character.experience, experience.experience_for_level(50));
+
+    // Murderers lose four times as much zen as commoners
+    character.player_kills = 3;
+    let penalty = experience.apply_death_penalty(&mut character);
+    assert_eq!(penalty.money, 153);
+
+    // Only the zen lost is persisted, relative to the stored amount
+    characters.save_money_change(&character, -193).unwrap();
+    let character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(character.inventory.money, 1144);
+  }
+
+  #[test]
+  fn move_items_and_save_them() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.character_service();
+
+    let mut character = service.find_by_name("deadbeef").unwrap().unwrap();
+    let (source, target) = (
+      ItemLocation::Inventory(0),
+      ItemLocation::Equipment(ItemSlot::WeaponLeft),
+    );
+    assert!(character.move_item(source, target));
+    service.save_item_move(&character, source, target).unwrap();
+
+    let mut character = service.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(character.inventory.items(), 0);
+    assert_eq!(
+      character.equipment[ItemSlot::WeaponLeft]
+        .as_ref()
+        .map(|item| item.name.as_str()),
+      Some("Kris")
+    );
+
+    let (source, target) = (target, ItemLocation::Inventory(10));
+    assert!(character.move_item(source, target));
+    service.save_item_move(&character, source, target).unwrap();
+
+    let character = service.find_by_name("deadbeef").unwrap().unwrap();
+    assert!(character.equipment[ItemSlot::WeaponLeft].is_none());
+    assert!(character.inventory.get_item_at_slot(10).is_some());
+  }
+
+  #[test]
+  fn monster_experience_decreases_for_weaker_monsters() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.experience_service();
+
+    assert_eq!(service.monster_experience(1, 2), 18);
+    assert_eq!(service.monster_experience(30, 2), 7);
+    assert_eq!(service.monster_experience(400, 0), 1);
+  }
+
+  #[test]
+  fn find_npcs_by_map() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.npc_service();
+
+    let npcs = service.find_by_map(3).unwrap();
+    assert_eq!(npcs.len(), 1);
+    assert_eq!(npcs[0].kind, NpcKind::ChaosGoblin);
+    assert_eq!(npcs[0].position, Position::new(180, 106));
+  }
+
+  #[test]
+  fn buy_and_sell_shop_items() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.shop_service();
+    let characters = manager.character_service();
+
+    let shop = service
+      .parse(1, "// Hanzo\n0 2 0 0 0 0 0 0\n\n0 0 0 0 1 0 0 0 // Kris\n")
+      .unwrap();
+    assert_eq!(shop.items(), 2);
+    assert!(service.parse(1, "0 2 0").is_err());
+
+    let rapier = shop.get_item_at_slot(0).unwrap();
+    assert_eq!((rapier.name.as_str(), rapier.durability), ("Rapier", 23));
+    assert_eq!(pricing::buy_price(rapier), 596);
+    assert_eq!(pricing::buy_price(shop.get_item_at_slot(1).unwrap()), 767);
+
+    let mut character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(service.buy(&mut character, &shop, 0).unwrap(), Ok(1));
+    assert_eq!(character.inventory.money, 741);
+    assert_eq!(
+      service.buy(&mut character, &shop, 1).unwrap(),
+      Err(ShopError::InsufficientMoney)
+    );
+    assert_eq!(service.sell(&mut character, 0).unwrap(), Ok(1086));
+    assert_eq!(service.sell(&mut character, 0).unwrap(), Err(ShopError::NotFound));
+
+    let character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(character.inventory.money, 1086);
+    assert_eq!(character.inventory.items(), 1);
+    assert_eq!(character.inventory.get_item_at_slot(1).unwrap().name, "Rapier");
+  }
+
+  #[test]
+  fn wear_down_and_repair_items() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.shop_service();
+    let items = manager.item_service();
+    let characters = manager.character_service();
+
+    let mut character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    let worn = (0..25)
+      .filter(|_| !character.equipment.wear_weapons().is_empty())
+      .count();
+    assert_eq!(worn, 1);
+
+    {
+      let sword = character.equipment[ItemSlot::WeaponRight].as_ref().unwrap();
+      assert_eq!(sword.durability, 21);
+      items.save_durability(sword).unwrap();
+    }
+
+    let helm = ItemLocation::Equipment(ItemSlot::Helm);
+    assert_eq!(
+      service.repair(&mut character, Some(helm), true).unwrap(),
+      Err(ShopError::InsufficientMoney)
+    );
+
+    character.inventory.money = 100_000;
+    characters.save_money_change(&character, 98_663).unwrap();
+    assert_eq!(
+      service.repair(&mut character, Some(helm), true).unwrap(),
+      Ok(vec![helm])
+    );
+    assert_eq!(character.inventory.money, 97_790);
+
+    assert_eq!(
+      service.repair(&mut character, Some(helm), false).unwrap(),
+      Err(ShopError::NotRepairable)
+    );
+    assert_eq!(
+      service
+        .repair(&mut character, Some(ItemLocation::Inventory(5)), false)
+        .unwrap(),
+      Err(ShopError::NotFound)
+    );
+
+    let character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(character.inventory.money, 97_790);
+    assert_eq!(character.equipment[ItemSlot::Helm].as_ref().unwrap().durability, 68);
+    assert_eq!(
+      character.equipment[ItemSlot::WeaponRight]
+        .as_ref()
+        .unwrap()
+        .durability,
+      21
+    );
+  }
+
+  #[test]
+  fn deposit_and_withdraw_vault_items_and_money() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.vault_service();
+    let accounts = manager.account_service();
+
+    let mut account = accounts.find_by_id(1).unwrap().unwrap();
+    {
+      let (character, vault) = (&mut account.characters[0], &mut account.vault);
+      assert_eq!((vault.money, vault.slots()), (5000, 120));
+
+      assert_eq!(service.deposit_item(character, vault, 0, 8).unwrap(), Ok(()));
+      assert_eq!(
+        service.deposit_item(character, vault, 0, 8).unwrap(),
+        Err(VaultError::InvalidMove)
+      );
+      assert_eq!(service.move_item(vault, 8, 119).unwrap(), Err(VaultError::InvalidMove));
+      assert_eq!(service.move_item(vault, 8, 9).unwrap(), Ok(()));
+      assert_eq!(
+        service.withdraw_money(character, vault, 5001).unwrap(),
+        Err(VaultError::InsufficientMoney)
+      );
+      assert_eq!(service.deposit_money(character, vault, 337).unwrap(), Ok(()));
+      assert!(vault.set_password(1234));
+    }
+    accounts.update(&account).unwrap();
+
+    // The vault is shared by the account, and locked once reloaded
+    let mut account = accounts.find_by_id(1).unwrap().unwrap();
+    let (character, vault) = (&mut account.characters[0], &mut account.vault);
+    assert_eq!((character.inventory.money, vault.money), (1000, 5337));
+    assert_eq!(vault.get_item_at_slot(9).unwrap().name, "Kris");
+    assert!(character.inventory.get_item_at_slot(0).is_none());
+    assert_eq!(
+      service.withdraw_item(character, vault, 9, 0).unwrap(),
+      Err(VaultError::Locked)
+    );
+
+    assert!(vault.unlock(1234));
+    assert_eq!(service.withdraw_item(character, vault, 9, 0).unwrap(), Ok(()));
+    assert_eq!(service.withdraw_money(character, vault, 5337).unwrap(), Ok(()));
+    assert_eq!(character.inventory.money, 6337);
+  }
+
+  #[test]
+  fn exchange_trade_offers() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.trade_service();
+    let characters = manager.character_service();
+    let shop = manager.shop_service();
+
+    let account = manager.account_service().find_by_id(1).unwrap().unwrap();
+    let mut character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    let (inventory_id, vault_id) = (character.inventory.id, account.vault.id);
+
+    let kris = character.inventory.get_item_at_slot(0).unwrap().clone();
+    assert!(character.inventory.lock_item(kris.id));
+    assert_eq!(shop.sell(&mut character, 0).unwrap(), Err(ShopError::ItemLocked));
+
+    let mut offer = TradeOffer::default();
+    offer.add_item_at_slot(0, kris).unwrap();
+    let mut other = TradeOffer::default();
+    other.money = 5001;
+
+    let exchange = |first: &TradeOffer, second: &TradeOffer| {
+      service
+        .exchange((inventory_id, first), (vault_id, second))
+        .unwrap()
+    };
+    assert_eq!(exchange(&offer, &other), Err(TradeError::InsufficientMoney));
+    other.money = 250;
+    assert_eq!(exchange(&offer, &other), Ok(()));
+    assert_eq!(exchange(&offer, &other), Err(TradeError::ItemNotFound));
+
+    characters.refresh_inventory(&mut character).unwrap();
+    assert!(character.inventory.get_item_at_slot(0).is_none());
+    assert_eq!(character.inventory.money, 1587);
+
+    let account = manager.account_service().find_by_id(1).unwrap().unwrap();
+    assert_eq!(account.vault.money, 4750);
+    assert_eq!(account.vault.get_item_at_slot(0).unwrap().name, "Kris");
+  }
+
+  #[test]
+  fn mix_chaos_recipes() {
+    let (_temp, manager) = setup_test_env();
+    let items = manager.item_service();
+    let characters = manager.character_service();
+
+    let create = |code: (ItemGroup, u16), level| {
+      let definition = items.find_definition_by_code(code.into()).unwrap().unwrap();
+      let mut item = Item::with_definition(definition);
+      item.level = level;
+      item
+    };
+
+    let recipes = ChaosRecipes::with_defaults();
+    let mut kris = create((ItemGroup::Sword, 0), 9);
+    kris.luck = true;
+    let chaos = create((ItemGroup::Wings, 15), 0);
+    let bless = create((ItemGroup::Potion, 13), 0);
+    let soul = create((ItemGroup::Potion, 14), 0);
+
+    let mix = recipes.evaluate(&[&kris, &chaos, &bless, &soul]).unwrap();
+    assert_eq!((mix.cost, mix.success_rate), (2_000_000, 75));
+    assert_eq!(mix.product, ChaosProduct::Upgrade { item: kris.id, level: 10 });
+    assert!(recipes.evaluate(&[&kris, &chaos, &bless]).is_none());
+
+    // First wings require a chaos weapon besides the items sacrificed
+    let mut armor = create((ItemGroup::Armor, 1), 4);
+    armor.option = 1;
+    let axe = create((ItemGroup::Axe, 6), 0);
+    assert!(recipes.evaluate(&[&armor, &chaos]).is_none());
+    assert!(recipes.evaluate(&[&axe, &axe, &armor, &chaos]).is_none());
+    let mix = recipes.evaluate(&[&axe, &armor, &chaos]).unwrap();
+    assert!(mix.success_rate > 0);
+    assert!(matches!(mix.product, ChaosProduct::Create { level: 0, .. }));
+
+    let mut character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    let add_ingredients = |character: &mut Character| {
+      let ingredients = vec![
+        create((ItemGroup::Wings, 15), 0),
+        create((ItemGroup::Potion, 17), 1),
+        create((ItemGroup::Potion, 18), 1),
+      ];
+      let ids = ingredients.iter().map(|item| item.id).collect::<Vec<_>>();
+      for item in ingredients {
+        character.inventory.add_item(item.clone()).unwrap();
+        let slot = character.inventory.get_item_slot(&item).unwrap();
+        items.add_to_inventory(character.inventory.id, slot, &item).unwrap();
+      }
+      ids
+    };
+    let seeded = |seed| {
+      let random = XorShiftRng::from_seed(seed);
+      ChaosService::with_random(manager.item_service(), ChaosRecipes::with_defaults(), random)
+    };
+
+    let service = seeded([1, 2, 3, 4]);
+    let ids = add_ingredients(&mut character);
+    let mut invalid = ids.clone();
+    invalid.push(character.inventory.get_item_at_slot(0).unwrap().id);
+    assert_eq!(
+      service.mix(&mut character, &invalid).unwrap(),
+      Err(ChaosError::InvalidItems)
+    );
+    assert_eq!(
+      service.mix(&mut character, &ids).unwrap(),
+      Err(ChaosError::InsufficientMoney)
+    );
+
+    // The ingredients and zen are spent whether or not the mix succeeds
+    character.inventory.money = 200_500;
+    characters.save_money_change(&character, 199_163).unwrap();
+    let slot = service.mix(&mut character, &ids).unwrap().unwrap();
+    assert_eq!(character.inventory.money, 100_500);
+    assert!(ids.iter().all(|&id| character.inventory.get_item(id).is_none()));
+
+    let product = slot
+      .and_then(|slot| character.inventory.get_item_at_slot(slot))
+      .map(|item| (item.name.clone(), item.level));
+    assert_eq!(product, Some(("Devil's Invitation".to_string(), 1)));
+
+    let ids = add_ingredients(&mut character);
+    assert_eq!(seeded([4, 3, 2, 1]).mix(&mut character, &ids).unwrap(), Ok(None));
+    assert_eq!(character.inventory.money, 500);
+    assert!(ids.iter().all(|&id| character.inventory.get_item(id).is_none()));
+
+    let character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(character.inventory.money, 500);
+    assert_eq!(character.inventory.items(), 2);
+  }
+
+  #[test]
+  fn apply_jewels_onto_items() {
+    let (_temp, manager) = setup_test_env();
+    let items = manager.item_service();
+    let characters = manager.character_service();
+
+    let (bless_code, soul_code, life_code) = (
+      (ItemGroup::Potion, 13),
+      (ItemGroup::Potion, 14),
+      (ItemGroup::Potion, 16),
+    );
+    let add_jewel = |character: &mut Character, code: (ItemGroup, u16)| {
+      let definition = items.find_definition_by_code(code.into()).unwrap().unwrap();
+      let jewel = Item::with_definition(definition);
+      character.inventory.add_item(jewel.clone()).unwrap();
+      let slot = character.inventory.get_item_slot(&jewel).unwrap();
+      items.add_to_inventory(character.inventory.id, slot, &jewel).unwrap();
+      slot
+    };
+
+    // The first roll of these seeds is 29 and 61 respectively, against the
+    // success rate of 50 for items without luck
+    let (success, failure) = ([4, 3, 2, 1], [1, 2, 3, 4]);
+    let apply = |character: &mut Character, seed, jewel, target| {
+      let service = JewelService::with_random(manager.item_service(), XorShiftRng::from_seed(seed));
+      service.apply(character, jewel, target).unwrap()
+    };
+    let kris = |character: &Character| {
+      let kris = character.inventory.get_item_at_slot(0).unwrap();
+      (kris.level, kris.option)
+    };
+    let set_kris_level = |character: &mut Character, level| {
+      character.inventory.get_item_at_slot_mut(0).unwrap().level = level;
+    };
+
+    // The Kris stands upright in the first slot, so the jewels follow it
+    let mut character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    let bless = add_jewel(&mut character, bless_code);
+    let soul = add_jewel(&mut character, soul_code);
+    let life = add_jewel(&mut character, life_code);
+    assert_eq!((bless, soul, life), (1, 2, 3));
+
+    assert_eq!(apply(&mut character, success, 0, bless), Err(JewelError::NotJewel));
+    assert_eq!(apply(&mut character, success, bless, soul), Err(JewelError::NotApplicable));
+    assert_eq!(apply(&mut character, success, soul, 0), Err(JewelError::NotApplicable));
+    assert_eq!(apply(&mut character, success, bless, 0), Ok(true));
+    assert_eq!(apply(&mut character, success, bless, 0), Err(JewelError::NotFound));
+    assert_eq!(kris(&character), (3, 0));
+
+    // A Jewel of Life raises the additional option, or removes it
+    assert_eq!(apply(&mut character, success, life, 0), Ok(true));
+    assert_eq!(kris(&character), (3, 1));
+    let life = add_jewel(&mut character, life_code);
+    assert_eq!(apply(&mut character, failure, life, 0), Ok(false));
+    assert_eq!(kris(&character), (3, 0));
+
+    // A Jewel of Soul raises the level, or lowers it, resetting it from +7
+    set_kris_level(&mut character, 6);
+    assert_eq!(apply(&mut character, success, soul, 0), Ok(true));
+    assert_eq!(kris(&character), (7, 0));
+    let soul = add_jewel(&mut character, soul_code);
+    assert_eq!(apply(&mut character, failure, soul, 0), Ok(false));
+    assert_eq!(kris(&character), (0, 0));
+
+    set_kris_level(&mut character, 6);
+    let soul = add_jewel(&mut character, soul_code);
+    assert_eq!(apply(&mut character, failure, soul, 0), Ok(false));
+    assert_eq!(kris(&character), (5, 0));
+
+    let character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(kris(&character), (5, 0));
+    assert_eq!(character.inventory.items(), 1);
+  }
+
+  #[test]
+  fn merge_and_consume_potions() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.potion_service();
+    let items = manager.item_service();
+    let characters = manager.character_service();
+
+    let mut character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    for &(index, count) in &[(1, 2), (1, 2), (0, 1)] {
+      let definition = items
+        .find_definition_by_code((ItemGroup::Potion, index).into())
+        .unwrap()
+        .unwrap();
+      let mut potion = Item::with_definition(definition);
+      potion.durability = count;
+      character.inventory.add_item(potion.clone()).unwrap();
+      let slot = character.inventory.get_item_slot(&potion).unwrap();
+      items.add_to_inventory(character.inventory.id, slot, &potion).unwrap();
+    }
+
+    // The Kris stands upright in the first slot, so the potions follow it
+    let (first, second, apple) = (1, 2, 3);
+    let source = character.inventory.get_item_at_slot(first).unwrap().id;
+    assert!(character.inventory.merge_items(first, apple).is_none());
+    assert_eq!(character.inventory.merge_items(first, second), Some(1));
+    characters
+      .save_item_merge(&character, source, second)
+      .unwrap();
+
+    assert_eq!(service.consume(&mut character, 0).unwrap(), Err(PotionError::NotPotion));
+    assert_eq!(service.consume(&mut character, 4).unwrap(), Err(PotionError::NotFound));
+    assert_eq!(service.consume(&mut character, first).unwrap(), Ok(Potion::SmallHealing));
+    assert_eq!(service.consume(&mut character, second).unwrap(), Ok(Potion::SmallHealing));
+    assert_eq!(service.consume(&mut character, apple).unwrap(), Ok(Potion::Apple));
+
+    let character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(character.inventory.items(), 2);
+    assert!(character.inventory.get_item_at_slot(first).is_none());
+    assert_eq!(character.inventory.get_item_at_slot(second).unwrap().stack_size(), 2);
+  }
+
+  #[test]
+  fn price_stacks_by_their_size() {
+    let (_temp, manager) = setup_test_env();
+    let items = manager.item_service();
+
+    let definition = items
+      .find_definition_by_code((ItemGroup::Potion, 1).into())
+      .unwrap()
+      .unwrap();
+    let mut potions = Item::with_definition(definition);
+    potions.durability = 1;
+    let price = pricing::buy_price(&potions);
+
+    potions.durability = 3;
+    assert_eq!(pricing::buy_price(&potions), price * 3);
+    assert_eq!(pricing::sell_price(&potions), price);
+  }
+
   #[test]
   fn find_items_by_id() {
     let (_temp, manager) = setup_test_env();
@@ -111,5 +628,127 @@ mod tests {
 
     assert_eq!(item.level, 2);
     assert_eq!(item.name, "Kris");
+    assert_eq!(item.boost(ItemAttribute::MaxDamage), 11);
+  }
+
+  #[test]
+  fn roll_monster_drops() {
+    let (_temp, manager) = setup_test_env();
+    let roll = |seed| {
+      let service = DropService::with_random(manager.item_service(), XorShiftRng::from_seed(seed));
+      (0..200)
+        .map(|_| service.roll(6).unwrap())
+        .collect::<Vec<_>>()
+    };
+
+    let drops = roll([1, 2, 3, 4]);
+    assert!(drops.iter().any(|loot| matches!(loot, Some(Loot::Item(_)))));
+    assert!(drops.iter().any(|loot| matches!(loot, Some(Loot::Money(_)))));
+
+    for loot in drops.iter().filter_map(|loot| loot.as_ref()) {
+      match *loot {
+        Loot::Item(ref item) => {
+          assert!(item.drop_from_monster && item.drop_level <= 6);
+          assert_eq!(item.level, 0);
+        },
+        Loot::Money(amount) => assert!(amount >= 35 && amount <= 70),
+      }
+    }
+
+    // The same seed must always yield the same drops
+    let describe = |drops: Vec<Option<Loot>>| {
+      drops
+        .into_iter()
+        .map(|loot| match loot {
+          Some(Loot::Item(item)) => Some((item.code.as_raw() as u32, item.level as u32)),
+          Some(Loot::Money(amount)) => Some((u32::max_value(), amount)),
+          None => None,
+        })
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(describe(drops), describe(roll([1, 2, 3, 4])));
+  }
+
+  #[test]
+  fn find_monster_spawns_by_map() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.monster_service();
+
+    let spawns = service.find_spawns_by_map(0).unwrap();
+    assert_eq!(spawns.len(), 7);
+
+    let spider = spawns.iter().find(|spawn| spawn.id == 1).unwrap();
+    assert_eq!(spider.definition.name, "Spider");
+    assert_eq!(spider.count, 10);
+    assert!(spider.direction.is_none());
+  }
+
+  #[test]
+  fn find_skills_and_learn_them() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.skill_service();
+
+    let skills = service.find_all().unwrap();
+    let slash = skills.iter().find(|skill| skill.id == 41).unwrap();
+    assert_eq!(slash.name, "Twisting Slash");
+    assert!(slash.eligible_classes.contains(&Class::DarkKnight));
+
+    assert_eq!(service.find_by_id(41).unwrap().unwrap().name, "Twisting Slash");
+    assert!(service.find_by_id(6).unwrap().is_none());
+  }
+
+  #[test]
+  fn learn_skills_from_orbs_and_scrolls() {
+    let (_temp, manager) = setup_test_env();
+    let service = manager.skill_service();
+    let items = manager.item_service();
+    let characters = manager.character_service();
+
+    let mut character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    for &code in &[(ItemGroup::Wings, 7), (ItemGroup::Wings, 7), (ItemGroup::Scroll, 3)] {
+      let definition = items.find_definition_by_code(code.into()).unwrap().unwrap();
+      let item = Item::with_definition(definition);
+      character.inventory.add_item(item.clone()).unwrap();
+      let slot = character.inventory.get_item_slot(&item).unwrap();
+      items.add_to_inventory(character.inventory.id, slot, &item).unwrap();
+    }
+
+    // The Kris stands upright in the first slot, so the orbs and scroll follow it
+    let (orb, spare, scroll) = (1, 2, 3);
+    assert_eq!(service.learn(&mut character, 0).unwrap(), Err(SkillError::NotSkillItem));
+    assert_eq!(service.learn(&mut character, 4).unwrap(), Err(SkillError::NotFound));
+    assert_eq!(
+      service.learn(&mut character, orb).unwrap(),
+      Err(SkillError::RequirementsNotMet)
+    );
+    assert_eq!(
+      service.learn(&mut character, scroll).unwrap(),
+      Err(SkillError::RequirementsNotMet)
+    );
+
+    character.level = 80;
+    assert_eq!(service.learn(&mut character, orb).unwrap(), Ok(41));
+    assert_eq!(
+      service.learn(&mut character, spare).unwrap(),
+      Err(SkillError::AlreadyLearned)
+    );
+    assert!(character.inventory.get_item_at_slot(orb).is_none());
+    assert_eq!(character.skills, vec![19, 20, 21, 41]);
+
+    let character = characters.find_by_name("deadbeef").unwrap().unwrap();
+    assert_eq!(character.skills, vec![19, 20, 21, 41]);
+    assert_eq!(character.inventory.items(), 3);
+
+    // An Orb of Summoning teaches the creature matching its level
+    let definition = items
+      .find_definition_by_code((ItemGroup::Wings, 11).into())
+      .unwrap()
+      .unwrap();
+    let mut summoning = Item::with_definition(definition);
+    assert_eq!(taught_skill(&summoning), Some(30));
+    summoning.level = 6;
+    assert_eq!(taught_skill(&summoning), Some(36));
+    summoning.level = 7;
+    assert_eq!(taught_skill(&summoning), None);
   }
 }
@@ -1,5 +1,8 @@
+use chaos::ChaosRecipes;
 use murust_repository::*;
-use {AccountService, CharacterService, ItemService};
+use {AccountService, ChaosService, CharacterService, DropService, ExperienceService, ItemService,
+     JewelService, MonsterService, NpcService, PotionService, ShopService, SkillService,
+     TradeService, VaultService};
 
 /// A manager for all services.
 #[derive(Clone)]
@@ -19,12 +22,68 @@ impl ServiceManager {
     )
   }
 
+  /// Returns the chaos service.
+  pub fn chaos_service(&self) -> ChaosService {
+    ChaosService::new(self.item_service(), ChaosRecipes::with_defaults())
+  }
+
+  /// Returns the drop service.
+  pub fn drop_service(&self) -> DropService { DropService::new(self.item_service()) }
+
+  /// Returns the experience service.
+  pub fn experience_service(&self) -> ExperienceService { ExperienceService::new() }
+
   /// Returns the item service.
   pub fn item_service(&self) -> ItemService {
     ItemService::new(
       ItemRepository::new(&self.context),
       ItemDefinitionRepository::new(&self.context),
       ItemEligibleClassRepository::new(&self.context),
+      ItemAttributeBoostRepository::new(&self.context),
+      ItemAttributeRequirementRepository::new(&self.context),
+      ItemSetRepository::new(&self.context),
+    )
+  }
+
+  /// Returns the jewel service.
+  pub fn jewel_service(&self) -> JewelService { JewelService::new(self.item_service()) }
+
+  /// Returns the monster service.
+  pub fn monster_service(&self) -> MonsterService {
+    MonsterService::new(
+      MonsterDefinitionRepository::new(&self.context),
+      MonsterSpawnRepository::new(&self.context),
+    )
+  }
+
+  /// Returns the NPC service.
+  pub fn npc_service(&self) -> NpcService { NpcService::new(NpcRepository::new(&self.context)) }
+
+  /// Returns the potion service.
+  pub fn potion_service(&self) -> PotionService { PotionService::new(self.item_service()) }
+
+  /// Returns the shop service.
+  pub fn shop_service(&self) -> ShopService { ShopService::new(self.item_service()) }
+
+  /// Returns the skill service.
+  pub fn skill_service(&self) -> SkillService {
+    SkillService::new(
+      SkillDefinitionRepository::new(&self.context),
+      SkillEligibleClassRepository::new(&self.context),
+      CharacterSkillRepository::new(&self.context),
+    )
+  }
+
+  /// Returns the trade service.
+  pub fn trade_service(&self) -> TradeService {
+    TradeService::new(self.character_service(), ItemRepository::new(&self.context))
+  }
+
+  /// Returns the vault service.
+  pub fn vault_service(&self) -> VaultService {
+    VaultService::new(
+      ItemRepository::new(&self.context),
+      InventoryRepository::new(&self.context),
     )
   }
 
@@ -35,6 +94,7 @@ impl ServiceManager {
       ItemRepository::new(&self.context),
       CharacterRepository::new(&self.context),
       InventoryRepository::new(&self.context),
+      CharacterSkillRepository::new(&self.context),
     )
   }
 }
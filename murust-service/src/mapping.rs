@@ -1,8 +1,11 @@
 use murust_data_model::entities::*;
-use murust_data_model::types::{Class, ItemCode, ItemSlot, ItemStorage, Position};
+use murust_data_model::types::{
+  Class, Direction, ExcellentOptions, ItemAttribute, ItemCode, ItemSlot, ItemStorage, NpcKind,
+  Position, SkillArea, SkillId,
+};
 use murust_repository::models;
 use num_traits::FromPrimitive;
-use std::{convert::TryFrom, num::TryFromIntError};
+use std::{collections::HashMap, convert::TryFrom, num::TryFromIntError, sync::Arc, time::Duration};
 
 #[derive(Debug, Fail)]
 pub enum MappingError {
@@ -31,36 +34,70 @@ pub trait MappableToDomain<T> {
 }
 
 impl MappableToDomain<Account> for models::Account {
-  type Dependencies = (Vec<Character>,);
+  type Dependencies = (Vec<Character>, Inventory);
+
+  fn map_to_entity(self, (characters, vault): Self::Dependencies) -> Result<Account> {
+    let password = match self.vault_password {
+      Some(password) => Some(u16::try_from(password)?),
+      None => None,
+    };
 
-  fn map_to_entity(self, (characters,): Self::Dependencies) -> Result<Account> {
     Ok(Account {
       id: self.id,
       username: self.username,
       security_code: u32::try_from(self.security_code)?,
       email: self.email,
       characters,
+      vault: Vault::with_inventory(vault, password),
     })
   }
 }
 
 impl MappableToDomain<Item> for models::Item {
-  type Dependencies = ItemDefinition;
+  type Dependencies = (ItemDefinition, Option<Arc<ItemSet>>);
 
-  fn map_to_entity(self, definition: Self::Dependencies) -> Result<Item> {
+  fn map_to_entity(self, (definition, set): Self::Dependencies) -> Result<Item> {
     Ok(Item {
       id: *self.id,
       level: u8::try_from(self.level)?,
       durability: u8::try_from(self.durability)?,
+      wear: 0,
+      skill: self.skill,
+      luck: self.luck,
+      option: u8::try_from(self.option)?,
+      excellent: ExcellentOptions::from_bits(u8::try_from(self.excellent)?)
+        .ok_or(MappingError::Enum)?,
+      set,
       definition: ::std::sync::Arc::new(definition),
     })
   }
 }
 
+impl MappableToDomain<ItemSet> for models::ItemSet {
+  type Dependencies = (Vec<ItemCode>, Vec<ItemSetBonus>);
+
+  fn map_to_entity(self, (members, bonuses): Self::Dependencies) -> Result<ItemSet> {
+    Ok(ItemSet {
+      id: u16::try_from(self.id)?,
+      name: self.name,
+      tier: u8::try_from(self.tier)?,
+      members,
+      bonuses,
+    })
+  }
+}
+
 impl MappableToDomain<ItemDefinition> for models::ItemDefinition {
-  type Dependencies = (Vec<Class>,);
+  type Dependencies = (
+    Vec<Class>,
+    HashMap<ItemAttribute, u16>,
+    HashMap<ItemAttribute, u16>,
+  );
 
-  fn map_to_entity(self, (eligible_classes,): Self::Dependencies) -> Result<ItemDefinition> {
+  fn map_to_entity(
+    self,
+    (eligible_classes, attribute_boosts, attribute_requirements): Self::Dependencies,
+  ) -> Result<ItemDefinition> {
     Ok(ItemDefinition {
       code: ItemCode::from(u16::try_from(self.code)?),
       name: self.name,
@@ -73,21 +110,27 @@ impl MappableToDomain<ItemDefinition> for models::ItemDefinition {
       drop_from_monster: self.drop_from_monster,
       drop_level: u16::try_from(self.drop_level)?,
       eligible_classes,
+      attribute_boosts,
+      attribute_requirements,
     })
   }
 }
 
 impl MappableToDomain<Character> for models::Character {
-  type Dependencies = (Equipment, Inventory);
+  type Dependencies = (Equipment, Inventory, Vec<SkillId>);
 
-  fn map_to_entity(self, (equipment, inventory): Self::Dependencies) -> Result<Character> {
+  fn map_to_entity(
+    self,
+    (equipment, inventory, skills): Self::Dependencies,
+  ) -> Result<Character> {
     Ok(Character {
       id: self.id,
       slot: u8::try_from(self.slot)?,
       name: self.name,
       level: u16::try_from(self.level)?,
       class: Class::from_str(&self.class).ok_or(MappingError::Enum)?,
-      experience: self.experience as u32,
+      experience: u32::try_from(self.experience)?,
+      points: u16::try_from(self.points)?,
       strength: u16::try_from(self.strength)?,
       agility: u16::try_from(self.agility)?,
       vitality: u16::try_from(self.vitality)?,
@@ -101,6 +144,67 @@ impl MappableToDomain<Character> for models::Character {
       player_kills: self.player_kills,
       equipment,
       inventory,
+      skills,
+    })
+  }
+}
+
+impl MappableToDomain<MonsterDefinition> for models::MonsterDefinition {
+  type Dependencies = ();
+
+  fn map_to_entity(self, _: Self::Dependencies) -> Result<MonsterDefinition> {
+    Ok(MonsterDefinition {
+      class: u16::try_from(self.class)?,
+      name: self.name,
+      level: u16::try_from(self.level)?,
+      max_health: u32::try_from(self.max_health)?,
+      min_damage: u16::try_from(self.min_damage)?,
+      max_damage: u16::try_from(self.max_damage)?,
+      defense: u16::try_from(self.defense)?,
+      attack_rate: u16::try_from(self.attack_rate)?,
+      defense_rate: u16::try_from(self.defense_rate)?,
+      move_range: u8::try_from(self.move_range)?,
+      attack_range: u8::try_from(self.attack_range)?,
+      view_range: u8::try_from(self.view_range)?,
+      move_delay: Duration::from_millis(u64::try_from(self.move_delay)?),
+      attack_delay: Duration::from_millis(u64::try_from(self.attack_delay)?),
+      respawn_time: Duration::from_millis(u64::try_from(self.respawn_time)?),
+    })
+  }
+}
+
+impl MappableToDomain<MonsterSpawn> for models::MonsterSpawn {
+  type Dependencies = Arc<MonsterDefinition>;
+
+  fn map_to_entity(self, definition: Self::Dependencies) -> Result<MonsterSpawn> {
+    Ok(MonsterSpawn {
+      id: self.id,
+      definition,
+      map: u8::try_from(self.map)?,
+      area_start: Position::new(u8::try_from(self.start_x)?, u8::try_from(self.start_y)?),
+      area_end: Position::new(u8::try_from(self.end_x)?, u8::try_from(self.end_y)?),
+      direction: self.direction.map_or(Ok(None), |direction| {
+        Direction::from_i32(direction)
+          .ok_or(MappingError::Enum)
+          .map(Some)
+      })?,
+      count: u8::try_from(self.count)?,
+    })
+  }
+}
+
+impl MappableToDomain<NpcDefinition> for models::Npc {
+  type Dependencies = ();
+
+  fn map_to_entity(self, _: Self::Dependencies) -> Result<NpcDefinition> {
+    Ok(NpcDefinition {
+      id: self.id,
+      class: u16::try_from(self.class)?,
+      name: self.name,
+      kind: NpcKind::from_str(&self.kind).ok_or(MappingError::Enum)?,
+      map: u8::try_from(self.map)?,
+      position: Position::new(u8::try_from(self.position_x)?, u8::try_from(self.position_y)?),
+      direction: Direction::from_i32(self.direction).ok_or(MappingError::Enum)?,
     })
   }
 }
@@ -142,7 +246,67 @@ impl<I: IntoIterator<Item = (i32, Item)>> MappableToDomain<Equipment> for I {
   }
 }
 
+impl MappableToDomain<SkillDefinition> for models::SkillDefinition {
+  type Dependencies = (Vec<Class>,);
+
+  fn map_to_entity(self, (eligible_classes,): Self::Dependencies) -> Result<SkillDefinition> {
+    Ok(SkillDefinition {
+      id: u8::try_from(self.id)?,
+      name: self.name,
+      damage: u16::try_from(self.damage)?,
+      magic: self.magic,
+      mana_cost: u16::try_from(self.mana_cost)?,
+      ability_cost: u16::try_from(self.ability_cost)?,
+      cooldown: Duration::from_millis(u64::try_from(self.cooldown)?),
+      range: u8::try_from(self.range)?,
+      area: SkillArea::from_str(&self.area, u8::try_from(self.area_size)?)
+        .ok_or(MappingError::Enum)?,
+      min_level: u16::try_from(self.min_level)?,
+      min_energy: u16::try_from(self.min_energy)?,
+      eligible_classes,
+    })
+  }
+}
+
 /// Parses a character class from a string.
 pub fn to_character_class(eligible: models::ItemEligibleClass) -> Result<Class> {
   Class::from_str(&eligible.class).ok_or(MappingError::Enum)
 }
+
+/// Parses an item attribute boost from its persisted form.
+pub fn to_attribute_boost(boost: models::ItemAttributeBoost) -> Result<(ItemAttribute, u16)> {
+  let attribute = ItemAttribute::from_str(&boost.attribute).ok_or(MappingError::Enum)?;
+  Ok((attribute, u16::try_from(boost.boost)?))
+}
+
+/// Parses an item attribute requirement from its persisted form.
+pub fn to_attribute_requirement(
+  requirement: models::ItemAttributeRequirement,
+) -> Result<(ItemAttribute, u16)> {
+  let attribute = ItemAttribute::from_str(&requirement.attribute).ok_or(MappingError::Enum)?;
+  Ok((attribute, u16::try_from(requirement.requirement)?))
+}
+
+/// Parses an item set member's code.
+pub fn to_set_member(member: models::ItemSetMember) -> Result<ItemCode> {
+  Ok(ItemCode::from(u16::try_from(member.item_code)?))
+}
+
+/// Parses an item set bonus from its persisted form.
+pub fn to_set_bonus(bonus: models::ItemSetBonus) -> Result<ItemSetBonus> {
+  Ok(ItemSetBonus {
+    pieces: u8::try_from(bonus.pieces)?,
+    attribute: ItemAttribute::from_str(&bonus.attribute).ok_or(MappingError::Enum)?,
+    bonus: u16::try_from(bonus.bonus)?,
+  })
+}
+
+/// Parses a skill's eligible class from a string.
+pub fn to_skill_class(eligible: models::SkillEligibleClass) -> Result<Class> {
+  Class::from_str(&eligible.class).ok_or(MappingError::Enum)
+}
+
+/// Parses the ID of a learned skill.
+pub fn to_skill_id(skill: models::CharacterSkill) -> Result<SkillId> {
+  u8::try_from(skill.skill_id).map_err(Into::into)
+}
@@ -0,0 +1,79 @@
+//! Item pricing, as used by NPC shops.
+
+use murust_data_model::entities::Item;
+
+/// The highest price of any item.
+pub const MAX_PRICE: u32 = 2_000_000_000;
+
+/// The item levels gained by being excellent, when determining value.
+const EXCELLENT_LEVEL: u32 = 25;
+
+/// The value gained (in percent) by each additional option level.
+static OPTION_BONUS: [u64; 8] = [0, 60, 140, 280, 560, 1120, 2240, 4480];
+
+/// Returns the price of buying an item, or each item of a stack.
+///
+/// The value grows steeply with the item's drop level and item level, and is
+/// further raised by its skill, luck and additional options.
+pub fn buy_price(item: &Item) -> u32 {
+  let price = unit_price(item) * u64::from(item.stack_size());
+  price.min(u64::from(MAX_PRICE)) as u32
+}
+
+/// Returns the price received when selling an item.
+///
+/// Items are sold for a third of their value, with worn items losing up to
+/// sixty percent of it. Stacks keep their count in their durability, so they
+/// are never considered worn.
+pub fn sell_price(item: &Item) -> u32 {
+  let price = u64::from(buy_price(item) / 3);
+  let max_durability = u64::from(item.max_durability);
+  if max_durability == 0 || item.is_stackable() {
+    return price as u32;
+  }
+
+  let worn = max_durability - u64::from(item.durability).min(max_durability);
+  (price - price * 6 * worn / (10 * max_durability)) as u32
+}
+
+/// Returns the price of buying a single item.
+fn unit_price(item: &Item) -> u64 {
+  let mut level = u64::from(item.drop_level) + u64::from(item.level) * 3;
+  if item.is_excellent() {
+    level += u64::from(EXCELLENT_LEVEL);
+  }
+
+  let mut price = (level + 40) * level * level / 8 + 100;
+  if item.skill {
+    price += price * 3 / 2;
+  }
+  if item.luck {
+    price += price / 4;
+  }
+
+  let option = (item.option as usize).min(OPTION_BONUS.len() - 1);
+  price + price * OPTION_BONUS[option] / 100
+}
+
+/// The surcharge (in percent) of repairing an item from the inventory,
+/// rather than at an NPC.
+const SELF_REPAIR_SURCHARGE: u64 = 150;
+
+/// Returns the price of restoring an item's durability.
+///
+/// Repairs cost up to a fifth of the item's value, in proportion to the
+/// durability lost, with repairs from the inventory being more expensive than
+/// those made at an NPC.
+pub fn repair_price(item: &Item, at_npc: bool) -> u32 {
+  let max_durability = u64::from(item.max_durability);
+  if max_durability == 0 {
+    return 0;
+  }
+
+  let lost = max_durability - u64::from(item.durability).min(max_durability);
+  let mut price = u64::from(buy_price(item)) * lost / (5 * max_durability);
+  if !at_npc {
+    price += price * SELF_REPAIR_SURCHARGE / 100;
+  }
+  price as u32
+}
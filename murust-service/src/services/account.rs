@@ -2,7 +2,7 @@ use CharacterService;
 use bcrypt;
 use error::{Error, Result};
 use mapping::MappableToDomain;
-use murust_data_model::entities::Account;
+use murust_data_model::entities::{Account, Vault};
 use murust_repository::{models, AccountRepository};
 
 /// A collection of possible login errors.
@@ -97,9 +97,19 @@ impl AccountService {
     email: &str,
   ) -> Result<Account> {
     let password_hash = self.hash_password(password)?;
-    let account = self
-      .repository
-      .create(username, &password_hash, security_code as i32, email)?;
+    let vault = models::Inventory {
+      id: Vault::default().id.into(),
+      width: Vault::WIDTH.into(),
+      height: Vault::HEIGHT.into(),
+      money: 0,
+    };
+    let account = self.repository.create(
+      username,
+      &password_hash,
+      security_code as i32,
+      email,
+      &vault,
+    )?;
     Ok(self.map_account_to_entity(account)?)
   }
 
@@ -119,6 +129,7 @@ impl AccountService {
     }
 
     models.security_code = account.security_code as i32;
+    models.vault_password = account.vault.password.map(Into::into);
     self.repository.update(&models).map_err(Into::into)
   }
 
@@ -132,7 +143,11 @@ impl AccountService {
 
   fn map_account_to_entity(&self, account: models::Account) -> Result<Account> {
     let characters = self.characters.find_by_account_id(account.id)?;
-    Ok(account.map_to_entity((characters,))?)
+    let vault = self
+      .characters
+      .find_inventory_by_id(*account.vault_id)?
+      .ok_or_else(|| Error::MissingAssociation("Vault".into()))?;
+    Ok(account.map_to_entity((characters, vault))?)
   }
 
   /// Returns the hash of a password.
@@ -0,0 +1,147 @@
+use chaos::{ChaosProduct, ChaosRecipes};
+use error::{Error, Result};
+use murust_data_model::entities::{item, Character, Item};
+use murust_data_model::types::ItemStorage;
+use rand::{self, Rng, XorShiftRng};
+use services::ItemService;
+use std::sync::{Mutex, MutexGuard};
+
+/// A collection of possible chaos mix errors.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ChaosError {
+  /// The items do not match any recipe.
+  InvalidItems,
+  /// The character cannot afford the mix.
+  InsufficientMoney,
+  /// The character's inventory has no room for the product.
+  InventoryFull,
+}
+
+/// A service for chaos machine mixes.
+pub struct ChaosService {
+  item_service: ItemService,
+  recipes: ChaosRecipes,
+  random: Mutex<XorShiftRng>,
+}
+
+impl ChaosService {
+  /// Constructs a new chaos service.
+  pub fn new(item_service: ItemService, recipes: ChaosRecipes) -> Self {
+    Self::with_random(item_service, recipes, rand::weak_rng())
+  }
+
+  /// Constructs a new chaos service using a specific random generator.
+  pub fn with_random(
+    item_service: ItemService,
+    recipes: ChaosRecipes,
+    random: XorShiftRng,
+  ) -> Self {
+    ChaosService {
+      item_service,
+      recipes,
+      random: Mutex::new(random),
+    }
+  }
+
+  /// Lets a character mix a set of its inventory items, returning the
+  /// inventory slot of the product if the mix succeeded.
+  ///
+  /// The ingredients and zen are spent regardless of the outcome, and are
+  /// persisted along with any product as a single transaction. The character
+  /// is left untouched unless they are.
+  pub fn mix(
+    &self,
+    character: &mut Character,
+    ingredients: &[item::Id],
+  ) -> Result<::std::result::Result<Option<u8>, ChaosError>> {
+    let mix = {
+      let items = ingredients
+        .iter()
+        .map(|&id| character.inventory.get_item(id))
+        .collect::<Option<Vec<_>>>();
+      items.and_then(|items| self.recipes.evaluate(&items))
+    };
+
+    let mix = match mix {
+      Some(mix) => mix,
+      None => return Ok(Err(ChaosError::InvalidItems)),
+    };
+
+    let money = match character.inventory.money.checked_sub(mix.cost) {
+      Some(money) => money,
+      None => return Ok(Err(ChaosError::InsufficientMoney)),
+    };
+
+    // The product may take the place of its ingredients, so it's placed
+    // within a copy of the inventory without them.
+    let mut storage = character.inventory.storage.clone();
+    let product = self.create_product(&storage, &mix.product)?;
+    let upgraded = storage.get_item_slot(&product);
+
+    for &id in ingredients {
+      storage.unlock_item(id);
+      storage.remove_item(id).expect("removing mix ingredient");
+    }
+
+    let placed = match upgraded {
+      Some(slot) => storage.add_item_at_slot(slot, product.clone()),
+      None => storage.add_item(product.clone()),
+    };
+
+    if placed.is_err() {
+      return Ok(Err(ChaosError::InventoryFull));
+    }
+
+    let slot = storage.get_item_slot(&product).expect("retrieving mix product");
+    let succeeded = mix.succeeds(&mut *self.random());
+    if !succeeded {
+      storage.remove_item(product.id);
+    }
+
+    self.item_service.mix_in_inventory(
+      character.inventory.id,
+      ingredients,
+      if succeeded { Some((slot, &product)) } else { None },
+      mix.cost,
+    )?;
+
+    character.inventory.storage = storage;
+    character.inventory.money = money;
+    Ok(Ok(if succeeded { Some(slot) } else { None }))
+  }
+
+  /// Returns the product of a mix, either upgrading an ingredient or creating
+  /// a new item.
+  fn create_product(&self, storage: &ItemStorage, product: &ChaosProduct) -> Result<Item> {
+    match *product {
+      ChaosProduct::Upgrade { item, level } => {
+        let mut item = storage
+          .get_item(item)
+          .expect("retrieving upgraded item")
+          .clone();
+        item.level = level;
+        Ok(item)
+      },
+      ChaosProduct::Create {
+        ref candidates,
+        level,
+      } => {
+        let code = *self
+          .random()
+          .choose(candidates)
+          .expect("choosing mix product");
+        let definition = self
+          .item_service
+          .find_definition_by_code(code)?
+          .ok_or_else(|| Error::MissingAssociation("ItemDefinition".into()))?;
+
+        let mut item = Item::with_definition(definition);
+        item.level = level;
+        Ok(item)
+      },
+    }
+  }
+
+  /// Returns the service's random generator.
+  fn random(&self) -> MutexGuard<XorShiftRng> { self.random.lock().expect("locking chaos random") }
+}
@@ -1,8 +1,8 @@
 use ItemService;
 use error::{Error, Result};
-use mapping::MappableToDomain;
-use murust_data_model::entities::{Character, Equipment, Inventory};
-use murust_data_model::types::{Class, CHARACTER_SLOTS};
+use mapping::{self, MappableToDomain};
+use murust_data_model::entities::{inventory, item, Character, Equipment, Inventory};
+use murust_data_model::types::{Class, ItemLocation, CHARACTER_SLOTS};
 use murust_repository::*;
 use std::ops::Range;
 
@@ -29,6 +29,7 @@ pub struct CharacterService {
   repo_items: ItemRepository,
   repo_characters: CharacterRepository,
   repo_inventory: InventoryRepository,
+  repo_character_skills: CharacterSkillRepository,
   valid_name_range: Range<usize>,
 }
 
@@ -39,12 +40,14 @@ impl CharacterService {
     repo_items: ItemRepository,
     repo_characters: CharacterRepository,
     repo_inventory: InventoryRepository,
+    repo_character_skills: CharacterSkillRepository,
   ) -> Self {
     CharacterService {
       item_service,
       repo_items,
       repo_characters,
       repo_inventory,
+      repo_character_skills,
       valid_name_range: (4..11),
     }
   }
@@ -119,12 +122,112 @@ impl CharacterService {
       .map_err(Into::into)
       .and_then(|character| {
         character
-          .map_to_entity((Equipment::default(), Inventory::new(8, 8)))
+          .map_to_entity((Equipment::default(), Inventory::new(8, 8), Vec::new()))
           .map_err(Into::into)
       })
       .map(Ok)
   }
 
+  /// Persists a character's level, experience and stats.
+  pub fn save_progress(&self, character: &Character) -> Result<()> {
+    let progress = models::CharacterProgress {
+      level: character.level.into(),
+      experience: character.experience.into(),
+      points: character.points.into(),
+      strength: character.strength.into(),
+      agility: character.agility.into(),
+      vitality: character.vitality.into(),
+      energy: character.energy.into(),
+      command: character.command.into(),
+    };
+
+    self
+      .repo_characters
+      .save_progress(character.id, &progress)
+      .map_err(Into::into)
+  }
+
+  /// Persists an amount of zen added to a character's inventory, or removed
+  /// from it if negative, once it has been applied to the character.
+  pub fn save_money_change(&self, character: &Character, amount: i32) -> Result<()> {
+    self
+      .repo_inventory
+      .add_money(character.inventory.id, amount)
+      .map_err(Into::into)
+  }
+
+  /// Reloads a character's inventory from the underlying storage, e.g after
+  /// it has been changed by a trade.
+  pub fn refresh_inventory(&self, character: &mut Character) -> Result<()> {
+    character.inventory = self
+      .find_inventory_by_id(character.inventory.id)?
+      .ok_or(Error::MissingPersistence)?;
+    Ok(())
+  }
+
+  /// Persists an item moved by a character, as done by `Character::move_item`.
+  pub fn save_item_move(
+    &self,
+    character: &Character,
+    source: ItemLocation,
+    target: ItemLocation,
+  ) -> Result<()> {
+    let item = match target {
+      ItemLocation::Inventory(slot) => character.inventory.get_item_at_slot(slot),
+      ItemLocation::Equipment(slot) => character.equipment[slot].as_ref(),
+    };
+    let item_id = item.map(|item| item.id).ok_or(Error::MissingPersistence)?;
+
+    match (source, target) {
+      (ItemLocation::Inventory(source), ItemLocation::Inventory(target)) => self
+        .repo_items
+        .move_inventory_item(character.inventory.id, source as i32, target as i32)?,
+      (ItemLocation::Equipment(source), ItemLocation::Equipment(target)) => self
+        .repo_items
+        .move_equipment_item(character.id, source as i32, target as i32)?,
+      (ItemLocation::Inventory(source), ItemLocation::Equipment(target)) => {
+        self.repo_items.equip_inventory_item(
+          character.inventory.id,
+          source as i32,
+          &models::EquipmentItem {
+            character_id: character.id,
+            item_id: item_id.into(),
+            slot: target as i32,
+          },
+        )?
+      },
+      (ItemLocation::Equipment(source), ItemLocation::Inventory(target)) => {
+        self.repo_items.unequip_equipment_item(
+          character.id,
+          source as i32,
+          &models::InventoryItem {
+            inventory_id: character.inventory.id.into(),
+            item_id: item_id.into(),
+            slot: target as i32,
+          },
+        )?
+      },
+    }
+    Ok(())
+  }
+
+  /// Persists a stack merged by a character, as done by
+  /// `ItemStorage::merge_items`.
+  ///
+  /// The source stack is deleted if it has been emptied, otherwise the counts
+  /// of both stacks are saved.
+  pub fn save_item_merge(&self, character: &Character, source: item::Id, target: u8) -> Result<()> {
+    let inventory = &character.inventory;
+    let target = inventory
+      .get_item_at_slot(target)
+      .ok_or(Error::MissingPersistence)?;
+
+    match inventory.get_item(source) {
+      Some(source) => self.item_service.save_durabilities(vec![source, target]),
+      None => self.item_service.save_consuming(target, source),
+    }
+  }
+
   /// Removes a character from the underlying storage.
   pub fn delete(
     &self,
@@ -152,6 +255,19 @@ impl CharacterService {
     Ok(slots_free.pop().map(|slot| slot as u8))
   }
 
+  /// Returns an inventory along with its items.
+  pub(crate) fn find_inventory_by_id(
+    &self,
+    inventory_id: inventory::Id,
+  ) -> Result<Option<Inventory>> {
+    self
+      .repo_inventory
+      .find_by_id(inventory_id)?
+      .map_or(Ok(None), |inventory| {
+        self.map_inventory_to_entity(inventory).map(Some)
+      })
+  }
+
   fn map_character_to_entity(&self, character: models::Character) -> Result<Character> {
     let equipment = self
       .item_service
@@ -159,13 +275,18 @@ impl CharacterService {
       .and_then(|equipment| equipment.map_to_entity(()).map_err(Into::into))?;
 
     let inventory = self
-      .repo_inventory
-      .find_by_id(character.inventory_id)?
-      .ok_or_else(|| Error::MissingAssociation("Inventory".into()))
-      .and_then(|inventory| self.map_inventory_to_entity(inventory))?;
+      .find_inventory_by_id(*character.inventory_id)?
+      .ok_or_else(|| Error::MissingAssociation("Inventory".into()))?;
+
+    let skills = self
+      .repo_character_skills
+      .find_by_character_id(character.id)?
+      .into_iter()
+      .map(mapping::to_skill_id)
+      .collect::<mapping::Result<Vec<_>>>()?;
 
     character
-      .map_to_entity((equipment, inventory))
+      .map_to_entity((equipment, inventory, skills))
       .map_err(Into::into)
   }
 
@@ -0,0 +1,89 @@
+use error::Result;
+use murust_data_model::entities::{Item, ItemDefinition, Loot};
+use rand::{self, Rng, XorShiftRng};
+use services::ItemService;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The chance (in percent) of a monster dropping an item.
+const ITEM_DROP_CHANCE: u32 = 20;
+
+/// The chance (in percent) of a monster dropping zen, unless it drops an item.
+const MONEY_DROP_CHANCE: u32 = 40;
+
+/// The highest level of an item dropped by a monster.
+const MAX_DROP_ITEM_LEVEL: u16 = 6;
+
+/// The number of monster levels required for each dropped item level.
+const MONSTER_LEVELS_PER_ITEM_LEVEL: u16 = 10;
+
+/// A service for monster drops.
+pub struct DropService {
+  item_service: ItemService,
+  random: Mutex<XorShiftRng>,
+  /// The item definitions dropped by monsters, by monster level.
+  definitions: Mutex<HashMap<u16, Vec<Arc<ItemDefinition>>>>,
+}
+
+impl DropService {
+  /// Constructs a new drop service.
+  pub fn new(item_service: ItemService) -> Self {
+    Self::with_random(item_service, rand::weak_rng())
+  }
+
+  /// Constructs a new drop service using a specific random generator.
+  pub fn with_random(item_service: ItemService, random: XorShiftRng) -> Self {
+    DropService {
+      item_service,
+      random: Mutex::new(random),
+      definitions: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Rolls the loot dropped by a killed monster, if any.
+  ///
+  /// Items are chosen among the definitions dropped at the monster's level,
+  /// with their item level scaling by the monster's level. The definitions of
+  /// each level are only retrieved once.
+  pub fn roll(&self, monster_level: u16) -> Result<Option<Loot>> {
+    let mut random = self.random();
+    if random.gen_range(0, 100) < ITEM_DROP_CHANCE {
+      if let Some(definition) = self.droppable_definition(monster_level, &mut *random)? {
+        let mut item = Item::with_definition(definition);
+
+        let max_level = (monster_level / MONSTER_LEVELS_PER_ITEM_LEVEL).min(MAX_DROP_ITEM_LEVEL);
+        item.level = random.gen_range(0, max_level + 1) as u8;
+        return Ok(Some(Loot::Item(item)));
+      }
+    }
+
+    if random.gen_range(0, 100) < MONEY_DROP_CHANCE {
+      let base = u32::from(monster_level) * 10 + 10;
+      return Ok(Some(Loot::Money(random.gen_range(base / 2, base + 1))));
+    }
+
+    Ok(None)
+  }
+
+  /// Chooses one of the item definitions dropped at a monster level, if any.
+  fn droppable_definition<R: Rng>(
+    &self,
+    monster_level: u16,
+    random: &mut R,
+  ) -> Result<Option<Arc<ItemDefinition>>> {
+    let mut definitions = self.definitions.lock().expect("locking drop definitions");
+    if !definitions.contains_key(&monster_level) {
+      let droppable = self
+        .item_service
+        .find_droppable_definitions(monster_level)?
+        .into_iter()
+        .map(Arc::new)
+        .collect();
+      definitions.insert(monster_level, droppable);
+    }
+    Ok(random.choose(&definitions[&monster_level]).cloned())
+  }
+
+  /// Returns the service's random generator.
+  fn random(&self) -> MutexGuard<XorShiftRng> { self.random.lock().expect("locking drop random") }
+}
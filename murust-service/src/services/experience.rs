@@ -0,0 +1,131 @@
+use murust_data_model::entities::Character;
+use murust_data_model::types::HeroStatus;
+
+/// The highest level a character can reach.
+pub const MAX_LEVEL: u16 = 400;
+
+/// The level above which the experience curve steepens.
+const STEEP_LEVEL: u32 = 255;
+
+/// The highest level at which characters die without any penalty.
+const DEATH_PENALTY_FREE_LEVEL: u16 = 10;
+
+/// The experience and zen lost by a character upon death.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct DeathPenalty {
+  pub experience: u32,
+  pub money: u32,
+}
+
+/// A service for experience and level progression.
+pub struct ExperienceService {
+  /// The total experience required to reach each level, indexed by level.
+  table: Vec<u32>,
+}
+
+impl ExperienceService {
+  /// Constructs a new experience service.
+  pub fn new() -> Self {
+    let mut table = vec![0; MAX_LEVEL as usize + 1];
+    for level in 2..table.len() {
+      table[level] = Self::level_experience(level as u32 - 1);
+    }
+    ExperienceService { table }
+  }
+
+  /// Returns the total experience required to reach a level.
+  pub fn experience_for_level(&self, level: u16) -> u32 {
+    self.table[level.min(MAX_LEVEL) as usize]
+  }
+
+  /// Returns the experience awarded for killing a monster.
+  ///
+  /// Monsters far below the character's level award proportionally less.
+  pub fn monster_experience(&self, character_level: u16, monster_level: u16) -> u32 {
+    let (character_level, monster_level) = (u32::from(character_level), u32::from(monster_level));
+    let mut experience = (monster_level + 25) * monster_level / 3;
+
+    if monster_level + 10 < character_level {
+      experience = experience * (monster_level + 10) / character_level;
+    }
+
+    if monster_level >= 65 {
+      experience += (monster_level - 64) * (monster_level / 4);
+    }
+
+    experience.max(1)
+  }
+
+  /// Awards experience to a character, returning the number of levels gained.
+  ///
+  /// Each level gained awards the character its class' stat points.
+  pub fn gain_experience(&self, character: &mut Character, experience: u32) -> u16 {
+    let maximum = self.experience_for_level(MAX_LEVEL);
+    character.experience = character.experience.saturating_add(experience).min(maximum);
+
+    let mut levels = 0;
+    while character.level < MAX_LEVEL
+      && character.experience >= self.experience_for_level(character.level + 1)
+    {
+      character.level += 1;
+      character.points = character
+        .points
+        .saturating_add(character.class.info().points_per_level);
+      levels += 1;
+    }
+    levels
+  }
+
+  /// Applies the penalty of dying to a character, returning what was lost.
+  ///
+  /// A share of the experience required for the character's next level is
+  /// lost, decreasing with the level, along with a share of its zen. Both
+  /// shares are scaled by the character's hero status, and a character never
+  /// loses a level.
+  pub fn apply_death_penalty(&self, character: &mut Character) -> DeathPenalty {
+    if character.level <= DEATH_PENALTY_FREE_LEVEL {
+      return DeathPenalty::default();
+    }
+
+    // Rates are expressed in per mille, and scaled in percent
+    let experience_rate = if character.level < 150 {
+      20
+    } else if character.level < 220 {
+      10
+    } else {
+      5
+    };
+    let money_rate = 40;
+    let scale = match character.hero_status() {
+      HeroStatus::Revered => 50,
+      HeroStatus::Hero => 75,
+      HeroStatus::Commoner => 100,
+      HeroStatus::Outcast => 200,
+      HeroStatus::Outlaw => 300,
+      HeroStatus::Murderer => 400,
+    };
+    let share = |amount: u32, rate: u64| (u64::from(amount) * rate * scale / 100_000) as u32;
+
+    let floor = self.experience_for_level(character.level);
+    let span = self
+      .experience_for_level(character.level + 1)
+      .saturating_sub(floor);
+    let experience = share(span, experience_rate).min(character.experience.saturating_sub(floor));
+    let money = share(character.inventory.money, money_rate);
+
+    character.experience -= experience;
+    character.inventory.money -= money;
+    DeathPenalty { experience, money }
+  }
+
+  /// Returns the total experience required to reach the level following a
+  /// level.
+  fn level_experience(level: u32) -> u32 {
+    let mut experience = 10 * (level + 9) * level * level;
+    if level > STEEP_LEVEL {
+      let level = level - STEEP_LEVEL;
+      experience += 1000 * (level + 9) * level * level;
+    }
+    experience
+  }
+}
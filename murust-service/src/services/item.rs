@@ -1,13 +1,19 @@
 use error::{Error, Result};
 use mapping::{self, MappableToDomain};
-use murust_data_model::entities::{inventory, item, Item};
+use murust_data_model::entities::{inventory, item, Item, ItemDefinition, ItemSet};
+use murust_data_model::types::ItemCode;
 use murust_repository::*;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A service for item management.
 pub struct ItemService {
   repo_item: ItemRepository,
   repo_item_defintion: ItemDefinitionRepository,
   repo_item_eligible_class: ItemEligibleClassRepository,
+  repo_item_attribute_boost: ItemAttributeBoostRepository,
+  repo_item_attribute_requirement: ItemAttributeRequirementRepository,
+  repo_item_set: ItemSetRepository,
 }
 
 impl ItemService {
@@ -16,11 +22,17 @@ impl ItemService {
     repo_item: ItemRepository,
     repo_item_defintion: ItemDefinitionRepository,
     repo_item_eligible_class: ItemEligibleClassRepository,
+    repo_item_attribute_boost: ItemAttributeBoostRepository,
+    repo_item_attribute_requirement: ItemAttributeRequirementRepository,
+    repo_item_set: ItemSetRepository,
   ) -> Self {
     ItemService {
       repo_item,
       repo_item_defintion,
       repo_item_eligible_class,
+      repo_item_attribute_boost,
+      repo_item_attribute_requirement,
+      repo_item_set,
     }
   }
 
@@ -57,12 +69,213 @@ impl ItemService {
       .collect::<Result<Vec<_>>>()
   }
 
+  /// Returns all item definitions dropped by monsters of a level.
+  pub fn find_droppable_definitions(&self, level: u16) -> Result<Vec<ItemDefinition>> {
+    self
+      .repo_item_defintion
+      .find_droppable_by_level(level as i32)?
+      .into_iter()
+      .map(|definition| self.map_definition_to_entity(definition))
+      .collect()
+  }
+
+  /// Returns an item definition by its code.
+  pub fn find_definition_by_code(&self, code: ItemCode) -> Result<Option<ItemDefinition>> {
+    self
+      .repo_item_defintion
+      .find_by_item_code(code.as_raw() as i32)?
+      .map_or(Ok(None), |definition| {
+        self.map_definition_to_entity(definition).map(Some)
+      })
+  }
+
+  /// Adds a new item to an inventory slot.
+  pub fn add_to_inventory(&self, inventory_id: inventory::Id, slot: u8, item: &Item) -> Result<()> {
+    let inventory_item = models::InventoryItem {
+      inventory_id: inventory_id.into(),
+      item_id: item.id.into(),
+      slot: slot as i32,
+    };
+
+    self
+      .repo_item
+      .add_to_inventory(&Self::map_item_to_model(item), &inventory_item)
+      .map_err(Into::into)
+  }
+
+  /// Persists the durability of an item.
+  pub fn save_durability(&self, item: &Item) -> Result<()> {
+    self
+      .repo_item
+      .save_durability(item.id, item.durability as i32)
+      .map_err(Into::into)
+  }
+
+  /// Persists the durability of items, e.g the counts of merged stacks.
+  pub(crate) fn save_durabilities<'a, I>(&self, items: I) -> Result<()>
+  where
+    I: IntoIterator<Item = &'a Item>,
+  {
+    let items = items
+      .into_iter()
+      .map(|item| (item.id, item.durability as i32))
+      .collect::<Vec<_>>();
+
+    self
+      .repo_item
+      .save_durabilities(&items)
+      .map_err(Into::into)
+  }
+
+  /// Deletes an item, e.g once it has been consumed.
+  pub(crate) fn delete(&self, item_id: item::Id) -> Result<()> {
+    self.repo_item.delete(item_id).map_err(Into::into)
+  }
+
+  /// Restores the durability of items, whilst removing the price of their
+  /// repair from the inventory paying for them.
+  pub(crate) fn repair_items<'a, I: IntoIterator<Item = &'a Item>>(
+    &self,
+    items: I,
+    inventory_id: inventory::Id,
+    price: u32,
+  ) -> Result<()> {
+    let items = items
+      .into_iter()
+      .map(|item| (item.id, item.max_durability as i32))
+      .collect::<Vec<_>>();
+
+    self
+      .repo_item
+      .repair_items(&items, inventory_id, price as i32)
+      .map_err(Into::into)
+  }
+
+  /// Saves an item, whilst deleting an item consumed by it.
+  pub(crate) fn save_consuming(&self, item: &Item, consumed: item::Id) -> Result<()> {
+    self
+      .repo_item
+      .save_consuming(&Self::map_item_to_model(item), consumed)
+      .map_err(Into::into)
+  }
+
+  /// Adds a new item to an inventory slot, whilst removing its price from the
+  /// inventory's zen.
+  pub(crate) fn buy_into_inventory(
+    &self,
+    inventory_id: inventory::Id,
+    slot: u8,
+    item: &Item,
+    price: u32,
+  ) -> Result<()> {
+    let inventory_item = models::InventoryItem {
+      inventory_id: inventory_id.into(),
+      item_id: item.id.into(),
+      slot: slot as i32,
+    };
+
+    self
+      .repo_item
+      .buy_into_inventory(&Self::map_item_to_model(item), &inventory_item, price as i32)
+      .map_err(Into::into)
+  }
+
+  /// Deletes an inventory item, whilst adding its price to the inventory's
+  /// zen.
+  pub(crate) fn sell_from_inventory(
+    &self,
+    inventory_id: inventory::Id,
+    item_id: item::Id,
+    price: u32,
+  ) -> Result<()> {
+    self
+      .repo_item
+      .sell_from_inventory(item_id, inventory_id, price as i32)
+      .map_err(Into::into)
+  }
+
+  /// Deletes the ingredients of a chaos mix along with adding its product, if
+  /// any, whilst removing its cost from the inventory's zen.
+  pub(crate) fn mix_in_inventory(
+    &self,
+    inventory_id: inventory::Id,
+    consumed: &[item::Id],
+    product: Option<(u8, &Item)>,
+    cost: u32,
+  ) -> Result<()> {
+    let products = product
+      .into_iter()
+      .map(|(slot, item)| {
+        let inventory_item = models::InventoryItem {
+          inventory_id: inventory_id.into(),
+          item_id: item.id.into(),
+          slot: slot as i32,
+        };
+        (Self::map_item_to_model(item), inventory_item)
+      })
+      .collect::<Vec<_>>();
+
+    self
+      .repo_item
+      .mix_inventory_items(inventory_id, consumed, &products, cost as i32)
+      .map_err(Into::into)
+  }
+
+  fn map_item_to_model(item: &Item) -> models::Item {
+    models::Item {
+      id: item.id.into(),
+      code: item.code.as_raw() as i32,
+      level: item.level as i32,
+      durability: item.durability as i32,
+      skill: item.skill,
+      luck: item.luck,
+      option: item.option as i32,
+      excellent: item.excellent.bits() as i32,
+      tier: item.tier() as i32,
+    }
+  }
+
   fn map_item_to_entity(&self, item: models::Item) -> Result<Item> {
     let definition: models::ItemDefinition = self
       .repo_item_defintion
       .find_by_item_code(item.code)?
       .ok_or(Error::MissingAssociation("ItemDefinition".into()))?;
 
+    let set = match item.tier {
+      0 => None,
+      tier => {
+        let set = self
+          .repo_item_set
+          .find_by_item_code_and_tier(item.code, tier)?
+          .ok_or(Error::MissingAssociation("ItemSet".into()))?;
+        Some(Arc::new(self.map_set_to_entity(set)?))
+      },
+    };
+
+    item
+      .map_to_entity((self.map_definition_to_entity(definition)?, set))
+      .map_err(Into::into)
+  }
+
+  fn map_set_to_entity(&self, set: models::ItemSet) -> Result<ItemSet> {
+    let members = self
+      .repo_item_set
+      .find_members_by_id(set.id)?
+      .into_iter()
+      .map(mapping::to_set_member)
+      .collect::<mapping::Result<Vec<_>>>()?;
+
+    let bonuses = self
+      .repo_item_set
+      .find_bonuses_by_id(set.id)?
+      .into_iter()
+      .map(mapping::to_set_bonus)
+      .collect::<mapping::Result<Vec<_>>>()?;
+
+    set.map_to_entity((members, bonuses)).map_err(Into::into)
+  }
+
+  fn map_definition_to_entity(&self, definition: models::ItemDefinition) -> Result<ItemDefinition> {
     let classes = self
       .repo_item_eligible_class
       .find_by_item_code(definition.code)?
@@ -70,8 +283,22 @@ impl ItemService {
       .map(mapping::to_character_class)
       .collect::<mapping::Result<Vec<_>>>()?;
 
-    item
-      .map_to_entity(definition.map_to_entity((classes,))?)
+    let boosts = self
+      .repo_item_attribute_boost
+      .find_by_item_code(definition.code)?
+      .into_iter()
+      .map(mapping::to_attribute_boost)
+      .collect::<mapping::Result<HashMap<_, _>>>()?;
+
+    let requirements = self
+      .repo_item_attribute_requirement
+      .find_by_item_code(definition.code)?
+      .into_iter()
+      .map(mapping::to_attribute_requirement)
+      .collect::<mapping::Result<HashMap<_, _>>>()?;
+
+    definition
+      .map_to_entity((classes, boosts, requirements))
       .map_err(Into::into)
   }
 }
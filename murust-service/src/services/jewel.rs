@@ -0,0 +1,165 @@
+use chaos;
+use error::Result;
+use murust_data_model::entities::{Character, Item};
+use murust_data_model::types::ItemGroup;
+use rand::{self, Rng, XorShiftRng};
+use services::ItemService;
+use std::sync::Mutex;
+
+/// The highest level reached using a Jewel of Bless.
+const MAX_BLESS_LEVEL: u8 = 6;
+
+/// The highest level reached using a Jewel of Soul.
+const MAX_SOUL_LEVEL: u8 = 9;
+
+/// The highest additional option reached using a Jewel of Life.
+const MAX_LIFE_OPTION: u8 = 4;
+
+/// A collection of jewels applied directly onto items.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Jewel {
+  /// Raises an item's level, up to +6.
+  Bless,
+  /// Gives a chance of raising an item's level from +6, up to +9.
+  Soul,
+  /// Gives a chance of raising an item's additional option.
+  Life,
+}
+
+impl Jewel {
+  /// Returns the jewel an item represents, if any.
+  pub fn from_item(item: &Item) -> Option<Self> {
+    match item.code.tuple() {
+      (ItemGroup::Potion, 13) => Some(Jewel::Bless),
+      (ItemGroup::Potion, 14) => Some(Jewel::Soul),
+      (ItemGroup::Potion, 16) => Some(Jewel::Life),
+      _ => None,
+    }
+  }
+
+  /// Applies the jewel onto an item, returning whether the upgrade succeeded,
+  /// unless the item cannot be upgraded by it.
+  ///
+  /// A failed Jewel of Soul lowers the item's level by one, or resets it once
+  /// at +7 or above. A failed Jewel of Life removes the additional option.
+  pub fn apply<R: Rng>(self, item: &mut Item, random: &mut R) -> Option<bool> {
+    if !chaos::is_upgradeable(item) {
+      return None;
+    }
+
+    match self {
+      Jewel::Bless => {
+        if item.level >= MAX_BLESS_LEVEL {
+          return None;
+        }
+
+        item.level += 1;
+        Some(true)
+      },
+      Jewel::Soul => {
+        if item.level < MAX_BLESS_LEVEL || item.level >= MAX_SOUL_LEVEL {
+          return None;
+        }
+
+        let success_rate = if item.luck { 75 } else { 50 };
+        let success = random.gen_range(0, 100) < success_rate;
+        item.level = match (success, item.level) {
+          (true, level) => level + 1,
+          (false, level) if level >= 7 => 0,
+          (false, level) => level - 1,
+        };
+        Some(success)
+      },
+      Jewel::Life => {
+        if item.option >= MAX_LIFE_OPTION {
+          return None;
+        }
+
+        let success = random.gen_range(0, 100) < 50;
+        item.option = if success { item.option + 1 } else { 0 };
+        Some(success)
+      },
+    }
+  }
+}
+
+/// A collection of possible jewel application errors.
+#[derive(Debug, Eq, PartialEq)]
+pub enum JewelError {
+  /// There is no item at either slot, or it is locked.
+  NotFound,
+  /// The applied item is not a jewel.
+  NotJewel,
+  /// The jewel cannot be applied onto the item.
+  NotApplicable,
+}
+
+/// A service for applying jewels onto items.
+pub struct JewelService {
+  item_service: ItemService,
+  random: Mutex<XorShiftRng>,
+}
+
+impl JewelService {
+  /// Constructs a new jewel service.
+  pub fn new(item_service: ItemService) -> Self {
+    Self::with_random(item_service, rand::weak_rng())
+  }
+
+  /// Constructs a new jewel service using a specific random generator.
+  pub fn with_random(item_service: ItemService, random: XorShiftRng) -> Self {
+    JewelService {
+      item_service,
+      random: Mutex::new(random),
+    }
+  }
+
+  /// Applies a jewel onto an item, both within a character's inventory,
+  /// returning whether the upgrade succeeded.
+  ///
+  /// The jewel is consumed regardless of the outcome, which is persisted along
+  /// with it.
+  pub fn apply(
+    &self,
+    character: &mut Character,
+    jewel_slot: u8,
+    target_slot: u8,
+  ) -> Result<::std::result::Result<bool, JewelError>> {
+    let (jewel_id, jewel) = {
+      let inventory = &character.inventory;
+      match inventory.get_item_at_slot(jewel_slot) {
+        Some(item) if !inventory.is_item_locked(item.id) => match Jewel::from_item(item) {
+          Some(jewel) => (item.id, jewel),
+          None => return Ok(Err(JewelError::NotJewel)),
+        },
+        _ => return Ok(Err(JewelError::NotFound)),
+      }
+    };
+
+    let mut item = {
+      let inventory = &character.inventory;
+      match inventory.get_item_at_slot(target_slot) {
+        Some(item) if item.id != jewel_id && !inventory.is_item_locked(item.id) => item.clone(),
+        _ => return Ok(Err(JewelError::NotFound)),
+      }
+    };
+
+    let applied = {
+      let mut random = self.random.lock().expect("locking jewel random");
+      jewel.apply(&mut item, &mut *random)
+    };
+
+    let success = match applied {
+      Some(success) => success,
+      None => return Ok(Err(JewelError::NotApplicable)),
+    };
+
+    self.item_service.save_consuming(&item, jewel_id)?;
+    character.inventory.remove_item(jewel_id);
+    *character
+      .inventory
+      .get_item_at_slot_mut(target_slot)
+      .expect("retrieving upgraded item") = item;
+    Ok(Ok(success))
+  }
+}
@@ -1,7 +1,29 @@
 pub use self::account::{AccountLoginError, AccountService};
+pub use self::chaos::{ChaosError, ChaosService};
 pub use self::character::{CharacterCreateError, CharacterDeleteError, CharacterService};
+pub use self::drop::DropService;
+pub use self::experience::{DeathPenalty, ExperienceService, MAX_LEVEL};
 pub use self::item::ItemService;
+pub use self::jewel::{Jewel, JewelError, JewelService};
+pub use self::monster::MonsterService;
+pub use self::npc::NpcService;
+pub use self::potion::{Potion, PotionError, PotionService};
+pub use self::shop::{ShopError, ShopService, MAX_MONEY};
+pub use self::skill::{taught_skill, SkillError, SkillService};
+pub use self::trade::{TradeError, TradeService};
+pub use self::vault::{VaultError, VaultService, MAX_VAULT_MONEY};
 
 mod account;
 mod character;
+mod chaos;
+mod drop;
+mod experience;
 mod item;
+mod jewel;
+mod monster;
+mod npc;
+mod potion;
+mod shop;
+mod skill;
+mod trade;
+mod vault;
@@ -0,0 +1,62 @@
+use error::Result;
+use mapping::MappableToDomain;
+use murust_data_model::entities::{MonsterDefinition, MonsterSpawn};
+use murust_repository::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A service for monster management.
+pub struct MonsterService {
+  repo_monster_definition: MonsterDefinitionRepository,
+  repo_monster_spawn: MonsterSpawnRepository,
+}
+
+impl MonsterService {
+  /// Constructs a new monster service.
+  pub fn new(
+    repo_monster_definition: MonsterDefinitionRepository,
+    repo_monster_spawn: MonsterSpawnRepository,
+  ) -> Self {
+    MonsterService {
+      repo_monster_definition,
+      repo_monster_spawn,
+    }
+  }
+
+  /// Returns a monster definition by its class.
+  pub fn find_definition_by_class(&self, class: u16) -> Result<Option<MonsterDefinition>> {
+    self
+      .repo_monster_definition
+      .find_by_class(class as i32)?
+      .map_or(Ok(None), |definition| {
+        definition.map_to_entity(()).map(Some).map_err(Into::into)
+      })
+  }
+
+  /// Returns a map's monster spawns.
+  ///
+  /// Spawns of the same monster class share their definition.
+  pub fn find_spawns_by_map(&self, map: u8) -> Result<Vec<MonsterSpawn>> {
+    let mut definitions = HashMap::new();
+
+    self
+      .repo_monster_spawn
+      .find_by_map(map as i32)?
+      .into_iter()
+      .map(|(spawn, definition)| {
+        let cached = definitions.get(&definition.class).cloned();
+        let definition = match cached {
+          Some(definition) => definition,
+          None => {
+            let class = definition.class;
+            let definition = Arc::new(definition.map_to_entity(())?);
+            definitions.insert(class, definition.clone());
+            definition
+          },
+        };
+
+        spawn.map_to_entity(definition).map_err(Into::into)
+      })
+      .collect()
+  }
+}
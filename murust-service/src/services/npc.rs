@@ -0,0 +1,24 @@
+use error::Result;
+use mapping::MappableToDomain;
+use murust_data_model::entities::NpcDefinition;
+use murust_repository::*;
+
+/// A service for NPC management.
+pub struct NpcService {
+  repo_npc: NpcRepository,
+}
+
+impl NpcService {
+  /// Constructs a new NPC service.
+  pub fn new(repo_npc: NpcRepository) -> Self { NpcService { repo_npc } }
+
+  /// Returns a map's NPCs.
+  pub fn find_by_map(&self, map: u8) -> Result<Vec<NpcDefinition>> {
+    self
+      .repo_npc
+      .find_by_map(map as i32)?
+      .into_iter()
+      .map(|npc| npc.map_to_entity(()).map_err(Into::into))
+      .collect()
+  }
+}
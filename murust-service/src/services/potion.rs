@@ -0,0 +1,108 @@
+use error::Result;
+use murust_data_model::entities::{Character, Item};
+use murust_data_model::types::ItemGroup;
+use services::ItemService;
+
+/// A collection of apples and potions, restoring health or mana over time.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Potion {
+  Apple,
+  SmallHealing,
+  Healing,
+  LargeHealing,
+  SmallMana,
+  Mana,
+  LargeMana,
+}
+
+impl Potion {
+  /// Returns the potion an item represents, if any.
+  pub fn from_item(item: &Item) -> Option<Self> {
+    match item.code.tuple() {
+      (ItemGroup::Potion, 0) => Some(Potion::Apple),
+      (ItemGroup::Potion, 1) => Some(Potion::SmallHealing),
+      (ItemGroup::Potion, 2) => Some(Potion::Healing),
+      (ItemGroup::Potion, 3) => Some(Potion::LargeHealing),
+      (ItemGroup::Potion, 4) => Some(Potion::SmallMana),
+      (ItemGroup::Potion, 5) => Some(Potion::Mana),
+      (ItemGroup::Potion, 6) => Some(Potion::LargeMana),
+      _ => None,
+    }
+  }
+
+  /// Returns the share (in percent) of the maximum health restored.
+  pub fn health(self) -> u32 {
+    match self {
+      Potion::Apple => 10,
+      Potion::SmallHealing => 20,
+      Potion::Healing => 30,
+      Potion::LargeHealing => 40,
+      _ => 0,
+    }
+  }
+
+  /// Returns the share (in percent) of the maximum mana restored.
+  pub fn mana(self) -> u32 {
+    match self {
+      Potion::SmallMana => 20,
+      Potion::Mana => 30,
+      Potion::LargeMana => 40,
+      _ => 0,
+    }
+  }
+}
+
+/// A collection of possible potion consumption errors.
+#[derive(Debug, Eq, PartialEq)]
+pub enum PotionError {
+  /// There is no item at the slot, or it is locked.
+  NotFound,
+  /// The item is not a potion.
+  NotPotion,
+}
+
+/// A service for consuming apples and potions.
+pub struct PotionService {
+  item_service: ItemService,
+}
+
+impl PotionService {
+  /// Constructs a new potion service.
+  pub fn new(item_service: ItemService) -> Self { PotionService { item_service } }
+
+  /// Consumes a single potion off a stack within a character's inventory,
+  /// returning the kind of potion consumed.
+  ///
+  /// The stack's count is persisted, or the stack is deleted once its last
+  /// potion has been consumed.
+  pub fn consume(
+    &self,
+    character: &mut Character,
+    slot: u8,
+  ) -> Result<::std::result::Result<Potion, PotionError>> {
+    let (mut stack, potion) = {
+      let inventory = &character.inventory;
+      match inventory.get_item_at_slot(slot) {
+        Some(item) if !inventory.is_item_locked(item.id) => match Potion::from_item(item) {
+          Some(potion) => (item.clone(), potion),
+          None => return Ok(Err(PotionError::NotPotion)),
+        },
+        _ => return Ok(Err(PotionError::NotFound)),
+      }
+    };
+
+    if stack.stack_size() > 1 {
+      stack.durability -= 1;
+      self.item_service.save_durability(&stack)?;
+      character
+        .inventory
+        .get_item_at_slot_mut(slot)
+        .expect("retrieving consumed potion")
+        .durability -= 1;
+    } else {
+      self.item_service.delete(stack.id)?;
+      character.inventory.remove_item(stack.id);
+    }
+    Ok(Ok(potion))
+  }
+}
@@ -0,0 +1,233 @@
+use error::{Error, Result};
+use murust_data_model::entities::{Character, Item, Shop};
+use murust_data_model::types::{ExcellentOptions, ItemCode, ItemGroup, ItemLocation};
+use num_traits::FromPrimitive;
+use pricing;
+use services::ItemService;
+
+/// The maximum amount of zen a character can carry.
+pub const MAX_MONEY: u32 = 2_000_000_000;
+
+/// A collection of possible shop transaction errors.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ShopError {
+  /// There is no item at the slot.
+  NotFound,
+  /// The character cannot afford the item.
+  InsufficientMoney,
+  /// The character's inventory has no room for the item.
+  InventoryFull,
+  /// The character cannot carry the zen received.
+  MoneyLimitReached,
+  /// The item has no durability to restore.
+  NotRepairable,
+  /// The item is locked, e.g offered in a trade.
+  ItemLocked,
+}
+
+/// A service for NPC shops.
+pub struct ShopService {
+  item_service: ItemService,
+}
+
+impl ShopService {
+  /// Constructs a new shop service.
+  pub fn new(item_service: ItemService) -> Self { ShopService { item_service } }
+
+  /// Parses the wares of an NPC from the contents of its shop file.
+  ///
+  /// Each line describes an item as `group index level durability skill luck
+  /// option excellent`, where a durability of zero implies the item's
+  /// maximum. Empty lines and `//` comments are ignored.
+  pub fn parse(&self, npc: i32, source: &str) -> Result<Shop> {
+    let mut shop = Shop::new(npc);
+
+    for (index, line) in source.lines().enumerate() {
+      let line = line.split("//").next().unwrap_or_default().trim();
+      if line.is_empty() {
+        continue;
+      }
+
+      let invalid = || Error::InvalidShopEntry(format!("{} (line {})", line, index + 1));
+      let values = line
+        .split_whitespace()
+        .map(str::parse::<u16>)
+        .collect::<::std::result::Result<Vec<_>, _>>()
+        .map_err(|_| invalid())?;
+      if values.len() != 8 {
+        return Err(invalid());
+      }
+
+      let group = ItemGroup::from_u16(values[0]).ok_or_else(&invalid)?;
+      let definition = self
+        .item_service
+        .find_definition_by_code(ItemCode::new(group, values[1]))?
+        .ok_or_else(&invalid)?;
+
+      let mut item = Item::with_definition(definition);
+      item.level = values[2] as u8;
+      if values[3] > 0 {
+        item.durability = values[3] as u8;
+      }
+      item.skill = values[4] != 0;
+      item.luck = values[5] != 0;
+      item.option = (values[6] as u8).min(Item::MAX_OPTION);
+      item.excellent = ExcellentOptions::from_bits_truncate(values[7] as u8);
+      shop.add_item(item).map_err(|_| invalid())?;
+    }
+
+    Ok(shop)
+  }
+
+  /// Lets a character buy an item from a shop, returning its inventory slot.
+  ///
+  /// The item and the character's remaining zen are persisted together, and
+  /// the character is left untouched unless they are.
+  pub fn buy(
+    &self,
+    character: &mut Character,
+    shop: &Shop,
+    slot: u8,
+  ) -> Result<::std::result::Result<u8, ShopError>> {
+    let item = match shop.get_item_at_slot(slot) {
+      Some(item) => item.duplicate(),
+      None => return Ok(Err(ShopError::NotFound)),
+    };
+
+    let price = pricing::buy_price(&item);
+    let money = match character.inventory.money.checked_sub(price) {
+      Some(money) => money,
+      None => return Ok(Err(ShopError::InsufficientMoney)),
+    };
+
+    let item_id = item.id;
+    if character.inventory.add_item(item).is_err() {
+      return Ok(Err(ShopError::InventoryFull));
+    }
+
+    let slot = character
+      .inventory
+      .into_iter()
+      .find(|&(_, item)| item.id == item_id)
+      .map(|(slot, _)| slot)
+      .expect("retrieving bought inventory item");
+
+    let result = {
+      let item = character
+        .inventory
+        .get_item_at_slot(slot)
+        .expect("retrieving bought inventory item");
+      self
+        .item_service
+        .buy_into_inventory(character.inventory.id, slot, item, price)
+    };
+
+    if let Err(error) = result {
+      character.inventory.remove_item(item_id);
+      return Err(error);
+    }
+
+    character.inventory.money = money;
+    Ok(Ok(slot))
+  }
+
+  /// Lets a character sell an inventory item, returning its new amount of
+  /// zen.
+  ///
+  /// The removal of the item and the character's zen are persisted together,
+  /// and the character is left untouched unless they are.
+  pub fn sell(
+    &self,
+    character: &mut Character,
+    slot: u8,
+  ) -> Result<::std::result::Result<u32, ShopError>> {
+    let (item_id, price) = match character.inventory.get_item_at_slot(slot) {
+      Some(item) => (item.id, pricing::sell_price(item)),
+      None => return Ok(Err(ShopError::NotFound)),
+    };
+
+    if character.inventory.is_item_locked(item_id) {
+      return Ok(Err(ShopError::ItemLocked));
+    }
+
+    let money = match character.inventory.money.checked_add(price) {
+      Some(money) if money <= MAX_MONEY => money,
+      _ => return Ok(Err(ShopError::MoneyLimitReached)),
+    };
+
+    self
+      .item_service
+      .sell_from_inventory(character.inventory.id, item_id, price)?;
+    character.inventory.remove_item(item_id);
+    character.inventory.money = money;
+    Ok(Ok(money))
+  }
+
+  /// Lets a character repair an item, or all of its items if no location is
+  /// specified, returning the locations of those repaired.
+  ///
+  /// The restored durability and the character's remaining zen are persisted
+  /// together, and the character is left untouched unless they are.
+  pub fn repair(
+    &self,
+    character: &mut Character,
+    location: Option<ItemLocation>,
+    at_npc: bool,
+  ) -> Result<::std::result::Result<Vec<ItemLocation>, ShopError>> {
+    let locations = match location {
+      Some(location) => match character.item(location) {
+        Some(item) if item.is_damaged() => vec![location],
+        Some(_) => return Ok(Err(ShopError::NotRepairable)),
+        None => return Ok(Err(ShopError::NotFound)),
+      },
+      None => {
+        let equipment = character
+          .equipment
+          .into_iter()
+          .filter_map(|(slot, item)| match *item {
+            Some(ref item) if item.is_damaged() => Some(ItemLocation::Equipment(slot)),
+            _ => None,
+          });
+        let inventory = character
+          .inventory
+          .into_iter()
+          .filter(|&(_, item)| item.is_damaged())
+          .map(|(slot, _)| ItemLocation::Inventory(slot));
+        equipment.chain(inventory).collect::<Vec<_>>()
+      },
+    };
+
+    if locations.is_empty() {
+      return Ok(Err(ShopError::NotRepairable));
+    }
+
+    let price = locations
+      .iter()
+      .filter_map(|&location| character.item(location))
+      .map(|item| pricing::repair_price(item, at_npc))
+      .fold(0u32, |total, price| total.saturating_add(price));
+
+    let money = match character.inventory.money.checked_sub(price) {
+      Some(money) => money,
+      None => return Ok(Err(ShopError::InsufficientMoney)),
+    };
+
+    {
+      let items = locations
+        .iter()
+        .filter_map(|&location| character.item(location));
+      self
+        .item_service
+        .repair_items(items, character.inventory.id, price)?;
+    }
+
+    for &location in &locations {
+      if let Some(item) = character.item_mut(location) {
+        item.repair();
+      }
+    }
+
+    character.inventory.money = money;
+    Ok(Ok(locations))
+  }
+}
@@ -0,0 +1,150 @@
+use error::Result;
+use mapping::{self, MappableToDomain};
+use murust_data_model::entities::{Character, Item, SkillDefinition};
+use murust_data_model::types::{ItemGroup, SkillId};
+use murust_repository::*;
+
+/// Returns the skill taught by an orb or scroll, if any.
+pub fn taught_skill(item: &Item) -> Option<SkillId> {
+  match item.code.tuple() {
+    (ItemGroup::Wings, 7) => Some(41),
+    (ItemGroup::Wings, 8) => Some(26),
+    (ItemGroup::Wings, 9) => Some(27),
+    (ItemGroup::Wings, 10) => Some(28),
+    // The level of an Orb of Summoning decides the creature summoned
+    (ItemGroup::Wings, 11) if item.level <= 6 => Some(item.level as SkillId + 30),
+    (ItemGroup::Wings, 12) => Some(42),
+    (ItemGroup::Wings, 13) => Some(47),
+    (ItemGroup::Wings, 16) => Some(55),
+    (ItemGroup::Wings, 17) => Some(52),
+    (ItemGroup::Wings, 18) => Some(51),
+    (ItemGroup::Wings, 19) => Some(43),
+    (ItemGroup::Wings, 20) => Some(56),
+    // Scrolls are ordered by the skills they teach
+    (ItemGroup::Scroll, index) if index < 14 => Some(index as SkillId + 1),
+    _ => None,
+  }
+}
+
+/// A collection of possible skill learning errors.
+#[derive(Debug, Eq, PartialEq)]
+pub enum SkillError {
+  /// There is no item at the slot, or it is locked.
+  NotFound,
+  /// The item does not teach any skill.
+  NotSkillItem,
+  /// The skill has already been learned.
+  AlreadyLearned,
+  /// The character does not meet the skill's requirements.
+  RequirementsNotMet,
+}
+
+/// A service for skill management.
+pub struct SkillService {
+  repo_skill_definition: SkillDefinitionRepository,
+  repo_skill_eligible_class: SkillEligibleClassRepository,
+  repo_character_skill: CharacterSkillRepository,
+}
+
+impl SkillService {
+  /// Constructs a new skill service.
+  pub fn new(
+    repo_skill_definition: SkillDefinitionRepository,
+    repo_skill_eligible_class: SkillEligibleClassRepository,
+    repo_character_skill: CharacterSkillRepository,
+  ) -> Self {
+    SkillService {
+      repo_skill_definition,
+      repo_skill_eligible_class,
+      repo_character_skill,
+    }
+  }
+
+  /// Returns all skill definitions.
+  pub fn find_all(&self) -> Result<Vec<SkillDefinition>> {
+    self
+      .repo_skill_definition
+      .find_all()?
+      .into_iter()
+      .map(|definition| self.map_definition_to_entity(definition))
+      .collect()
+  }
+
+  /// Returns a skill definition by its ID.
+  pub fn find_by_id(&self, id: SkillId) -> Result<Option<SkillDefinition>> {
+    self
+      .repo_skill_definition
+      .find_by_id(i32::from(id))?
+      .map_or(Ok(None), |definition| {
+        self.map_definition_to_entity(definition).map(Some)
+      })
+  }
+
+  /// Returns the skills learned by a character.
+  pub fn find_learned_by_character_id(&self, character_id: i32) -> Result<Vec<SkillId>> {
+    self
+      .repo_character_skill
+      .find_by_character_id(character_id)?
+      .into_iter()
+      .map(|skill| mapping::to_skill_id(skill).map_err(Into::into))
+      .collect()
+  }
+
+  /// Learns the skill taught by an orb or scroll within a character's
+  /// inventory, consuming the item.
+  ///
+  /// The in-memory character is left untouched unless the skill could be
+  /// persisted.
+  pub fn learn(
+    &self,
+    character: &mut Character,
+    slot: u8,
+  ) -> Result<::std::result::Result<SkillId, SkillError>> {
+    let (item_id, skill) = {
+      let inventory = &character.inventory;
+      match inventory.get_item_at_slot(slot) {
+        Some(item) if !inventory.is_item_locked(item.id) => match taught_skill(item) {
+          Some(skill) => (item.id, skill),
+          None => return Ok(Err(SkillError::NotSkillItem)),
+        },
+        _ => return Ok(Err(SkillError::NotFound)),
+      }
+    };
+
+    if character.skills.contains(&skill) {
+      return Ok(Err(SkillError::AlreadyLearned));
+    }
+
+    match self.find_by_id(skill)? {
+      Some(ref definition) if definition.is_usable_by(character) => (),
+      Some(_) => return Ok(Err(SkillError::RequirementsNotMet)),
+      None => return Ok(Err(SkillError::NotSkillItem)),
+    }
+
+    let learned = models::CharacterSkill {
+      character_id: character.id,
+      skill_id: i32::from(skill),
+    };
+    self
+      .repo_character_skill
+      .save_consuming(&learned, item_id)?;
+    character.inventory.remove_item(item_id);
+    character.skills.push(skill);
+    Ok(Ok(skill))
+  }
+
+  /// Maps a skill definition model to its entity, along with its eligible
+  /// classes.
+  fn map_definition_to_entity(
+    &self,
+    definition: models::SkillDefinition,
+  ) -> Result<SkillDefinition> {
+    let classes = self
+      .repo_skill_eligible_class
+      .find_by_skill_id(definition.id)?
+      .into_iter()
+      .map(mapping::to_skill_class)
+      .collect::<mapping::Result<Vec<_>>>()?;
+    definition.map_to_entity((classes,)).map_err(Into::into)
+  }
+}
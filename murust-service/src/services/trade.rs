@@ -0,0 +1,134 @@
+use error::{Error, Result};
+use murust_data_model::entities::{inventory, Inventory, Item, TradeOffer};
+use murust_repository::{models, ItemRepository};
+use services::{CharacterService, MAX_MONEY};
+
+/// A collection of possible trade errors.
+#[derive(Debug, Eq, PartialEq)]
+pub enum TradeError {
+  /// An offered item is no longer within its owner's inventory.
+  ItemNotFound,
+  /// An inventory has no room for the items received.
+  InventoryFull,
+  /// A character cannot afford the zen offered.
+  InsufficientMoney,
+  /// A character cannot carry the zen received.
+  MoneyLimitReached,
+}
+
+/// A service for trades between characters.
+pub struct TradeService {
+  characters: CharacterService,
+  repo_items: ItemRepository,
+}
+
+impl TradeService {
+  /// Constructs a new trade service.
+  pub fn new(characters: CharacterService, repo_items: ItemRepository) -> Self {
+    TradeService {
+      characters,
+      repo_items,
+    }
+  }
+
+  /// Exchanges the offers of two inventories, as a single transaction.
+  ///
+  /// The placement of the items received is based on the persisted contents
+  /// of each inventory, so the characters involved must be refreshed
+  /// afterwards.
+  pub fn exchange(
+    &self,
+    (first_id, first): (inventory::Id, &TradeOffer),
+    (second_id, second): (inventory::Id, &TradeOffer),
+  ) -> Result<::std::result::Result<(), TradeError>> {
+    let mut first_inventory = self.find_inventory(first_id)?;
+    let mut second_inventory = self.find_inventory(second_id)?;
+
+    let (first_items, second_items) = match (
+      Self::take_offer(&mut first_inventory, first),
+      Self::take_offer(&mut second_inventory, second),
+    ) {
+      (Some(first_items), Some(second_items)) => (first_items, second_items),
+      _ => return Ok(Err(TradeError::ItemNotFound)),
+    };
+
+    let mut entries = Vec::new();
+    if !Self::receive(&mut first_inventory, second_items, &mut entries)
+      || !Self::receive(&mut second_inventory, first_items, &mut entries)
+    {
+      return Ok(Err(TradeError::InventoryFull));
+    }
+
+    let money = match (
+      Self::exchange_money(&first_inventory, first.money, second.money),
+      Self::exchange_money(&second_inventory, second.money, first.money),
+    ) {
+      (Ok(_), Ok(_)) => {
+        let change = second.money as i32 - first.money as i32;
+        [(first_id, change), (second_id, -change)]
+      },
+      (Err(error), _) | (_, Err(error)) => return Ok(Err(error)),
+    };
+
+    self.repo_items.exchange_inventory_items(&entries, &money)?;
+    Ok(Ok(()))
+  }
+
+  fn find_inventory(&self, inventory_id: inventory::Id) -> Result<Inventory> {
+    self
+      .characters
+      .find_inventory_by_id(inventory_id)?
+      .ok_or(Error::MissingPersistence)
+  }
+
+  /// Removes the items of an offer from its owner's inventory.
+  fn take_offer(inventory: &mut Inventory, offer: &TradeOffer) -> Option<Vec<Item>> {
+    offer
+      .storage
+      .into_iter()
+      .map(|(_, item)| inventory.remove_item(item.id))
+      .collect()
+  }
+
+  /// Adds items to an inventory, returning whether there was room for all.
+  fn receive(
+    inventory: &mut Inventory,
+    items: Vec<Item>,
+    entries: &mut Vec<models::InventoryItem>,
+  ) -> bool {
+    for item in items {
+      let item_id = item.id;
+      if inventory.add_item(item).is_err() {
+        return false;
+      }
+
+      let slot = inventory
+        .into_iter()
+        .find(|&(_, item)| item.id == item_id)
+        .map(|(slot, _)| slot)
+        .expect("retrieving received item");
+      entries.push(models::InventoryItem {
+        inventory_id: inventory.id.into(),
+        item_id: item_id.into(),
+        slot: slot as i32,
+      });
+    }
+    true
+  }
+
+  /// Returns the amount of zen within an inventory after an exchange.
+  fn exchange_money(
+    inventory: &Inventory,
+    given: u32,
+    received: u32,
+  ) -> ::std::result::Result<u32, TradeError> {
+    let money = inventory
+      .money
+      .checked_sub(given)
+      .ok_or(TradeError::InsufficientMoney)?;
+    match money.checked_add(received) {
+      Some(money) if money <= MAX_MONEY => Ok(money),
+      _ => Err(TradeError::MoneyLimitReached),
+    }
+  }
+}
@@ -0,0 +1,186 @@
+use error::Result;
+use murust_data_model::entities::{Character, Inventory, Vault};
+use murust_repository::{InventoryRepository, ItemRepository};
+use services::MAX_MONEY;
+
+/// The maximum amount of zen a vault can hold.
+pub const MAX_VAULT_MONEY: u32 = 100_000_000;
+
+/// A collection of possible vault transaction errors.
+#[derive(Debug, Eq, PartialEq)]
+pub enum VaultError {
+  /// The vault is protected by a password and has not been unlocked.
+  Locked,
+  /// There is no item at the source slot, or no room for it at the target.
+  InvalidMove,
+  /// There is not enough zen to transfer.
+  InsufficientMoney,
+  /// The receiving side cannot hold the zen transferred.
+  MoneyLimitReached,
+}
+
+/// A service for account vaults.
+///
+/// The in-memory entities are left untouched unless a transaction could be
+/// persisted.
+pub struct VaultService {
+  repo_items: ItemRepository,
+  repo_inventory: InventoryRepository,
+}
+
+impl VaultService {
+  /// Constructs a new vault service.
+  pub fn new(repo_items: ItemRepository, repo_inventory: InventoryRepository) -> Self {
+    VaultService {
+      repo_items,
+      repo_inventory,
+    }
+  }
+
+  /// Moves an item from a character's inventory into a vault.
+  pub fn deposit_item(
+    &self,
+    character: &mut Character,
+    vault: &mut Vault,
+    source: u8,
+    target: u8,
+  ) -> Result<::std::result::Result<(), VaultError>> {
+    if vault.is_locked() {
+      return Ok(Err(VaultError::Locked));
+    }
+    self.transfer_item(&mut character.inventory, &mut vault.inventory, source, target)
+  }
+
+  /// Moves an item from a vault into a character's inventory.
+  pub fn withdraw_item(
+    &self,
+    character: &mut Character,
+    vault: &mut Vault,
+    source: u8,
+    target: u8,
+  ) -> Result<::std::result::Result<(), VaultError>> {
+    if vault.is_locked() {
+      return Ok(Err(VaultError::Locked));
+    }
+    self.transfer_item(&mut vault.inventory, &mut character.inventory, source, target)
+  }
+
+  /// Moves an item between two slots of a vault.
+  pub fn move_item(
+    &self,
+    vault: &mut Vault,
+    source: u8,
+    target: u8,
+  ) -> Result<::std::result::Result<(), VaultError>> {
+    if vault.is_locked() {
+      return Ok(Err(VaultError::Locked));
+    }
+
+    if vault.move_item_between_slots(source, target).is_none() {
+      return Ok(Err(VaultError::InvalidMove));
+    }
+
+    let result = self
+      .repo_items
+      .move_inventory_item(vault.id, source as i32, target as i32);
+    if let Err(error) = result {
+      vault
+        .move_item_between_slots(target, source)
+        .expect("returning vault item");
+      return Err(error.into());
+    }
+    Ok(Ok(()))
+  }
+
+  /// Moves zen from a character's inventory into a vault.
+  pub fn deposit_money(
+    &self,
+    character: &mut Character,
+    vault: &mut Vault,
+    amount: u32,
+  ) -> Result<::std::result::Result<(), VaultError>> {
+    if vault.is_locked() {
+      return Ok(Err(VaultError::Locked));
+    }
+    self.transfer_money(
+      &mut character.inventory,
+      &mut vault.inventory,
+      amount,
+      MAX_VAULT_MONEY,
+    )
+  }
+
+  /// Moves zen from a vault into a character's inventory.
+  pub fn withdraw_money(
+    &self,
+    character: &mut Character,
+    vault: &mut Vault,
+    amount: u32,
+  ) -> Result<::std::result::Result<(), VaultError>> {
+    if vault.is_locked() {
+      return Ok(Err(VaultError::Locked));
+    }
+    self.transfer_money(
+      &mut vault.inventory,
+      &mut character.inventory,
+      amount,
+      MAX_MONEY,
+    )
+  }
+
+  fn transfer_item(
+    &self,
+    source: &mut Inventory,
+    target: &mut Inventory,
+    source_slot: u8,
+    target_slot: u8,
+  ) -> Result<::std::result::Result<(), VaultError>> {
+    if source
+      .storage
+      .transfer_item(source_slot, &mut target.storage, target_slot)
+      .is_none()
+    {
+      return Ok(Err(VaultError::InvalidMove));
+    }
+
+    let result = self.repo_items.transfer_inventory_item(
+      source.id,
+      source_slot as i32,
+      target.id,
+      target_slot as i32,
+    );
+    if let Err(error) = result {
+      target
+        .storage
+        .transfer_item(target_slot, &mut source.storage, source_slot)
+        .expect("returning transferred item");
+      return Err(error.into());
+    }
+    Ok(Ok(()))
+  }
+
+  fn transfer_money(
+    &self,
+    source: &mut Inventory,
+    target: &mut Inventory,
+    amount: u32,
+    limit: u32,
+  ) -> Result<::std::result::Result<(), VaultError>> {
+    let source_money = match source.money.checked_sub(amount) {
+      Some(money) => money,
+      None => return Ok(Err(VaultError::InsufficientMoney)),
+    };
+
+    let target_money = match target.money.checked_add(amount) {
+      Some(money) if money <= limit => money,
+      _ => return Ok(Err(VaultError::MoneyLimitReached)),
+    };
+
+    self
+      .repo_inventory
+      .transfer_money(source.id, target.id, amount as i32)?;
+    source.money = source_money;
+    target.money = target_money;
+    Ok(Ok(()))
+  }
+}